@@ -0,0 +1,78 @@
+//! Context fields scoped to the current thread.
+//!
+//! The fields set with [scope] are attached to the logs of the current thread while the returned
+//! guard lives, in addition to the global ones set with [`crate::logger::add_context`]. They are
+//! rendered by the `{context}` placeholder and given to the custom formatters.
+//!
+//! ```rust
+//! use loggit::{context, info};
+//!
+//! fn handle_request(id: &str) {
+//!     let _guard = context::scope("request_id", id);
+//!     info!("processing"); // `{context}` gives `request_id=...`
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+thread_local! {
+    static SCOPED_CONTEXT: RefCell<BTreeMap<String, String>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Removes the field set by [scope] when dropped, restoring the value of an outer scope with the
+/// same key.
+///
+/// The guards are expected to be dropped in the reverse order of their creation, which is the
+/// case when they are bound to local variables.
+#[must_use = "the field is removed as soon as the guard is dropped"]
+pub struct ContextGuard {
+    key: String,
+    previous: Option<String>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        SCOPED_CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+            match self.previous.take() {
+                Some(previous) => context.insert(self.key.clone(), previous),
+                None => context.remove(&self.key),
+            };
+        });
+    }
+}
+
+/// Attaches a field to the logs of the current thread until the returned guard is dropped.
+///
+/// A nested scope with the same key shadows the outer value, which is restored when the inner
+/// guard is dropped. A scoped field also shadows a global one with the same key. The fields are
+/// never visible from other threads.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::context;
+///
+/// let _request = context::scope("request_id", "abc123");
+/// {
+///     let _user = context::scope("user", "42");
+///     // the logs have `request_id=abc123 user=42`
+/// }
+/// // the logs have `request_id=abc123`
+/// ```
+pub fn scope(key: &str, value: &str) -> ContextGuard {
+    let previous = SCOPED_CONTEXT.with(|context| {
+        context
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string())
+    });
+    ContextGuard {
+        key: key.to_string(),
+        previous,
+    }
+}
+
+/// Returns the fields scoped to the current thread.
+pub(crate) fn scoped_fields() -> BTreeMap<String, String> {
+    SCOPED_CONTEXT.with(|context| context.borrow().clone())
+}
@@ -1,32 +1,255 @@
 //! Internal helper functions for time handling and file I/O.
 
-use chrono::{self, Datelike, Timelike};
-use std::io::Write;
-use thiserror::Error;
+use chrono::{self, Datelike, Offset, Timelike};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
 
-pub(crate) fn get_current_time_in_utc() -> (u32, u32, i32, u32, u32, u32) {
-    let date_time = chrono::Utc::now();
-    let (day, month, year) = (
-        date_time.date_naive().day(),
-        date_time.date_naive().month(),
-        date_time.date_naive().year(),
-    );
-    let (hour, minute, second) = (
-        date_time.time().hour(),
-        date_time.time().minute(),
-        date_time.time().second(),
-    );
-    (day, month, year, hour, minute, second)
+use crate::{MultilineStyle, TimePrecision, TimeZoneChoice};
+
+/// Returns the current time. The system clock isn't available in the browser, the time is read
+/// through JavaScript there (see the `wasm` feature).
+pub(crate) fn now() -> std::time::SystemTime {
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        chrono::Utc::now().into()
+    }
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        std::time::SystemTime::now()
+    }
+}
+
+/// Returns the id of the process, 0 in the browser where there are no processes.
+pub(crate) fn process_id() -> u32 {
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        0
+    }
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        std::process::id()
+    }
+}
+
+/// Returns the current date and time in the given time zone.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn get_now(timezone: TimeZoneChoice) -> chrono::DateTime<chrono::FixedOffset> {
+    match timezone {
+        TimeZoneChoice::Utc => chrono::Utc::now().fixed_offset(),
+        TimeZoneChoice::Local => chrono::Local::now().fixed_offset(),
+        TimeZoneChoice::FixedOffset(secs) => {
+            // the offset is validated by `set_timezone`, UTC is only a safety net
+            let offset = chrono::FixedOffset::east_opt(secs).unwrap_or(chrono::Utc.fix());
+            chrono::Utc::now().with_timezone(&offset)
+        }
+    }
+}
+
+/// Returns the moment in the given time zone.
+pub(crate) fn in_timezone(
+    time: std::time::SystemTime,
+    timezone: TimeZoneChoice,
+) -> chrono::DateTime<chrono::FixedOffset> {
+    let utc = chrono::DateTime::<chrono::Utc>::from(time);
+    match timezone {
+        TimeZoneChoice::Utc => utc.fixed_offset(),
+        TimeZoneChoice::Local => utc.with_timezone(&chrono::Local).fixed_offset(),
+        TimeZoneChoice::FixedOffset(secs) => {
+            let offset = chrono::FixedOffset::east_opt(secs).unwrap_or(chrono::Utc.fix());
+            utc.with_timezone(&offset)
+        }
+    }
 }
 
-pub(crate) fn get_current_date_in_string() -> String {
-    let (day, month, year, _, _, _) = get_current_time_in_utc();
-    format!("{}-{}-{}", day, month, year)
+/// Renders the date and time as RFC 3339 with milliseconds: `2025-03-05T09:05:03.123Z` in UTC,
+/// `2025-03-05T11:05:03.123+02:00` with an offset.
+pub(crate) fn format_iso8601(date_time: &chrono::DateTime<chrono::FixedOffset>) -> String {
+    date_time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
 }
 
-pub(crate) fn get_current_time_in_string() -> String {
-    let (_, _, _, hour, minute, second) = get_current_time_in_utc();
-    format!("{}:{}:{}", hour, minute, second)
+/// Settings of the `{date}` and `{time}` rendering, stored in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TimeSettings {
+    pub(crate) precision: TimePrecision,
+    pub(crate) timezone: TimeZoneChoice,
+    /// `05-03-2025` and `09:05:03` instead of `5-3-2025` and `9:5:3`
+    pub(crate) zero_padded: bool,
+}
+
+impl Default for TimeSettings {
+    fn default() -> Self {
+        Self {
+            precision: TimePrecision::Seconds,
+            timezone: TimeZoneChoice::Utc,
+            zero_padded: true,
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn get_current_date_in_string(settings: TimeSettings) -> String {
+    format_date(&get_now(settings.timezone), settings)
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn get_current_time_in_string(settings: TimeSettings) -> String {
+    format_time(&get_now(settings.timezone), settings)
+}
+
+/// Renders the date as `{date}` does: `05-03-2025`, or `5-3-2025` without the zero padding.
+pub(crate) fn format_date(
+    date_time: &chrono::DateTime<chrono::FixedOffset>,
+    settings: TimeSettings,
+) -> String {
+    let date = date_time.date_naive();
+    let (day, month, year) = (date.day(), date.month(), date.year());
+    match settings.zero_padded {
+        true => format!("{:02}-{:02}-{}", day, month, year),
+        false => format!("{}-{}-{}", day, month, year),
+    }
+}
+
+/// Renders the time as `{time}` does: `09:05:03`, with the milliseconds if the precision asks
+/// for them.
+pub(crate) fn format_time(
+    now: &chrono::DateTime<chrono::FixedOffset>,
+    settings: TimeSettings,
+) -> String {
+    let (hour, minute, second) = (now.time().hour(), now.time().minute(), now.time().second());
+    let hms = match settings.zero_padded {
+        true => format!("{:02}:{:02}:{:02}", hour, minute, second),
+        false => format!("{}:{}:{}", hour, minute, second),
+    };
+    match settings.precision {
+        TimePrecision::Seconds => hms,
+        // leap seconds are reported as 1000+ ms
+        TimePrecision::Millis => format!("{}.{:03}", hms, now.timestamp_subsec_millis() % 1000),
+    }
+}
+
+/// Returns the last `count` components of the path: `src/a/b.rs` gives `b.rs` for 1 and `a/b.rs`
+/// for 2.
+pub(crate) fn get_last_path_components(path: &str, count: usize) -> &str {
+    let mut separators = 0;
+    for (idx, c) in path.char_indices().rev() {
+        if c == '/' || c == '\\' {
+            separators += 1;
+            if separators == count {
+                return &path[idx + 1..];
+            }
+        }
+    }
+    path
+}
+
+/// Renders context fields as `key=value` pairs separated by spaces, the values that are empty or
+/// contain spaces, quotes or `=` are quoted: `service=billing note="a b"`.
+pub(crate) fn format_context(context: &BTreeMap<String, String>) -> String {
+    context
+        .iter()
+        .map(|(key, value)| {
+            let needs_quotes = value.is_empty()
+                || value
+                    .chars()
+                    .any(|c| c.is_whitespace() || c == '"' || c == '=');
+            match needs_quotes {
+                true => format!("{}={:?}", key, value),
+                false => format!("{}={}", key, value),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Cuts the message to `max_len` bytes at a character boundary and appends a marker with the
+/// number of removed bytes, a `max_len` of 0 keeps the message as it is.
+pub(crate) fn truncate_message(message: &str, max_len: usize) -> std::borrow::Cow<'_, str> {
+    if max_len == 0 || message.len() <= max_len {
+        return std::borrow::Cow::Borrowed(message);
+    }
+    let mut end = max_len;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    std::borrow::Cow::Owned(format!(
+        "{}… [truncated {} bytes]",
+        &message[..end],
+        message.len() - end
+    ))
+}
+
+/// Renders the line breaks of a message in the given style, `column` is the column where the
+/// message starts (see [`MultilineStyle`]).
+pub(crate) fn format_multiline(message: &str, style: MultilineStyle, column: usize) -> String {
+    match style {
+        MultilineStyle::Keep | MultilineStyle::PrefixEach => message.to_string(),
+        MultilineStyle::Indent => message.replace('\n', &format!("\n{}", " ".repeat(column))),
+        MultilineStyle::Escape => message.replace('\r', "\\r").replace('\n', "\\n"),
+    }
+}
+
+/// Returns the column at the end of the text written from `column`.
+pub(crate) fn column_after(text: &str, column: usize) -> usize {
+    match text.rsplit_once('\n') {
+        Some((_, last_line)) => last_line.chars().count(),
+        None => column + text.chars().count(),
+    }
+}
+
+/// Returns the id of the current run: 8 hex characters, random and generated on the first call.
+pub(crate) fn get_run_id() -> &'static str {
+    static RUN_ID: Lazy<String> = Lazy::new(|| {
+        use std::hash::{BuildHasher, Hasher};
+        // the std hasher is randomly seeded for each process, the time and the pid are mixed in
+        // as well in case the seed is weak on some platform
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u32(process_id());
+        if let Ok(since_epoch) = now().duration_since(std::time::UNIX_EPOCH) {
+            hasher.write_u128(since_epoch.as_nanos());
+        }
+        let random = hasher.finish();
+        format!("{:08x}", (random ^ (random >> 32)) as u32)
+    });
+    &RUN_ID
+}
+
+/// Returns the id of the process as a string. It doesn't change during the execution, so it is
+/// computed only once and the rotated files keep it.
+pub(crate) fn get_pid() -> &'static str {
+    static PID: Lazy<String> = Lazy::new(|| process_id().to_string());
+    &PID
+}
+
+/// Returns the name of the machine, resolved once and cached.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn get_hostname() -> &'static str {
+    static HOSTNAME: Lazy<String> = Lazy::new(|| match gethostname::gethostname().into_string() {
+        Ok(name) if !name.is_empty() => name,
+        _ => std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string()),
+    });
+    &HOSTNAME
+}
+/// The browser doesn't expose the name of the machine.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) fn get_hostname() -> &'static str {
+    "unknown"
+}
+
+/// Returns the numeric id of the current thread.
+pub(crate) fn get_current_thread_id() -> String {
+    // `ThreadId` only exposes its number through `Debug`: `ThreadId(N)`
+    let id = format!("{:?}", std::thread::current().id());
+    id.trim_start_matches("ThreadId(")
+        .trim_end_matches(')')
+        .to_string()
+}
+
+/// Returns the name of the current thread, or its numeric id if it is unnamed.
+pub(crate) fn get_current_thread_name() -> String {
+    match std::thread::current().name() {
+        Some(name) => name.to_string(),
+        None => get_current_thread_id(),
+    }
 }
 
 pub(crate) fn seconds_to_ymdhms(mut seconds: u64) -> (u64, u64, u64, u64, u64, u64) {
@@ -84,11 +307,14 @@ pub(crate) fn seconds_to_ymdhms(mut seconds: u64) -> (u64, u64, u64, u64, u64, u
     (year, month, day, hour, minute, second)
 }
 
-#[derive(Debug, Error)]
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[derive(Debug, thiserror::Error)]
 pub(crate) enum WriteToFileError {
     #[error("unexpected error")]
     UnexpectedError(std::io::Error),
 }
-pub(crate) fn write_to_file(file: &mut std::fs::File, text: &str) -> Result<(), WriteToFileError> {
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn write_to_file(mut file: &std::fs::File, text: &str) -> Result<(), WriteToFileError> {
+    use std::io::Write;
     writeln!(file, "{}", text).map_err(WriteToFileError::UnexpectedError)
 }
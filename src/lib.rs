@@ -163,6 +163,7 @@
 //!     set_file("{level}-log-on-{date}.txt");
 //!     add_rotation("1 week"); // change the file every week
 //!     add_rotation("5 MB"); // max file size 5 MB, then the file changes again
+//!     add_rotation("daily"); // a new file at midnight ("hourly" at the start of each hour)
 //! }
 //! ````
 //!
@@ -173,7 +174,7 @@
 //! fn main() {
 //!     // provide file name
 //!     set_file("{level}-log-on-{date}.txt");
-//!     set_compression("zip");
+//!     set_compression("zip"); // or "gzip" for `.gz` archives
 //! }
 //! ```
 //!
@@ -188,6 +189,18 @@
 //!     set_archive_dir("my_archives"); // all the archives will be stored in the `my_archives` directory
 //! }
 //! ```
+//!
+//! Remove the archives older than a given age, on each rotation or when `purge_archives` is called
+//! ```rust,no_run
+//! use loggit::logger::{set_file, set_compression, set_archive_retention_age, purge_archives};
+//!
+//! fn main() {
+//!     set_file("app_{date}.log");
+//!     set_compression("zip");
+//!     set_archive_retention_age("30 day"); // only the `app_*.log.zip` archives are removed
+//!     purge_archives();
+//! }
+//! ```
 //! ### Configure logger using environment variables
 //! ```sh
 //! colorized=false file_name="save_here.txt" cargo run
@@ -209,21 +222,44 @@
 //!
 //! And it will be loaded automatically
 //!
+//! ### In the browser
+//! On `wasm32-unknown-unknown` enable the `wasm` feature: the same macros print to the browser
+//! console (`console.error`, `console.warn` and `console.log`). There is no file system there, so
+//! `set_file` returns `SetFileError::UnsupportedTarget`.
+//! ```toml
+//! loggit = { version = "0.1", features = ["wasm"] }
+//! ```
+//!
 //! ## Modules
 //!
 //! - [`logger`]: Contains functions to control logging configuration and macros to log messages.
+//! - [`context`]: Context fields attached to the logs of the current thread.
 
-use ctor::ctor;
-use logger::{file_handler::file_manager::FileManager, formatter::LogFormatter};
+use ctor::{ctor, dtor};
+use logger::{
+    file_handler::{
+        archive_name::{ArchiveNameFormat, ArchiveSubdirFormat},
+        file_manager::FileManager,
+    },
+    formatter::{LogColor, LogFormatter},
+};
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::{Arc, Mutex};
-use std::{fmt::Display, path::PathBuf, sync::RwLock};
+use std::time::SystemTime;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
 pub(crate) mod helper;
+use helper::TimeSettings;
 
 #[cfg(test)]
 mod tests;
 
-#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Serialize, Deserialize)]
 /// Represents the log level used throughout the application.
 pub enum Level {
     TRACE,
@@ -234,26 +270,483 @@ pub enum Level {
     ERROR,
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// Controls when the terminal output gets colorized.
+///
+/// - `Auto` colorizes only when the output stream is a terminal (the default).
+/// - `Always` colorizes even if the output is piped or redirected.
+/// - `Never` disables colors completely.
+pub enum ColorWhen {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// Encoding of the logs written to an output.
+///
+/// - `Text` renders the logs with the formats (the default).
+/// - `Json` writes one JSON object per log with the `ts` (rendered as `{iso8601}`), `level`,
+///   `message`, `module`, `file`, `line`, `column`, `thread`, `thread_id` and `context` fields
+///   (JSON Lines).
+/// - `Csv` writes one row per log with the `timestamp`, `level`, `module`, `file`, `line` and
+///   `message` columns, each log file starts with a header row.
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// How the messages spanning several lines are rendered.
+///
+/// - `Keep` writes the message as it is, the lines after the first one start at the column zero
+///   (the default for the terminal).
+/// - `Indent` pads the lines after the first one to align them under the start of the message.
+/// - `PrefixEach` renders the whole format for each line, so each line gets its own prefix.
+/// - `Escape` replaces the line breaks with `\n` (and `\r`), so each log stays on one line (the
+///   default for the file).
+pub enum MultilineStyle {
+    #[default]
+    Keep,
+    Indent,
+    PrefixEach,
+    Escape,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// Precision of the `{time}` placeholder, both in log formats and in file names.
+///
+/// - `Seconds` renders the time as `09:05:03` (the default).
+/// - `Millis` adds the milliseconds: `09:05:03.042`.
+pub enum TimePrecision {
+    #[default]
+    Seconds,
+    Millis,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// Time zone used for the `{date}` and `{time}` placeholders, the file names and the time of day
+/// rotations (e.g. `"12:30"`).
+///
+/// - `Utc` (the default).
+/// - `Local` uses the time zone of the machine.
+/// - `FixedOffset(secs)` uses a fixed offset in seconds east of UTC, e.g. `FixedOffset(3600)`
+///   for UTC+1.
+pub enum TimeZoneChoice {
+    #[default]
+    Utc,
+    Local,
+    FixedOffset(i32),
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// What the async mode does when its queue is full, see [logger::set_overflow_policy].
+///
+/// - `Block` makes the logging call wait for a free slot, no record is dropped (the default).
+/// - `DropNewest` drops the record being logged.
+/// - `DropOldest` drops the oldest record of the queue to make room for the new one.
+pub enum Overflow {
+    #[default]
+    Block,
+    DropNewest,
+    DropOldest,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// When the records are written to the log files, see [logger::set_file_buffering].
+///
+/// - `PerRecord` writes each record as it is logged (the default).
+/// - `Batched` keeps the records in memory and writes them together once `max_bytes` are
+///   buffered or the oldest one has waited `max_delay`, both checked when a record is logged.
+pub enum BufferPolicy {
+    #[default]
+    PerRecord,
+    Batched {
+        max_bytes: usize,
+        max_delay: std::time::Duration,
+    },
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// When the written records are synced to the disk, see [logger::set_file_sync].
+///
+/// - `Never` leaves it to the OS, the records may stay in the page cache for a while (the
+///   default).
+/// - `EveryRecord` syncs after each write, the records are durable when the log call returns.
+/// - `Interval` syncs at the first write once the interval has passed since the last sync.
+pub enum SyncPolicy {
+    #[default]
+    Never,
+    EveryRecord,
+    Interval(std::time::Duration),
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// What a rotation does with the old log file, see [logger::set_archive_mode].
+///
+/// - `Compress` hands it to the archiver set with [logger::set_archiver] or compresses it into the
+///   archive directory with the compression set with [logger::set_compression], then removes it.
+/// - `Move` moves it into the archive directory as it is.
+/// - `LeaveInPlace` leaves it next to the new file.
+pub enum ArchiveMode {
+    Compress,
+    Move,
+    LeaveInPlace,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+/// When a log file with several rotations is rotated, see [logger::set_rotation_mode].
+///
+/// - `Any` rotates it as soon as one of its rotations is due (the default).
+/// - `All` rotates it once all of them are due, e.g. on a new day if it exceeds 100 MB.
+pub enum RotationMode {
+    #[default]
+    Any,
+    All,
+}
+
+#[cfg(feature = "checksum")]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// The checksum written next to each archive, see [logger::set_archive_checksum].
+///
+/// - `Sha256` writes `<archive>.sha256` in the format of `sha256sum`.
+pub enum ChecksumAlgo {
+    Sha256,
+}
+
+/// Processes the archives of the rotated log files once they are written, e.g. to upload them,
+/// see [logger::set_archive_handler]. [logger::archivation::MoveToDir] moves them to another
+/// directory.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::ArchiveHandler;
+/// use std::path::Path;
+///
+/// struct Upload;
+///
+/// impl ArchiveHandler for Upload {
+///     fn handle(&self, archive: &Path) -> Result<(), Box<dyn std::error::Error>> {
+///         println!("uploading {}", archive.display());
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait ArchiveHandler: Send + Sync {
+    /// Handles the archive by the given path, an error is reported and the archive is kept or
+    /// removed by the [ArchiveHandlerOptions].
+    fn handle(&self, archive: &Path) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// How the handler set with [logger::set_archive_handler] runs, see
+/// [logger::set_archive_handler_options].
+///
+/// - `keep_on_failure`: the archive is kept when the handler fails (the default), otherwise it is
+///   removed with its checksum.
+/// - `background`: the handler runs on a background thread, one archive after the other, instead
+///   of during the rotation (the default).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct ArchiveHandlerOptions {
+    pub keep_on_failure: bool,
+    pub background: bool,
+}
+
+impl Default for ArchiveHandlerOptions {
+    fn default() -> Self {
+        ArchiveHandlerOptions {
+            keep_on_failure: true,
+            background: false,
+        }
+    }
+}
+
+/// Counters of the logger, see [stats].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Records dropped by the async mode because its queue was full, since the start of the
+    /// program
+    pub dropped_records: u64,
+}
+
+/// An archive of a rotated log file, see [logger::list_archives].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ArchiveInfo {
+    /// Path of the archive, in the archive directory
+    pub path: PathBuf,
+    /// Size of the archive in bytes
+    pub size: u64,
+    /// When the archive was last modified, usually when it was written
+    pub modified: SystemTime,
+    /// Name of the archived log file when the archive is named after it (`app.log` for
+    /// `app.log.zip`, or with an extension allowed with
+    /// [`logger::set_allowed_file_extensions`]), `None` for the tarballs and the names of an
+    /// archive name format not ending with it
+    pub file_name: Option<String>,
+}
+
+/// Calls [logger::shutdown] when it is dropped, see [logger::init_with_guard].
+#[must_use = "the logger is shut down as soon as the guard is dropped"]
+pub struct LoggerGuard {
+    pub(crate) _private: (),
+}
+
+impl Drop for LoggerGuard {
+    fn drop(&mut self) {
+        logger::shutdown();
+    }
+}
+
+/// A log message with everything known about it when it was emitted.
+///
+/// The records are created by the logging macros and given to the custom formatters (see
+/// [logger::set_formatter]). The existing fields are part of the public API and keep their meaning
+/// and type across the minor versions, but new fields may be added: don't rely on the exact set
+/// of fields, e.g. when deserializing records produced by another version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    pub level: Level,
+    pub message: String,
+    /// Module path of the call site, e.g. `my_app::billing`
+    pub module_path: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    /// Name of the thread, or its numeric id if it is unnamed
+    pub thread: String,
+    pub thread_id: String,
+    /// The moment the log was emitted
+    pub timestamp: SystemTime,
+    /// The context fields, see [logger::add_context] and [context::scope]
+    pub context: BTreeMap<String, String>,
+}
+
+/// A user provided function rendering the log records, see [logger::set_formatter]
+type CustomFormatter = Arc<dyn Fn(&LogRecord) -> String + Send + Sync>;
+
+/// A user provided function archiving the rotated log files, see [logger::set_archiver]
+type CustomArchiver = Arc<dyn Fn(&Path) -> Result<(), Box<dyn std::error::Error>> + Send + Sync>;
+
+/// Id of a sink added with [logger::add_sink], used to remove it with [logger::remove_sink].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SinkId(u64);
+
+/// Id of a file output added with [logger::add_file], used to remove it with
+/// [logger::remove_file] or to configure it alone, e.g. with [logger::add_rotation_for].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u64);
+
+/// A log file with its rotations and compression, see [logger::add_file]
+#[derive(Clone)]
+struct FileOutput {
+    id: FileId,
+    manager: Arc<Mutex<FileManager>>,
+    /// The levels written to the file, all of them if not set
+    levels: Option<Vec<Level>>,
+}
+
+impl FileOutput {
+    /// Returns whether the logs of the level are written to the file.
+    fn accepts(&self, level: Level) -> bool {
+        match &self.levels {
+            Some(levels) => levels.contains(&level),
+            None => true,
+        }
+    }
+}
+
+/// A user provided function receiving every emitted record, see [logger::add_sink]
+#[derive(Clone)]
+struct Sink {
+    id: SinkId,
+    callback: Arc<dyn Fn(&LogRecord) + Send + Sync>,
+    /// Set after the first panic of the sink, which is the only one reported
+    panicked: Arc<std::sync::atomic::AtomicBool>,
+}
+
+/// The last records kept in memory, see [logger::enable_ring_buffer]
+struct RingBuffer {
+    capacity: usize,
+    records: VecDeque<LogRecord>,
+}
+
+impl RingBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+    /// Adds the record, dropping the oldest one if the buffer is full.
+    fn push(&mut self, record: LogRecord) {
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+    /// Changes the capacity, keeping the most recent records that fit in it.
+    fn resize(&mut self, capacity: usize) {
+        while self.records.len() > capacity {
+            self.records.pop_front();
+        }
+        self.records.shrink_to(capacity);
+        self.capacity = capacity;
+    }
+}
+
+/// A user provided destination of the terminal output, see [logger::set_writer]
+type CustomWriter = Arc<Mutex<Box<dyn std::io::Write + Send>>>;
+
 #[derive(Clone)]
 struct Config {
     level: Level,
+    /// Minimal level of the terminal output, the log level if not set
+    terminal_level: Option<Level>,
+    /// Minimal level of the file output, the log level if not set
+    file_level: Option<Level>,
     print_to_terminal: bool,
-    colorized: bool,
+    /// The logs of this level and the more important ones are printed to stderr
+    stderr_threshold: Level,
+    color_when: ColorWhen,
+    auto_level_colors: bool,
+    time_settings: TimeSettings,
+    /// Rendering of the multi-line messages in the terminal and in the file
+    multiline: MultilineStyle,
+    file_multiline: MultilineStyle,
+    /// Encoding of the terminal and the file outputs
+    terminal_output_format: OutputFormat,
+    file_output_format: OutputFormat,
+    /// Maximal length of the messages in bytes, 0 if they aren't truncated
+    max_message_len: usize,
+    /// Custom names of the levels, e.g. `AUDIT` instead of `INFO`
+    level_names: BTreeMap<Level, String>,
+    /// Symbols of the levels rendered by `{symbol}`, e.g. `✔`, empty for the levels without one
+    level_symbols: BTreeMap<Level, String>,
+    /// Custom colors of the levels used by the `<level>` tag and the automatic level colors
+    level_colors: BTreeMap<Level, LogColor>,
+    /// Fields added to every log, e.g. `service=billing`
+    context: BTreeMap<String, String>,
     trace_log_format: LogFormatter,
     debug_log_format: LogFormatter,
     info_log_format: LogFormatter,
     warn_log_format: LogFormatter,
     error_log_format: LogFormatter,
-    file_manager: Option<Arc<Mutex<FileManager>>>,
+    /// Formats of the file output by level, the levels without one use the formats above
+    file_log_formats: BTreeMap<Level, LogFormatter>,
+    /// Replaces the formats above when set
+    custom_formatter: Option<CustomFormatter>,
+    /// Functions receiving every emitted record, in the order they were added
+    sinks: Vec<Sink>,
+    /// Replaces stdout and stderr for the terminal output when set
+    writer: Option<CustomWriter>,
+    /// Sends the records to a remote collector when set
+    #[cfg(feature = "tcp")]
+    tcp_sink: Option<logger::tcp_sink::TcpSink>,
+    /// Sends the records to a syslog server when set
+    #[cfg(feature = "syslog")]
+    syslog: Option<logger::syslog::SyslogSink>,
+    /// Writes the records to the Windows Event Log when set
+    #[cfg(feature = "windows-eventlog")]
+    event_log: Option<logger::event_log::EventLogSink>,
+    /// Posts the error records to a webhook when set
+    #[cfg(feature = "http")]
+    webhook: Option<logger::webhook::WebhookSink>,
+    /// The last records, kept only when enabled
+    ring_buffer: Option<Arc<Mutex<RingBuffer>>>,
+    /// Minimal level of the records kept in the ring buffer, the log level if not set
+    ring_buffer_level: Option<Level>,
+    /// The files the logs are written to, in the order they were added
+    files: Vec<FileOutput>,
+    /// Writes the files on a background thread when set
+    async_writer: Option<logger::async_writer::AsyncWriter>,
+    /// What the async writer does when its queue is full
+    overflow_policy: Overflow,
     archive_dir: Option<PathBuf>,
+    /// Names of the archives of the rotated files, `<file name>.zip` (`.gz`, `.zst`) if not set
+    archive_name_format: Option<ArchiveNameFormat>,
+    /// Subdirectory of the archive dir the archives are written to, e.g. one by day
+    archive_subdir_format: Option<ArchiveSubdirFormat>,
+    /// Replaces the compression of the rotated files when set
+    archiver: Option<CustomArchiver>,
+    /// What is done with the rotated files, chosen from the compression and the archive dir if
+    /// not set
+    archive_mode: Option<ArchiveMode>,
+    /// The checksum written next to each archive when set
+    #[cfg(feature = "checksum")]
+    archive_checksum: Option<ChecksumAlgo>,
+    /// Processes the archives once they are written when set
+    archive_handler: Option<Arc<dyn ArchiveHandler>>,
+    archive_handler_options: ArchiveHandlerOptions,
+    /// Age in seconds after which the archives are removed, they are kept forever if not set
+    archive_retention: Option<u64>,
+    /// Total size in bytes the archives may take, the oldest ones are removed beyond it
+    archive_max_total_size: Option<u64>,
+    /// Whether a new log file is started when the file of the pattern already exists
+    rotate_on_startup: bool,
+    /// Whether a file is rotated when one of its rotations is due or all of them
+    rotation_mode: RotationMode,
+    /// Mode of the log files and archives created by the logger, from the umask if not set
+    file_permissions: Option<u32>,
+    /// Extensions of the log files allowed besides `txt` and `log`, without the dot
+    allowed_file_extensions: Vec<String>,
+    /// Age in seconds after which the log files of the pattern are removed by [logger::set_file]
+    purge_on_startup: Option<u64>,
+}
+
+impl Config {
+    /// Returns the manager of the first log file, if any.
+    #[cfg(test)]
+    fn file_manager(&self) -> Option<&Arc<Mutex<FileManager>>> {
+        self.files.first().map(|file| &file.manager)
+    }
+    /// Returns the name of the level as rendered by `{level}`: its custom name if one is set,
+    /// `TRACE`, `DEBUG`, ... otherwise.
+    fn level_name(&self, level: Level) -> String {
+        match self.level_names.get(&level) {
+            Some(name) => name.clone(),
+            None => level.to_string(),
+        }
+    }
+    /// Returns the color of the `<level>` tag for the level: its custom color if one is set,
+    /// red, yellow, green, cyan and gray from ERROR to TRACE otherwise.
+    fn level_color(&self, level: Level) -> LogColor {
+        match self.level_colors.get(&level) {
+            Some(color) => *color,
+            None => match level {
+                Level::ERROR => LogColor::Red,
+                Level::WARN => LogColor::Yellow,
+                Level::INFO => LogColor::Green,
+                Level::DEBUG => LogColor::Rgb(0, 255, 255), // cyan
+                Level::TRACE => LogColor::BrightBlack,
+            },
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             level: Default::default(),
+            terminal_level: None,
+            file_level: None,
             print_to_terminal: true,
-            colorized: true,
+            stderr_threshold: Level::ERROR,
+            color_when: ColorWhen::Auto,
+            auto_level_colors: false,
+            time_settings: Default::default(),
+            multiline: MultilineStyle::Keep,
+            file_multiline: MultilineStyle::Escape,
+            terminal_output_format: OutputFormat::Text,
+            file_output_format: OutputFormat::Text,
+            max_message_len: 0,
+            level_names: BTreeMap::new(),
+            level_symbols: BTreeMap::new(),
+            level_colors: BTreeMap::new(),
+            context: BTreeMap::new(),
             trace_log_format: Default::default(),
             debug_log_format: Default::default(),
             info_log_format: Default::default(),
@@ -262,8 +755,39 @@ impl Default for Config {
                 "<red>[{level}]<red> <blue>({file} {line})<blue> - <red>{message}<red>",
             )
             .unwrap(),
-            file_manager: None,
+            file_log_formats: BTreeMap::new(),
+            custom_formatter: None,
+            sinks: Vec::new(),
+            writer: None,
+            #[cfg(feature = "tcp")]
+            tcp_sink: None,
+            #[cfg(feature = "syslog")]
+            syslog: None,
+            #[cfg(feature = "windows-eventlog")]
+            event_log: None,
+            #[cfg(feature = "http")]
+            webhook: None,
+            ring_buffer: None,
+            ring_buffer_level: None,
+            files: Vec::new(),
+            async_writer: None,
+            overflow_policy: Overflow::Block,
             archive_dir: None,
+            archive_name_format: None,
+            archive_subdir_format: None,
+            archiver: None,
+            archive_mode: None,
+            #[cfg(feature = "checksum")]
+            archive_checksum: None,
+            archive_handler: None,
+            archive_handler_options: ArchiveHandlerOptions::default(),
+            archive_retention: None,
+            archive_max_total_size: None,
+            rotate_on_startup: false,
+            rotation_mode: RotationMode::Any,
+            file_permissions: None,
+            allowed_file_extensions: Vec::new(),
+            purge_on_startup: None,
         }
     }
 }
@@ -281,15 +805,44 @@ impl Display for Level {
     }
 }
 
-static CONFIG: Lazy<RwLock<Config>> = Lazy::new(|| {
-    RwLock::new(Config {
+static CONFIG: Lazy<RwLock<Arc<Config>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(Config {
         ..Default::default()
-    })
+    }))
 });
 
+/// A user provided placeholder, see [logger::register_placeholder]
+type CustomPlaceholder = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// The placeholders registered by the user, by name
+static PLACEHOLDERS: Lazy<RwLock<BTreeMap<String, CustomPlaceholder>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+pub mod context;
 pub mod logger;
 
+/// Returns the id of the current run: 8 random hex characters (e.g. `3fa91c07`), the same for
+/// the whole execution of the program. It is what the `{run_id}` placeholder renders, so the
+/// logs of a run can be found from the id printed or reported elsewhere.
+pub fn run_id() -> &'static str {
+    helper::get_run_id()
+}
+
+/// Returns the counters of the logger, e.g. `stats().dropped_records` tells whether the async
+/// mode dropped records (see [logger::set_overflow_policy]).
+pub fn stats() -> Stats {
+    Stats {
+        dropped_records: logger::async_writer::dropped_records(),
+    }
+}
+
 #[ctor]
 fn library_startup() {
     crate::logger::init_with_imports();
 }
+
+#[dtor]
+fn library_shutdown() {
+    // no waiting on the logger threads at exit, [logger::shutdown] is left to the guard
+    crate::logger::flush_at_exit();
+}
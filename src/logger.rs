@@ -5,42 +5,126 @@
 //! The public macros (`trace!`, `debug!`, `info!`, `warn!`, `error!`) use the internal
 //! handlers to format and print the log message.
 
-use file_handler::file_manager::FileManager;
+use file_handler::{
+    archive_name::{ArchiveNameFormat, ArchiveSubdirFormat},
+    file_manager::FileManager,
+};
 use formatter::{LogColor, LogFormatter};
 use from_env::load_config_from_env;
+use once_cell::sync::Lazy;
 use set_errors::ReadFromConfigFileError;
+#[cfg(feature = "checksum")]
+use set_errors::SetArchiveChecksumError;
+#[cfg(feature = "windows-eventlog")]
+use set_errors::SetSinkError;
+#[cfg(feature = "syslog")]
+use set_errors::SetSyslogError;
+#[cfg(feature = "tcp")]
+use set_errors::SetTcpSinkError;
+#[cfg(feature = "http")]
+use set_errors::SetWebhookError;
 use set_errors::{
-    AccessError, AddRotationError, SetArchiveDirError, SetColorizedError, SetCompressionError,
-    SetFileError, SetLevelFormattingError, SetLogLevelError, SetPrintToTerminalError,
+    AccessError, AddRotationError, AddSinkError, ListArchivesError, ListRotationsError,
+    PurgeArchivesError, PurgeError, RegisterPlaceholderError, RemoveFileError, RemoveRotationError,
+    RemoveSinkError, RotateError, SetAllowedFileExtensionsError, SetArchiveDirError,
+    SetArchiveHandlerError, SetArchiveMaxTotalSizeError, SetArchiveModeError,
+    SetArchiveNameFormatError, SetArchiveRetentionError, SetArchiveSubdirFormatError,
+    SetArchiverError, SetAsyncError, SetAutoLevelColorsError, SetColorWhenError, SetColorizedError,
+    SetCompressionError, SetContextError, SetFileBufferingError, SetFileError,
+    SetFilePermissionsError, SetFileSyncError, SetFormatterError, SetLevelColorError,
+    SetLevelFormattingError, SetLevelNameError, SetLevelSymbolError, SetLogLevelError,
+    SetMaxMessageLenError, SetMultilineError, SetOutputFormatError, SetOutputLevelError,
+    SetOverflowPolicyError, SetPrintToTerminalError, SetPurgeOnStartupError, SetRingBufferError,
+    SetRingBufferLevelError, SetRotateOnStartupError, SetRotationModeError,
+    SetStderrThresholdError, SetTimePrecisionError, SetTimezoneError, SetWriterError,
+    SetZeroPaddingError,
 };
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Once;
+use std::time::Duration;
 use std::{
-    path::PathBuf,
-    sync::{Arc, Mutex, RwLockReadGuard, RwLockWriteGuard},
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLockWriteGuard},
 };
 
+#[cfg(feature = "checksum")]
+use crate::ChecksumAlgo;
 use crate::{
-    helper::{get_current_date_in_string, get_current_time_in_string},
-    Config, Level, CONFIG,
+    helper, ArchiveHandler, ArchiveHandlerOptions, ArchiveInfo, ArchiveMode, BufferPolicy,
+    ColorWhen, Config, CustomPlaceholder, FileId, FileOutput, Level, LogRecord, LoggerGuard,
+    MultilineStyle, OutputFormat, Overflow, RingBuffer, RotationMode, Sink, SinkId, SyncPolicy,
+    TimePrecision, TimeZoneChoice, CONFIG, PLACEHOLDERS,
 };
 //pub(crate) mod formatter;
 pub mod archivation;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) mod archive_handler;
+pub(crate) mod async_writer;
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) mod browser_console;
+pub(crate) mod console;
+#[cfg(feature = "windows-eventlog")]
+pub(crate) mod event_log;
 pub mod file_handler;
+pub mod format_builder;
 pub mod formatter;
 pub mod from_env;
 pub mod from_file_config;
 pub mod set_errors;
+#[cfg(feature = "syslog")]
+pub mod syslog;
+#[cfg(feature = "tcp")]
+pub(crate) mod tcp_sink;
+#[cfg(feature = "http")]
+pub(crate) mod webhook;
 
-struct LogInfo {
-    module_path: String,
-    file: String,
-    line: u32,
-    message: String,
-    level: Level,
+/// Set by [shutdown], the logs are only printed to stderr until the next [init]
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// The moment the logger was initialized, used by the `{elapsed}` placeholders
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+static START: Lazy<std::time::Instant> = Lazy::new(std::time::Instant::now);
+/// `Instant` isn't available in the browser, the system time is used there
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+static START: Lazy<std::time::SystemTime> = Lazy::new(helper::now);
+
+/// Returns the time since the logger was initialized.
+fn elapsed_since_start() -> Duration {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        START.elapsed()
+    }
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        helper::now().duration_since(*START).unwrap_or_default()
+    }
 }
 
 // helper
-fn with_fm<T, E, F>(f: F) -> Result<T, E>
+/// Calls `f` with the manager of every log file, stopping at the first error.
+fn with_fm<E, F>(mut f: F) -> Result<(), E>
+where
+    F: FnMut(&mut FileManager) -> Result<(), E>,
+    E: From<AccessError>,
+{
+    let files = {
+        let cfg_lock = CONFIG.read().map_err(|_| AccessError::LoadConfig)?;
+        cfg_lock.files.clone()
+    };
+    if files.is_empty() {
+        return Err(AccessError::FileNotSet.into());
+    }
+    for file in files {
+        let mut guard = file.manager.lock().unwrap(); // poisoned = panic, fine for logger
+        f(&mut guard)?;
+    }
+    Ok(())
+}
+/// Calls `f` with the manager of the log file added with the given id.
+fn with_fm_id<T, E, F>(id: FileId, f: F) -> Result<T, E>
 where
     F: FnOnce(&mut FileManager) -> Result<T, E>,
     E: From<AccessError>,
@@ -48,9 +132,11 @@ where
     let fm_arc = {
         let cfg_lock = CONFIG.read().map_err(|_| AccessError::LoadConfig)?;
         cfg_lock
-            .file_manager
-            .as_ref()
-            .ok_or(AccessError::FileNotSet)?
+            .files
+            .iter()
+            .find(|file| file.id == id)
+            .ok_or(AccessError::UnknownFile)?
+            .manager
             .clone()
     };
     let mut guard = fm_arc.lock().unwrap(); // poisoned = panic, fine for logger
@@ -58,35 +144,72 @@ where
 }
 
 // -- Getter functions for config --
-fn get_log_level() -> Level {
-    get_config().level
+/// Returns the minimal level of the terminal output.
+fn get_terminal_level(config: &Config) -> Level {
+    config.terminal_level.unwrap_or(config.level)
 }
-fn get_config() -> RwLockReadGuard<'static, Config> {
-    let config_lock = match CONFIG.read() {
-        Ok(r) => r,
+/// Returns the minimal level of the file output.
+fn get_file_level(config: &Config) -> Level {
+    config.file_level.unwrap_or(config.level)
+}
+/// Returns the least important level emitted by one of the outputs.
+fn get_min_output_level(config: &Config) -> Level {
+    config
+        .level
+        .min(get_terminal_level(config))
+        .min(get_file_level(config))
+}
+/// Returns a snapshot of the config. The setters replace the config instead of changing the
+/// snapshots in use, so a log is handled with one config even if it changes meanwhile, and the
+/// lock is only held to clone the `Arc`.
+fn get_config() -> Arc<Config> {
+    match CONFIG.read() {
+        Ok(config) => config.clone(),
         Err(e) => {
             eprintln!("Problem with getting config, here's an error: {}", e);
             panic!("Poisoned lock")
         }
-    };
-
-    config_lock
+    }
 }
 
-fn get_log_format(level: Level) -> LogFormatter {
-    let tmp_cfg = get_config();
+fn get_log_format(config: &Config, level: Level) -> &LogFormatter {
     match level {
-        Level::TRACE => tmp_cfg.trace_log_format.clone(),
-        Level::DEBUG => tmp_cfg.debug_log_format.clone(),
-        Level::INFO => tmp_cfg.info_log_format.clone(),
-        Level::WARN => tmp_cfg.warn_log_format.clone(),
-        Level::ERROR => tmp_cfg.error_log_format.clone(),
+        Level::TRACE => &config.trace_log_format,
+        Level::DEBUG => &config.debug_log_format,
+        Level::INFO => &config.info_log_format,
+        Level::WARN => &config.warn_log_format,
+        Level::ERROR => &config.error_log_format,
+    }
+}
+
+/// Returns the format of the file output for the level, the terminal one if none is set
+fn get_file_log_format(config: &Config, level: Level) -> &LogFormatter {
+    match config.file_log_formats.get(&level) {
+        Some(format) => format,
+        None => get_log_format(config, level),
+    }
+}
+
+/// Write access to the config, the config is copied on the first change if snapshots of it are
+/// still in use (see [get_config]).
+struct ConfigWriteGuard(RwLockWriteGuard<'static, Arc<Config>>);
+
+impl Deref for ConfigWriteGuard {
+    type Target = Config;
+    fn deref(&self) -> &Config {
+        &self.0
+    }
+}
+
+impl DerefMut for ConfigWriteGuard {
+    fn deref_mut(&mut self) -> &mut Config {
+        Arc::make_mut(&mut self.0)
     }
 }
 
-fn get_write_config() -> Option<RwLockWriteGuard<'static, Config>> {
+fn get_write_config() -> Option<ConfigWriteGuard> {
     match CONFIG.write() {
-        Ok(guard) => Some(guard),
+        Ok(guard) => Some(ConfigWriteGuard(guard)),
         Err(e) => {
             eprintln!(
                 "An error while getting the config to write, here's an error: {}",
@@ -106,34 +229,282 @@ fn get_write_config() -> Option<RwLockWriteGuard<'static, Config>> {
 ///  - `{time}` – Current time.
 ///  - `{date}` – Current date.
 ///  - `{level}` - Current loggin level.
-///  - Other literal text.
+///  - `{hostname}` - Name of the machine.
+///  - `{pid}` - Id of the process, e.g. `app_{hostname}_{pid}_{date}.log` for processes sharing
+///    a directory. It stays the same at each rotation.
+///  - `{num}` - Number of the file, from 1, increased when the name is already taken (e.g. by
+///    the previous file of the day at a rotation), zero-padded with `{num:3}` (`001`), up to 10
+///    digits. Without it, a taken name is numbered before the extension: `app_5-3-2025(1).log`.
+///  - `{run_id}` - Random id of the current run, see [crate::run_id].
+///  - `{iso8601}` - Current date and time as RFC 3339, with `-` instead of `:`
+///    (`2025-03-05T09-05-03.123Z`).
+///  - `{env:NAME}` - Value of the `NAME` environment variable.
+///  - Other literal text, with `{{` and `}}` for literal braces.
 ///
 ///- **Allowed values:**  
-///  - The format string **must** end with a text section containing a file extension (e.g. `.txt` or `.log`).  
+///  - The format string **must** end with a text section containing a file extension: `.txt`,
+///    `.log` or one allowed with [set_allowed_file_extensions] (e.g. `.jsonl`).
 ///  - Any forbidden characters such as `<`, `>`, `&`, or `%` will cause configuration to fail.  
+///  - It may start with the directories of the file, relative or absolute, which are created
+///    when they don't exist. Only the name of the file is checked for the forbidden characters
+///    and the extension.
 ///  - *Examples:*  
 ///    - `"app_{date}_{time}.txt"`  
 ///    - `"{level}-log-on-{date}.log"`
+///    - `"logs/{date}/app_{time}.log"`
+///
+/// It replaces the log files set before, see [add_file] to write to several files. The old log
+/// files of the pattern are removed if an age is set with [set_purge_on_startup].
 pub fn set_file(format: &str) -> Result<(), SetFileError> {
-    let file_manager = match FileManager::init_from_string(format, get_config().clone()) {
+    let file = new_file_output(format)?;
+
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        return Err(SetFileError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.files = vec![file];
+    let purge_age = config_lock.purge_on_startup;
+    drop(config_lock);
+
+    if let Some(max_age) = purge_age {
+        if let Err(e) = purge_logs_older_than(max_age) {
+            eprintln!(
+                "Couldn't remove the old log files due to the next error: {}",
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Adds a log file with the given name format (see [set_file]) to the ones the logs are already
+/// written to, e.g. a verbose debug file along with a compact audit file.
+///
+/// The returned id configures the file alone ([add_rotation_for], [set_compression_for]) or
+/// removes it ([remove_file]).
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{add_file, add_rotation_for, set_compression_for};
+///
+/// let debug_file = add_file("debug_{date}.log").unwrap();
+/// let audit_file = add_file("audit_{date}.txt").unwrap();
+/// add_rotation_for(debug_file, "10 MB").unwrap();
+/// add_rotation_for(audit_file, "1 day").unwrap();
+/// set_compression_for(audit_file, "zip").unwrap();
+/// ```
+pub fn add_file(format: &str) -> Result<FileId, SetFileError> {
+    let file = new_file_output(format)?;
+    let id = file.id;
+
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        return Err(SetFileError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.files.push(file);
+
+    Ok(id)
+}
+
+/// Adds a log file (see [add_file]) receiving only the logs of the given levels, e.g. the errors
+/// duplicated into their own file while every log goes to the main one.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{add_file_for_levels, set_file};
+/// use loggit::Level;
+///
+/// set_file("app_{date}.log").unwrap();
+/// add_file_for_levels(&[Level::WARN, Level::ERROR], "errors_{date}.log").unwrap();
+/// loggit::error!("written to both files");
+/// loggit::info!("written to app_<date>.log only");
+/// ```
+pub fn add_file_for_levels(levels: &[Level], format: &str) -> Result<FileId, SetFileError> {
+    let mut file = new_file_output(format)?;
+    file.levels = Some(levels.to_vec());
+    let id = file.id;
+
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        return Err(SetFileError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.files.push(file);
+
+    Ok(id)
+}
+
+/// Stops writing the logs to a file added with [add_file].
+pub fn remove_file(id: FileId) -> Result<(), RemoveFileError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        return Err(RemoveFileError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    match config_lock.files.iter().position(|file| file.id == id) {
+        Some(idx) => {
+            config_lock.files.remove(idx);
+            Ok(())
+        }
+        None => Err(RemoveFileError::UnknownFile),
+    }
+}
+
+fn new_file_output(format: &str) -> Result<FileOutput, SetFileError> {
+    static NEXT_FILE_ID: AtomicU64 = AtomicU64::new(0);
+    let file_manager = match FileManager::init_from_string(format, Config::clone(&get_config())) {
         Ok(r) => r,
         Err(e) => {
-            return Err(SetFileError::UnableToLoadFromString(e));
+            return Err(SetFileError::from(e));
         }
     };
+    Ok(FileOutput {
+        id: FileId(NEXT_FILE_ID.fetch_add(1, Ordering::Relaxed)),
+        manager: Arc::new(Mutex::new(file_manager)),
+        levels: None,
+    })
+}
 
+/// Sets whether a new log file is started when the process starts and the file of the pattern
+/// already exists (e.g. `app_{date}.log` after a restart on the same day). It is `false` by
+/// default: the logs are appended to the existing file.
+///
+/// When it is `true`, the existing file is left as is and the logs go to a new one, numbered if
+/// the pattern gives the same name (`app_16-10-2026(1).log`). The existing file is archived like
+/// a rotated one (see [add_rotation]). It is done at the first log, so the compression and
+/// the rotations set after [set_file] are taken into account; it applies to the files set after
+/// this call.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_compression, set_file, set_rotate_on_startup};
+///
+/// set_rotate_on_startup(true).unwrap();
+/// set_file("service_{date}.log").unwrap();
+/// set_compression("zip").unwrap();
+/// loggit::info!("started"); // the file of the last run is archived
+/// ```
+pub fn set_rotate_on_startup(value: bool) -> Result<(), SetRotateOnStartupError> {
     let config_lock = get_write_config();
     if config_lock.is_none() {
-        return Err(SetFileError::UnableToLoadConfig);
+        eprintln!("An error while getting the config to write!");
+        return Err(SetRotateOnStartupError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.rotate_on_startup = value;
+    Ok(())
+}
+
+/// Allows the log files to have the given extensions besides `txt` and `log`, e.g. `jsonl`,
+/// `ndjson` or `csv`, for the files set after this call with [set_file] or [add_file]. The
+/// extensions are given without the dot (a leading one is ignored) and replace the ones allowed
+/// before, `txt` and `log` are always allowed.
+///
+/// An extension must be made of letters, digits, `-` and `_`, otherwise it fails with
+/// [SetAllowedFileExtensionsError::IncorrectExtensionGiven] and nothing is changed.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_allowed_file_extensions, set_file};
+///
+/// set_allowed_file_extensions(&["jsonl", "ndjson", "csv"]).unwrap();
+/// set_file("audit_{date}.jsonl").unwrap();
+/// ```
+pub fn set_allowed_file_extensions(
+    extensions: &[&str],
+) -> Result<(), SetAllowedFileExtensionsError> {
+    let mut allowed = Vec::with_capacity(extensions.len());
+    for ext in extensions {
+        let ext = ext.trim().trim_start_matches('.');
+        let is_correct = !ext.is_empty()
+            && ext
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+        if !is_correct {
+            return Err(SetAllowedFileExtensionsError::IncorrectExtensionGiven(
+                ext.to_string(),
+            ));
+        }
+        allowed.push(ext.to_string());
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetAllowedFileExtensionsError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.allowed_file_extensions = allowed;
+    Ok(())
+}
+
+/// Sets the permissions of the log files created by the logger, e.g. `0o640` or `0o600` on a
+/// multi-user system, whatever the umask. The archives of the rotated files (and their
+/// checksums) get the same permissions. It applies to the files created after this call, so it
+/// is set before [set_file]; an existing file that is continued keeps its permissions.
+///
+/// The permissions are only supported on unix, elsewhere it fails with
+/// [SetFilePermissionsError::UnsupportedPlatform]. A mode with more than the permission bits
+/// (`0o7777`) fails with [SetFilePermissionsError::IncorrectModeGiven].
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_file, set_file_permissions};
+///
+/// set_file_permissions(0o640).unwrap();
+/// set_file("app_{date}.log").unwrap();
+/// ```
+pub fn set_file_permissions(mode: u32) -> Result<(), SetFilePermissionsError> {
+    if !cfg!(unix) {
+        return Err(SetFilePermissionsError::UnsupportedPlatform);
+    }
+    if mode > 0o7777 {
+        return Err(SetFilePermissionsError::IncorrectModeGiven);
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetFilePermissionsError::UnableToLoadConfig);
     }
     let mut config_lock = config_lock.unwrap();
-    config_lock.file_manager = Some(Arc::new(Mutex::new(file_manager)));
+    config_lock.file_permissions = Some(mode);
+    Ok(())
+}
 
+/// Sets the age after which the log files of the pattern are removed when it is set with
+/// [set_file], written like a period rotation: `"7 day"`, `"12 hour"`, ... It removes the raw log
+/// files left by the previous runs, e.g. after a crash, see [purge_old_logs]. It applies to the
+/// files set after this call, none are removed by default.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_file, set_purge_on_startup};
+///
+/// set_purge_on_startup("7 day").unwrap();
+/// set_file("service_{date}.log").unwrap(); // the files of more than a week ago are removed
+/// ```
+pub fn set_purge_on_startup(age: &str) -> Result<(), SetPurgeOnStartupError> {
+    let secs = match FileManager::parse_age(age) {
+        Some(secs) => secs,
+        None => return Err(SetPurgeOnStartupError::IncorrectAgeGiven),
+    };
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetPurgeOnStartupError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.purge_on_startup = Some(secs);
     Ok(())
 }
 
 /// ### Sets a directory to save archives of used log files
 ///
+/// Without a compression, the rotated log files are moved there uncompressed. It applies from the
+/// next rotation, even if archives were already written to the previous directory. See
+/// [set_archive_subdir_format] to sort the archives in subdirectories by date.
+///
 /// *Example*:
 /// ```rs
 /// let _ = set_archive_dir("log_archives");
@@ -154,6 +525,285 @@ pub fn set_archive_dir(dir: &str) -> Result<PathBuf, SetArchiveDirError> {
     Ok(path)
 }
 
+/// Sets what a rotation does with the old log file:
+///
+/// - [ArchiveMode::Compress]: it is handed to the archiver set with [set_archiver] or compressed
+///   into the archive directory with the compression set with [set_compression], then removed.
+///   Without any, the rotation reports an error and the file is left in place.
+/// - [ArchiveMode::Move]: it is moved into the archive directory as it is (the default one if
+///   none was set with [set_archive_dir]), e.g. for another system to compress it later. It is
+///   copied and removed if it can't be renamed there, e.g. on another file system.
+/// - [ArchiveMode::LeaveInPlace]: it is left next to the new file.
+///
+/// Without a mode, the old file is compressed if a compression or an archiver is set, moved if
+/// only an archive directory is set, and left in place otherwise.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_archive_dir, set_archive_mode};
+/// use loggit::ArchiveMode;
+///
+/// set_archive_dir("archives").unwrap();
+/// set_archive_mode(ArchiveMode::Move).unwrap();
+/// ```
+pub fn set_archive_mode(mode: ArchiveMode) -> Result<(), SetArchiveModeError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveModeError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_mode = Some(mode);
+    Ok(())
+}
+
+/// Sets the checksum written next to each archive once the old log file is compressed, e.g.
+/// [ChecksumAlgo::Sha256] writes `<archive>.sha256` with the hash and the name of the archive in
+/// the format of `sha256sum`, so `sha256sum -c app.log.zip.sha256` checks it. `None` stops
+/// writing them.
+///
+/// The archive is kept if its checksum can't be written, the error is printed. The checksum is
+/// removed along with its archive by [purge_archives]. The archives of an archiver set with
+/// [set_archiver] have no checksum.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_archive_checksum, set_compression, set_file};
+/// use loggit::ChecksumAlgo;
+///
+/// set_file("app_{date}_{time}.log").unwrap();
+/// set_compression("zip").unwrap();
+/// set_archive_checksum(Some(ChecksumAlgo::Sha256)).unwrap();
+/// ```
+#[cfg(feature = "checksum")]
+pub fn set_archive_checksum(algo: Option<ChecksumAlgo>) -> Result<(), SetArchiveChecksumError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveChecksumError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_checksum = algo;
+    Ok(())
+}
+
+/// Sets the names of the archives of the rotated log files (`<file name>.zip`, `.gz` or `.zst` by
+/// default), the pattern may contain:
+/// - `{name}`: the name of the rotated file without its extension, e.g. `app_16-10-2026`
+/// - `{ext}`: its extension, e.g. `log`
+/// - `{num}`: a number making the name unique, from 1
+/// - the placeholders of the file names, e.g. `{date}`, `{time}` or `{hostname}`, rendered at
+///   the rotation
+///
+/// The pattern gives the whole name, the extension of the archive included. If an archive
+/// already has the name, the next archives are numbered (`{num}` is increased, or `(1)`, `(2)`,
+/// ... is added before the last extension). The archives stay in the archive directory: the
+/// pattern can't contain a path separator. The tarballs of `"tar.gz"` keep their names.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_archive_name_format, set_compression, set_file};
+///
+/// set_file("app.log").unwrap();
+/// set_compression("zip").unwrap();
+/// // e.g. `app_16-10-2026_12:30:00.zip`
+/// set_archive_name_format("{name}_{date}_{time}.zip").unwrap();
+/// ```
+pub fn set_archive_name_format(format: &str) -> Result<(), SetArchiveNameFormatError> {
+    let format = ArchiveNameFormat::try_from_string(format)?;
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveNameFormatError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_name_format = Some(format);
+    Ok(())
+}
+
+/// Sets the subdirectory of the archive directory (see [set_archive_dir]) the archives are written
+/// to, so they are sorted by day or by month instead of piling up in one directory. The pattern
+/// is a relative path rendered at each rotation with the placeholders of the file names, e.g.
+/// `{date}` for a directory by day, `{date:%Y-%m}` by month or `{date:%Y}/{date:%m}` for nested
+/// ones; the directories are created when the first archive is written there.
+///
+/// The retention age (see [set_archive_retention_age]) and the total size of the archives (see
+/// [set_archive_max_total_size]) apply to the archives of all the subdirectories, and a
+/// subdirectory left empty by them is removed.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_archive_dir, set_archive_subdir_format, set_compression, set_file};
+///
+/// set_file("app.log").unwrap();
+/// set_compression("zip").unwrap();
+/// set_archive_dir("archives").unwrap();
+/// // e.g. `archives/2026-10/app.log.zip`
+/// set_archive_subdir_format("{date:%Y-%m}").unwrap();
+/// ```
+pub fn set_archive_subdir_format(format: &str) -> Result<(), SetArchiveSubdirFormatError> {
+    let format = ArchiveSubdirFormat::try_from_string(format)?;
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveSubdirFormatError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_subdir_format = Some(format);
+    Ok(())
+}
+
+/// Sets the age after which the archives of the log files are removed, written like a period
+/// rotation: `"30 day"`, `"12 hour"`, `"2 week"`, `"6 month"` or `"1 year"`.
+///
+/// The old archives are removed each time a log file is rotated, or by [purge_archives]. Only the
+/// archives named like the ones of a log file are removed (the fixed start of the file name and
+/// its extension followed by `.zip`, `.gz` or `.zst`, e.g. `app_*.log.zip` for `app_{date}.log`,
+/// or the tarballs starting the same way), the other files of the archive directory are never
+/// touched. The archives are kept forever by default.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_archive_retention_age, set_compression, set_file};
+///
+/// set_file("app_{date}.log").unwrap();
+/// set_compression("zip").unwrap();
+/// set_archive_retention_age("30 day").unwrap();
+/// ```
+pub fn set_archive_retention_age(age: &str) -> Result<(), SetArchiveRetentionError> {
+    let secs = match FileManager::parse_age(age) {
+        Some(secs) => secs,
+        None => return Err(SetArchiveRetentionError::IncorrectAgeGiven),
+    };
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveRetentionError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_retention = Some(secs);
+    Ok(())
+}
+
+/// Sets the total size the archives of the log files may take, written like a size rotation:
+/// `"500 KB"`, `"200 MB"`, `"1 GB"` or `"1 TB"`.
+///
+/// Each time a log file is rotated (or by [purge_archives]) the sizes of its archives are summed
+/// and the oldest ones are removed until the total fits, even the newest one if it is bigger
+/// than the size on its own. The same archives as for [set_archive_retention_age] are
+/// considered; if both are set the archives older than the age are removed first, then the size
+/// is applied to the remaining ones.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_archive_max_total_size, set_compression, set_file};
+///
+/// set_file("app_{date}.log").unwrap();
+/// set_compression("zip").unwrap();
+/// set_archive_max_total_size("200 MB").unwrap();
+/// ```
+pub fn set_archive_max_total_size(size: &str) -> Result<(), SetArchiveMaxTotalSizeError> {
+    let bytes = match FileManager::parse_size(size) {
+        Some(bytes) => bytes,
+        None => return Err(SetArchiveMaxTotalSizeError::IncorrectSizeGiven),
+    };
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveMaxTotalSizeError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_max_total_size = Some(bytes);
+    Ok(())
+}
+
+/// Removes the archives older than the age set with [set_archive_retention_age] and the oldest
+/// ones beyond the size set with [set_archive_max_total_size], returns how many were removed.
+/// Nothing is removed if none is set.
+pub fn purge_archives() -> Result<usize, PurgeArchivesError> {
+    let config = CONFIG
+        .read()
+        .map_err(|_| PurgeArchivesError::UnableToLoadConfig)?
+        .clone();
+    let mut removed = 0;
+    for file in &config.files {
+        let fm = file.manager.lock().unwrap(); // poisoned = panic, fine for logger
+        removed += fm.purge_archives(&config)?;
+    }
+    Ok(removed)
+}
+
+/// Removes the log files of the patterns set with [set_file] and [add_file] last modified more
+/// than `max_age` ago, written like a period rotation (`"7 day"`), returns how many were
+/// removed. The files being written to are kept.
+///
+/// It removes the raw log files left by the previous runs, never rotated because the process
+/// crashed or the pattern changed. The directory of the pattern is searched (e.g. `logs/` for
+/// `logs/app_{date}.log`): its texts must match, each placeholder matches what it may render
+/// (`{date}` and `{time}` by their format, `{pid}` and `{num}` by digits, `{level}` by the names
+/// of the levels), and the numbered names (`app_16-10-2026(1).log`) match too. The archives
+/// aren't considered, see [purge_archives].
+///
+/// Nothing is removed if the name of a pattern has no fixed text besides its extension
+/// (`logs/{date}.log`), the files of others could match it.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{purge_old_logs, set_file};
+///
+/// set_file("app_{date}.log").unwrap();
+/// let removed = purge_old_logs("7 day").unwrap();
+/// println!("{removed} old log files removed");
+/// ```
+pub fn purge_old_logs(max_age: &str) -> Result<usize, PurgeError> {
+    match FileManager::parse_age(max_age) {
+        Some(secs) => purge_logs_older_than(secs),
+        None => Err(PurgeError::IncorrectAgeGiven),
+    }
+}
+
+/// Removes the log files of the patterns older than `max_age` seconds, see [purge_old_logs].
+fn purge_logs_older_than(max_age: u64) -> Result<usize, PurgeError> {
+    let config = CONFIG
+        .read()
+        .map_err(|_| PurgeError::UnableToLoadConfig)?
+        .clone();
+    if config.files.is_empty() {
+        return Err(PurgeError::FileIsntSet);
+    }
+    let active: Vec<String> = config
+        .files
+        .iter()
+        .map(|file| file.manager.lock().unwrap().get_file_name()) // poisoned = panic
+        .collect();
+    // poisoned = panic, fine for logger
+    if !config
+        .files
+        .iter()
+        .all(|file| file.manager.lock().unwrap().has_fixed_text())
+    {
+        return Err(PurgeError::NoFixedTextInPattern);
+    }
+    let mut removed = 0;
+    for file in &config.files {
+        let fm = file.manager.lock().unwrap(); // poisoned = panic, fine for logger
+        removed += fm.purge_old_logs(max_age, &active, &config)?;
+    }
+    Ok(removed)
+}
+
+/// Returns the archives of the rotated files in the archive dir (the one set with
+/// [set_archive_dir] or the default one) and its subdirectories when a format is set with
+/// [set_archive_subdir_format], the oldest first. Only the `.zip`, `.gz` and `.zst` files are
+/// listed, the archives of a custom archiver set with [set_archiver] may be missing.
+pub fn list_archives() -> Result<Vec<ArchiveInfo>, ListArchivesError> {
+    let config = CONFIG
+        .read()
+        .map_err(|_| ListArchivesError::UnableToLoadConfig)?
+        .clone();
+    Ok(FileManager::list_archives(&config)?)
+}
+
 /// ### Loads config from the given file
 ///
 /// #### Supported file extensions:
@@ -167,20 +817,33 @@ pub fn set_archive_dir(dir: &str) -> Result<PathBuf, SetArchiveDirError> {
 /// level: str
 /// print_to_terminal: bool
 /// colorized: bool
+/// color_when: str ("auto", "always" or "never")
+/// terminal_output_format: str ("text" or "json", same for file_output_format)
 /// global_formatting: str
 /// trace_formatting: str
 /// debug_formatting: str
 /// info_formatting: str
 /// warn_formatting: str
 /// error_formatting: str
+/// level_name_trace: str (same for debug, info, warn and error, e.g. level_name_info = "AUDIT")
 ///
 /// file_name: str
-/// compression: str
+/// compression: str ("zip", "gzip", "tar.gz" with the `tar-gz` feature or "zstd" with the `zstd`
+/// feature)
 /// rotations: arr[str]
 /// archive_dir: str
+/// archive_max_total_size: str (e.g. "200 MB")
+/// archive_name_format: str (e.g. "{name}_{date}_{time}.zip")
+/// archive_subdir_format: str (e.g. "{date:%Y-%m}")
+/// archive_mode: str ("compress", "move" or "leave_in_place")
+/// rotation_mode: str ("any" or "all")
+/// rotate_on_startup: bool
+/// purge_on_startup: str (e.g. "7 day")
+/// file_permissions: str (octal, e.g. "0640")
+/// allowed_file_extensions: arr[str] (e.g. ["jsonl", "csv"])
 /// ```
-/// > Note: For the `ini` and `env` files, for rotations you should write a single string with ','
-/// > divisor, example:
+/// > Note: For the `ini` and `env` files, for rotations and allowed_file_extensions you should
+/// > write a single string with ',' divisor, example:
 /// ```
 /// rotations = "1 week, 12 MB, 12:30"
 /// ```
@@ -209,8 +872,8 @@ pub fn set_archive_dir(dir: &str) -> Result<PathBuf, SetArchiveDirError> {
 ///     ]
 /// }
 /// ```
-/// > Note: in a json file you must pass an array of string for rotations (not as in other files
-/// > where you pass it with ',' (coma))
+/// > Note: in a json file you must pass an array of string for rotations and
+/// > allowed_file_extensions (not as in other files where you pass it with ',' (coma))
 ///
 /// **Example for a `env` file:**
 /// ```env
@@ -221,7 +884,7 @@ pub fn set_archive_dir(dir: &str) -> Result<PathBuf, SetArchiveDirError> {
 /// archive_dir="archives_loggit"
 /// ```
 pub fn load_config_from_file(path: &str) -> Result<(), ReadFromConfigFileError> {
-    let curr_conf = get_config().clone();
+    let curr_conf = Config::clone(&get_config());
 
     match crate::logger::from_file_config::load_config_from_file(path) {
         Ok(_) => Ok(()),
@@ -247,8 +910,21 @@ pub fn load_config_from_file(path: &str) -> Result<(), ReadFromConfigFileError>
 ///  Sets the compression type for log files. After file logging is configured, you can enable compression to archive old logs.
 ///
 ///- **Allowed values:**  
-///  - Accepts only a single allowed value: `"zip"`.  
+///  - `"zip"`: each old file is compressed into `<name>.zip` in the archive directory.  
+///  - `"gzip"` (or `"gz"`): each old file is compressed into `<name>.gz`, readable with `zcat` or
+///    `gzip -d`.  
+///  - `"tar.gz"` (or `"tgz"`, with the `tar-gz` feature): the old files of a day are bundled in
+///    one tarball, `<prefix>logs-<date>.tar.gz` where the prefix is the fixed start of the file
+///    name (e.g. `app-logs-16-10-2026.tar.gz` for `app-{time}.log`). A compressed tarball can't be
+///    appended to, so at each rotation it is rewritten with the new file into a temporary file
+///    which then replaces it: the tarball is never left half written, but the day's archives
+///    are rewritten each time. An entry with a name already in the tarball is numbered
+///    (`app(1).log`).  
+///  - `"zstd"` (with the `zstd` feature): each old file is compressed into `<name>.zst` at the
+///    zstd level 3, a better ratio and speed than zip and gzip for the logs.  
 ///  - Any other string will output an error and leave the compression configuration unchanged.
+///
+/// It applies to every log file, see [set_compression_for] to set it for one file.
 pub fn set_compression(ctype: &str) -> Result<(), SetCompressionError> {
     with_fm(|fm| {
         if fm.set_compression(ctype) {
@@ -259,95 +935,1640 @@ pub fn set_compression(ctype: &str) -> Result<(), SetCompressionError> {
     })
 }
 
-///Adds a new constraint for rotating log files.
+/// Enables the compression (see [set_compression]) of the log file added with [add_file] only.
+pub fn set_compression_for(id: FileId, ctype: &str) -> Result<(), SetCompressionError> {
+    with_fm_id(id, |fm| {
+        if fm.set_compression(ctype) {
+            Ok(())
+        } else {
+            Err(SetCompressionError::IncorrectCompressionValue)
+        }
+    })
+}
+
+/// Sets a function archiving the rotated log files, replacing the compression (see
+/// [set_compression]) of every log file, e.g. to hand them to `xz` or to an uploader.
+///
+/// At a rotation the new log file is created first, then the function gets the path of the old
+/// one. If it succeeds, the old file is removed (unless the function moved it away already); if
+/// it fails, the old file is kept and the error is reported with its message. The function is
+/// called while the log file is locked, so it must not log to that file itself, the other files
+/// are fine (also from the writer thread of the async mode, see [set_async]). Use
+/// [remove_archiver] to come back to the compression.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_archiver;
+/// use std::process::Command;
+///
+/// set_archiver(|path| {
+///     let status = Command::new("xz").arg("--keep").arg(path).status()?;
+///     if !status.success() {
+///         return Err(format!("xz exited with {status}").into());
+///     }
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn set_archiver<F>(f: F) -> Result<(), SetArchiverError>
+where
+    F: Fn(&Path) -> Result<(), Box<dyn std::error::Error>> + Send + Sync + 'static,
+{
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiverError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archiver = Some(Arc::new(f));
+    Ok(())
+}
+
+/// Removes the function set with [set_archiver], the rotated files are compressed again.
+pub fn remove_archiver() -> Result<(), SetArchiverError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiverError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archiver = None;
+    Ok(())
+}
+
+/// Sets a handler processing the archives of the rotated log files once they are written, e.g. to
+/// upload them or to run a script, along with their checksum (see [set_archive_checksum]).
+///
+/// It gets the path of each archive produced by the compression (see [set_compression]), not the
+/// files moved or left in place, nor the ones given to the archiver set with [set_archiver]. A
+/// handler failure doesn't fail the rotation, it is reported on stderr and the archive is kept,
+/// unless [ArchiveHandlerOptions::keep_on_failure] is disabled. By default the handler runs during
+/// the rotation, while the log file is locked: it must not log to the files itself, and a slow
+/// handler is better run in the background ([ArchiveHandlerOptions::background]), in which case
+/// [flush] waits for it. Use [remove_archive_handler] to stop handling the archives.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::archivation::MoveToDir;
+/// use loggit::logger::{set_archive_handler, set_compression, set_file};
+/// use std::sync::Arc;
+///
+/// set_file("app_{date}_{time}.log").unwrap();
+/// set_compression("zip").unwrap();
+/// set_archive_handler(Arc::new(MoveToDir::new("/mnt/backup/logs"))).unwrap();
+/// ```
+pub fn set_archive_handler(handler: Arc<dyn ArchiveHandler>) -> Result<(), SetArchiveHandlerError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveHandlerError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_handler = Some(handler);
+    Ok(())
+}
+
+/// Sets how the handler set with [set_archive_handler] runs: whether the archive is kept when it
+/// fails (the default) and whether it runs on a background thread rather than during the
+/// rotation (the default).
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_archive_handler_options;
+/// use loggit::ArchiveHandlerOptions;
+///
+/// set_archive_handler_options(ArchiveHandlerOptions {
+///     background: true,
+///     ..Default::default()
+/// })
+/// .unwrap();
+/// ```
+pub fn set_archive_handler_options(
+    options: ArchiveHandlerOptions,
+) -> Result<(), SetArchiveHandlerError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveHandlerError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_handler_options = options;
+    Ok(())
+}
+
+/// Removes the handler set with [set_archive_handler], the archives stay in the archive
+/// directory again.
+pub fn remove_archive_handler() -> Result<(), SetArchiveHandlerError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetArchiveHandlerError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.archive_handler = None;
+    Ok(())
+}
+
+/// Sets when the log files are written: each record as it is logged ([BufferPolicy::PerRecord],
+/// the default) or in batches ([BufferPolicy::Batched]), which is much faster on slow or network
+/// file systems.
+///
+/// In the batched mode the records are kept in memory until `max_bytes` are buffered or the
+/// oldest one has waited `max_delay`. The delay is checked when a record is logged, no thread
+/// writes the batch in the background: call [flush] to write the buffered records, it is done by
+/// [shutdown] and when the file is removed or rotated. The buffered records count in the size of
+/// the file, so the size rotations happen at the same record as without batching.
+///
+/// If the process crashes (e.g. it is killed or aborts on a panic), the buffered records are
+/// lost: up to `max_bytes` of logs, the last ones, which are often the ones explaining the crash.
+///
+/// It applies to every log file, see [set_file_buffering_for] to set it for one file.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{flush, set_file, set_file_buffering};
+/// use loggit::BufferPolicy;
+/// use std::time::Duration;
+///
+/// set_file("batched_{date}.txt").unwrap();
+/// set_file_buffering(BufferPolicy::Batched {
+///     max_bytes: 64 * 1024,
+///     max_delay: Duration::from_secs(1),
+/// })
+/// .unwrap();
+/// loggit::info!("buffered");
+/// flush().unwrap(); // written to the file
+/// ```
+pub fn set_file_buffering(policy: BufferPolicy) -> Result<(), SetFileBufferingError> {
+    with_fm(|fm| Ok(fm.set_buffer_policy(policy)?))
+}
+
+/// Sets when the log file added with [add_file] is written, see [set_file_buffering].
+pub fn set_file_buffering_for(
+    id: FileId,
+    policy: BufferPolicy,
+) -> Result<(), SetFileBufferingError> {
+    with_fm_id(id, |fm| Ok(fm.set_buffer_policy(policy)?))
+}
+
+/// Sets when the records written to the log files are synced to the disk.
+///
+/// By default ([SyncPolicy::Never]) a written record may stay in the OS page cache for a while
+/// and be lost if the machine goes down. With [SyncPolicy::EveryRecord] each write is followed
+/// by a sync, the record is durable once the log call returns, at the cost of a disk round-trip
+/// per log. [SyncPolicy::Interval] syncs at the first write once the interval has passed since
+/// the last sync, no thread is spawned: a record is synced at the latest by the next write after
+/// the deadline, by [flush], or when the file is rotated or removed. With batched writes (see
+/// [set_file_buffering]) the sync follows the write of the batch.
+///
+/// The records not synced yet are synced at once. It applies to every log file, see
+/// [set_file_sync_for] to set it for one file.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{set_file, set_file_sync};
+/// use loggit::SyncPolicy;
+///
+/// set_file("audit_{date}.txt").unwrap();
+/// set_file_sync(SyncPolicy::EveryRecord).unwrap();
+/// loggit::warn!("user 42 deleted the project"); // on the disk when the macro returns
+/// ```
+pub fn set_file_sync(policy: SyncPolicy) -> Result<(), SetFileSyncError> {
+    with_fm(|fm| Ok(fm.set_sync_policy(policy)?))
+}
+
+/// Sets when the log file added with [add_file] is synced to the disk, see [set_file_sync].
+pub fn set_file_sync_for(id: FileId, policy: SyncPolicy) -> Result<(), SetFileSyncError> {
+    with_fm_id(id, |fm| Ok(fm.set_sync_policy(policy)?))
+}
+
+///Adds a new constraint for rotating log files.
+///
+///- **Description:**  
+///  Adds a rotation strategy so that log files are rotated based on either time or file size. When a log file “expires” under the configured constraint, a new file is automatically created (and optionally compressed).
+///
+///- **The old file:**  
+///  With a compression (see [set_compression]) it is compressed into the archive directory and
+///  removed. Without one it is moved into the archive directory as it is if one was set with
+///  [set_archive_dir], and left in place otherwise. [set_archive_mode] chooses it explicitly.
+///
+///- **Allowed values:**  
+///  The `constraint` string can be in one of the following formats:
+///  - **Period rotation:**  
+///    - Numeric value followed by a unit:  
+///      - `"30 second"`, `"5 minute"`, `"1 hour"`, `"2 day"`, `"33 week"`, `"6 month"`, `"12 year"`  
+///      - The unit isn't case sensitive, the space before it is optional (e.g. `"2hours"`) and
+///        the plural form is accepted as well (e.g. `"2 days"`).
+///      - A `month` is 30 days and a `year` 365 days from the start, see `"monthly"` and
+///        `"yearly"` to follow the calendar.
+///  - **Time-based rotation:**  
+///    - Time in a 24‑hour format using a colon separator:  
+///      - `"HH:MM"` (e.g. `"12:30"`).
+///    - `"daily"` (or `"midnight"`) rotates at 00:00 each day and `"hourly"` at the start of
+///      each hour, on the clock of the time zone (see [set_timezone]). The keywords aren't case
+///      sensitive. Unlike `"1 day"`, which rotates 24 hours after the start, the file follows
+///      the calendar day.
+///    - A weekday and a time, optionally after `"weekly"`: `"sunday 03:00"` or
+///      `"weekly sunday 03:00"` rotates every sunday at 03:00 on the clock of the time zone. The
+///      weekday may be abbreviated (`"sun 03:00"`) and isn't case sensitive.
+///    - `"monthly"`, optionally followed by a day of the month and a time: `"monthly"` rotates
+///      on the 1st of each month at 00:00, `"monthly 15 03:00"` on the 15th at 03:00, on the
+///      clock of the time zone. A day missing from the month (e.g. the 31st) is the last day of
+///      that month, and the day of the next months stays the given one.
+///    - `"yearly"`, optionally followed by a month and a day: `"yearly"` rotates on the 1st of
+///      january at 00:00, `"yearly 07-01"` on the 1st of july at 00:00, on the clock of the time
+///      zone. `"yearly 02-29"` rotates on the 28th of february out of the leap years.
+///  - **Size-based rotation:**  
+///    - Numeric value followed by a size unit:  
+///      - `"500 KB"`, `"5 MB"`, `"1 GB"`, or `"2 TB"`  
+///      - The unit isn't case sensitive and the space before it is optional (e.g. `"5mb"`).
+///      - The value may be decimal (e.g. `"1.5 GB"`), it is rounded down to bytes.
+///    - The file is rotated before a record would make it exceed the size, so the files stay
+///      within it. A single record longer than the size is written to a new file on its own,
+///      which is rotated at the next record.
+///
+///- If an incorrect value is provided, the rotation is not added and
+///  [AddRotationError::IncorrectFormatGiven] tells what was wrong.
+///
+///- It applies to every log file, see [add_rotation_for] to add it to one file.
+///
+///- [add_rotation_period], [add_rotation_size_bytes] and [add_rotation_at] add the same rotations
+///  from typed values.
+///
+///- With several rotations the file is rotated as soon as one of them is due, then they all start
+///  again; [set_rotation_mode] waits for all of them instead.
+pub fn add_rotation(constraint: &str) -> Result<(), AddRotationError> {
+    let timezone = get_config().time_settings.timezone;
+    with_fm(|fm| {
+        fm.add_rotation(constraint, timezone)
+            .map_err(AddRotationError::IncorrectFormatGiven)
+    })
+}
+
+/// Adds a rotation (see [add_rotation]) to the log file added with [add_file] only.
+pub fn add_rotation_for(id: FileId, constraint: &str) -> Result<(), AddRotationError> {
+    let timezone = get_config().time_settings.timezone;
+    with_fm_id(id, |fm| {
+        fm.add_rotation(constraint, timezone)
+            .map_err(AddRotationError::IncorrectFormatGiven)
+    })
+}
+
+/// Adds a rotation every `period` (see the period rotations of [add_rotation]) to every log file,
+/// in whole seconds: the fraction of a second is dropped and a period shorter than a second is
+/// an [AddRotationError::IncorrectValueGiven].
+///
+/// **Example:**
+///
+/// ```rust,no_run
+/// use loggit::logger::{add_rotation_period, set_file};
+/// use std::time::Duration;
+///
+/// set_file("app_{date}_{time}.log").unwrap();
+/// add_rotation_period(Duration::from_secs(6 * 60 * 60)).unwrap(); // same as "6 hours"
+/// ```
+pub fn add_rotation_period(period: std::time::Duration) -> Result<(), AddRotationError> {
+    let timezone = get_config().time_settings.timezone;
+    with_fm(|fm| {
+        fm.add_rotation_period(period, timezone)
+            .map_err(AddRotationError::IncorrectValueGiven)
+    })
+}
+
+/// Adds a rotation once the file would exceed `bytes` (see the size rotations of [add_rotation])
+/// to every log file. A size of 0 is an [AddRotationError::IncorrectValueGiven].
+///
+/// **Example:**
+///
+/// ```rust,no_run
+/// use loggit::logger::{add_rotation_size_bytes, set_file};
+///
+/// set_file("app_{date}_{time}.log").unwrap();
+/// add_rotation_size_bytes(50 * 1024 * 1024).unwrap(); // same as "50 MB"
+/// ```
+pub fn add_rotation_size_bytes(bytes: u64) -> Result<(), AddRotationError> {
+    let timezone = get_config().time_settings.timezone;
+    with_fm(|fm| {
+        fm.add_rotation_size(bytes, timezone)
+            .map_err(AddRotationError::IncorrectValueGiven)
+    })
+}
+
+/// Adds a rotation each day at `hour`:`minute` on the clock of the time zone (see the time
+/// rotations of [add_rotation]) to every log file. An hour over 23 or a minute over 59 is an
+/// [AddRotationError::IncorrectValueGiven].
+///
+/// **Example:**
+///
+/// ```rust,no_run
+/// use loggit::logger::{add_rotation_at, set_file};
+///
+/// set_file("app_{date}.log").unwrap();
+/// add_rotation_at(12, 30).unwrap(); // same as "12:30"
+/// assert!(add_rotation_at(25, 0).is_err());
+/// ```
+pub fn add_rotation_at(hour: u8, minute: u8) -> Result<(), AddRotationError> {
+    let timezone = get_config().time_settings.timezone;
+    with_fm(|fm| {
+        fm.add_rotation_at(hour, minute, timezone)
+            .map_err(AddRotationError::IncorrectValueGiven)
+    })
+}
+
+/// Removes every rotation of every log file.
+pub fn remove_rotations() -> Result<(), RemoveRotationError> {
+    with_fm(|fm| {
+        fm.remove_rotations();
+        Ok(())
+    })
+}
+
+/// Sets when a log file with several rotations is rotated: as soon as one of them is due
+/// ([RotationMode::Any], the default) or once all of them are ([RotationMode::All]). Either way
+/// all the rotations start again from the rotation.
+///
+/// With [RotationMode::All], `"daily"` and `"100 MB"` rotate the file on a new day if it exceeds
+/// 100 MB: a rotation due before the others stays due, e.g. the file of a quiet day keeps growing
+/// on the next days until it reaches the size. It applies to every log file.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{add_rotation, set_file, set_rotation_mode};
+/// use loggit::RotationMode;
+///
+/// set_file("app_{date}_{time}.log").unwrap();
+/// add_rotation("daily").unwrap();
+/// add_rotation("100 MB").unwrap();
+/// set_rotation_mode(RotationMode::All).unwrap();
+/// ```
+pub fn set_rotation_mode(mode: RotationMode) -> Result<(), SetRotationModeError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetRotationModeError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.rotation_mode = mode;
+    Ok(())
+}
+
+/// Removes the rotations that are the same as the given one (see [add_rotation] for the format)
+/// from every log file. The strings don't have to be identical, `"2 weeks"` removes a rotation
+/// added with `"14 day"`.
+///
+/// **Example:**
+///
+/// ```rust,no_run
+/// use loggit::logger::{add_rotation, list_rotations, remove_rotation, set_file};
+///
+/// set_file("app_{date}.log").unwrap();
+/// add_rotation("1 day").unwrap();
+/// add_rotation("500 MB").unwrap();
+/// remove_rotation("24 hours").unwrap();
+/// assert_eq!(list_rotations().unwrap(), vec!["500 MB".to_string()]);
+/// ```
+///
+/// Returns [RemoveRotationError::RotationNotFound] if no log file has such a rotation.
+pub fn remove_rotation(constraint: &str) -> Result<(), RemoveRotationError> {
+    let mut removed = false;
+    with_fm::<RemoveRotationError, _>(|fm| {
+        removed |= fm
+            .remove_rotation(constraint)
+            .map_err(RemoveRotationError::IncorrectFormatGiven)?;
+        Ok(())
+    })?;
+    if removed {
+        Ok(())
+    } else {
+        Err(RemoveRotationError::RotationNotFound)
+    }
+}
+
+/// Returns the rotations of the log files, each written once in the largest unit that fits it
+/// (e.g. `"2 week"`, `"500 MB"`, `"12:30"`, `"hourly"` or `"sunday 03:00"`), in the order they were
+/// added.
+pub fn list_rotations() -> Result<Vec<String>, ListRotationsError> {
+    let mut rotations: Vec<String> = Vec::new();
+    with_fm::<ListRotationsError, _>(|fm| {
+        for rotation in fm.list_rotations() {
+            if !rotations.contains(&rotation) {
+                rotations.push(rotation);
+            }
+        }
+        Ok(())
+    })?;
+    Ok(rotations)
+}
+
+/// Rotates every log file at once, whatever its rotations, e.g. right before uploading the logs:
+/// a new file is started, the old one is archived as on a rotation (see [set_archive_mode]) and
+/// the rotations start again from now. The records being logged by the other threads are written
+/// either to the old file or to the new one, none is lost. In the async mode (see [set_async])
+/// the records still queued are written to the new file, call [flush] first to keep them in the
+/// old one.
+///
+/// **Example:**
+///
+/// ```rust,no_run
+/// use loggit::logger::{rotate_now, set_compression, set_file};
+///
+/// set_file("app_{date}_{time}.log").unwrap();
+/// set_compression("zip").unwrap();
+/// rotate_now().unwrap(); // the logs so far are in the archive directory
+/// ```
+pub fn rotate_now() -> Result<(), RotateError> {
+    let config = get_config();
+    with_fm(|fm| {
+        fm.rotate_now(&config)
+            .map_err(|e| RotateError::UnableToRotate(e.to_string()))
+    })
+}
+
+/// Rotates the log file added with [add_file] only, see [rotate_now].
+pub fn rotate_now_for(id: FileId) -> Result<(), RotateError> {
+    let config = get_config();
+    with_fm_id(id, |fm| {
+        fm.rotate_now(&config)
+            .map_err(|e| RotateError::UnableToRotate(e.to_string()))
+    })
+}
+
+/// Sets the minimum log level to display.
+/// Messages with a level lower than the given level will be ignored.
+///
+/// **Example:**
+///
+/// ```rust
+/// use loggit::logger;
+/// use loggit::Level;
+///
+/// logger::set_log_level(Level::DEBUG);
+///
+/// logger::TRACE!("trace mess"); // this will not be printed or written to a file
+/// logger::DEBUG!("debug mess"); // this will
+/// logger::INFO!("debug mess");
+/// ```
+///
+/// **Level hierarchy:**
+/// 1. ERROR
+/// 2. WARN
+/// 3. INFO
+/// 4. DEBUG
+/// 5. TRACE
+///
+/// The levels are written in the most important to less important, i.e, if you set a level, the
+/// ones below the set won't be printed or written to the file as shown in the example above (`TRACE`
+/// hasn't been taken into account as it's below the `DEBUG` in the hierarchy).
+///
+/// The terminal and the file outputs can have their own level, see [set_terminal_level] and
+/// [set_file_level], the log level applies to the ones without it and to the other outputs.
+pub fn set_log_level(lvl: Level) -> Result<(), SetLogLevelError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLogLevelError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.level = lvl;
+
+    Ok(())
+}
+/// Sets the minimal level of the terminal output, e.g. INFO in the terminal while the file gets
+/// the DEBUG logs too. Until it is set the terminal uses the log level (see [set_log_level]).
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{set_file_level, set_log_level, set_terminal_level};
+/// use loggit::Level;
+///
+/// set_log_level(Level::INFO).unwrap();
+/// set_file_level(Level::DEBUG).unwrap();
+/// set_terminal_level(Level::WARN).unwrap();
+/// ```
+pub fn set_terminal_level(lvl: Level) -> Result<(), SetOutputLevelError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetOutputLevelError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.terminal_level = Some(lvl);
+    Ok(())
+}
+/// Sets the minimal level of the file output, see [set_terminal_level]. It applies to every log
+/// file, the files added with [add_file_for_levels] still get only their levels.
+pub fn set_file_level(lvl: Level) -> Result<(), SetOutputLevelError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetOutputLevelError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.file_level = Some(lvl);
+    Ok(())
+}
+/// Sets the least important level printed to stderr instead of stdout, ERROR by default.
+///
+/// With `Level::WARN` the warnings and the errors go to stderr, so they stay visible when stdout
+/// is redirected to a file.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_stderr_threshold;
+/// use loggit::Level;
+///
+/// set_stderr_threshold(Level::WARN).unwrap();
+/// ```
+pub fn set_stderr_threshold(lvl: Level) -> Result<(), SetStderrThresholdError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetStderrThresholdError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.stderr_threshold = lvl;
+    Ok(())
+}
+/// Enables or disables terminal output of log messages.
+/// When set to false, log messages will not be printed to the terminal.
+pub fn set_print_to_terminal(val: bool) -> Result<(), SetPrintToTerminalError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetPrintToTerminalError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.print_to_terminal = val;
+    Ok(())
+}
+/// Enables or disables colorized output of log messages.
+/// If enabled, logs will be printed with colors as configured in the format.
+///
+/// This is a shortcut for [set_color_when]: `true` maps to [ColorWhen::Auto] (colors are used
+/// only when the output is a terminal) and `false` maps to [ColorWhen::Never].
+pub fn set_colorized(val: bool) -> Result<(), SetColorizedError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetColorizedError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.color_when = match val {
+        true => ColorWhen::Auto,
+        false => ColorWhen::Never,
+    };
+    Ok(())
+}
+
+/// Sets when the terminal output should be colorized.
+///
+/// - [ColorWhen::Auto] (default): colors are used only if the output stream (`stdout`, or
+///   `stderr` for errors) is a terminal, so piping the output to a file or through `grep`
+///   doesn't produce escape codes.
+/// - [ColorWhen::Always]: colors are always used, even when the output is redirected.
+/// - [ColorWhen::Never]: colors are never used.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_color_when;
+/// use loggit::ColorWhen;
+///
+/// set_color_when(ColorWhen::Always).unwrap();
+/// ```
+pub fn set_color_when(val: ColorWhen) -> Result<(), SetColorWhenError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetColorWhenError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.color_when = val;
+    Ok(())
+}
+
+/// Sets the encoding of the terminal output: [OutputFormat::Text] (the default) renders the logs
+/// with the formats, [OutputFormat::Json] writes one JSON object per log and [OutputFormat::Csv]
+/// one CSV row per log.
+pub fn set_terminal_output_format(format: OutputFormat) -> Result<(), SetOutputFormatError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetOutputFormatError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.terminal_output_format = format;
+    Ok(())
+}
+
+/// Sets the encoding of the file output, e.g. JSON Lines for ingestion while the terminal stays
+/// readable, see [set_terminal_output_format].
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_file_output_format;
+/// use loggit::OutputFormat;
+///
+/// set_file_output_format(OutputFormat::Json).unwrap();
+/// ```
+pub fn set_file_output_format(format: OutputFormat) -> Result<(), SetOutputFormatError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetOutputFormatError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.file_output_format = format;
+    Ok(())
+}
+
+/// Enables or disables the async mode: the log files are written by a background thread, so a
+/// slow disk doesn't stall the threads that log. The records are queued rendered, up to 1024 of
+/// them, see [set_async_with_capacity] to choose the size of the queue.
+///
+/// Disabling it writes the queued records first, the next logs are written by the logging
+/// thread. The logs of the writer thread itself, e.g. of an archiver (see [set_archiver]) called
+/// during a rotation, are written at once instead of waiting for a slot of the queue.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{flush, set_async, set_file};
+///
+/// set_file("app_{date}.txt").unwrap();
+/// set_async(true).unwrap();
+/// loggit::info!("written by the writer thread");
+/// flush().unwrap(); // the log is in the file now
+/// ```
+pub fn set_async(enabled: bool) -> Result<(), SetAsyncError> {
+    match enabled {
+        true => set_async_with_capacity(async_writer::DEFAULT_CAPACITY),
+        false => {
+            stop_async_writer();
+            Ok(())
+        }
+    }
+}
+
+/// Enables the async mode (see [set_async]) with a queue of `capacity` records.
+///
+/// When the queue is full, e.g. the disk can't keep up with a burst of logs, the logging call
+/// waits for the writer thread to free a slot, unless another policy is chosen with
+/// [set_overflow_policy].
+pub fn set_async_with_capacity(capacity: usize) -> Result<(), SetAsyncError> {
+    if capacity == 0 {
+        return Err(SetAsyncError::ZeroCapacity);
+    }
+    // the records queued by a previous writer are written first, so the order is kept
+    stop_async_writer();
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetAsyncError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.async_writer = Some(async_writer::AsyncWriter::start(capacity)?);
+    Ok(())
+}
+
+/// Sets what the async mode (see [set_async]) does when its queue is full: wait for a free slot
+/// (the default), drop the new record or drop the oldest queued one.
+///
+/// The dropped records are counted in [stats](crate::stats), and a `dropped N records due to
+/// backpressure` warning is written to the files once the queue is empty again.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{set_async, set_overflow_policy};
+/// use loggit::Overflow;
+///
+/// set_async(true).unwrap();
+/// set_overflow_policy(Overflow::DropNewest).unwrap();
+/// loggit::debug!("dropped rather than waited for if the disk is too slow");
+/// println!("{} records dropped", loggit::stats().dropped_records);
+/// ```
+pub fn set_overflow_policy(policy: Overflow) -> Result<(), SetOverflowPolicyError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetOverflowPolicyError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.overflow_policy = policy;
+    Ok(())
+}
+
+/// Writes the records queued in the async mode (finishing their rotations and compressions),
+/// flushes the terminal output and stops the logger: the next logs are only printed to stderr,
+/// until [init] is called again.
+///
+/// It is called when the guard returned by [init_with_guard] is dropped, call it explicitly if
+/// the program can't rely on it, e.g. before `std::process::exit`. When the process exits, the
+/// files and the terminal are only flushed: the records still queued in the async mode are lost.
+pub fn shutdown() {
+    stop_async_writer();
+    if let Err(e) = flush() {
+        eprintln!(
+            "Couldn't flush the terminal output due to the next error: {}",
+            e
+        );
+    }
+    SHUT_DOWN.store(true, Ordering::Relaxed);
+}
+
+/// Flushes the files and the terminal when the process exits. Unlike [shutdown], it doesn't wait
+/// for the writer thread of the async mode nor for the archiving thread: they can be blocked at
+/// that point, e.g. by an archiver or a handler that never returns, and the process would never
+/// exit.
+pub(crate) fn flush_at_exit() {
+    let config = get_config();
+    for file in &config.files {
+        // a file written by the writer thread at the same time is left to it
+        if let Ok(mut fm) = file.manager.try_lock() {
+            let _ = fm.flush_buffer();
+            let _ = fm.sync_pending();
+        }
+    }
+    let _ = match &config.writer {
+        Some(writer) => flush_writer(writer),
+        None => std::io::stdout()
+            .flush()
+            .and_then(|_| std::io::stderr().flush()),
+    };
+}
+
+/// Writes the records queued in the async mode, stops the writer thread and goes back to writing
+/// the files on the logging thread.
+fn stop_async_writer() {
+    let writer = match get_write_config() {
+        Some(mut config) => config.async_writer.take(),
+        None => None,
+    };
+    // the config isn't held while waiting, the writer thread reads it
+    if let Some(writer) = writer {
+        writer.shutdown();
+    }
+}
+
+/// Sets the maximal length of the messages in bytes, 0 disables the limit (the default).
+///
+/// The longer messages are cut at a character boundary and end with a marker telling how many
+/// bytes were removed, e.g. `aaaa… [truncated 1048320 bytes]`, so a huge message can't produce a
+/// huge line in the terminal or in the file. The limit applies to `{message}`, the rest of the
+/// format is not counted.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_max_message_len;
+///
+/// set_max_message_len(4096).unwrap();
+/// ```
+pub fn set_max_message_len(max_len: usize) -> Result<(), SetMaxMessageLenError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetMaxMessageLenError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.max_message_len = max_len;
+    Ok(())
+}
+
+/// Sets how the messages spanning several lines are rendered, in the terminal and in the file.
+///
+/// By default the terminal shows the messages as they are ([MultilineStyle::Keep]) while the
+/// file escapes the line breaks ([MultilineStyle::Escape]) so one log stays one line; use
+/// [set_file_multiline] after this function to keep a different style for the file.
+///
+/// With `"[{level}] {message}"` and the message `"first\nsecond"`:
+/// - [MultilineStyle::Keep] gives `[INFO] first` and `second`
+/// - [MultilineStyle::Indent] gives `[INFO] first` and `       second`
+/// - [MultilineStyle::PrefixEach] gives `[INFO] first` and `[INFO] second`
+/// - [MultilineStyle::Escape] gives `[INFO] first\nsecond`
+///
+/// The logs rendered with [set_formatter] are left as the function returns them.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_multiline;
+/// use loggit::MultilineStyle;
+///
+/// set_multiline(MultilineStyle::Indent).unwrap();
+/// ```
+pub fn set_multiline(style: MultilineStyle) -> Result<(), SetMultilineError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetMultilineError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.multiline = style;
+    config_lock.file_multiline = style;
+    Ok(())
+}
+
+/// Sets how the messages spanning several lines are written to the file only, see
+/// [set_multiline]. The default is [MultilineStyle::Escape].
+pub fn set_file_multiline(style: MultilineStyle) -> Result<(), SetMultilineError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetMultilineError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.file_multiline = style;
+    Ok(())
+}
+
+/// Enables or disables automatic per-level colors.
+///
+/// When enabled, the parts of the terminal output that have no color tag get a color depending on
+/// the level of the message: red for `ERROR`, yellow for `WARN`, cyan for `DEBUG`, gray for
+/// `TRACE` and the default terminal color for `INFO`. Color tags given in the format always win
+/// over the automatic color, and the logs written to files are never colored.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{set_auto_level_colors, set_global_formatting};
+///
+/// set_global_formatting("[{level}] {message}").unwrap();
+/// set_auto_level_colors(true).unwrap();
+/// ```
+pub fn set_auto_level_colors(val: bool) -> Result<(), SetAutoLevelColorsError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetAutoLevelColorsError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.auto_level_colors = val;
+    Ok(())
+}
+
+/// Sets the precision of the `{time}` placeholder.
+///
+/// With [TimePrecision::Millis] the time is rendered as `09:05:03.042` instead of `09:05:03`, both in the
+/// log messages and in the file names, so events of the same second can be ordered and rotated
+/// files created within the same second get different names.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_time_precision;
+/// use loggit::TimePrecision;
+///
+/// set_time_precision(TimePrecision::Millis).unwrap();
+/// ```
+pub fn set_time_precision(val: TimePrecision) -> Result<(), SetTimePrecisionError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetTimePrecisionError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.time_settings.precision = val;
+    Ok(())
+}
+
+/// Enables or disables the zero padding of the `{date}` and `{time}` placeholders.
+///
+/// Enabled by default: dates and times are rendered as `05-03-2025` and `09:05:03`, in the log
+/// messages and in the file names. Disable it to get the unpadded `5-3-2025` and `9:5:3` of the
+/// previous versions, e.g. if you depend on the old file names.
+pub fn set_zero_padding(val: bool) -> Result<(), SetZeroPaddingError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetZeroPaddingError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.time_settings.zero_padded = val;
+    Ok(())
+}
+
+/// Sets the time zone of the `{date}` and `{time}` placeholders, of the file names and of the
+/// time of day rotations, so that a `"12:30"` rotation happens at 12:30 on the clock shown in the
+/// logs.
+///
+/// The default is [TimeZoneChoice::Utc]. A [TimeZoneChoice::FixedOffset] must be strictly between
+/// -24h and +24h. The rotations added before the change keep their current deadline.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_timezone;
+/// use loggit::TimeZoneChoice;
+///
+/// set_timezone(TimeZoneChoice::Local).unwrap();
+/// set_timezone(TimeZoneChoice::FixedOffset(2 * 3600)).unwrap(); // UTC+2
+/// ```
+pub fn set_timezone(val: TimeZoneChoice) -> Result<(), SetTimezoneError> {
+    if let TimeZoneChoice::FixedOffset(secs) = val {
+        if chrono::FixedOffset::east_opt(secs).is_none() {
+            return Err(SetTimezoneError::IncorrectOffset(secs));
+        }
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetTimezoneError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.time_settings.timezone = val;
+    Ok(())
+}
+
+/// Sets a custom name for the level, used wherever `{level}` is rendered: terminal output, files
+/// and file names. An empty name restores the default one.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_level_name;
+/// use loggit::Level;
+///
+/// set_level_name(Level::INFO, "AUDIT").unwrap();
+/// set_level_name(Level::ERROR, "FATAL").unwrap();
+/// ```
+pub fn set_level_name(level: Level, name: &str) -> Result<(), SetLevelNameError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLevelNameError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    match name.is_empty() {
+        true => config_lock.level_names.remove(&level),
+        false => config_lock.level_names.insert(level, name.to_string()),
+    };
+    Ok(())
+}
+
+/// Sets the symbol of the level rendered by the `{symbol}` placeholder, e.g. `✔` or `⚠`. The
+/// levels have no symbol by default, and an empty symbol removes the one of the level.
+///
+/// The symbol is stored as it is given, so a width specifier like `{symbol:<2}` counts its
+/// characters and not the columns it takes in the terminal: a wide emoji may be misaligned with
+/// the narrow symbols of other levels.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{set_global_formatting, set_level_symbol};
+/// use loggit::Level;
+///
+/// set_level_symbol(Level::INFO, "✔").unwrap();
+/// set_level_symbol(Level::WARN, "⚠").unwrap();
+/// set_level_symbol(Level::ERROR, "✖").unwrap();
+/// set_global_formatting("<level>{symbol}<level> {message}").unwrap();
+/// ```
+pub fn set_level_symbol(level: Level, symbol: &str) -> Result<(), SetLevelSymbolError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLevelSymbolError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    match symbol.is_empty() {
+        true => config_lock.level_symbols.remove(&level),
+        false => config_lock.level_symbols.insert(level, symbol.to_string()),
+    };
+    Ok(())
+}
+
+/// Sets the color of the `<level>` tag for the level, also used for it by the automatic level
+/// colors (see [set_auto_level_colors]). The color is given as in the color tags: a name
+/// (`red`, `bright_black`, ...), a hex or rgb value (`#ff8800`, `rgb(255,136,0)`) or a palette
+/// color (`c208`).
+///
+/// The default colors are red for ERROR, yellow for WARN, green for INFO, cyan for DEBUG and
+/// gray for TRACE.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{set_global_formatting, set_level_color};
+/// use loggit::Level;
+///
+/// set_global_formatting("<level>[{level}]<level> {message}").unwrap();
+/// set_level_color(Level::INFO, "#5fafff").unwrap();
+/// ```
+pub fn set_level_color(level: Level, color: &str) -> Result<(), SetLevelColorError> {
+    let color = match LogColor::try_from_str(color) {
+        Some(LogColor::Level) | None => {
+            return Err(SetLevelColorError::IncorrectColorGiven(color.to_string()))
+        }
+        Some(c) => c,
+    };
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLevelColorError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.level_colors.insert(level, color);
+    Ok(())
+}
+
+/// Restores the default names of all the levels, see [set_level_name].
+pub fn reset_level_names() -> Result<(), SetLevelNameError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLevelNameError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.level_names.clear();
+    Ok(())
+}
+
+/// Adds a field to the context of the logs, or replaces its value. The context fields are
+/// rendered by the `{context}` placeholder as `key=value` pairs, e.g.
+/// `service=billing version=1.4.2`, and given to the custom formatters (see [set_formatter]).
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{add_context, set_global_formatting};
+///
+/// add_context("service", "billing").unwrap();
+/// add_context("version", "1.4.2").unwrap();
+/// set_global_formatting("[{level}] {message} {context}").unwrap();
+/// ```
+pub fn add_context(key: &str, value: &str) -> Result<(), SetContextError> {
+    if key.is_empty() {
+        return Err(SetContextError::EmptyKey);
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetContextError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock
+        .context
+        .insert(key.to_string(), value.to_string());
+    Ok(())
+}
+
+/// Removes a field from the context of the logs, see [add_context].
+pub fn remove_context(key: &str) -> Result<(), SetContextError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetContextError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.context.remove(key);
+    Ok(())
+}
+
+/// Removes all the fields from the context of the logs, see [add_context].
+pub fn clear_context() -> Result<(), SetContextError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetContextError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.context.clear();
+    Ok(())
+}
+
+/// Registers a placeholder rendered by the given function, e.g. `{tenant}` after
+/// `register_placeholder("tenant", ...)`. The function is called each time a log using the
+/// placeholder is rendered.
+///
+/// The placeholder must be registered before setting the formats using it, which otherwise are
+/// rejected. The name can contain letters, digits and `_` and can't be the one of a placeholder
+/// of the crate. Registering an existing name replaces its function. The registered placeholders
+/// accept a width specifier (`{tenant:<10}`) and can't be used in file names.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{register_placeholder, set_global_formatting};
+///
+/// register_placeholder("tenant", || "acme".to_string()).unwrap();
+/// set_global_formatting("[{level}] {tenant}: {message}").unwrap();
+/// ```
+pub fn register_placeholder<F>(name: &str, f: F) -> Result<(), RegisterPlaceholderError>
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    let is_name_valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !formatter::LogPart::is_builtin(name);
+    if !is_name_valid {
+        return Err(RegisterPlaceholderError::IncorrectNameGiven(
+            name.to_string(),
+        ));
+    }
+    let mut placeholders = match PLACEHOLDERS.write() {
+        Ok(guard) => guard,
+        Err(_) => return Err(RegisterPlaceholderError::UnableToLoadConfig),
+    };
+    placeholders.insert(name.to_string(), Arc::new(f));
+    Ok(())
+}
+
+/// Removes a placeholder added with [register_placeholder]. The formats that still use it
+/// render it as an empty string.
+pub fn unregister_placeholder(name: &str) -> Result<(), RegisterPlaceholderError> {
+    let mut placeholders = match PLACEHOLDERS.write() {
+        Ok(guard) => guard,
+        Err(_) => return Err(RegisterPlaceholderError::UnableToLoadConfig),
+    };
+    placeholders.remove(name);
+    Ok(())
+}
+
+pub(crate) fn is_placeholder_registered(name: &str) -> bool {
+    match PLACEHOLDERS.read() {
+        Ok(placeholders) => placeholders.contains_key(name),
+        Err(_) => false,
+    }
+}
+
+/// Renders a registered placeholder, an empty string if it isn't registered anymore
+fn render_placeholder(name: &str) -> String {
+    // the function is called without holding the registry, so it can use the logger
+    let placeholder: Option<CustomPlaceholder> = match PLACEHOLDERS.read() {
+        Ok(placeholders) => placeholders.get(name).cloned(),
+        Err(_) => None,
+    };
+    match placeholder {
+        Some(f) => f(),
+        None => String::new(),
+    }
+}
+
+/// Sets a function rendering the logs, replacing the formats (see [set_level_formatting]) for
+/// both terminal and file output.
+///
+/// The function gets a [LogRecord] and returns the line to print, the colors are then up to it:
+/// no escape codes are added. Use [remove_formatter] to come back to the formats.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::set_formatter;
+///
+/// set_formatter(|record| format!("{} {}:{} {}", record.level, record.file, record.line, record.message))
+///     .unwrap();
+/// ```
+pub fn set_formatter<F>(f: F) -> Result<(), SetFormatterError>
+where
+    F: Fn(&LogRecord) -> String + Send + Sync + 'static,
+{
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetFormatterError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.custom_formatter = Some(Arc::new(f));
+    Ok(())
+}
+
+/// Adds a function called with every emitted record, after it is printed and written to the
+/// file, e.g. to count the errors or to show the logs in a console. The records below the log
+/// level (see [set_log_level]) are not emitted.
+///
+/// The sinks are called in the order they were added, the returned id removes the sink with
+/// [remove_sink]. A panic of a sink is caught so it doesn't stop the logging, and only its first
+/// panic is reported (the panic hook isn't called for the panics of the sinks).
+///
+/// *Example*:
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use loggit::logger::{add_sink, remove_sink};
+/// use loggit::Level;
+///
+/// let errors = Arc::new(AtomicUsize::new(0));
+/// let counter = errors.clone();
+/// let id = add_sink(move |record| {
+///     if record.level == Level::ERROR {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///     }
+/// })
+/// .unwrap();
+/// loggit::error!("something failed");
+/// remove_sink(id).unwrap();
+/// ```
+pub fn add_sink<F>(f: F) -> Result<SinkId, AddSinkError>
+where
+    F: Fn(&LogRecord) + Send + Sync + 'static,
+{
+    static NEXT_SINK_ID: AtomicU64 = AtomicU64::new(0);
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(AddSinkError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    let id = SinkId(NEXT_SINK_ID.fetch_add(1, Ordering::Relaxed));
+    config_lock.sinks.push(Sink {
+        id,
+        callback: Arc::new(f),
+        panicked: Arc::new(AtomicBool::new(false)),
+    });
+    Ok(id)
+}
+
+/// Adds a sink sending a copy of every emitted record to the returned receiver, e.g. to check
+/// the logs in a test or to forward them from another thread.
+///
+/// Once the receiver is dropped the sink removes itself, the logging goes on.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::channel_sink;
+///
+/// let receiver = channel_sink().unwrap();
+/// loggit::info!("captured");
+/// assert_eq!(receiver.recv().unwrap().message, "captured");
+/// ```
+pub fn channel_sink() -> Result<std::sync::mpsc::Receiver<LogRecord>, AddSinkError> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    add_channel_sink(sender)?;
+    Ok(receiver)
+}
+
+/// Adds a sink sending a copy of every emitted record to the given sender, see [channel_sink].
+pub fn add_channel_sink(
+    sender: std::sync::mpsc::Sender<LogRecord>,
+) -> Result<SinkId, AddSinkError> {
+    let own_id = Arc::new(std::sync::OnceLock::new());
+    let sink_id = own_id.clone();
+    let id = add_sink(move |record| {
+        if sender.send(record.clone()).is_err() {
+            // the receiver was dropped
+            if let Some(id) = sink_id.get() {
+                let _ = remove_sink(*id);
+            }
+        }
+    })?;
+    let _ = own_id.set(id);
+    Ok(id)
+}
+
+/// Removes a sink added with [add_sink].
+pub fn remove_sink(id: SinkId) -> Result<(), RemoveSinkError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(RemoveSinkError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    match config_lock.sinks.iter().position(|sink| sink.id == id) {
+        Some(idx) => {
+            config_lock.sinks.remove(idx);
+            Ok(())
+        }
+        None => Err(RemoveSinkError::UnknownSink),
+    }
+}
+
+/// Keeps the last `capacity` records in memory, to be read with [recent_logs] (e.g. to show them
+/// in a crash report).
+///
+/// The records are kept whether they are printed to the terminal, written to the file or not.
+/// When the buffer is full the oldest record is dropped, so it never holds more than `capacity`
+/// records. Enabling it again changes the capacity and keeps the most recent records.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{enable_ring_buffer, recent_logs};
+///
+/// enable_ring_buffer(100).unwrap();
+/// loggit::info!("started");
+/// for record in recent_logs() {
+///     println!("{}: {}", record.level, record.message);
+/// }
+/// ```
+pub fn enable_ring_buffer(capacity: usize) -> Result<(), SetRingBufferError> {
+    if capacity == 0 {
+        return Err(SetRingBufferError::ZeroCapacity);
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetRingBufferError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    match &config_lock.ring_buffer {
+        Some(buffer) => buffer.lock().unwrap().resize(capacity),
+        None => config_lock.ring_buffer = Some(Arc::new(Mutex::new(RingBuffer::new(capacity)))),
+    }
+    Ok(())
+}
+
+/// Stops keeping the records in memory and drops the ones kept by [enable_ring_buffer].
+pub fn disable_ring_buffer() -> Result<(), SetRingBufferError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetRingBufferError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.ring_buffer = None;
+    Ok(())
+}
+
+/// Sets the minimal level of the records kept by [enable_ring_buffer], which can be below the log
+/// level: with `Some(Level::DEBUG)` and the INFO log level, the DEBUG records aren't printed but
+/// they are kept to be shown after an error. `None` keeps the records of the log level and above
+/// (the default).
+pub fn set_ring_buffer_level(level: Option<Level>) -> Result<(), SetRingBufferLevelError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetRingBufferLevelError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.ring_buffer_level = level;
+    Ok(())
+}
+
+/// Returns the records kept by [enable_ring_buffer], from the oldest to the most recent one, or
+/// nothing if it isn't enabled.
+pub fn recent_logs() -> Vec<LogRecord> {
+    let buffer = get_config().ring_buffer.clone();
+    match buffer {
+        Some(buffer) => buffer.lock().unwrap().records.iter().cloned().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Ships the logs to a remote collector listening at `addr` (`host:port`) over TCP, one record
+/// per line rendered with the file format (so a JSON file format sends JSON lines). Requires the
+/// `tcp` feature.
+///
+/// The connection is opened with the first record. The records are sent by a background thread,
+/// so the logging never waits for the network: if the collector is unreachable the thread
+/// reconnects with an exponential backoff (up to 30 seconds) and keeps the last records in the
+/// meantime. The failures are reported once to stderr until the connection succeeds again.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::set_tcp_sink;
+///
+/// set_tcp_sink("collector.internal:6000").unwrap();
+/// loggit::info!("sent to the collector");
+/// ```
+#[cfg(feature = "tcp")]
+pub fn set_tcp_sink(addr: &str) -> Result<(), SetTcpSinkError> {
+    let valid = match addr.rsplit_once(':') {
+        Some((host, port)) => !host.is_empty() && port.parse::<u16>().is_ok(),
+        None => false,
+    };
+    if !valid {
+        return Err(SetTcpSinkError::IncorrectAddressGiven(addr.to_string()));
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetTcpSinkError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.tcp_sink = Some(tcp_sink::TcpSink::start(addr)?);
+    Ok(())
+}
+
+/// Stops shipping the logs set with [set_tcp_sink], the records not sent yet are dropped.
+#[cfg(feature = "tcp")]
+pub fn remove_tcp_sink() -> Result<(), SetTcpSinkError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetTcpSinkError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.tcp_sink = None;
+    Ok(())
+}
+
+/// Posts the records of `min_level` and above to a webhook, e.g. a Slack or Discord incoming
+/// webhook to get alerted on the errors. Requires the `http` feature.
+///
+/// Each request is a small JSON object with the `timestamp`, `level`, `message` and `location`
+/// (`file:line`) fields, plus a `text` and a `content` summary that Slack and Discord display.
+/// The requests are sent by a background thread, at most one every 10 seconds: the records
+/// emitted in the meantime are counted in the `suppressed` field of the next request. The
+/// failures never block the logging, they are reported once to stderr until a request succeeds
+/// again.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::set_error_webhook;
+/// use loggit::Level;
+///
+/// set_error_webhook("https://hooks.slack.com/services/T000/B000/XXXX", Level::ERROR).unwrap();
+/// loggit::error!("the payment service is down");
+/// ```
+#[cfg(feature = "http")]
+pub fn set_error_webhook(url: &str, min_level: Level) -> Result<(), SetWebhookError> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(SetWebhookError::IncorrectUrlGiven(url.to_string()));
+    }
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetWebhookError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    let sink = webhook::WebhookSink::start(url, min_level, webhook::MIN_INTERVAL)?;
+    config_lock.webhook = Some(sink);
+    Ok(())
+}
+
+/// Stops posting the records to the webhook set with [set_error_webhook], the record held back by
+/// the interval is still posted at once with the count of the ones coalesced into it.
+#[cfg(feature = "http")]
+pub fn remove_error_webhook() -> Result<(), SetWebhookError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetWebhookError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.webhook = None;
+    Ok(())
+}
+
+/// Sends the logs to a syslog server over UDP, along with the terminal and file outputs.
+/// Requires the `syslog` feature.
+///
+/// The records are formatted per RFC 5424 with the timestamp, the hostname, the application name
+/// and the process id. The levels map to the severities `err` (ERROR), `warning` (WARN), `info`
+/// (INFO) and `debug` (DEBUG and TRACE). The messages that don't fit in `max_len` bytes are
+/// truncated with a `… [truncated N bytes]` marker. The send failures are reported once to
+/// stderr until a send succeeds again.
+///
+/// It can also be set from a config file with the `syslog_addr`, `syslog_facility`
+/// (`user`, `daemon`, `local0`, ...) and `syslog_app_name` keys.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::set_syslog;
+/// use loggit::logger::syslog::{Facility, SyslogConfig};
+///
+/// set_syslog(SyslogConfig {
+///     addr: "logs.internal:514".to_string(),
+///     facility: Facility::Local0,
+///     app_name: "billing".to_string(),
+///     ..Default::default()
+/// })
+/// .unwrap();
+/// loggit::info!("sent to syslog");
+/// ```
+#[cfg(feature = "syslog")]
+pub fn set_syslog(config: syslog::SyslogConfig) -> Result<(), SetSyslogError> {
+    // the address is resolved before taking the config
+    let sink = syslog::SyslogSink::open(config)?;
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetSyslogError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.syslog = Some(sink);
+    Ok(())
+}
+
+/// Stops sending the logs to the syslog server set with [set_syslog].
+#[cfg(feature = "syslog")]
+pub fn remove_syslog() -> Result<(), SetSyslogError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetSyslogError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.syslog = None;
+    Ok(())
+}
+
+/// Writes the logs to the Windows Event Log as events of the `source_name` source, rendered with
+/// the file format. Requires the `windows-eventlog` feature.
 ///
-///- **Description:**  
-///  Adds a rotation strategy so that log files are rotated based on either time or file size. When a log file “expires” under the configured constraint, a new file is automatically created (and optionally compressed).
+/// ERROR records are written as error events, WARN records as warning events and the other
+/// levels as information events; the records filtered out by the log level aren't written.
+/// Registering the source fails on other platforms than Windows.
 ///
-///- **Allowed values:**  
-///  The `constraint` string can be in one of the following formats:
-///  - **Period rotation:**  
-///    - Numeric value followed by a unit:  
-///      - `"1 hour"`, `"2 day"`, `"33 week"`, `"6 month"`, `"12 year"`  
-///      - The unit is case sensitive and must match exactly (e.g. `" hour"`, `" day"`, etc.).
-///  - **Time-based rotation:**  
-///    - Time in a 24‑hour format using a colon separator:  
-///      - `"HH:MM"` (e.g. `"12:30"`).
-///  - **Size-based rotation:**  
-///    - Numeric value followed by a size unit:  
-///      - `"500 KB"`, `"5 MB"`, `"1 GB"`, or `"2 TB"`  
-///      - Note the space before the unit.
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::set_windows_event_log;
 ///
-///- If an incorrect value is provided, the rotation is not added and an error message is logged.
-pub fn add_rotation(constraint: &str) -> Result<(), AddRotationError> {
-    with_fm(|fm| {
-        if fm.add_rotation(constraint) {
-            Ok(())
-        } else {
-            Err(AddRotationError::IncorrectFormatGiven)
-        }
-    })
+/// set_windows_event_log("MyService").unwrap();
+/// loggit::warn!("written to the Event Log");
+/// ```
+#[cfg(feature = "windows-eventlog")]
+pub fn set_windows_event_log(source_name: &str) -> Result<(), SetSinkError> {
+    let sink = event_log::EventLogSink::register(source_name)?;
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetSinkError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.event_log = Some(sink);
+    Ok(())
 }
 
-/// Sets the minimum log level to display.
-/// Messages with a level lower than the given level will be ignored.
+/// Stops writing the logs to the Windows Event Log set with [set_windows_event_log].
+#[cfg(feature = "windows-eventlog")]
+pub fn remove_windows_event_log() -> Result<(), SetSinkError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetSinkError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    config_lock.event_log = None;
+    Ok(())
+}
+
+/// Sends the terminal output to the given writer instead of stdout and stderr, e.g. a buffer
+/// shown in a GUI or captured by a test.
 ///
-/// **Example:**
+/// Each log is written as one line and the writer is flushed after it. Since the writer isn't a
+/// terminal, the logs are colorized only with [ColorWhen::Always] (see [set_color_when]).
+/// [clear_writer] restores stdout and stderr.
 ///
+/// *Example*:
 /// ```rust
-/// use loggit::logger;
-/// use loggit::Level;
-///
-/// logger::set_log_level(Level::DEBUG);
+/// use loggit::logger::{clear_writer, set_writer};
 ///
-/// logger::TRACE!("trace mess"); // this will not be printed or written to a file
-/// logger::DEBUG!("debug mess"); // this will
-/// logger::INFO!("debug mess");
+/// set_writer(Box::new(std::io::sink())).unwrap();
+/// loggit::info!("this log is discarded");
+/// clear_writer().unwrap();
 /// ```
-///
-/// **Level hierarchy:**
-/// 1. ERROR
-/// 2. WARN
-/// 3. INFO
-/// 4. DEBUG
-/// 5. TRACE
-///
-/// The levels are written in the most important to less important, i.e, if you set a level, the
-/// ones below the set won't be printed or written to the file as shown in the example above (`TRACE`
-/// hasn't been taken into account as it's below the `DEBUG` in the hierarchy).
-pub fn set_log_level(lvl: Level) -> Result<(), SetLogLevelError> {
+pub fn set_writer(writer: Box<dyn std::io::Write + Send>) -> Result<(), SetWriterError> {
     let config_lock = get_write_config();
     if config_lock.is_none() {
         eprintln!("An error while getting the config to write!");
-        return Err(SetLogLevelError::UnableToLoadConfig);
+        return Err(SetWriterError::UnableToLoadConfig);
     }
     let mut config_lock = config_lock.unwrap();
-    config_lock.level = lvl;
-
+    config_lock.writer = Some(Arc::new(Mutex::new(writer)));
     Ok(())
 }
-/// Enables or disables terminal output of log messages.
-/// When set to false, log messages will not be printed to the terminal.
-pub fn set_print_to_terminal(val: bool) -> Result<(), SetPrintToTerminalError> {
+
+/// Removes the writer set with [set_writer] after flushing it, the logs are printed to stdout
+/// and stderr again.
+pub fn clear_writer() -> Result<(), SetWriterError> {
     let config_lock = get_write_config();
     if config_lock.is_none() {
         eprintln!("An error while getting the config to write!");
-        return Err(SetPrintToTerminalError::UnableToLoadConfig);
+        return Err(SetWriterError::UnableToLoadConfig);
     }
     let mut config_lock = config_lock.unwrap();
-    config_lock.print_to_terminal = val;
+    let writer = config_lock.writer.take();
+    drop(config_lock);
+    if let Some(writer) = writer {
+        if let Err(e) = flush_writer(&writer) {
+            eprintln!("Couldn't flush the writer due to the next error: {}", e);
+        }
+    }
     Ok(())
 }
-/// Enables or disables colorized output of log messages.
-/// If enabled, logs will be printed with colors as configured in the format.
-pub fn set_colorized(val: bool) -> Result<(), SetColorizedError> {
+
+/// Flushes the terminal output: the writer set with [set_writer], or stdout and stderr. In the
+/// async mode (see [set_async]) it waits until the queued records are written to the files first,
+/// and the records buffered by [set_file_buffering] are written. The archives given to a
+/// background archive handler (see [set_archive_handler_options]) are handled first as well.
+pub fn flush() -> std::io::Result<()> {
+    let config = get_config();
+    if let Some(async_writer) = &config.async_writer {
+        async_writer.flush();
+    }
+    // the archives of the rotations done so far are handled
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    archive_handler::flush();
+    // on the writer thread, the file being rotated is locked by the rotation calling the archiver
+    let on_writer = async_writer::on_writer_thread();
+    for file in &config.files {
+        let mut fm = match on_writer {
+            true => match file.manager.try_lock() {
+                Ok(fm) => fm,
+                Err(_) => continue,
+            },
+            false => file.manager.lock().unwrap(), // poisoned = panic, fine for logger
+        };
+        fm.flush_buffer()?;
+        fm.sync_pending()?;
+    }
+    match &config.writer {
+        Some(writer) => flush_writer(writer),
+        None => {
+            std::io::stdout().flush()?;
+            std::io::stderr().flush()
+        }
+    }
+}
+
+/// Removes the function set with [set_formatter], the logs are rendered with the formats again.
+pub fn remove_formatter() -> Result<(), SetFormatterError> {
     let config_lock = get_write_config();
     if config_lock.is_none() {
         eprintln!("An error while getting the config to write!");
-        return Err(SetColorizedError::UnableToLoadConfig);
+        return Err(SetFormatterError::UnableToLoadConfig);
     }
     let mut config_lock = config_lock.unwrap();
-    config_lock.colorized = val;
+    config_lock.custom_formatter = None;
     Ok(())
 }
 
@@ -368,6 +2589,59 @@ pub fn set_global_formatting(format: &str) -> Result<(), SetLevelFormattingError
 ///
 /// The formatting string may contain placeholders like `{level}`, `{file}`, `{line}`, `{module}` and `{message}`.
 ///
+/// `{date}` and `{time}` accept a [strftime](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+/// specifier after a colon, e.g. `{date:%Y-%m-%d}` or `{time:%H:%M:%S%.3f}`. Without a specifier
+/// they are rendered as `14-03-2025` and `09:05:03` (see [set_zero_padding]).
+///
+/// Your own placeholders can be added with [register_placeholder].
+///
+/// An optional section `{name?...}` is rendered only if the placeholder `name` isn't empty:
+/// `{context?[{context}] }{message}` gives `[user=bob] msg` with a context field and just `msg`
+/// without one. The section may contain text, placeholders, other sections and color tags closed
+/// inside it.
+///
+/// `{run_id}` is replaced by a random id generated once per run of the program (e.g. `3fa91c07`),
+/// see [crate::run_id].
+///
+/// `{iso8601}` renders the date and time as RFC 3339 with milliseconds, in the time zone set with
+/// [set_timezone]: `2025-03-05T09:05:03.123Z`, or `2025-03-05T11:05:03.123+02:00` with an offset.
+///
+/// `{thread}` is replaced by the name of the thread that logged the message (or its numeric id if
+/// the thread is unnamed) and `{thread_id}` by the numeric id only. `{pid}` is replaced by the id
+/// of the current process. `{timestamp}` and `{timestamp_ms}` are replaced by the seconds and
+/// milliseconds since the unix epoch. `{elapsed}` is replaced by the seconds since the logger
+/// initialization with a millisecond precision (e.g. `[+{elapsed}s]` gives `[+0.532s]`) and
+/// `{elapsed_ms}` by the milliseconds. `{hostname}` is replaced by the name of the machine.
+///
+/// `{column}` is replaced by the column of the call site, next to `{line}`: `{file}:{line}:{column}`
+/// gives `src/main.rs:12:5`.
+///
+/// `{context}` is replaced by the context fields as `key=value` pairs (see [add_context] and
+/// [crate::context::scope]).
+///
+/// `{file_short}` renders only the file name of `{file}` (`invoice.rs` for
+/// `src/billing/invoice.rs`), and `{file_short:2}` keeps the last two components
+/// (`billing/invoice.rs`).
+///
+/// `{level:lower}` renders the level in lowercase (`info`) and `{level:short}` as a single letter
+/// (`I`), `{level}` and `{level:upper}` give `INFO`.
+///
+/// `{num}` numbers the log files (see [set_file]) and renders nothing in the logs.
+///
+/// ### Width and alignment
+///
+/// Any placeholder (except `{env:NAME}`) accepts a width specifier `[<^>][width][.max_width]`
+/// after a colon, to get aligned columns:
+/// - `{level:<5}` pads the level with spaces on the right up to 5 characters (`<` is the default);
+/// - `{line:>4}` aligns the line number on the right, `{module:^20}` centers the module;
+/// - `{file:.30}` truncates the file to 30 characters, `{file:>20.30}` does both.
+///
+/// `{env:NAME}` is replaced by the value of the `NAME` environment variable. The variable is read
+/// once, when the format is set; a missing variable is rendered as an empty string.
+///
+/// To write a literal brace, double it: `{{json}} {message}` gives `{json} hello`. Angle brackets
+/// are escaped with a backslash: `-\> {message} \<-` gives `-> hello <-`.
+///
 /// ### Colors
 ///
 /// The next colors are supported:
@@ -379,6 +2653,9 @@ pub fn set_global_formatting(format: &str) -> Result<(), SetLevelFormattingError
 /// - white
 /// - purple
 ///
+/// and their bright variants: `bright_red`, `bright_green`, `bright_blue`, `bright_yellow`,
+/// `bright_black` (gray), `bright_white` and `bright_purple`.
+///
 /// To apply a color to a part of your format, use the next syntax:
 /// ```
 /// ... <color>text {placeholder}<color> ...
@@ -386,6 +2663,30 @@ pub fn set_global_formatting(format: &str) -> Result<(), SetLevelFormattingError
 ///
 /// > Note: each opened <color> tag must be close with the same <color> tag!
 ///
+/// Color tags can be nested, the inner color applies until its tag is closed and then the outer
+/// color is restored. The inner tags must be closed first:
+/// ```text
+/// <blue>({file}:{line} <yellow>{level}<yellow>)<blue>
+/// ```
+///
+/// Any 24-bit color can also be given as a hex value or as rgb components, e.g. `<#ff8800>` or
+/// `<rgb(255,136,0)>`, and any color of the 256 colors palette as `<cN>` with N from 0 to 255,
+/// e.g. `<c208>`.
+///
+/// To set a background color, prefix the color name with `bg_`, e.g. `<bg_red>text<bg_red>`.
+///
+/// The `<level>` tag picks the color by the level of the message: red for ERROR, yellow for
+/// WARN, green for INFO, cyan for DEBUG and gray for TRACE (see [set_level_color]), e.g.
+/// `<level>[{level}]<level> {message}`.
+///
+/// ### Styles
+///
+/// The text can be styled with the `<bold>`, `<dim>`, `<italic>` and `<underline>` tags, that
+/// follow the same open/close rules as colors and can be combined with them:
+/// ```text
+/// ... <bold><red>text<red><bold> ...
+/// ```
+///
 /// Example:
 /// ```rust
 /// use loggit::logger;
@@ -426,24 +2727,277 @@ pub fn set_level_formatting(level: Level, format: &str) -> Result<(), SetLevelFo
     Ok(())
 }
 
+/// Sets a log formatting string for the file output of all log levels, see [set_file_formatting].
+pub fn set_file_global_formatting(format: &str) -> Result<(), SetLevelFormattingError> {
+    set_file_formatting(Level::TRACE, format)?;
+    set_file_formatting(Level::DEBUG, format)?;
+    set_file_formatting(Level::INFO, format)?;
+    set_file_formatting(Level::WARN, format)?;
+    set_file_formatting(Level::ERROR, format)?;
+    Ok(())
+}
+
+/// ## Sets the log formatting string of the file output for the specified log level.
+///
+/// The logs written to the file (see [set_file]) use the terminal format of their level (see
+/// [set_level_formatting]) until a file format is set for it, so the terminal can show compact
+/// lines while the file keeps verbose ones:
+/// ```rust
+/// use loggit::logger::{set_file_formatting, set_level_formatting};
+/// use loggit::Level;
+///
+/// set_level_formatting(Level::INFO, "<green>{level}<green> {message}").unwrap();
+/// set_file_formatting(Level::INFO, "{date} {time} [{level}] {file}:{line} - {message}").unwrap();
+/// ```
+/// The file formats accept the same placeholders as the terminal ones; their color tags are
+/// ignored since the files aren't colorized.
+pub fn set_file_formatting(level: Level, format: &str) -> Result<(), SetLevelFormattingError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLevelFormattingError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    let formatter = LogFormatter::parse_from_string(format)?;
+    config_lock.file_log_formats.insert(level, formatter);
+    Ok(())
+}
+
+/// ## Returns the log formatting of the specified log level as a template.
+///
+/// The template is accepted by [set_level_formatting] and gives the same format, but it is
+/// rebuilt from the parsed format so it may be written differently than the one that was set:
+/// ```rust
+/// use loggit::logger::{get_level_formatting, set_level_formatting};
+/// use loggit::Level;
+///
+/// set_level_formatting(Level::INFO, "<green>[{level}]<green> {message}").unwrap();
+/// assert_eq!(get_level_formatting(Level::INFO), "<green>[{level}]<green> {message}");
+/// ```
+pub fn get_level_formatting(level: Level) -> String {
+    get_log_format(&get_config(), level).to_template_string()
+}
+
+/// Returns the log formatting as a template if all the log levels have the same one, `None`
+/// otherwise (see [get_level_formatting]).
+pub fn get_global_formatting() -> Option<String> {
+    let format = get_level_formatting(Level::TRACE);
+    let levels = [Level::DEBUG, Level::INFO, Level::WARN, Level::ERROR];
+    match levels
+        .iter()
+        .all(|level| get_level_formatting(*level) == format)
+    {
+        true => Some(format),
+        false => None,
+    }
+}
+
+/// Returns whether a log of the given level would be emitted by one of the outputs (or kept in
+/// the ring buffer). The logging macros check it before formatting the message, it can also
+/// guard the preparation of an expensive log.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::{level_enabled, set_log_level};
+/// use loggit::Level;
+///
+/// set_log_level(Level::INFO).unwrap();
+/// assert!(level_enabled(Level::WARN));
+/// assert!(!level_enabled(Level::DEBUG));
+///
+/// // the arguments of a filtered log aren't evaluated
+/// let mut evaluated = false;
+/// loggit::debug!("{}", {
+///     evaluated = true;
+///     "expensive"
+/// });
+/// assert!(!evaluated);
+/// ```
+pub fn level_enabled(level: Level) -> bool {
+    let config = get_config();
+    let kept = matches!(ring_buffer_level(&config), Some(kept_level) if level >= kept_level);
+    level >= get_min_output_level(&config) || kept
+}
+
+/// ## Sets a log format built with [format_builder::LogFormat::builder] for the specified log level.
+///
+/// It is the programmatic equivalent of [set_level_formatting]:
+/// ```rust
+/// use loggit::logger::format_builder::LogFormat;
+/// use loggit::logger::formatter::LogColor;
+/// use loggit::logger::set_level_format_built;
+/// use loggit::Level;
+///
+/// // same as "<green>{level}<green> ({file}:{line}) - {message}"
+/// let format = LogFormat::builder()
+///     .colored(LogColor::Green, |b| b.level())
+///     .text(" (")
+///     .file()
+///     .text(":")
+///     .line()
+///     .text(") - ")
+///     .message()
+///     .build();
+/// set_level_format_built(Level::INFO, format).unwrap();
+/// ```
+pub fn set_level_format_built(
+    level: Level,
+    format: format_builder::LogFormat,
+) -> Result<(), SetLevelFormattingError> {
+    let config_lock = get_write_config();
+    if config_lock.is_none() {
+        eprintln!("An error while getting the config to write!");
+        return Err(SetLevelFormattingError::UnableToLoadConfig);
+    }
+    let mut config_lock = config_lock.unwrap();
+    match level {
+        Level::TRACE => config_lock.trace_log_format = format.formatter,
+        Level::DEBUG => config_lock.debug_log_format = format.formatter,
+        Level::INFO => config_lock.info_log_format = format.formatter,
+        Level::WARN => config_lock.warn_log_format = format.formatter,
+        Level::ERROR => config_lock.error_log_format = format.formatter,
+    }
+    Ok(())
+}
+
+/// Sets a log format built with [format_builder::LogFormat::builder] for all the log levels, see
+/// [set_level_format_built].
+pub fn set_global_format_built(
+    format: format_builder::LogFormat,
+) -> Result<(), SetLevelFormattingError> {
+    set_level_format_built(Level::TRACE, format.clone())?;
+    set_level_format_built(Level::DEBUG, format.clone())?;
+    set_level_format_built(Level::INFO, format.clone())?;
+    set_level_format_built(Level::WARN, format.clone())?;
+    set_level_format_built(Level::ERROR, format)?;
+    Ok(())
+}
+
 // -- Internal functions for logging --
-fn string_log(log_info: &LogInfo, colorize: bool) -> String {
+/// Renders the log with the terminal format of its level.
+pub(crate) fn string_log(config: &Config, record: &LogRecord, colorize: bool) -> String {
+    let format = get_log_format(config, record.level);
+    string_log_with_format(config, record, format, colorize, config.multiline)
+}
+fn string_log_with_format(
+    config: &Config,
+    record: &LogRecord,
+    format: &LogFormatter,
+    colorize: bool,
+    multiline: MultilineStyle,
+) -> String {
+    if multiline == MultilineStyle::PrefixEach && record.message.contains('\n') {
+        // each line is rendered as a log of its own
+        let lines = record.message.lines().map(|line| {
+            let record = LogRecord {
+                message: line.to_string(),
+                ..record.clone()
+            };
+            string_log_with_format(config, &record, format, colorize, MultilineStyle::Keep)
+        });
+        return lines.collect::<Vec<_>>().join("\n");
+    }
+    // the console is set up for colors the first time they are needed
+    let colorize = colorize && console::enable_ansi_support();
+    let level_color = config.level_color(record.level);
+    let level_color_override = config.level_colors.get(&record.level).copied();
+    let auto_color = match colorize && config.auto_level_colors {
+        true => level_color_override.or_else(|| auto_level_color(record.level)),
+        false => None,
+    };
     let mut mess_to_print = String::new();
-    let curr_time: String = get_current_time_in_string();
-    let curr_date = get_current_date_in_string();
-    for log_part in get_log_format(log_info.level).parts {
-        let str_to_push = match log_part.part {
-            formatter::LogPart::Message => &log_info.message,
-            formatter::LogPart::Time => &curr_time,
-            formatter::LogPart::File => &log_info.file,
-            formatter::LogPart::Line => &log_info.line.to_string(),
-            formatter::LogPart::Date => &curr_date,
-            formatter::LogPart::Level => &log_info.level.to_string(),
-            formatter::LogPart::Text(text) => &text.clone(),
-            formatter::LogPart::ModulePath => &log_info.module_path,
+    let time_settings = config.time_settings;
+    let level_name = config.level_name(record.level);
+    let level_symbol = config
+        .level_symbols
+        .get(&record.level)
+        .cloned()
+        .unwrap_or_default();
+    // the time of the record, not of the rendering which can be later (async, replay)
+    let now = helper::in_timezone(record.timestamp, time_settings.timezone);
+    let curr_time = helper::format_time(&now, time_settings);
+    let curr_date = helper::format_date(&now, time_settings);
+    let iso8601 = helper::format_iso8601(&now);
+    let timestamp = now.timestamp().to_string();
+    let timestamp_ms = now.timestamp_millis().to_string();
+    let elapsed = elapsed_since_start();
+    let elapsed_secs = format!("{}.{:03}", elapsed.as_secs(), elapsed.subsec_millis());
+    let elapsed_ms = elapsed.as_millis().to_string();
+    let context = helper::format_context(&record.context);
+    let part_value = |part: &formatter::LogPart| -> String {
+        let value: &str = match part {
+            formatter::LogPart::Message => &record.message,
+            formatter::LogPart::Time(None) => &curr_time,
+            formatter::LogPart::Time(Some(spec)) => &now.format(spec).to_string(),
+            formatter::LogPart::File => &record.file,
+            formatter::LogPart::FileShort(count) => {
+                helper::get_last_path_components(&record.file, *count)
+            }
+            formatter::LogPart::Line => &record.line.to_string(),
+            formatter::LogPart::Column => &record.column.to_string(),
+            formatter::LogPart::Date(None) => &curr_date,
+            formatter::LogPart::Date(Some(spec)) => &now.format(spec).to_string(),
+            formatter::LogPart::Iso8601 => &iso8601,
+            formatter::LogPart::Level(style) => &style.apply(&level_name),
+            formatter::LogPart::Symbol => &level_symbol,
+            formatter::LogPart::Text(text) => text,
+            formatter::LogPart::ModulePath => &record.module_path,
+            formatter::LogPart::Thread => &record.thread,
+            formatter::LogPart::ThreadId => &record.thread_id,
+            formatter::LogPart::Pid => helper::get_pid(),
+            formatter::LogPart::Timestamp => &timestamp,
+            formatter::LogPart::TimestampMs => &timestamp_ms,
+            formatter::LogPart::Elapsed => &elapsed_secs,
+            formatter::LogPart::ElapsedMs => &elapsed_ms,
+            formatter::LogPart::Hostname => helper::get_hostname(),
+            formatter::LogPart::RunId => helper::get_run_id(),
+            // the counter of the file names
+            formatter::LogPart::Num(_) => "",
+            formatter::LogPart::Custom(name) => &render_placeholder(name),
+            formatter::LogPart::Env(_, value) => value,
+            formatter::LogPart::Context => &context,
+            formatter::LogPart::Optional(_, _) => "",
+        };
+        value.to_string()
+    };
+    let max_message_len = config.max_message_len;
+    // the column of the end of the rendered text, without the escape codes
+    let mut column = 0;
+    // the parts left to render, the next one is the last
+    let mut parts: Vec<&formatter::LogFormatWrapper> = format.parts.iter().rev().collect();
+    while let Some(log_part) = parts.pop() {
+        if let formatter::LogPart::Optional(condition, section) = &log_part.part {
+            // the section takes the place of the optional part if the placeholder isn't empty
+            if !part_value(condition).is_empty() {
+                parts.extend(section.iter().rev());
+            }
+            continue;
+        }
+        let str_to_push = match &log_part.part {
+            formatter::LogPart::Message => {
+                let message = helper::truncate_message(&record.message, max_message_len);
+                helper::format_multiline(&message, multiline, column)
+            }
+            part => part_value(part),
+        };
+        let str_to_push = str_to_push.as_str();
+        // the width is applied before the colors so the escape codes don't count in it
+        let aligned;
+        let str_to_push = match &log_part.width {
+            Some(width) => {
+                aligned = width.apply(str_to_push);
+                aligned.as_str()
+            }
+            None => str_to_push,
         };
-        if colorize && log_part.color.is_some() {
-            let colored_str = LogColor::colorize_str(str_to_push, log_part.color.unwrap());
+        column = helper::column_after(str_to_push, column);
+        let color = log_part
+            .color
+            .map(|c| c.resolve(level_color))
+            .or(auto_color);
+        let bg_color = log_part.bg_color.map(|c| c.resolve(level_color));
+        if colorize && !str_to_push.is_empty() && (log_part.is_styled() || color.is_some()) {
+            let colored_str = LogColor::colorize_str(str_to_push, color, bg_color, log_part.style);
             mess_to_print.push_str(&colored_str);
         } else {
             mess_to_print.push_str(str_to_push);
@@ -451,67 +3005,336 @@ fn string_log(log_info: &LogInfo, colorize: bool) -> String {
     }
     mess_to_print
 }
-fn print_log(log_info: &LogInfo) {
-    let mess_to_print = string_log(log_info, get_config().colorized);
-    match log_info.level {
-        Level::ERROR => eprintln!("{}", mess_to_print),
-        _ => println!("{}", mess_to_print),
+/// Returns the context fields attached to the logs: the global ones, and the ones scoped to the
+/// current thread that take precedence.
+pub(crate) fn current_context(config: &Config) -> BTreeMap<String, String> {
+    let mut context = config.context.clone();
+    context.extend(crate::context::scoped_fields());
+    context
+}
+/// Renders the log with the custom formatter if one is set, with `render` otherwise.
+fn render_with(config: &Config, record: &LogRecord, render: impl FnOnce() -> String) -> String {
+    // the snapshot isn't a lock, the formatter can log or change the config
+    match &config.custom_formatter {
+        Some(f) => f(record),
+        None => render(),
+    }
+}
+/// Renders the log as a JSON object on one line.
+pub(crate) fn json_log(config: &Config, record: &LogRecord) -> String {
+    let time_settings = config.time_settings;
+    let max_message_len = config.max_message_len;
+    let timestamp = helper::in_timezone(record.timestamp, time_settings.timezone);
+    let json = serde_json::json!({
+        "ts": helper::format_iso8601(&timestamp),
+        "level": record.level.to_string(),
+        "message": helper::truncate_message(&record.message, max_message_len),
+        "module": record.module_path,
+        "file": record.file,
+        "line": record.line,
+        "column": record.column,
+        "thread": record.thread,
+        "thread_id": record.thread_id,
+        "context": record.context,
+    });
+    json.to_string()
+}
+/// Header row of the files written with [OutputFormat::Csv]
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) const CSV_HEADER: &str = "timestamp,level,module,file,line,message";
+/// Returns the value as a CSV field, quoted per RFC 4180 if it contains a comma, a quote or a
+/// line break.
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+/// Renders the log as a CSV row with the columns of [CSV_HEADER].
+pub(crate) fn csv_log(config: &Config, record: &LogRecord) -> String {
+    let time_settings = config.time_settings;
+    let max_message_len = config.max_message_len;
+    let timestamp = helper::in_timezone(record.timestamp, time_settings.timezone);
+    let fields = [
+        helper::format_iso8601(&timestamp),
+        record.level.to_string(),
+        record.module_path.clone(),
+        record.file.clone(),
+        record.line.to_string(),
+        helper::truncate_message(&record.message, max_message_len).into_owned(),
+    ];
+    fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+/// Renders the log for the terminal: as JSON with [OutputFormat::Json], as a CSV row with
+/// [OutputFormat::Csv], otherwise with the custom formatter if one is set or the format of its
+/// level.
+pub(crate) fn render_log(config: &Config, record: &LogRecord, colorize: bool) -> String {
+    match config.terminal_output_format {
+        OutputFormat::Text => render_with(config, record, || string_log(config, record, colorize)),
+        OutputFormat::Json => json_log(config, record),
+        OutputFormat::Csv => csv_log(config, record),
+    }
+}
+/// Renders the log for the file: as JSON with [OutputFormat::Json], as a CSV row with
+/// [OutputFormat::Csv], otherwise with the custom formatter if one is set or the file format of
+/// its level.
+pub(crate) fn render_file_log(config: &Config, record: &LogRecord) -> String {
+    match config.file_output_format {
+        OutputFormat::Text => render_with(config, record, || {
+            let format = get_file_log_format(config, record.level);
+            string_log_with_format(config, record, format, false, config.file_multiline)
+        }),
+        OutputFormat::Json => json_log(config, record),
+        OutputFormat::Csv => csv_log(config, record),
+    }
+}
+/// Returns the color used for the parts without color tags when automatic level colors are
+/// enabled.
+pub(crate) fn auto_level_color(level: Level) -> Option<LogColor> {
+    match level {
+        Level::ERROR => Some(LogColor::Red),
+        Level::WARN => Some(LogColor::Yellow),
+        Level::INFO => None,
+        Level::DEBUG => Some(LogColor::Rgb(0, 255, 255)), // cyan
+        Level::TRACE => Some(LogColor::BrightBlack),
+    }
+}
+/// Decides whether the terminal output should be colorized for the given stream.
+pub(crate) fn should_colorize(color_when: ColorWhen, to_stderr: bool) -> bool {
+    match color_when {
+        ColorWhen::Always => true,
+        ColorWhen::Never => false,
+        ColorWhen::Auto => match to_stderr {
+            true => std::io::stderr().is_terminal(),
+            false => std::io::stdout().is_terminal(),
+        },
+    }
+}
+/// Decides whether a log of the given level is printed to stderr rather than stdout.
+pub(crate) fn is_stderr_level(level: Level, threshold: Level) -> bool {
+    level >= threshold
+}
+fn flush_writer(writer: &crate::CustomWriter) -> std::io::Result<()> {
+    match writer.lock() {
+        Ok(mut writer) => writer.flush(),
+        Err(poisoned) => poisoned.into_inner().flush(),
+    }
+}
+/// Writes the log as one line to the writer and flushes it.
+fn write_to_writer(writer: &crate::CustomWriter, mess_to_print: &str) -> std::io::Result<()> {
+    let mut writer = match writer.lock() {
+        Ok(writer) => writer,
+        Err(poisoned) => poisoned.into_inner(),
     };
+    writeln!(writer, "{}", mess_to_print)?;
+    writer.flush()
 }
-fn write_file_log(log_info: &LogInfo) {
-    let mess_to_print = string_log(log_info, false);
-    let cfg_snapshot = get_config().clone();
-
-    let _ = with_fm::<(), AccessError, _>(|file_manager| {
-        let res = file_manager.write_log(&mess_to_print, cfg_snapshot);
-
-        match res {
-            Ok(_) => Ok(()),
-            Err(e) => {
+fn print_log(config: &Config, record: &LogRecord) {
+    let to_stderr = is_stderr_level(record.level, config.stderr_threshold);
+    let writer = config.writer.clone();
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+    if writer.is_none() {
+        // the console doesn't render the ANSI colors
+        browser_console::write(record.level, &render_log(config, record, false));
+        return;
+    }
+    let colorize = match writer {
+        // a custom writer is never a terminal
+        Some(_) => config.color_when == ColorWhen::Always,
+        None => should_colorize(config.color_when, to_stderr),
+    };
+    let mess_to_print = render_log(config, record, colorize);
+    match (writer, to_stderr) {
+        (Some(writer), _) => {
+            if let Err(e) = write_to_writer(&writer, &mess_to_print) {
                 eprintln!(
-                    "Couldn't write a log to the file due to the next error: {}",
+                    "Couldn't write a log to the writer due to the next error: {}",
                     e
                 );
-                Ok(()) // we don't return a result from this function
             }
         }
+        (None, true) => eprintln!("{}", mess_to_print),
+        (None, false) => println!("{}", mess_to_print),
+    };
+}
+fn write_file_log(config: &Arc<Config>, record: &LogRecord) {
+    let mess_to_print = render_file_log(config, record);
+    let files = config
+        .files
+        .iter()
+        .filter(|file| file.accepts(record.level));
+
+    match &config.async_writer {
+        Some(writer) => writer.write(mess_to_print, files.cloned().collect(), config.clone()),
+        None => write_to_files(&mess_to_print, files, config),
+    }
+}
+/// Writes the rendered log to the files, on the writer thread in the async mode.
+fn write_to_files<'a>(
+    mess_to_print: &str,
+    files: impl IntoIterator<Item = &'a FileOutput>,
+    cfg_snapshot: &Config,
+) {
+    for file in files {
+        let mut file_manager = file.manager.lock().unwrap(); // poisoned = panic, fine for logger
+        let res = file_manager.write_log(mess_to_print, cfg_snapshot);
+
+        if let Err(e) = res {
+            eprintln!(
+                "Couldn't write a log to the file due to the next error: {}",
+                e
+            );
+        }
+    }
+}
+/// Queues the record for the collector set with [set_tcp_sink], if any.
+#[cfg(feature = "tcp")]
+fn send_to_tcp_sink(config: &Config, record: &LogRecord) {
+    if let Some(sink) = &config.tcp_sink {
+        let mut line = render_file_log(config, record);
+        line.push('\n');
+        sink.send(line);
+    }
+}
+thread_local! {
+    /// Set while a sink is called on this thread, its panics are reported by [call_sinks]
+    static IN_SINK: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+/// Wraps the panic hook, once, so that it stays silent for the panics of the sinks. The other
+/// panics still go to the previous hook (the default one or the one set by the user).
+fn silence_sink_panics() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !IN_SINK.with(|in_sink| in_sink.get()) {
+                previous(info);
+            }
+        }));
     });
 }
-fn log_handler(log_info: LogInfo) {
-    if get_config().print_to_terminal {
-        print_log(&log_info);
+/// Calls the sinks with the record, a panicking sink is reported once and doesn't stop the others
+fn call_sinks(config: &Config, record: &LogRecord) {
+    if config.sinks.is_empty() {
+        return;
+    }
+    silence_sink_panics();
+    // the snapshot isn't a lock, the sinks can log or change the config
+    for sink in &config.sinks {
+        // a sink can log, the flag of the outer call is restored after the inner one
+        let outer = IN_SINK.with(|in_sink| in_sink.replace(true));
+        let res =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| (sink.callback)(record)));
+        IN_SINK.with(|in_sink| in_sink.set(outer));
+        if res.is_err() && !sink.panicked.swap(true, Ordering::Relaxed) {
+            eprintln!("A log sink panicked, its next panics won't be reported!");
+        }
+    }
+}
+/// Returns the minimal level of the records kept in the ring buffer, `None` if it isn't enabled.
+fn ring_buffer_level(config: &Config) -> Option<Level> {
+    config
+        .ring_buffer
+        .as_ref()
+        .map(|_| config.ring_buffer_level.unwrap_or(config.level))
+}
+/// Keeps the record in the ring buffer if it is enabled and the record is important enough.
+fn store_in_ring_buffer(config: &Config, record: &LogRecord) {
+    if let (Some(buffer), Some(level)) = (&config.ring_buffer, ring_buffer_level(config)) {
+        if record.level >= level {
+            buffer.lock().unwrap().push(record.clone());
+        }
+    }
+}
+/// Sends the record to the outputs, all of them use the same snapshot of the config.
+fn log_handler(config: &Arc<Config>, record: LogRecord) {
+    if SHUT_DOWN.load(Ordering::Relaxed) {
+        eprintln!("{}", render_log(config, &record, false));
+        return;
     }
-    if get_config().file_manager.is_some() {
-        write_file_log(&log_info);
+    store_in_ring_buffer(config, &record);
+    if config.print_to_terminal && record.level >= get_terminal_level(config) {
+        print_log(config, &record);
+    }
+    if !config.files.is_empty() && record.level >= get_file_level(config) {
+        write_file_log(config, &record);
+    }
+    if record.level < config.level {
+        // the other outputs use the log level
+        return;
+    }
+    #[cfg(feature = "tcp")]
+    send_to_tcp_sink(config, &record);
+    #[cfg(feature = "syslog")]
+    if let Some(syslog) = &config.syslog {
+        syslog.send(&record);
+    }
+    #[cfg(feature = "windows-eventlog")]
+    if let Some(event_log) = &config.event_log {
+        event_log.send(record.level, &render_file_log(config, &record));
+    }
+    #[cfg(feature = "http")]
+    if let Some(webhook) = &config.webhook {
+        webhook.send(&record);
+    }
+    if !config.sinks.is_empty() {
+        call_sinks(config, &record);
     }
 }
 
 // handles call from macro and passes deeper
-fn macro_handler(module_path: &str, file: &str, line: u32, deb_str: String, level: Level) {
-    let log_info = LogInfo {
+fn macro_handler(
+    module_path: &str,
+    file: &str,
+    line: u32,
+    column: u32,
+    deb_str: String,
+    level: Level,
+) {
+    let config = get_config();
+    let kept = matches!(ring_buffer_level(&config), Some(kept_level) if level >= kept_level);
+    if level < get_min_output_level(&config) && !kept {
+        return;
+    }
+    let record = LogRecord {
+        level,
+        message: deb_str,
         module_path: module_path.to_string(),
         file: file.to_string(),
         line,
-        message: deb_str,
-        level,
+        column,
+        thread: helper::get_current_thread_name(),
+        thread_id: helper::get_current_thread_id(),
+        timestamp: helper::now(),
+        context: current_context(&config),
     };
-    if level >= get_log_level() {
-        log_handler(log_info);
-    }
+    log_handler(&config, record);
 }
 
 /// Internal function for handling log macros.
 ///
 /// It is used by the public logger macros to format and output the log message.
-pub fn __debug_handler(module_path: &str, file: &str, line: u32, deb_str: String, level: Level) {
-    macro_handler(module_path, file, line, deb_str, level);
+pub fn __debug_handler(
+    module_path: &str,
+    file: &str,
+    line: u32,
+    column: u32,
+    deb_str: String,
+    level: Level,
+) {
+    macro_handler(module_path, file, line, column, deb_str, level);
 }
 
 // -- Publicly exported logging macros --
 
 #[macro_export]
 /// Logs a message at the TRACE level.
-/// The message is formatted using standard Rust formatting.
+/// The message is formatted using standard Rust formatting, the arguments are only evaluated if
+/// the level is enabled (see [level_enabled](crate::logger::level_enabled)).
 ///
 /// # Example
 /// ```rust
@@ -521,14 +3344,17 @@ pub fn __debug_handler(module_path: &str, file: &str, line: u32, deb_str: String
 /// ```
 macro_rules! trace {
         ($($arg:tt)*) => {{
-            let res_str = format!($($arg)*);
-            $crate::logger::__debug_handler(module_path!(), file!(), line!(), res_str, $crate::Level::TRACE);
+            if $crate::logger::level_enabled($crate::Level::TRACE) {
+                let res_str = format!($($arg)*);
+                $crate::logger::__debug_handler(module_path!(), file!(), line!(), column!(), res_str, $crate::Level::TRACE);
+            }
         }};
     }
 
 #[macro_export]
 /// Logs a message at the DEBUG level.
-/// The message is formatted using standard Rust formatting.
+/// The message is formatted using standard Rust formatting, the arguments are only evaluated if
+/// the level is enabled (see [level_enabled](crate::logger::level_enabled)).
 ///
 /// # Example
 /// ```rust
@@ -538,14 +3364,17 @@ macro_rules! trace {
 /// ```
 macro_rules! debug {
         ($($arg:tt)*) => {{
-            let res_str = format!($($arg)*);
-            $crate::logger::__debug_handler(module_path!(), file!(), line!(), res_str, $crate::Level::DEBUG);
+            if $crate::logger::level_enabled($crate::Level::DEBUG) {
+                let res_str = format!($($arg)*);
+                $crate::logger::__debug_handler(module_path!(), file!(), line!(), column!(), res_str, $crate::Level::DEBUG);
+            }
         }};
     }
 
 #[macro_export]
 /// Logs a message at the INFO level.
-/// The message is formatted using standard Rust formatting.
+/// The message is formatted using standard Rust formatting, the arguments are only evaluated if
+/// the level is enabled (see [level_enabled](crate::logger::level_enabled)).
 ///
 /// # Example
 /// ```rust
@@ -555,14 +3384,17 @@ macro_rules! debug {
 /// ```
 macro_rules! info {
         ($($arg:tt)*) => {{
-            let res_str = format!($($arg)*);
-            $crate::logger::__debug_handler(module_path!(), file!(), line!(), res_str, $crate::Level::INFO);
+            if $crate::logger::level_enabled($crate::Level::INFO) {
+                let res_str = format!($($arg)*);
+                $crate::logger::__debug_handler(module_path!(), file!(), line!(), column!(), res_str, $crate::Level::INFO);
+            }
         }};
     }
 
 #[macro_export]
 /// Logs a message at the WARN level.
-/// The message is formatted using standard Rust formatting.
+/// The message is formatted using standard Rust formatting, the arguments are only evaluated if
+/// the level is enabled (see [level_enabled](crate::logger::level_enabled)).
 ///
 /// # Example
 /// ```rust
@@ -572,14 +3404,17 @@ macro_rules! info {
 /// ```
 macro_rules! warn {
         ($($arg:tt)*) => {{
-            let res_str = format!($($arg)*);
-            $crate::logger::__debug_handler(module_path!(), file!(), line!(), res_str, $crate::Level::WARN);
+            if $crate::logger::level_enabled($crate::Level::WARN) {
+                let res_str = format!($($arg)*);
+                $crate::logger::__debug_handler(module_path!(), file!(), line!(), column!(), res_str, $crate::Level::WARN);
+            }
         }};
     }
 
 #[macro_export]
 /// Logs a message at the ERROR level.
-/// The message is formatted using standard Rust formatting.
+/// The message is formatted using standard Rust formatting, the arguments are only evaluated if
+/// the level is enabled (see [level_enabled](crate::logger::level_enabled)).
 ///
 /// # Example
 /// ```rust
@@ -589,17 +3424,50 @@ macro_rules! warn {
 /// ```
 macro_rules! error {
         ($($arg:tt)*) => {{
-            let res_str = format!($($arg)*);
-            $crate::logger::__debug_handler(module_path!(), file!(), line!(), res_str, $crate::Level::ERROR);
+            if $crate::logger::level_enabled($crate::Level::ERROR) {
+                let res_str = format!($($arg)*);
+                $crate::logger::__debug_handler(module_path!(), file!(), line!(), column!(), res_str, $crate::Level::ERROR);
+            }
         }};
     }
 
 /// Initializes the logger with default configuration settings.
 pub fn init() {
+    console::enable_ansi_support();
+    Lazy::force(&START);
+    if let Ok(mut placeholders) = PLACEHOLDERS.write() {
+        placeholders.clear();
+    }
+    // the records queued for the files are written before the files are removed
+    stop_async_writer();
     let mut config = CONFIG.write().unwrap();
-    *config = Config {
+    *config = Arc::new(Config {
         ..Default::default()
-    }
+    });
+    SHUT_DOWN.store(false, Ordering::Relaxed);
+}
+
+/// Returns a guard calling [shutdown] when it is dropped, so the queued logs are written before a
+/// short-lived program exits.
+///
+/// Unlike [init], it doesn't reset the config: the one imported from the config files when the
+/// program starts (see [init_with_imports]) and the settings changed before are kept. The logs go
+/// to the outputs again if the logger was shut down.
+///
+/// *Example*:
+/// ```rust,no_run
+/// use loggit::logger::{init_with_guard, set_async, set_file};
+///
+/// fn main() {
+///     let _guard = init_with_guard();
+///     set_file("cli_{date}.txt").unwrap();
+///     set_async(true).unwrap();
+///     loggit::info!("written before the program exits");
+/// }
+/// ```
+pub fn init_with_guard() -> LoggerGuard {
+    SHUT_DOWN.store(false, Ordering::Relaxed);
+    LoggerGuard { _private: () }
 }
 
 /// Initializes the logger with default settings using [init] function.
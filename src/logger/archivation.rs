@@ -1,22 +1,17 @@
 //! Helper utilities for log file archiving.
 //!
 //! This module exposes functions to determine archive locations and ensure
-//! directories are created when needed.
+//! directories are created when needed, and [`MoveToDir`], an archive handler moving the
+//! archives to another directory.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::CONFIG;
+use crate::ArchiveHandler;
 
-/// Returns a path to the default archive dir (the one set in the config or in the system cache)
+/// Returns a path to the default archive dir, in the system cache, used when none is set in the
+/// config
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 pub(crate) fn default_archive_dir() -> PathBuf {
-    // Highest priority: user‑supplied (env or API setter)
-    let config = CONFIG.read();
-    if let Ok(cfg) = config {
-        if let Some(path) = &cfg.archive_dir {
-            return path.clone();
-        }
-    }
-
     // XDG on Unix, %LOCALAPPDATA% on Windows, ~/Library on macOS …
     dirs::data_local_dir()
         .unwrap_or_else(std::env::temp_dir)
@@ -24,10 +19,25 @@ pub(crate) fn default_archive_dir() -> PathBuf {
         .join("archives")
 }
 
-/// Returns a path to the current archive dir
-pub(crate) fn archive_dir() -> PathBuf {
-    static DIR: once_cell::sync::Lazy<PathBuf> = once_cell::sync::Lazy::new(default_archive_dir);
-    DIR.clone() // cheap Arc‑style clone of PathBuf
+/// Returns a path to the archive dir of the config (the one set with
+/// [`crate::logger::set_archive_dir`] or the default one), without its dated subdirectory
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn base_archive_dir(config: &crate::Config) -> PathBuf {
+    match &config.archive_dir {
+        Some(path) => path.clone(),
+        None => default_archive_dir(),
+    }
+}
+
+/// Returns a path to the current archive dir: the archive dir of the config and, if a format is
+/// set with [`crate::logger::set_archive_subdir_format`], its subdirectory for the current date
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn archive_dir(config: &crate::Config) -> PathBuf {
+    let dir = base_archive_dir(config);
+    match &config.archive_subdir_format {
+        Some(format) => dir.join(format.render(config)),
+        None => dir,
+    }
 }
 
 /// Ensures that the provided path is correct to crate a directory for archives
@@ -35,7 +45,107 @@ pub(crate) fn ensure_archivable_dir(path: &PathBuf) -> std::io::Result<()> {
     std::fs::create_dir_all(path)
 }
 
-/// Ensures that the current directory for archives exists and if not so, creates one
-pub(crate) fn ensure_archive_dir() -> std::io::Result<()> {
-    std::fs::create_dir_all(archive_dir())
+/// Moves the file to the target path, never replacing a file already there (an error of the
+/// `AlreadyExists` kind is returned instead). It is copied then removed if it can't be renamed
+/// there, i.e. the target is on another file system
+pub(crate) fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    // `rename` replaces the target silently
+    if to.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    match std::fs::rename(from, to) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            let mut target = std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(to)?;
+            std::io::copy(&mut std::fs::File::open(from)?, &mut target)?;
+            std::fs::remove_file(from)
+        }
+        res => res,
+    }
+}
+
+/// Returns the path in the directory free for the file by the given name: the name itself, or
+/// numbered before its last extension like the archives (`app.log(1).zip`)
+fn free_path_in_dir(dir: &Path, file_name: &str) -> PathBuf {
+    let (stem, extension) = match file_name.rfind('.') {
+        Some(idx) if idx > 0 => file_name.split_at(idx),
+        _ => (file_name, ""),
+    };
+    let mut path = dir.join(file_name);
+    let mut num = 1;
+    while path.exists() {
+        path = dir.join(format!("{}({}){}", stem, num, extension));
+        num += 1;
+    }
+    path
+}
+
+/// An archive handler (see [`crate::logger::set_archive_handler`]) moving the archives and their
+/// checksums to another directory, created with its parents if needed, e.g. a mounted backup
+/// disk. An archive is copied then removed when the directory is on another file system. A name
+/// already taken in the directory is numbered like the archives (`app.log(1).zip`), no file of
+/// the directory is ever replaced.
+///
+/// *Example*:
+/// ```rust
+/// use loggit::logger::archivation::MoveToDir;
+/// use loggit::logger::set_archive_handler;
+/// use std::sync::Arc;
+///
+/// set_archive_handler(Arc::new(MoveToDir::new("/mnt/backup/logs"))).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MoveToDir {
+    dir: PathBuf,
+}
+
+impl MoveToDir {
+    /// Creates a handler moving the archives to `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> MoveToDir {
+        MoveToDir { dir: dir.into() }
+    }
+    /// Moves the file by the given path into the directory under a free name, returns its new
+    /// path
+    fn move_into_dir(&self, path: &Path) -> std::io::Result<PathBuf> {
+        let file_name = match path.file_name() {
+            Some(file_name) => file_name.to_string_lossy(),
+            None => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("{} isn't a file", path.display()),
+                ))
+            }
+        };
+        let target = free_path_in_dir(&self.dir, &file_name);
+        move_file(path, &target)?;
+        Ok(target)
+    }
+}
+
+impl ArchiveHandler for MoveToDir {
+    fn handle(&self, archive: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        ensure_archivable_dir(&self.dir)?;
+        let _target = self.move_into_dir(archive)?;
+        // its checksum, if one was written, goes with it under the name of the moved archive
+        #[cfg(all(
+            feature = "checksum",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        {
+            use crate::logger::file_handler::checksum::checksum_path;
+            let checksum = checksum_path(archive, crate::ChecksumAlgo::Sha256);
+            if checksum.exists() {
+                move_file(
+                    &checksum,
+                    &checksum_path(&_target, crate::ChecksumAlgo::Sha256),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
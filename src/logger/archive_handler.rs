@@ -0,0 +1,122 @@
+//! Handling of the archives by the handler set with
+//! [set_archive_handler](crate::logger::set_archive_handler).
+//!
+//! The handler runs during the rotation, or on a background thread started with the first
+//! archive it gets: the archives are handled one after the other, in the order of the rotations,
+//! and [flush](crate::logger::flush) waits until the ones queued so far are handled.
+
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+
+use once_cell::sync::Lazy;
+
+use crate::{ArchiveHandler, ArchiveHandlerOptions};
+
+enum Command {
+    /// Handles the archive
+    Handle {
+        handler: Arc<dyn ArchiveHandler>,
+        archive: PathBuf,
+        keep_on_failure: bool,
+    },
+    /// Answers once the archives queued before it are handled
+    Flush(mpsc::Sender<()>),
+}
+
+/// The queue of the background thread, started with the first archive handled in the background
+static QUEUE: Lazy<Mutex<Option<mpsc::Sender<Command>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Hands the archive to the handler, at once or on the background thread by the options.
+pub(crate) fn dispatch(
+    handler: &Arc<dyn ArchiveHandler>,
+    archive: PathBuf,
+    options: ArchiveHandlerOptions,
+) {
+    if !options.background {
+        handle(handler.as_ref(), &archive, options.keep_on_failure);
+        return;
+    }
+    // the thread holds no lock, it can't be poisoned by a failing handler
+    let mut queue = QUEUE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if queue.is_none() {
+        let (sender, receiver) = mpsc::channel();
+        let started = std::thread::Builder::new()
+            .name("loggit-archives".to_string())
+            .spawn(move || run(receiver));
+        if let Err(e) = started {
+            eprintln!(
+                "Unable to start the archive handler thread, handling the archive at once: {}",
+                e
+            );
+            drop(queue);
+            handle(handler.as_ref(), &archive, options.keep_on_failure);
+            return;
+        }
+        *queue = Some(sender);
+    }
+    if let Some(sender) = queue.as_ref() {
+        let _ = sender.send(Command::Handle {
+            handler: handler.clone(),
+            archive,
+            keep_on_failure: options.keep_on_failure,
+        });
+    }
+}
+
+/// Waits until the archives queued so far are handled.
+pub(crate) fn flush() {
+    let (ack_sender, ack_receiver) = mpsc::channel();
+    {
+        let queue = QUEUE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match queue.as_ref() {
+            Some(sender) if sender.send(Command::Flush(ack_sender)).is_ok() => {}
+            _ => return,
+        }
+    }
+    let _ = ack_receiver.recv();
+}
+
+/// Body of the background thread.
+fn run(receiver: mpsc::Receiver<Command>) {
+    for command in receiver {
+        match command {
+            Command::Handle {
+                handler,
+                archive,
+                keep_on_failure,
+            } => handle(handler.as_ref(), &archive, keep_on_failure),
+            Command::Flush(ack) => {
+                let _ = ack.send(());
+            }
+        }
+    }
+}
+
+/// Runs the handler on the archive, the failure is reported and the archive is removed with its
+/// checksum unless it is kept.
+fn handle(handler: &dyn ArchiveHandler, archive: &Path, keep_on_failure: bool) {
+    let e = match handler.handle(archive) {
+        Ok(_) => return,
+        Err(e) => e,
+    };
+    eprintln!(
+        "The archive handler failed on {} due to the next error: {}",
+        archive.display(),
+        e
+    );
+    if keep_on_failure {
+        return;
+    }
+    if let Err(e) = std::fs::remove_file(archive) {
+        eprintln!("Unable to remove {}: {}", archive.display(), e);
+    }
+    #[cfg(feature = "checksum")]
+    let _ = std::fs::remove_file(super::file_handler::checksum::checksum_path(
+        archive,
+        crate::ChecksumAlgo::Sha256,
+    ));
+}
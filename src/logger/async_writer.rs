@@ -0,0 +1,296 @@
+//! Writing of the log files on a background thread, see
+//! [set_async](crate::logger::set_async).
+//!
+//! The logging call renders the record and queues it, the writer thread does the rotations, the
+//! compression and the file I/O, so a slow disk doesn't stall the threads that log. The queue is
+//! bounded, what happens when it is full is decided by the [Overflow] policy: the logging call
+//! waits for a free slot, or a record is dropped. The dropped records are counted (see
+//! [stats](crate::stats)) and a summary line is written to the files once the queue is empty
+//! again.
+
+use std::collections::VecDeque;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    mpsc, Arc, Condvar, Mutex, MutexGuard,
+};
+use std::thread::JoinHandle;
+
+use crate::{helper, Config, FileOutput, Level, LogRecord, Overflow};
+
+/// Capacity of the queue used by [set_async](crate::logger::set_async)
+pub(crate) const DEFAULT_CAPACITY: usize = 1024;
+
+/// Records dropped since the start of the program, by every writer
+static DROPPED_RECORDS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Set on the writer threads, the records they log (e.g. from an archiver called during a
+    /// rotation) are written at once rather than queued, as no other thread empties the queue
+    static ON_WRITER: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Whether the current thread is a writer thread, e.g. in an archiver called during a rotation
+/// of the async mode.
+pub(crate) fn on_writer_thread() -> bool {
+    ON_WRITER.with(|on_writer| on_writer.get())
+}
+
+/// Returns the number of records dropped because the queue was full.
+pub(crate) fn dropped_records() -> u64 {
+    DROPPED_RECORDS.load(Ordering::Relaxed)
+}
+
+enum Command {
+    /// Writes the rendered record to the files
+    Write {
+        message: String,
+        files: Vec<FileOutput>,
+        config: Arc<Config>,
+    },
+    /// Answers once the commands queued before it are done
+    Flush(mpsc::Sender<()>),
+}
+
+struct State {
+    commands: VecDeque<Command>,
+    /// Number of write commands in the queue, the flushes don't take a slot
+    writes: usize,
+    /// Records dropped since the last summary line
+    dropped: u64,
+    /// Set by the shutdown, the thread stops once the queue is empty
+    closed: bool,
+}
+
+/// The queue shared by the handles and the writer thread.
+struct Queue {
+    capacity: usize,
+    state: Mutex<State>,
+    /// Notified when a command is queued or the queue is closed
+    not_empty: Condvar,
+    /// Notified when a write command is taken by the thread
+    not_full: Condvar,
+}
+
+impl Queue {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // the lock is never held while writing, it can't be poisoned by a failed write
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+}
+
+/// Owner of the thread, closes the queue once every handle is dropped.
+struct Inner {
+    queue: Arc<Queue>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+/// Handle of the writer thread, the thread stops once every handle is dropped and the queue is
+/// empty.
+#[derive(Clone)]
+pub(crate) struct AsyncWriter {
+    inner: Arc<Inner>,
+}
+
+impl AsyncWriter {
+    /// Starts the writer thread with a queue of `capacity` records.
+    pub(crate) fn start(capacity: usize) -> std::io::Result<AsyncWriter> {
+        let queue = Arc::new(Queue {
+            capacity,
+            state: Mutex::new(State {
+                commands: VecDeque::with_capacity(capacity),
+                writes: 0,
+                dropped: 0,
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        });
+        let thread_queue = queue.clone();
+        let thread = std::thread::Builder::new()
+            .name("loggit-writer".to_string())
+            .spawn(move || run(&thread_queue))?;
+        Ok(AsyncWriter {
+            inner: Arc::new(Inner {
+                queue,
+                thread: Mutex::new(Some(thread)),
+            }),
+        })
+    }
+    /// Queues the record for the files. When the queue is full it waits or drops a record,
+    /// following the overflow policy of the config. On a writer thread the record is written at
+    /// once.
+    pub(crate) fn write(&self, message: String, files: Vec<FileOutput>, config: Arc<Config>) {
+        if on_writer_thread() {
+            super::write_to_files(&message, &files, &config);
+            return;
+        }
+        let queue = &self.inner.queue;
+        let mut state = queue.lock();
+        if config.overflow_policy == Overflow::Block {
+            while state.writes >= queue.capacity && !state.closed {
+                state = queue
+                    .not_full
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+        }
+        if state.closed {
+            // the writer is being shut down, the record is written here rather than lost
+            drop(state);
+            super::write_to_files(&message, &files, &config);
+            return;
+        }
+        if state.writes >= queue.capacity {
+            match config.overflow_policy {
+                Overflow::DropNewest | Overflow::Block => {
+                    state.dropped += 1;
+                    DROPPED_RECORDS.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Overflow::DropOldest => {
+                    let oldest = state
+                        .commands
+                        .iter()
+                        .position(|command| matches!(command, Command::Write { .. }));
+                    if let Some(oldest) = oldest {
+                        state.commands.remove(oldest);
+                        state.writes -= 1;
+                    }
+                    state.dropped += 1;
+                    DROPPED_RECORDS.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        state.commands.push_back(Command::Write {
+            message,
+            files,
+            config,
+        });
+        state.writes += 1;
+        queue.not_empty.notify_one();
+    }
+    /// Waits until the records queued so far are written, returns at once on a writer thread
+    /// which would wait for itself.
+    pub(crate) fn flush(&self) {
+        if on_writer_thread() {
+            return;
+        }
+        let (ack_sender, ack_receiver) = mpsc::channel();
+        {
+            let mut state = self.inner.queue.lock();
+            if state.closed {
+                return;
+            }
+            state.commands.push_back(Command::Flush(ack_sender));
+        }
+        self.inner.queue.not_empty.notify_one();
+        let _ = ack_receiver.recv();
+    }
+    /// Returns the number of records waiting in the queue.
+    #[cfg(test)]
+    pub(crate) fn queued_records(&self) -> usize {
+        self.inner.queue.lock().writes
+    }
+    /// Writes the queued records and waits for the thread to stop.
+    pub(crate) fn shutdown(self) {
+        self.inner.queue.close();
+        let thread = self.inner.thread.lock().unwrap().take();
+        if let Some(thread) = thread {
+            if thread.join().is_err() {
+                eprintln!("The log writer thread panicked, some logs may be lost!");
+            }
+        }
+    }
+}
+
+/// Body of the writer thread.
+fn run(queue: &Queue) {
+    ON_WRITER.with(|on_writer| on_writer.set(true));
+    // the config of the last written record, used to write the summary of the dropped ones
+    let mut last_config: Option<Arc<Config>> = None;
+    loop {
+        let (command, recovered, dropped) = {
+            let mut state = queue.lock();
+            while state.commands.is_empty() && !state.closed {
+                state = queue
+                    .not_empty
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+            let command = state.commands.pop_front();
+            if let Some(Command::Write { .. }) = command {
+                state.writes -= 1;
+                queue.not_full.notify_one();
+            }
+            // the summary is written once the queue has recovered, or before answering a flush
+            let recovered = state.commands.is_empty();
+            let dropped = match recovered || matches!(command, Some(Command::Flush(_))) {
+                true => std::mem::take(&mut state.dropped),
+                false => 0,
+            };
+            (command, recovered, dropped)
+        };
+        match command {
+            Some(Command::Write {
+                message,
+                files,
+                config,
+            }) => {
+                super::write_to_files(&message, &files, &config);
+                write_dropped_summary(dropped, Some(&config));
+                last_config = Some(config);
+            }
+            Some(Command::Flush(ack)) => {
+                write_dropped_summary(dropped, last_config.as_deref());
+                let _ = ack.send(());
+            }
+            // closed and empty
+            None => return,
+        }
+        if recovered {
+            // the config holds a handle of the writer, it isn't kept while waiting so the queue
+            // is closed once the other handles are dropped
+            last_config = None;
+        }
+    }
+}
+
+/// Writes the "dropped N records" warning to the files of the config, if some were dropped.
+fn write_dropped_summary(dropped: u64, config: Option<&Config>) {
+    let config = match (dropped, config) {
+        (0, _) | (_, None) => return,
+        (_, Some(config)) => config,
+    };
+    let record = LogRecord {
+        level: Level::WARN,
+        message: format!("dropped {} records due to backpressure", dropped),
+        module_path: module_path!().to_string(),
+        file: file!().to_string(),
+        line: line!(),
+        column: column!(),
+        thread: helper::get_current_thread_name(),
+        thread_id: helper::get_current_thread_id(),
+        timestamp: helper::now(),
+        context: Default::default(),
+    };
+    let files: Vec<FileOutput> = config
+        .files
+        .iter()
+        .filter(|file| file.accepts(Level::WARN))
+        .cloned()
+        .collect();
+    super::write_to_files(&super::render_file_log(config, &record), &files, config);
+}
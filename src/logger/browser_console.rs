@@ -0,0 +1,19 @@
+//! Printing of the logs to the browser console (behind the `wasm` feature).
+//!
+//! `println!` goes nowhere in the browser, so the terminal output is written with
+//! `console.error` for the errors, `console.warn` for the warnings and `console.log` for the
+//! other levels, letting the devtools filter them.
+
+use web_sys::{console, wasm_bindgen::JsValue};
+
+use crate::Level;
+
+/// Prints the rendered log with the console method of its level.
+pub(crate) fn write(level: Level, message: &str) {
+    let message = JsValue::from_str(message);
+    match level {
+        Level::ERROR => console::error_1(&message),
+        Level::WARN => console::warn_1(&message),
+        Level::INFO | Level::DEBUG | Level::TRACE => console::log_1(&message),
+    }
+}
@@ -0,0 +1,62 @@
+//! Console setup required to display colored output.
+//!
+//! Windows consoles print ANSI escape sequences literally unless the "virtual terminal
+//! processing" mode is enabled on the output handles. This module enables it once per process
+//! (behind the `windows-console` feature) and remembers whether it succeeded, so that the logger
+//! can fall back to plain text. On other platforms nothing has to be done.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Once,
+};
+
+static ENABLE_ANSI: Once = Once::new();
+static ANSI_SUPPORTED: AtomicBool = AtomicBool::new(true);
+
+/// Enables ANSI escape sequences support for the console (only the first call does the work).
+///
+/// Returns `false` if the console can't display colors, in which case the output must not be
+/// colorized.
+pub(crate) fn enable_ansi_support() -> bool {
+    ENABLE_ANSI.call_once(|| {
+        ANSI_SUPPORTED.store(platform_enable_ansi_support(), Ordering::Relaxed);
+    });
+    ANSI_SUPPORTED.load(Ordering::Relaxed)
+}
+
+#[cfg(all(windows, feature = "windows-console"))]
+fn platform_enable_ansi_support() -> bool {
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE,
+        ENABLE_VIRTUAL_TERMINAL_PROCESSING, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+
+    let mut supported = true;
+    for std_handle in [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE] {
+        // SAFETY: the calls only query and update the mode of the process' standard handles
+        unsafe {
+            let handle = GetStdHandle(std_handle);
+            if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+                continue;
+            }
+            let mut mode: CONSOLE_MODE = 0;
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                // not a console (e.g. redirected to a file), nothing to enable
+                continue;
+            }
+            if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING == 0
+                && SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) == 0
+            {
+                // legacy console that doesn't support escape sequences
+                supported = false;
+            }
+        }
+    }
+    supported
+}
+
+#[cfg(not(all(windows, feature = "windows-console")))]
+fn platform_enable_ansi_support() -> bool {
+    true
+}
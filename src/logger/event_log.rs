@@ -0,0 +1,132 @@
+//! Writing of the logs to the Windows Event Log (behind the `windows-eventlog` feature).
+//!
+//! The records are reported by an event source registered once by
+//! [set_windows_event_log](crate::logger::set_windows_event_log): ERROR as error events, WARN as
+//! warning events and the other levels as information events. On other platforms the source
+//! can't be registered.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::Level;
+
+/// Event source the records are reported by.
+#[derive(Clone)]
+pub(crate) struct EventLogSink {
+    source: Arc<platform::EventSource>,
+    /// Set after a failed report, so the failures are reported once until a report succeeds
+    failed: Arc<AtomicBool>,
+}
+
+impl EventLogSink {
+    /// Registers the event source with the given name.
+    pub(crate) fn register(source_name: &str) -> std::io::Result<EventLogSink> {
+        Ok(EventLogSink {
+            source: Arc::new(platform::EventSource::register(source_name)?),
+            failed: Arc::new(AtomicBool::new(false)),
+        })
+    }
+    /// Reports the message as an event of the type of the level, the failures are reported once.
+    pub(crate) fn send(&self, level: Level, message: &str) {
+        match self.source.report(level, message) {
+            Ok(_) => self.failed.store(false, Ordering::Relaxed),
+            Err(e) => {
+                if !self.failed.swap(true, Ordering::Relaxed) {
+                    eprintln!("Couldn't write a log to the Event Log: {}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows_sys::Win32::Foundation::HANDLE;
+    use windows_sys::Win32::System::EventLog::{
+        DeregisterEventSource, RegisterEventSourceW, ReportEventW, EVENTLOG_ERROR_TYPE,
+        EVENTLOG_INFORMATION_TYPE, EVENTLOG_WARNING_TYPE, REPORT_EVENT_TYPE,
+    };
+
+    use crate::Level;
+
+    pub(super) struct EventSource(HANDLE);
+
+    // SAFETY: the handle of an event source can be used from any thread
+    unsafe impl Send for EventSource {}
+    unsafe impl Sync for EventSource {}
+
+    /// Returns the text as a null terminated UTF-16 string.
+    fn to_wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    impl EventSource {
+        pub(super) fn register(name: &str) -> std::io::Result<EventSource> {
+            let name = to_wide(name);
+            // SAFETY: the name is a null terminated string, no server name means the local machine
+            let handle = unsafe { RegisterEventSourceW(std::ptr::null(), name.as_ptr()) };
+            if handle.is_null() {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(EventSource(handle))
+        }
+        pub(super) fn report(&self, level: Level, message: &str) -> std::io::Result<()> {
+            let event_type: REPORT_EVENT_TYPE = match level {
+                Level::ERROR => EVENTLOG_ERROR_TYPE,
+                Level::WARN => EVENTLOG_WARNING_TYPE,
+                Level::INFO | Level::DEBUG | Level::TRACE => EVENTLOG_INFORMATION_TYPE,
+            };
+            let message = to_wide(message);
+            let strings = [message.as_ptr()];
+            // SAFETY: one null terminated string is passed, without a user or raw data
+            let res = unsafe {
+                ReportEventW(
+                    self.0,
+                    event_type,
+                    0,
+                    0,
+                    std::ptr::null_mut(),
+                    1,
+                    0,
+                    strings.as_ptr(),
+                    std::ptr::null(),
+                )
+            };
+            if res == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for EventSource {
+        fn drop(&mut self) {
+            // SAFETY: the handle was returned by `RegisterEventSourceW` and is closed only here
+            unsafe {
+                DeregisterEventSource(self.0);
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use crate::Level;
+
+    /// The Event Log only exists on Windows, so no source can be registered.
+    pub(super) enum EventSource {}
+
+    impl EventSource {
+        pub(super) fn register(_name: &str) -> std::io::Result<EventSource> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "the Event Log is only available on Windows",
+            ))
+        }
+        pub(super) fn report(&self, _level: Level, _message: &str) -> std::io::Result<()> {
+            match *self {}
+        }
+    }
+}
@@ -3,9 +3,24 @@
 //! This module groups together helpers responsible for formatting log file
 //! names, managing file rotation and compression, and writing log entries to
 //! disk.
+//!
+//! In the browser (`wasm32-unknown-unknown`) there is no file system: the file output is compiled
+//! out and [`file_manager::FileManager`] can't be created.
 
+pub(crate) mod archive_name;
+#[cfg(all(
+    feature = "checksum",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub(crate) mod checksum;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 pub(crate) mod file_formatter;
+#[cfg_attr(
+    all(target_arch = "wasm32", target_os = "unknown"),
+    path = "file_handler/unsupported.rs"
+)]
 pub(crate) mod file_manager;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 pub(crate) mod file_name;
 
 // FileName
@@ -0,0 +1,234 @@
+//! Names of the archives of the rotated log files.
+//!
+//! [`ArchiveNameFormat`] is parsed from a pattern such as `{name}_{date}_{time}.zip`: the
+//! placeholders of the file names (`{date}`, `{time}`, `{level}`, ...) are parsed with the log
+//! formats, `{name}`, `{ext}` and `{num}` are the ones of the archive.
+//!
+//! [`ArchiveSubdirFormat`] is parsed from a pattern such as `{date:%Y-%m}`, naming the
+//! subdirectory of the archive directory the archives are written to.
+
+use thiserror::Error;
+
+use crate::logger::formatter::{parse_string_to_logparts, LogPart};
+
+/// A part of an archive name pattern
+#[derive(Clone, Debug)]
+// no archive is written in the browser, the pattern is only parsed
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+enum ArchiveNamePart {
+    /// `{name}`: the name of the rotated file without its extension
+    Name,
+    /// `{ext}`: the extension of the rotated file, without the dot
+    Ext,
+    /// `{num}`: the number making the name unique, from 1
+    Num,
+    /// A text or a placeholder of the file names, e.g. `{date}`
+    Part(LogPart),
+}
+
+#[derive(Clone, Debug)]
+/// A validated archive name pattern, see [`crate::logger::set_archive_name_format`].
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+pub(crate) struct ArchiveNameFormat {
+    parts: Vec<ArchiveNamePart>,
+}
+
+#[derive(Debug, Error)]
+/// Errors that can occur while parsing an archive name pattern.
+pub enum ArchiveNameFormatError {
+    #[error("an incorrect character given: {0}")]
+    IncorrectCharacterGiven(char),
+    #[error("an empty string was provided")]
+    EmptyStringGiven,
+    #[error("an unsupported part was provided, e.g. `{{message}}`")]
+    UnsupportedPart,
+    #[error("the path leads out of the archive directory")]
+    OutsideArchiveDir,
+}
+
+impl ArchiveNameFormat {
+    /// The placeholders of the archive and their parts
+    const PLACEHOLDERS: [(&'static str, ArchiveNamePart); 3] = [
+        ("{name}", ArchiveNamePart::Name),
+        ("{ext}", ArchiveNamePart::Ext),
+        ("{num}", ArchiveNamePart::Num),
+    ];
+    /// The characters of the file name patterns, and the path separators: the archives stay in
+    /// the archive directory
+    fn forbidden_characters() -> [char; 6] {
+        ['<', '>', '&', '%', '/', '\\']
+    }
+    /// Whether the part can be in an archive name, e.g. `{date}` but not `{message}`
+    fn is_part_authorized(part: &LogPart) -> bool {
+        matches!(
+            part,
+            LogPart::Text(_)
+                | LogPart::Time(_)
+                | LogPart::Date(_)
+                | LogPart::Level(_)
+                | LogPart::Iso8601
+                | LogPart::Hostname
+                | LogPart::Pid
+                | LogPart::RunId
+                | LogPart::Env(_, _)
+        )
+    }
+    /// Parses a pattern into an [`ArchiveNameFormat`], the parts between the placeholders of the
+    /// archive are parsed like the file name patterns.
+    pub(crate) fn try_from_string(
+        format: &str,
+    ) -> Result<ArchiveNameFormat, ArchiveNameFormatError> {
+        if format.trim().is_empty() {
+            return Err(ArchiveNameFormatError::EmptyStringGiven);
+        }
+        for ch in ArchiveNameFormat::forbidden_characters() {
+            if format.contains(ch) {
+                return Err(ArchiveNameFormatError::IncorrectCharacterGiven(ch));
+            }
+        }
+        let mut parts = Vec::new();
+        let mut rest = format;
+        while !rest.is_empty() {
+            let next = ArchiveNameFormat::PLACEHOLDERS
+                .iter()
+                .filter_map(|(placeholder, part)| {
+                    rest.find(placeholder).map(|idx| (idx, placeholder, part))
+                })
+                .min_by_key(|(idx, _, _)| *idx);
+            let chunk = match next {
+                Some((idx, _, _)) => &rest[..idx],
+                None => rest,
+            };
+            if !chunk.is_empty() {
+                let log_parts = parse_string_to_logparts(chunk)
+                    .map_err(|_| ArchiveNameFormatError::UnsupportedPart)?;
+                for part in log_parts {
+                    if !ArchiveNameFormat::is_part_authorized(&part) {
+                        return Err(ArchiveNameFormatError::UnsupportedPart);
+                    }
+                    parts.push(ArchiveNamePart::Part(part));
+                }
+            }
+            match next {
+                Some((idx, placeholder, part)) => {
+                    parts.push(part.clone());
+                    rest = &rest[idx + placeholder.len()..];
+                }
+                None => rest = "",
+            }
+        }
+        Ok(ArchiveNameFormat { parts })
+    }
+    /// Returns the name of the archive of the file named `file_name`. The `num`-th name (from 1)
+    /// renders `{num}` as `num`; without `{num}` in the pattern the names after the first one are
+    /// numbered like the log files, before the last extension (`app(1).zip`).
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub(crate) fn render(&self, file_name: &str, num: u32, config: &crate::Config) -> String {
+        let (name, ext) = match file_name.rfind('.') {
+            Some(idx) => (&file_name[..idx], &file_name[idx + 1..]),
+            None => (file_name, ""),
+        };
+        let log_parts = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                ArchiveNamePart::Name => LogPart::Text(name.to_string()),
+                ArchiveNamePart::Ext => LogPart::Text(ext.to_string()),
+                ArchiveNamePart::Num => LogPart::Text(num.to_string()),
+                ArchiveNamePart::Part(part) => part.clone(),
+            })
+            .collect();
+        let res = super::file_name::FileName::get_string_from_log_parts(log_parts, config);
+        let has_num = self
+            .parts
+            .iter()
+            .any(|part| matches!(part, ArchiveNamePart::Num));
+        if has_num || num <= 1 {
+            return res;
+        }
+        match res.rfind('.') {
+            Some(idx) => format!("{}({}){}", &res[..idx], num - 1, &res[idx..]),
+            None => format!("{}({})", res, num - 1),
+        }
+    }
+    /// Returns the text every archive name of the pattern starts with and the one it ends with,
+    /// `name_prefix` being the fixed start of the names of the log files and `ext` their
+    /// extension (e.g. `app_` and `.zip` for `{name}_{time}.zip` and `app_{date}.log`).
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub(crate) fn fixed_prefix_and_suffix(&self, name_prefix: &str, ext: &str) -> (String, String) {
+        let ext = ext.trim_start_matches('.');
+        let mut prefix = String::new();
+        let mut fixed = 0;
+        for part in &self.parts {
+            match part {
+                ArchiveNamePart::Part(LogPart::Text(text)) => prefix.push_str(text),
+                ArchiveNamePart::Ext => prefix.push_str(ext),
+                ArchiveNamePart::Name => {
+                    prefix.push_str(name_prefix);
+                    break;
+                }
+                _ => break,
+            }
+            fixed += 1;
+        }
+        if fixed == self.parts.len() {
+            // a fixed name, the next archives are numbered before its extension
+            let idx = prefix.rfind('.').unwrap_or(prefix.len());
+            let suffix = prefix.split_off(idx);
+            return (prefix, suffix);
+        }
+        let mut suffix = String::new();
+        for part in self.parts[fixed + 1..].iter().rev() {
+            match part {
+                ArchiveNamePart::Part(LogPart::Text(text)) => suffix.insert_str(0, text),
+                ArchiveNamePart::Ext => suffix.insert_str(0, ext),
+                _ => break,
+            }
+        }
+        (prefix, suffix)
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A validated pattern of the subdirectories of the archive directory, see
+/// [`crate::logger::set_archive_subdir_format`].
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+pub(crate) struct ArchiveSubdirFormat {
+    parts: Vec<LogPart>,
+}
+
+impl ArchiveSubdirFormat {
+    /// The characters of the tags, the `%` of the strftime specifiers is allowed
+    fn forbidden_characters() -> [char; 4] {
+        ['<', '>', '&', '\\']
+    }
+    /// Parses a pattern into an [`ArchiveSubdirFormat`], the placeholders are the ones of the
+    /// archive names except `{name}`, `{ext}` and `{num}`. The pattern is a relative path, `/`
+    /// separates the nested directories.
+    pub(crate) fn try_from_string(
+        format: &str,
+    ) -> Result<ArchiveSubdirFormat, ArchiveNameFormatError> {
+        if format.trim().is_empty() {
+            return Err(ArchiveNameFormatError::EmptyStringGiven);
+        }
+        for ch in ArchiveSubdirFormat::forbidden_characters() {
+            if format.contains(ch) {
+                return Err(ArchiveNameFormatError::IncorrectCharacterGiven(ch));
+            }
+        }
+        if format.starts_with('/') || format.split('/').any(|component| component == "..") {
+            return Err(ArchiveNameFormatError::OutsideArchiveDir);
+        }
+        let parts = parse_string_to_logparts(format)
+            .map_err(|_| ArchiveNameFormatError::UnsupportedPart)?;
+        if !parts.iter().all(ArchiveNameFormat::is_part_authorized) {
+            return Err(ArchiveNameFormatError::UnsupportedPart);
+        }
+        Ok(ArchiveSubdirFormat { parts })
+    }
+    /// Returns the subdirectory for the current date and time
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub(crate) fn render(&self, config: &crate::Config) -> String {
+        super::file_name::FileName::get_string_from_log_parts(self.parts.clone(), config)
+    }
+}
@@ -0,0 +1,48 @@
+//! Checksums written next to the archives, see [`crate::logger::set_archive_checksum`].
+//!
+//! The checksum of `app.log.zip` is written to `app.log.zip.sha256` in the format of `sha256sum`
+//! (`<hex>  app.log.zip`), so `sha256sum -c` checks the archive from its directory.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::ChecksumAlgo;
+
+/// Returns the path of the checksum of the archive by the given path, `<archive>.sha256`
+pub(crate) fn checksum_path(archive: &Path, algo: ChecksumAlgo) -> PathBuf {
+    let extension = match algo {
+        ChecksumAlgo::Sha256 => ".sha256",
+    };
+    let mut path = archive.as_os_str().to_owned();
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+/// Streams the archive by the given path through the hasher and writes its checksum next to it,
+/// returns the path of the checksum
+pub(crate) fn write_checksum(archive: &Path, algo: ChecksumAlgo) -> io::Result<PathBuf> {
+    let mut reader = BufReader::new(File::open(archive)?);
+    let digest = match algo {
+        ChecksumAlgo::Sha256 => {
+            let mut hasher = Sha256::new();
+            io::copy(&mut reader, &mut hasher)?;
+            hasher.finalize()
+        }
+    };
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let file_name = archive
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+
+    let path = checksum_path(archive, algo);
+    let mut file = File::create(&path)?;
+    writeln!(file, "{}  {}", hex, file_name)?;
+    file.sync_all()?;
+    Ok(path)
+}
@@ -5,6 +5,7 @@
 //! into a concrete file name.
 
 use crate::logger::formatter::LogPart;
+use crate::{helper, Config, Level, TimePrecision};
 
 use thiserror::Error;
 
@@ -29,7 +30,24 @@ pub enum FileFormatterTryFromStringError {
 
 impl FileFormatter {
     pub(crate) fn is_part_authorized(part: &LogPart) -> bool {
-        !matches!(part, LogPart::Message | LogPart::File | LogPart::Line)
+        !matches!(
+            part,
+            LogPart::Message
+                | LogPart::File
+                | LogPart::FileShort(_)
+                | LogPart::Line
+                | LogPart::Column
+                | LogPart::Thread
+                | LogPart::ThreadId
+                | LogPart::Timestamp
+                | LogPart::TimestampMs
+                | LogPart::Elapsed
+                | LogPart::ElapsedMs
+                | LogPart::Context
+                | LogPart::Custom(_)
+                | LogPart::Symbol
+                | LogPart::Optional(_, _)
+        )
     }
     fn forbidden_characters() -> [char; 4] {
         ['<', '>', '&', '%']
@@ -37,12 +55,21 @@ impl FileFormatter {
     /// Parses a template string into a [`FileFormatter`].
     ///
     /// Ensures that only allowed placeholders are present and that the
-    /// resulting file name ends with a valid extension (`.txt` or `.log`).
+    /// resulting file name ends with an extension, which is checked against the allowed ones
+    /// when the name is built (see [`super::file_name::FileName`]).
+    ///
+    /// The pattern may start with the directories of the file, relative or absolute (e.g.
+    /// `logs/app_{date}.log`), only the name of the file after the last separator is checked for
+    /// the forbidden characters and the extension.
     pub(crate) fn try_from_string(
         format: &str,
     ) -> Result<FileFormatter, FileFormatterTryFromStringError> {
+        let file_name = match format.rfind(std::path::is_separator) {
+            Some(idx) => &format[idx + 1..],
+            None => format,
+        };
         for ch in FileFormatter::forbidden_characters() {
-            if format.contains(ch) {
+            if file_name.contains(ch) {
                 return Err(FileFormatterTryFromStringError::IncorrectCharacterGiven(ch));
             }
         }
@@ -58,6 +85,11 @@ impl FileFormatter {
             crate::logger::formatter::LogPart::Text(t) => t,
             _ => return Err(FileFormatterTryFromStringError::NoFileExtensionProvided),
         };
+        // the extension is the one of the file, not of a directory (`logs.d/app`)
+        let text = match text.rfind(std::path::is_separator) {
+            Some(idx) => &text[idx + 1..],
+            None => text.as_str(),
+        };
         if !text.contains(".") {
             return Err(FileFormatterTryFromStringError::NoFileExtensionProvided);
         }
@@ -71,4 +103,150 @@ impl FileFormatter {
         }
         Ok(FileFormatter { format: elems })
     }
+    /// Returns the text every file name of the format starts with and its extension (e.g.
+    /// `app_` and `.log` for `app_{date}.log`).
+    pub(crate) fn fixed_prefix_and_extension(&self) -> (String, String) {
+        let prefix = match self.format.first() {
+            Some(LogPart::Text(text)) if self.format.len() > 1 => text.clone(),
+            _ => String::new(),
+        };
+        let extension = match self.format.last() {
+            Some(LogPart::Text(text)) => match text.rfind('.') {
+                Some(idx) => text[idx..].to_string(),
+                None => String::new(),
+            },
+            _ => String::new(),
+        };
+        (prefix, extension)
+    }
+    /// Whether the format has a fixed text in the name of its files, the extension aside, with a
+    /// letter or a digit (`app` in `logs/app_{date}.log`, none in `logs/{date}_{time}.log`)
+    pub(crate) fn has_fixed_text(&self) -> bool {
+        let mut fixed = String::new();
+        for part in &self.format {
+            if let LogPart::Text(text) | LogPart::Env(_, text) = part {
+                match text.rfind('/') {
+                    Some(idx) => fixed = text[idx + 1..].to_string(),
+                    None => fixed.push_str(text),
+                }
+            }
+        }
+        let stem = match fixed.rfind('.') {
+            Some(idx) => &fixed[..idx],
+            None => fixed.as_str(),
+        };
+        stem.chars().any(char::is_alphanumeric)
+    }
+    /// Whether the file by the given path may have been created with the format and the config:
+    /// its texts are matched as they are and each placeholder by the shape of what it renders,
+    /// `{date}` and `{time}` by their format, `{pid}` and `{num}` by digits, `{level}` by the
+    /// names of the levels. The number added before the extension of a taken name is matched
+    /// too (e.g. `app_16-10-2026.log` and `app_16-10-2026(1).log` for `app_{date}.log`).
+    pub(crate) fn matches(&self, path: &str, config: &Config) -> bool {
+        if FileFormatter::matches_parts(&self.format, path, config) {
+            return true;
+        }
+        let idx = match path.rfind('.') {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let (stem, extension) = path.split_at(idx);
+        let unnumbered = stem
+            .strip_suffix(')')
+            .and_then(|stem| stem.rsplit_once('('))
+            .filter(|(_, num)| is_number(num));
+        match unnumbered {
+            Some((stem, _)) => FileFormatter::matches_parts(
+                &self.format,
+                &format!("{}{}", stem, extension),
+                config,
+            ),
+            None => false,
+        }
+    }
+    /// Whether the text is matched by the parts, see [`FileFormatter::matches`]
+    fn matches_parts(parts: &[LogPart], text: &str, config: &Config) -> bool {
+        match parts.split_first() {
+            None => text.is_empty(),
+            Some((LogPart::Text(fixed), rest)) | Some((LogPart::Env(_, fixed), rest)) => {
+                match text.strip_prefix(fixed.as_str()) {
+                    Some(text) => FileFormatter::matches_parts(rest, text, config),
+                    None => false,
+                }
+            }
+            Some((part, rest)) => {
+                // a placeholder stays in its directory
+                let end = text.find('/').unwrap_or(text.len());
+                (0..=end)
+                    .filter(|idx| text.is_char_boundary(*idx))
+                    .any(|idx| {
+                        FileFormatter::matches_placeholder(part, &text[..idx], config)
+                            && FileFormatter::matches_parts(rest, &text[idx..], config)
+                    })
+            }
+        }
+    }
+    /// Whether the text has the shape of what the placeholder renders with the config
+    fn matches_placeholder(part: &LogPart, text: &str, config: &Config) -> bool {
+        match part {
+            // `%d` and `%H` read the numbers with or without the zero padding
+            LogPart::Date(None) => matches_time_format(text, "%d-%m-%Y"),
+            LogPart::Time(None) => match config.time_settings.precision {
+                TimePrecision::Seconds => matches_time_format(text, "%H:%M:%S"),
+                TimePrecision::Millis => matches_time_format(text, "%H:%M:%S%.3f"),
+            },
+            LogPart::Date(Some(spec)) | LogPart::Time(Some(spec)) => {
+                matches_time_format(text, spec)
+            }
+            // the colons are written as dashes, the offset is `Z` or like `+02-00`
+            LogPart::Iso8601 => {
+                let date_time = match text.strip_suffix('Z') {
+                    Some(date_time) => date_time,
+                    None => match text.len().checked_sub(6) {
+                        Some(idx) if is_offset(text.get(idx..).unwrap_or_default()) => &text[..idx],
+                        _ => return false,
+                    },
+                };
+                matches_time_format(date_time, "%Y-%m-%dT%H-%M-%S%.3f")
+            }
+            LogPart::Level(style) => [
+                Level::TRACE,
+                Level::DEBUG,
+                Level::INFO,
+                Level::WARN,
+                Level::ERROR,
+            ]
+            .iter()
+            .any(|level| style.apply(&config.level_name(*level)) == text),
+            LogPart::Hostname => text == helper::get_hostname(),
+            LogPart::Pid => is_number(text),
+            LogPart::RunId => text.len() == 8 && text.chars().all(|ch| ch.is_ascii_hexdigit()),
+            LogPart::Num(_) => is_number(text),
+            _ => false,
+        }
+    }
+}
+
+/// Whether the text is made of digits only, at least one
+fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|ch| ch.is_ascii_digit())
+}
+
+/// Whether the text is an offset of `{iso8601}` in a file name, like `+02-00`
+fn is_offset(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 6
+        && matches!(bytes[0], b'+' | b'-')
+        && bytes[3] == b'-'
+        && [1, 2, 4, 5].iter().all(|idx| bytes[*idx].is_ascii_digit())
+}
+
+/// Whether chrono reads the whole text with the strftime specifier
+fn matches_time_format(text: &str, spec: &str) -> bool {
+    chrono::format::parse(
+        &mut chrono::format::Parsed::new(),
+        text,
+        chrono::format::StrftimeItems::new(spec),
+    )
+    .is_ok()
 }
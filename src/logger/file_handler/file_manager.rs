@@ -7,33 +7,76 @@
 use std::{
     fs::File,
     io::{self, BufReader},
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime},
 };
 
-use chrono::Timelike;
+use chrono::{Datelike, Timelike};
+use flate2::{write::GzEncoder, Compression};
 use thiserror::Error;
 use zip::{result::ZipError, write::SimpleFileOptions, CompressionMethod, ZipWriter};
 
+#[cfg(feature = "checksum")]
+use crate::ChecksumAlgo;
 use crate::{
     helper::{self, WriteToFileError},
-    logger::archivation,
-    Config,
+    logger::{archivation, archive_handler, CSV_HEADER},
+    ArchiveInfo, ArchiveMode, BufferPolicy, Config, OutputFormat, RotationMode, SyncPolicy,
+    TimeZoneChoice,
 };
 
+#[cfg(feature = "checksum")]
+use super::checksum;
+
 use super::{
     file_formatter::{FileFormatter, FileFormatterTryFromStringError},
     file_name::{FileName, FileNameFromFileFormatterError},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 /// Central structure responsible for writing log records to files.
 ///
 /// It owns the current log file and metadata used to decide when a new file
-/// should be created.
+/// should be created. The records buffered in the batched mode are written
+/// when it is dropped.
 pub(crate) struct FileManager {
     file_format: FileFormatter,
     file_name: FileName,
     file_constraints: FileConstraints,
     curr_file: std::sync::Arc<std::fs::File>,
+    /// Size of the current file, counted as it is written so the size rotations don't read the
+    /// metadata for each record. `None` when it has to be read again, e.g. after a failed write
+    file_size: Option<u64>,
+    /// The file existed when it was opened and a fresh one is asked on startup, it is rotated
+    /// at the first write, once the compression is configured
+    rotate_on_first_write: bool,
+    buffer_policy: BufferPolicy,
+    /// The records waiting to be written in the batched mode
+    buffer: Vec<u8>,
+    /// When the oldest record of the buffer was added
+    buffered_since: Option<Instant>,
+    sync_policy: SyncPolicy,
+    /// Syncs the file to the disk, replaced in the tests to count the syncs
+    syncer: Box<dyn SyncData>,
+    /// When the file was last synced
+    last_sync: Instant,
+    /// Whether some records were written since the last sync
+    unsynced: bool,
+}
+
+/// Makes the data written to a file durable, see [`SyncPolicy`].
+pub(crate) trait SyncData: std::fmt::Debug + Send {
+    fn sync_data(&self, file: &File) -> io::Result<()>;
+}
+
+/// Syncs with [`File::sync_data`].
+#[derive(Debug)]
+pub(crate) struct FileSync;
+
+impl SyncData for FileSync {
+    fn sync_data(&self, file: &File) -> io::Result<()> {
+        file.sync_data()
+    }
 }
 
 #[derive(Error, Debug)]
@@ -44,15 +87,40 @@ pub enum FileManagerFromStringError {
     #[error("format parsing for the file name error: {0}")]
     FileNameParsingError(FileNameFromFileFormatterError),
     #[error("io error {0}")]
-    IoError(std::io::Error)
+    IoError(std::io::Error),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+/// Errors produced when parsing a rotation like `5 MB`, `1 day` or `12:30`.
+pub enum RotationParseError {
+    #[error("`{0}` isn't a valid number")]
+    InvalidNumber(String),
+    #[error("the unit is missing after the number")]
+    MissingUnit,
+    #[error("unknown unit `{0}`, expected kb, mb, gb, tb, second, minute, hour, day, week, month or year")]
+    UnknownUnit(String),
+    #[error("{hour:02}:{minute:02} isn't a time of the day, expected 00:00 to 23:59")]
+    InvalidTime { hour: u8, minute: u8 },
+    #[error("`{0}` isn't a time like 12:30")]
+    MalformedTime(String),
+    #[error("unknown weekday `{0}`")]
+    UnknownWeekday(String),
+    #[error("`{0}` isn't a day of the month, expected 1 to 31")]
+    InvalidDayOfMonth(String),
+    #[error("`{0}` isn't a day of the year, expected a month and a day like 07-01")]
+    InvalidDayOfYear(String),
+    #[error("the period {0:?} is shorter than a second")]
+    PeriodTooShort(std::time::Duration),
+    #[error("the size must be at least a byte")]
+    ZeroSize,
 }
 
 #[derive(Error, Debug)]
 pub(crate) enum CompressFileError {
-    #[error("error with verifying archivation folder {0}")]
-    UnableToCreateArchivationFolder(std::io::Error),
     #[error("unable to create a zip file: {0}")]
     UnableToCreateZipFile(std::io::Error),
+    #[error("unable to create a gzip file: {0}")]
+    UnableToCreateGzipFile(std::io::Error),
     #[error("unable to open file to compress: {0}")]
     UnableToOpenFileToCompress(std::io::Error),
     #[error("unable to start zip archiving: {0}")]
@@ -63,10 +131,20 @@ pub(crate) enum CompressFileError {
     UnableToWriteToArchive,
     #[error("unable to finish archivation: {0}")]
     UnableToFinishArchivation(ZipError),
+    #[error("unable to finish the gzip archivation: {0}")]
+    UnableToFinishGzipArchivation(std::io::Error),
+    #[cfg(feature = "tar-gz")]
+    #[error("unable to add the file to the tarball: {0}")]
+    UnableToWriteTarball(std::io::Error),
+    #[cfg(feature = "zstd")]
+    #[error("unable to write the zstd file: {0}")]
+    UnableToWriteZstdFile(std::io::Error),
     #[error("unable to get compression settings")]
     UnableToGetCompressionSettings,
     #[error("inaccessible archivation directory: {0}")]
     InaccessibleArchivationDirectory(std::io::Error),
+    #[error("unable to set the permissions of the archive: {0}")]
+    UnableToSetPermissions(std::io::Error),
 }
 
 #[derive(Error, Debug)]
@@ -81,10 +159,14 @@ pub(crate) enum VerifyConstraintsError {
     UnableToGetFileMetadata(String, std::io::Error),
     #[error("unable to delete old log files: {0} {1}")]
     UnableToDeleteOldLogFile(String, std::io::Error),
-    #[error("uable to compress file")]
-    UnableToCompressFile,
+    #[error("unable to compress file: {0}")]
+    UnableToCompressFile(String),
+    #[error("unable to move the old log file to the archive dir: {0} {1}")]
+    UnableToMoveOldLogFile(String, std::io::Error),
     #[error("unable to create a new file: {0}")]
     UnableToCreateNewFile(CreateNewFileError),
+    #[error("unable to remove the old archives: {0}")]
+    UnableToPurgeArchives(std::io::Error),
 }
 pub(crate) enum VerifyConstraintsRes {
     ConstraintsPassed,
@@ -96,6 +178,8 @@ pub(crate) enum WriteLogError {
     UnableToWriteToFile(WriteToFileError),
     #[error("constraints verification failed: {0}")]
     VerifyConstraintsError(VerifyConstraintsError),
+    #[error("unable to sync the file: {0}")]
+    UnableToSyncFile(std::io::Error),
 }
 #[derive(Debug, Error)]
 pub(crate) enum CreateNewFileError {
@@ -105,6 +189,10 @@ pub(crate) enum CreateNewFileError {
     UnableToCreateFileIO(std::io::Error),
     #[error("unable to get the file name: {0}")]
     UnableToGetFileName(FileNameFromFileFormatterError),
+    #[error("unable to write the buffered records to the old file: {0}")]
+    UnableToWriteBuffer(std::io::Error),
+    #[error("unable to sync the old file: {0}")]
+    UnableToSyncOldFile(std::io::Error),
 }
 
 impl FileManager {
@@ -118,49 +206,135 @@ impl FileManager {
                 return Err(FileManagerFromStringError::FileFormatParsingError(e));
             }
         };
-        let f_name = match FileName::from_file_formatter(f_format.clone(), config.level) {
+        let f_name = match FileName::from_file_formatter(f_format.clone(), &config) {
             Ok(f) => f,
             Err(e) => {
                 return Err(FileManagerFromStringError::FileNameParsingError(e));
             }
         };
         let full_file_name: String = f_name.clone().into();
-
-    let mut file = match std::fs::OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(full_file_name)
-    {
-        Ok(f) => f,
-        Err(e) => {
+        // an existing file is continued, unless a fresh one is asked on startup
+        let rotate_on_first_write =
+            config.rotate_on_startup && std::path::Path::new(&full_file_name).exists();
+        if let Err(e) = FileManager::create_parent_dir(&full_file_name) {
             return Err(FileManagerFromStringError::IoError(e));
         }
-    };
+
+        let mut file = match FileManager::open_log_file(&full_file_name, &config) {
+            Ok(f) => f,
+            Err(e) => {
+                return Err(FileManagerFromStringError::IoError(e));
+            }
+        };
 
         Ok(FileManager {
             file_format: f_format,
             file_name: f_name,
             file_constraints: Default::default(),
-            curr_file: std::sync::Arc::new(file)
+            curr_file: std::sync::Arc::new(file),
+            file_size: None,
+            rotate_on_first_write,
+            buffer_policy: BufferPolicy::PerRecord,
+            buffer: Vec::new(),
+            buffered_since: None,
+            sync_policy: SyncPolicy::Never,
+            syncer: Box::new(FileSync),
+            last_sync: Instant::now(),
+            unsynced: false,
         })
     }
+    /// Parses a size written like a size rotation (e.g. `200 MB`) to bytes
+    pub(crate) fn parse_size(text: &str) -> Option<u64> {
+        match RotationType::parse_allowing_zero(text) {
+            Ok(RotationType::Size(bytes)) => Some(bytes),
+            _ => None,
+        }
+    }
+    /// Parses an age written like a period rotation (e.g. `30 day`) to seconds
+    pub(crate) fn parse_age(text: &str) -> Option<u64> {
+        match RotationType::parse_allowing_zero(text) {
+            Ok(RotationType::Period(secs)) => Some(secs),
+            _ => None,
+        }
+    }
     /// Returns full current file name (that already exists) in a String
     pub(crate) fn get_file_name(&self) -> String {
         self.file_name.get_full_file_name()
     }
+    /// Returns the rotations, to move their deadlines in the tests
+    #[cfg(test)]
+    pub(crate) fn rotations_mut(&mut self) -> &mut [Rotation] {
+        &mut self.file_constraints.rotation
+    }
     pub(crate) fn remove_rotations(&mut self) {
         self.file_constraints.rotation = Vec::new();
     }
-    pub(crate) fn add_rotation(&mut self, string: &str) -> bool {
-        let rot_type = match RotationType::try_from_string(string) {
-            Some(r) => r,
-            None => {
-                return false;
-            }
-        };
-        let rot = Rotation::init_from_rotation_type(rot_type);
+    /// Removes the rotations parsing to the same rotation as the string, returns whether there
+    /// was one
+    pub(crate) fn remove_rotation(&mut self, string: &str) -> Result<bool, RotationParseError> {
+        let rot_type = RotationType::try_from_string(string)?;
+        let rotations = &mut self.file_constraints.rotation;
+        let count = rotations.len();
+        rotations.retain(|rot| rot.rotation_type != rot_type);
+        Ok(rotations.len() != count)
+    }
+    /// Returns the rotations, written like the strings they are parsed from
+    pub(crate) fn list_rotations(&self) -> Vec<String> {
+        self.file_constraints
+            .rotation
+            .iter()
+            .map(|rot| rot.rotation_type.to_string())
+            .collect()
+    }
+    pub(crate) fn add_rotation(
+        &mut self,
+        string: &str,
+        timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        let rot_type = RotationType::try_from_string(string)?;
+        self.push_rotation(rot_type, timezone);
+        Ok(())
+    }
+    /// Adds a period rotation, in whole seconds (rounded down)
+    pub(crate) fn add_rotation_period(
+        &mut self,
+        period: std::time::Duration,
+        timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        if period.as_secs() == 0 {
+            return Err(RotationParseError::PeriodTooShort(period));
+        }
+        self.push_rotation(RotationType::Period(period.as_secs()), timezone);
+        Ok(())
+    }
+    /// Adds a size rotation, in bytes
+    pub(crate) fn add_rotation_size(
+        &mut self,
+        bytes: u64,
+        timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        if bytes == 0 {
+            return Err(RotationParseError::ZeroSize);
+        }
+        self.push_rotation(RotationType::Size(bytes), timezone);
+        Ok(())
+    }
+    /// Adds a rotation at a time of the day
+    pub(crate) fn add_rotation_at(
+        &mut self,
+        hour: u8,
+        minute: u8,
+        timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        if hour > 23 || minute > 59 {
+            return Err(RotationParseError::InvalidTime { hour, minute });
+        }
+        self.push_rotation(RotationType::Time(hour, minute), timezone);
+        Ok(())
+    }
+    fn push_rotation(&mut self, rot_type: RotationType, timezone: TimeZoneChoice) {
+        let rot = Rotation::init_from_rotation_type(rot_type, timezone);
         self.file_constraints.rotation.push(rot);
-        true
     }
     pub(crate) fn set_compression(&mut self, string: &str) -> bool {
         match CompressionType::try_from_string(string) {
@@ -171,39 +345,141 @@ impl FileManager {
             None => false,
         }
     }
-    fn set_curr_file(&mut self, curr_file: std::fs::File){
+    fn set_curr_file(&mut self, curr_file: std::fs::File) {
         self.curr_file = std::sync::Arc::new(curr_file);
+        // the file may already have some content
+        self.file_size = None;
+    }
+    /// Returns the size of the current file, the metadata is only read when the size isn't known
+    fn file_size(&mut self) -> io::Result<u64> {
+        match self.file_size {
+            Some(size) => Ok(size),
+            None => {
+                let size = self.curr_file.metadata()?.len();
+                self.file_size = Some(size);
+                Ok(size)
+            }
+        }
+    }
+    /// Counts the bytes of a write in the size of the file, or forgets the size if the write
+    /// failed as a part of it may have been written
+    fn count_written<T, E>(&mut self, res: &Result<T, E>, bytes: usize) {
+        match (res, &mut self.file_size) {
+            (Ok(_), Some(size)) => *size += bytes as u64,
+            (Ok(_), None) => {}
+            (Err(_), _) => self.file_size = None,
+        }
     }
     pub(crate) fn remove_compression(&mut self) {
         self.file_constraints.compression = None;
     }
+    /// Sets when the records are written, the ones already buffered are written at once
+    pub(crate) fn set_buffer_policy(&mut self, policy: BufferPolicy) -> io::Result<()> {
+        self.buffer_policy = policy;
+        self.flush_buffer()
+    }
+    /// Writes the records buffered in the batched mode to the current file
+    pub(crate) fn flush_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let res = io::Write::write_all(&mut &*self.curr_file, &self.buffer);
+        self.count_written(&res, self.buffer.len());
+        // the records aren't written twice if the error happened in the middle
+        self.buffer.clear();
+        self.buffered_since = None;
+        res?;
+        self.after_write()
+    }
+    /// Sets when the written records are synced, the ones not synced yet are synced at once
+    pub(crate) fn set_sync_policy(&mut self, policy: SyncPolicy) -> io::Result<()> {
+        self.sync_pending()?;
+        self.sync_policy = policy;
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+    /// Replaces the way the file is synced
+    #[cfg(test)]
+    pub(crate) fn set_syncer(&mut self, syncer: Box<dyn SyncData>) {
+        self.syncer = syncer;
+    }
+    /// Syncs the records written since the last sync, unless the policy is `Never`
+    pub(crate) fn sync_pending(&mut self) -> io::Result<()> {
+        if !self.unsynced || self.sync_policy == SyncPolicy::Never {
+            return Ok(());
+        }
+        self.sync()
+    }
+    fn sync(&mut self) -> io::Result<()> {
+        self.syncer.sync_data(&self.curr_file)?;
+        self.last_sync = Instant::now();
+        self.unsynced = false;
+        Ok(())
+    }
+    /// Syncs the file after a write if the policy asks for it
+    fn after_write(&mut self) -> io::Result<()> {
+        self.unsynced = true;
+        match self.sync_policy {
+            SyncPolicy::Never => Ok(()),
+            SyncPolicy::EveryRecord => self.sync(),
+            SyncPolicy::Interval(interval) => match self.last_sync.elapsed() >= interval {
+                true => self.sync(),
+                false => Ok(()),
+            },
+        }
+    }
+    /// Writes the header row to the current file if the file output is CSV and the file is empty
+    fn write_csv_header(&mut self, config: &Config) -> io::Result<()> {
+        if config.file_output_format != OutputFormat::Csv
+            || !self.buffer.is_empty()
+            || self.file_size()? > 0
+        {
+            return Ok(());
+        }
+        let header = format!("{}\n", CSV_HEADER);
+        let res = io::Write::write_all(&mut &*self.curr_file, header.as_bytes());
+        self.count_written(&res, header.len());
+        res
+    }
 
     pub(crate) fn create_new_file(&mut self, config: &Config) -> Result<(), CreateNewFileError> {
+        // the buffered records belong to the old file
+        self.flush_buffer()
+            .map_err(CreateNewFileError::UnableToWriteBuffer)?;
+        self.sync_pending()
+            .map_err(CreateNewFileError::UnableToSyncOldFile)?;
         loop {
             match std::path::Path::new(&self.file_name.get_full_file_name()).exists() {
                 false => {
-                    let new_f_name =
-                        match FileName::from_file_formatter(self.file_format.clone(), config.level)
-                        {
+                    let mut new_f_name =
+                        match FileName::from_file_formatter(self.file_format.clone(), config) {
                             Ok(r) => r,
                             Err(e) => {
                                 return Err(CreateNewFileError::UnableToGetFileName(e));
                             }
                         };
+                    // the name may be taken, e.g. by the old file when it is rotated within the
+                    // same second or the pattern has no time
+                    while self.is_name_taken(&new_f_name.get_full_file_name(), config) {
+                        new_f_name.increase_num();
+                    }
                     self.file_name = new_f_name;
                     let f_name_str = self.file_name.get_full_file_name();
-                    let file = match std::fs::OpenOptions::new()
-                            .append(true)
-                            .create(true)
-                            .open(f_name_str) 
-                    {
+                    // the directories may depend on the date, or have been removed
+                    if let Err(e) = FileManager::create_parent_dir(&f_name_str) {
+                        return Err(CreateNewFileError::UnableToCreateFileIO(e));
+                    }
+                    let file = match FileManager::open_log_file(&f_name_str, config) {
                         Ok(f) => f,
                         Err(e) => {
                             return Err(CreateNewFileError::UnableToCreateFileIO(e));
                         }
                     };
                     self.set_curr_file(file);
-                    return Ok(())
+                    if let Err(e) = self.write_csv_header(config) {
+                        return Err(CreateNewFileError::UnableToCreateFileIO(e));
+                    }
+                    return Ok(());
                 }
                 true => {
                     self.file_name.increase_num();
@@ -212,12 +488,110 @@ impl FileManager {
         }
     }
 
-    /// compresses a file by the given path in a zip archive
-    fn compress_zip(&self, path: &str) -> Result<(), CompressFileError> {
-        if let Err(e) = archivation::ensure_archive_dir() {
-            return Err(CompressFileError::UnableToCreateArchivationFolder(e));
+    /// Opens the log file by the given path to append to it. A missing file is created with the
+    /// permissions of the config, see [`crate::logger::set_file_permissions`]
+    fn open_log_file(path: &str, config: &Config) -> io::Result<File> {
+        let existed = std::path::Path::new(path).exists();
+        let mut options = std::fs::OpenOptions::new();
+        options.append(true).create(true);
+        #[cfg(unix)]
+        if let Some(mode) = config.file_permissions {
+            use std::os::unix::fs::OpenOptionsExt;
+            // created no more open than the mode (the umask applies), then set to it below
+            options.mode(mode);
+        }
+        let file = options.open(path)?;
+        if !existed {
+            FileManager::apply_permissions(Path::new(path), config)?;
+        }
+        Ok(file)
+    }
+    /// Gives the permissions of the config to a file created by the logger (see
+    /// [`crate::logger::set_file_permissions`]), whatever the umask
+    fn apply_permissions(path: &Path, config: &Config) -> io::Result<()> {
+        #[cfg(unix)]
+        if let Some(mode) = config.file_permissions {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+        }
+        #[cfg(not(unix))]
+        let _ = (path, config);
+        Ok(())
+    }
+    /// Creates the directories of the file by the given path with their parents, if it has any
+    fn create_parent_dir(path: &str) -> io::Result<()> {
+        match std::path::Path::new(path).parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir),
+            _ => Ok(()),
+        }
+    }
+    /// Whether a new file can't take the name: the file exists, or its archive does and would be
+    /// overwritten once the new file is archived
+    fn is_name_taken(&self, name: &str, config: &Config) -> bool {
+        if std::path::Path::new(name).exists() {
+            return true;
+        }
+        let name = FileManager::archive_file_name(name);
+        match self.archive_mode(config) {
+            ArchiveMode::Move => return archivation::archive_dir(config).join(name).exists(),
+            ArchiveMode::LeaveInPlace => return false,
+            ArchiveMode::Compress => {}
+        }
+        // the archives named by the format are numbered instead
+        if config.archive_name_format.is_some() && self.file_constraints.compression.is_some() {
+            return false;
+        }
+        let archived = match self.file_constraints.compression {
+            Some(CompressionType::Zip) => format!("{}.zip", name),
+            Some(CompressionType::Gzip) => format!("{}.gz", name),
+            #[cfg(feature = "zstd")]
+            Some(CompressionType::Zstd) => format!("{}.zst", name),
+            // the tarball numbers the entries with the same name
+            #[cfg(feature = "tar-gz")]
+            Some(CompressionType::TarGz) => return false,
+            None => return false,
+        };
+        archivation::archive_dir(config).join(archived).exists()
+    }
+
+    /// Returns the name of the file by the given path without its directories, the archives are
+    /// all in the archive directory whatever the directory of the log file is
+    fn archive_file_name(path: &str) -> String {
+        std::path::Path::new(path)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Returns the path of the archive of the file by the given path: `<file name><suffix>` in
+    /// the archive directory `dir`, or the name given by the archive name format of the config,
+    /// numbered if an archive already has it
+    fn archive_path(path: &str, suffix: &str, dir: &Path, config: &Config) -> PathBuf {
+        let file_name = FileManager::archive_file_name(path);
+        let format = match &config.archive_name_format {
+            Some(format) => format,
+            None => return dir.join(format!("{}{}", file_name, suffix)),
+        };
+        let mut num = 1;
+        loop {
+            let archive = dir.join(format.render(&file_name, num, config));
+            if !archive.exists() {
+                return archive;
+            }
+            num += 1;
         }
-        let zip_file_path = archivation::archive_dir().join(format!("{}.zip", path));
+    }
+
+    /// compresses a file by the given path in a zip archive, `<path>.zip` or the name given by
+    /// the archive name format
+    fn compress_zip(
+        &self,
+        path: &str,
+        dir: &Path,
+        config: &Config,
+    ) -> Result<PathBuf, CompressFileError> {
+        let zip_file_path = FileManager::archive_path(path, ".zip", dir, config);
         let zip_file = std::fs::File::create(&zip_file_path)
             .map_err(CompressFileError::UnableToCreateZipFile)?;
         let mut zip = ZipWriter::new(zip_file);
@@ -227,169 +601,562 @@ impl FileManager {
             std::fs::File::open(path).map_err(CompressFileError::UnableToOpenFileToCompress)?;
         let mut reader = BufReader::new(file);
 
-        let entry_name = std::path::Path::new(path).file_name().unwrap_or_default().to_string_lossy();
+        let entry_name = FileManager::archive_file_name(path);
         zip.start_file(entry_name, options)
             .map_err(CompressFileError::UnableToStartZipArchiving)?;
         std::io::copy(&mut reader, &mut zip).map_err(CompressFileError::UnableToCopyContents)?;
         zip.finish()
             .map_err(CompressFileError::UnableToFinishArchivation)?;
-        Ok(())
+        Ok(zip_file_path)
 
         //println!("Files compressed successfully to {:?}", zip_file_path);
     }
+    /// Streams the file by the given path into `<path>.gz` in the archive directory, or the name
+    /// given by the archive name format
+    fn compress_gzip(
+        &self,
+        path: &str,
+        dir: &Path,
+        config: &Config,
+    ) -> Result<PathBuf, CompressFileError> {
+        let gz_file_path = FileManager::archive_path(path, ".gz", dir, config);
+        let gz_file = std::fs::File::create(&gz_file_path)
+            .map_err(CompressFileError::UnableToCreateGzipFile)?;
+        let mut encoder = GzEncoder::new(gz_file, Compression::default());
+
+        let file =
+            std::fs::File::open(path).map_err(CompressFileError::UnableToOpenFileToCompress)?;
+        let mut reader = BufReader::new(file);
+        std::io::copy(&mut reader, &mut encoder)
+            .map_err(CompressFileError::UnableToCopyContents)?;
+        encoder
+            .finish()
+            .map_err(CompressFileError::UnableToFinishGzipArchivation)?;
+        Ok(gz_file_path)
+    }
+    /// Streams the file by the given path into `<path>.zst` in the archive directory, or the
+    /// name given by the archive name format
+    #[cfg(feature = "zstd")]
+    fn compress_zstd(
+        &self,
+        path: &str,
+        dir: &Path,
+        config: &Config,
+    ) -> Result<PathBuf, CompressFileError> {
+        /// The default level of zstd, a good balance between the ratio and the speed
+        const ZSTD_LEVEL: i32 = 3;
+        let zst_file_path = FileManager::archive_path(path, ".zst", dir, config);
+        let zst_file = std::fs::File::create(&zst_file_path)
+            .map_err(CompressFileError::UnableToWriteZstdFile)?;
+        let mut encoder = zstd::stream::write::Encoder::new(zst_file, ZSTD_LEVEL)
+            .map_err(CompressFileError::UnableToWriteZstdFile)?;
+
+        let file =
+            std::fs::File::open(path).map_err(CompressFileError::UnableToOpenFileToCompress)?;
+        let mut reader = BufReader::new(file);
+        std::io::copy(&mut reader, &mut encoder)
+            .map_err(CompressFileError::UnableToCopyContents)?;
+        encoder
+            .finish()
+            .map_err(CompressFileError::UnableToWriteZstdFile)?;
+        Ok(zst_file_path)
+    }
+    /// Adds the file by the given path to the tarball of the day in the archive directory,
+    /// `<prefix>logs-<date>.tar.gz` (e.g. `app-logs-16-10-2026.tar.gz` for `app-{time}.log`).
+    ///
+    /// A compressed tarball can't be appended to, so it is recreated: its entries and the file are
+    /// written to `<tarball>.tmp`, which then replaces it. The tarball is never left half written,
+    /// at the cost of rewriting the day's archives at each rotation.
+    #[cfg(feature = "tar-gz")]
+    fn compress_tar_gz(
+        &self,
+        path: &str,
+        dir: &Path,
+        config: &Config,
+    ) -> Result<PathBuf, CompressFileError> {
+        let (prefix, _) = self.file_format.fixed_prefix_and_extension();
+        let prefix = FileManager::archive_file_name(&prefix);
+        let date = helper::get_current_date_in_string(config.time_settings);
+        let tar_path = dir.join(format!("{}logs-{}.tar.gz", prefix, date));
+        let entry_name = FileManager::archive_file_name(path);
+        FileManager::append_to_tar_gz(&tar_path, path, &entry_name)
+            .map_err(CompressFileError::UnableToWriteTarball)?;
+        Ok(tar_path)
+    }
+    /// Rewrites the tarball with the file appended as `entry_name`, numbered like the log files
+    /// (`app(1).log`) if the tarball already has an entry with this name
+    #[cfg(feature = "tar-gz")]
+    fn append_to_tar_gz(
+        tar_path: &std::path::Path,
+        path: &str,
+        entry_name: &str,
+    ) -> io::Result<()> {
+        if let Some(parent) = tar_path.parent() {
+            archivation::ensure_archivable_dir(&parent.to_path_buf())?;
+        }
+        let mut tmp_path = tar_path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_file = File::create(&tmp_path)?;
+        let mut builder = tar::Builder::new(GzEncoder::new(tmp_file, Compression::default()));
+
+        let mut names = std::collections::HashSet::new();
+        match File::open(tar_path) {
+            Ok(old) => {
+                let decoder = flate2::read::GzDecoder::new(BufReader::new(old));
+                let mut archive = tar::Archive::new(decoder);
+                for entry in archive.entries()? {
+                    let mut entry = entry?;
+                    let header = entry.header().clone();
+                    names.insert(entry.path()?.to_string_lossy().to_string());
+                    builder.append(&header, &mut entry)?;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let mut name = entry_name.to_string();
+        let (stem, extension) = match entry_name.rfind('.') {
+            Some(idx) => entry_name.split_at(idx),
+            None => (entry_name, ""),
+        };
+        let mut num = 0;
+        while names.contains(&name) {
+            num += 1;
+            name = format!("{}({}){}", stem, num, extension);
+        }
+        builder.append_path_with_name(path, &name)?;
+        builder.into_inner()?.finish()?;
+        std::fs::rename(&tmp_path, tar_path)
+    }
     /// Compresses a file by the given path depending on the set compression algortithm in the
-    /// config
-    pub(crate) fn compress_file(&self, path: &str) -> Result<(), CompressFileError> {
-        if let Err(e) = archivation::ensure_archive_dir() {
+    /// config, into the current archive directory (created with its parents if needed), returns
+    /// the path of the archive. It gets the permissions of the log files, if any are set
+    pub(crate) fn compress_file(
+        &self,
+        path: &str,
+        config: &Config,
+    ) -> Result<PathBuf, CompressFileError> {
+        // the directory may depend on the date, it is computed once for the whole archive
+        let dir = archivation::archive_dir(config);
+        if let Err(e) = archivation::ensure_archivable_dir(&dir) {
             return Err(CompressFileError::InaccessibleArchivationDirectory(e));
         }
-        if let Some(compr_t) = &self.file_constraints.compression {
-            match compr_t {
-                CompressionType::Zip => self.compress_zip(path),
+        let archive = match &self.file_constraints.compression {
+            Some(CompressionType::Zip) => self.compress_zip(path, &dir, config)?,
+            Some(CompressionType::Gzip) => self.compress_gzip(path, &dir, config)?,
+            #[cfg(feature = "tar-gz")]
+            Some(CompressionType::TarGz) => self.compress_tar_gz(path, &dir, config)?,
+            #[cfg(feature = "zstd")]
+            Some(CompressionType::Zstd) => self.compress_zstd(path, &dir, config)?,
+            None => return Err(CompressFileError::UnableToGetCompressionSettings),
+        };
+        // the archive is as private as the log files
+        FileManager::apply_permissions(&archive, config)
+            .map_err(CompressFileError::UnableToSetPermissions)?;
+        Ok(archive)
+    }
+    /// Removes the archives of this file older than the retention age of the config, then the
+    /// oldest ones until they fit in the maximal total size, returns how many were removed. Only
+    /// the files named like the archives of this file (the fixed start of its name and its
+    /// extension followed by `.zip`, `.gz` or `.zst`, or the tarballs starting the same way) are
+    /// considered, so the other files of a shared archive directory are never removed. With an
+    /// archive name format, the fixed start and end of its names are used instead. With a
+    /// subdirectory format the archives of all the subdirectories are considered, and the
+    /// subdirectories left empty are removed.
+    pub(crate) fn purge_archives(&self, config: &Config) -> io::Result<usize> {
+        if config.archive_retention.is_none() && config.archive_max_total_size.is_none() {
+            return Ok(0);
+        }
+        let (prefix, extension) = self.file_format.fixed_prefix_and_extension();
+        // the archive of `logs/app.log` is `<archive dir>/app.log.zip`
+        let name_prefix = match prefix.rfind('/') {
+            Some(idx) => &prefix[idx + 1..],
+            None => prefix.as_str(),
+        };
+        // (start, end) of the archive names
+        let mut patterns = match &config.archive_name_format {
+            Some(format) => {
+                let (start, end) = format.fixed_prefix_and_suffix(name_prefix, &extension);
+                // a format without any fixed part would match every file
+                if start.is_empty() && end.is_empty() {
+                    vec![]
+                } else {
+                    vec![(start, end)]
+                }
+            }
+            None => vec![
+                (name_prefix.to_string(), format!("{}.zip", extension)),
+                (name_prefix.to_string(), format!("{}.gz", extension)),
+                (name_prefix.to_string(), format!("{}.zst", extension)),
+            ],
+        };
+        patterns.push((name_prefix.to_string(), ".tar.gz".to_string()));
+        let base = archivation::base_archive_dir(config);
+        let in_subdirs = config.archive_subdir_format.is_some();
+        // (modification time, size, path) of the archives
+        let mut archives = Vec::new();
+        FileManager::find_archives(&base, in_subdirs, &patterns, &mut archives)?;
+        // the oldest first
+        archives.sort_by_key(|(modified, _, _)| *modified);
+
+        let mut removed = 0;
+        if let Some(retention) = config.archive_retention {
+            let retention = std::time::Duration::from_secs(retention);
+            // a modification time in the future has no age
+            let expired = archives
+                .iter()
+                .take_while(|(modified, _, _)| modified.elapsed().unwrap_or_default() > retention)
+                .count();
+            for (_, _, path) in archives.drain(..expired) {
+                FileManager::remove_archive(&path, &base, in_subdirs)?;
+                removed += 1;
+            }
+        }
+        if let Some(max_total_size) = config.archive_max_total_size {
+            let mut total_size: u64 = archives.iter().map(|(_, size, _)| size).sum();
+            for (_, size, path) in archives {
+                if total_size <= max_total_size {
+                    break;
+                }
+                FileManager::remove_archive(&path, &base, in_subdirs)?;
+                total_size -= size;
+                removed += 1;
             }
-        } else {
-            Err(CompressFileError::UnableToGetCompressionSettings)
         }
+        Ok(removed)
+    }
+    /// Adds the (modification time, size, path) of the files of the directory named like one of
+    /// the (start, end) patterns to `archives`, and the ones of its subdirectories if `recursive`
+    fn find_archives(
+        dir: &Path,
+        recursive: bool,
+        patterns: &[(String, String)],
+        archives: &mut Vec<(SystemTime, u64, PathBuf)>,
+    ) -> io::Result<()> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                if recursive {
+                    FileManager::find_archives(&entry.path(), true, patterns, archives)?;
+                }
+                continue;
+            }
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let is_archive = patterns.iter().any(|(start, end)| {
+                name.starts_with(start.as_str()) && name.ends_with(end.as_str())
+            });
+            if !is_archive {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            archives.push((metadata.modified()?, metadata.len(), entry.path()));
+        }
+        Ok(())
+    }
+    /// Returns the files of the archive directory (and of its subdirectories with a subdirectory
+    /// format) with the extension of an archive, `.zip`, `.gz` (the tarballs included) or
+    /// `.zst`, the oldest first
+    pub(crate) fn list_archives(config: &Config) -> io::Result<Vec<ArchiveInfo>> {
+        let patterns = [".zip", ".gz", ".zst"].map(|end| (String::new(), end.to_string()));
+        let mut found = Vec::new();
+        FileManager::find_archives(
+            &archivation::base_archive_dir(config),
+            config.archive_subdir_format.is_some(),
+            &patterns,
+            &mut found,
+        )?;
+        found.sort();
+        let archives = found
+            .into_iter()
+            .map(|(modified, size, path)| {
+                let name = FileManager::archive_file_name(&path.to_string_lossy());
+                // the archives named after a log file end with its extension, one allowed for
+                // the log files, unlike the tarballs bundling several ones
+                let file_name = name
+                    .strip_suffix(".zip")
+                    .or_else(|| name.strip_suffix(".gz"))
+                    .or_else(|| name.strip_suffix(".zst"))
+                    .filter(|file_name| match file_name.rsplit_once('.') {
+                        Some((_, ext)) => FileName::is_acceptable_file_extension(ext, config),
+                        None => false,
+                    })
+                    .map(str::to_string);
+                ArchiveInfo {
+                    path,
+                    size,
+                    modified,
+                    file_name,
+                }
+            })
+            .collect();
+        Ok(archives)
+    }
+    /// Whether the format of the file has a fixed text to find its old files by, see
+    /// [`FileFormatter::has_fixed_text`]
+    pub(crate) fn has_fixed_text(&self) -> bool {
+        self.file_format.has_fixed_text()
+    }
+    /// Removes the log files of the format older than `max_age` seconds, except the `active` ones
+    /// written to, returns how many were removed. The directory of the fixed start of the format
+    /// is searched (e.g. `logs/` for `logs/app_{date}.log`), its files are matched by
+    /// [`FileFormatter::matches`] with the config.
+    pub(crate) fn purge_old_logs(
+        &self,
+        max_age: u64,
+        active: &[String],
+        config: &Config,
+    ) -> io::Result<usize> {
+        let (prefix, _) = self.file_format.fixed_prefix_and_extension();
+        let dir = match prefix.rfind('/') {
+            Some(idx) => &prefix[..idx + 1],
+            None => "",
+        };
+        let entries = match std::fs::read_dir(if dir.is_empty() { "." } else { dir }) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let max_age = std::time::Duration::from_secs(max_age);
+        let mut removed = 0;
+        for entry in entries {
+            let entry = entry?;
+            let path = format!("{}{}", dir, entry.file_name().to_string_lossy());
+            if active.contains(&path) || !self.file_format.matches(&path, config) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            // a modification time in the future has no age
+            if !metadata.is_file() || metadata.modified()?.elapsed().unwrap_or_default() <= max_age
+            {
+                continue;
+            }
+            std::fs::remove_file(&path)?;
+            removed += 1;
+        }
+        Ok(removed)
     }
-    /// verifying file constraints (rotation time and file size) and if one of the constraints
-    /// doesn't pass, it creates new file (archives the changed file if it's set in the config)
+    /// Removes an archive and its checksum, and with `in_subdirs` the subdirectories of `base` it
+    /// leaves empty
+    fn remove_archive(path: &Path, base: &Path, in_subdirs: bool) -> io::Result<()> {
+        std::fs::remove_file(path)?;
+        // its checksum, if any, goes with it
+        #[cfg(feature = "checksum")]
+        let _ = std::fs::remove_file(checksum::checksum_path(path, ChecksumAlgo::Sha256));
+        if !in_subdirs {
+            return Ok(());
+        }
+        let mut dir = path.parent();
+        while let Some(parent) = dir {
+            // a directory with other files isn't removed, which ends the loop
+            if parent == base || !parent.starts_with(base) || std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+        Ok(())
+    }
+    /// Rotates the file if its rotations are due, one of them or all of them by the rotation mode
+    /// of the config (see [`crate::logger::set_rotation_mode`]). `pending` is the length of the
+    /// record about to be written: a size rotation happens before the record would make the file
+    /// exceed the size. A record longer than the size is still written to the new (empty) file,
+    /// which is rotated at the next record.
+    ///
+    /// The rotations are evaluated first, then on a rotation a new file is created, all the
+    /// rotations start again from now and the old file is archived. With [`RotationMode::All`] a
+    /// due rotation stays due until the others are.
     pub(crate) fn verify_constraints(
         &mut self,
         config: &Config,
+        pending: u64,
     ) -> Result<VerifyConstraintsRes, VerifyConstraintsError> {
+        if self.file_constraints.rotation.is_empty() {
+            return Ok(VerifyConstraintsRes::ConstraintsPassed);
+        }
         let curr_file_name = self.file_name.get_full_file_name();
-        let file = self.curr_file.clone();
-        let f_size = match file.metadata() {
+        let f_size = match self.file_size() {
             Err(e) => {
                 return Err(VerifyConstraintsError::UnableToGetFileMetadata(
                     curr_file_name.clone(),
                     e,
                 ));
             }
-            Ok(data) => data.len(),
+            // the buffered records count, so the size rotation happens at the same record
+            Ok(size) => size + self.buffer.len() as u64,
         };
-        let mut last_idx: i32 = -1;
-        // we need last_idx for: if we found not satsfying constraint, than we create a new file,
-        // thus we have to update all the constraints we had, to set the to the original values,
-        // as a consequence, we have last_idx, if it's not -1, than on last_idx rotation we created
-        // new file and update all the constraints to initial values
-        let mut idx: usize = 0;
-        let mut res: Result<VerifyConstraintsRes, VerifyConstraintsError> =
-            Ok(VerifyConstraintsRes::ConstraintsPassed);
-        loop {
-            if (idx) >= (self.file_constraints.rotation.len()) && last_idx == -1 {
-                // if we haven't
-                // met any
-                // unverified
-                // constraints
-                // and reached
-                // the end, we
-                // stop
-                break;
-            }
-            if (idx as i32) == last_idx {
-                // if we reached last index it means we restarted from 0,
-                // then we ran through the right part from last_idx and
-                // though the left as well
-                break;
-            }
-            if idx >= (self.file_constraints.rotation.len()) && last_idx != -1 {
-                // if we meet the
-                // end and
-                // last_idx is not
-                // -1, then we
-                // should go
-                // through the
-                // left part from
-                // last_idx again
-                idx = 0;
-            }
-            if last_idx == 0 {
-                // if last_idx == 0 then the first one wasn't satisfied and it was
-                // immediately handled
-                break;
-            }
+        let unix_now = chrono::Utc::now().timestamp().max(0) as u64; // never negative
+        let mut due = self
+            .file_constraints
+            .rotation
+            .iter()
+            .map(|rot| rot.is_due(unix_now, f_size, pending));
+        let rotate = match config.rotation_mode {
+            RotationMode::Any => due.any(|is_due| is_due),
+            RotationMode::All => due.all(|is_due| is_due),
+        };
+        if !rotate {
+            return Ok(VerifyConstraintsRes::ConstraintsPassed);
+        }
 
-            //each rot logic
-
-            let rot = self.file_constraints.rotation[idx];
-            match rot.rotation_type {
-                RotationType::Period(_) | RotationType::Time(_, _) => {
-                    let unix_now = chrono::Utc::now().timestamp()        
-                            .max(0) // never negative
-                            as u64;
-                    if unix_now > rot.next_rotation || last_idx != -1 {
-                        // if current time is ahead of our
-                        // rotation that we set a new one and create
-                        // a new file
-                        let new_rot = Rotation::init_from_rotation_type(rot.rotation_type);
-                        self.file_constraints.rotation[idx] = new_rot;
-                        if last_idx == -1 {
-                            match self.create_new_file(config) {
-                                Ok(_) => {}
-                                Err(e) => {
-                                    return Err(VerifyConstraintsError::UnableToCreateNewFile(e));
-                                }
-                            }
-                            if self.compress_file(&curr_file_name).is_ok() {
-                                if let Err(e) = FileManager::delete_file(&curr_file_name) {
-                                    res = Err(VerifyConstraintsError::UnableToDeleteOldLogFile(
-                                        curr_file_name.clone(),
-                                        e,
-                                    ));
-                                } else {
-                                    res = Ok(VerifyConstraintsRes::NewFileCreated)
-                                }
-                            } else {
-                                res = Err(VerifyConstraintsError::UnableToCompressFile)
-                            }
-                            last_idx = idx as i32;
-                        }
-                    }
-                }
-                RotationType::Size(_) => {
-                    if f_size > rot.next_rotation || last_idx != -1 {
-                        let new_rot = Rotation::init_from_rotation_type(rot.rotation_type);
-                        self.file_constraints.rotation[idx] = new_rot;
-                        if last_idx == -1 {
-                            match self.create_new_file(config) {
-                                Ok(_) => {}
-                                Err(e) => {
-                                    return Err(VerifyConstraintsError::UnableToCreateNewFile(e));
-                                }
-                            }
-                            if self.compress_file(&curr_file_name).is_ok() {
-                                if let Err(e) = FileManager::delete_file(&curr_file_name) {
-                                    res = Err(VerifyConstraintsError::UnableToDeleteOldLogFile(
-                                        curr_file_name.clone(),
-                                        e,
-                                    ));
-                                } else {
-                                    res = Ok(VerifyConstraintsRes::NewFileCreated)
-                                }
-                            } else {
-                                res = Err(VerifyConstraintsError::UnableToCompressFile)
-                            }
-                            last_idx = idx as i32;
-                        }
-                    }
-                }
+        self.create_new_file(config)
+            .map_err(VerifyConstraintsError::UnableToCreateNewFile)?;
+        self.reset_rotations(config);
+        let mut res = self
+            .archive_old_file(&curr_file_name, config)
+            .map(|_| VerifyConstraintsRes::NewFileCreated);
+        // the file was rotated, the old archives are removed
+        if let Err(e) = self.purge_archives(config) {
+            if res.is_ok() {
+                res = Err(VerifyConstraintsError::UnableToPurgeArchives(e));
             }
-            // end
-            idx += 1;
         }
         res
     }
+    /// Starts all the rotations again from now, once the file is rotated
+    fn reset_rotations(&mut self, config: &Config) {
+        let timezone = config.time_settings.timezone;
+        for rot in self.file_constraints.rotation.iter_mut() {
+            *rot = Rotation::init_from_rotation_type(rot.rotation_type, timezone);
+        }
+    }
     pub(crate) fn delete_file(path: &str) -> io::Result<()> {
         std::fs::remove_file(path)
     }
+    /// Moves a file into the archive directory as it is, without its directories. It is copied
+    /// and removed if it can't be renamed there, i.e. the archive directory is on another file
+    /// system
+    fn move_to_archive_dir(path: &str, config: &Config) -> io::Result<()> {
+        let dir = archivation::archive_dir(config);
+        archivation::ensure_archivable_dir(&dir)?;
+        let target = dir.join(FileManager::archive_file_name(path));
+        archivation::move_file(Path::new(path), &target)
+    }
+    /// Returns what is done with the rotated files: the mode of the config if one is set (see
+    /// [`crate::logger::set_archive_mode`]), otherwise they are compressed if the compression or
+    /// the archiver is set, moved if only the archive directory is set, and left in place if
+    /// none is
+    fn archive_mode(&self, config: &Config) -> ArchiveMode {
+        if let Some(mode) = config.archive_mode {
+            return mode;
+        }
+        if config.archiver.is_some() || self.file_constraints.compression.is_some() {
+            ArchiveMode::Compress
+        } else if config.archive_dir.is_some() {
+            ArchiveMode::Move
+        } else {
+            ArchiveMode::LeaveInPlace
+        }
+    }
+    /// Archives the old file once a new one is created, by the archive mode: it is handed to the
+    /// archiver and removed if one is set (see [`crate::logger::set_archiver`]) or compressed into
+    /// the archive directory and removed, moved there as it is, or left in place. The archive of
+    /// the compression is then given to the archive handler, if one is set (see
+    /// [`crate::logger::set_archive_handler`])
+    fn archive_old_file(&self, path: &str, config: &Config) -> Result<(), VerifyConstraintsError> {
+        match self.archive_mode(config) {
+            ArchiveMode::Compress => {}
+            ArchiveMode::Move => {
+                return FileManager::move_to_archive_dir(path, config).map_err(|e| {
+                    VerifyConstraintsError::UnableToMoveOldLogFile(path.to_string(), e)
+                })
+            }
+            ArchiveMode::LeaveInPlace => return Ok(()),
+        }
+        if let Some(archiver) = &config.archiver {
+            if let Err(e) = archiver(std::path::Path::new(path)) {
+                return Err(VerifyConstraintsError::UnableToCompressFile(e.to_string()));
+            }
+            // the archiver may have moved the file away already
+            if !std::path::Path::new(path).exists() {
+                return Ok(());
+            }
+            return FileManager::delete_file(path).map_err(|e| {
+                VerifyConstraintsError::UnableToDeleteOldLogFile(path.to_string(), e)
+            });
+        }
+        let archive = match self.compress_file(path, config) {
+            Ok(archive) => archive,
+            Err(e) => return Err(VerifyConstraintsError::UnableToCompressFile(e.to_string())),
+        };
+        // a missing checksum doesn't make the archive any worse, it is kept
+        #[cfg(feature = "checksum")]
+        if let Some(algo) = config.archive_checksum {
+            let written = checksum::write_checksum(&archive, algo)
+                .and_then(|path| FileManager::apply_permissions(&path, config));
+            if let Err(e) = written {
+                eprintln!(
+                    "Unable to write the checksum of {}: {}",
+                    archive.display(),
+                    e
+                );
+            }
+        }
+        FileManager::delete_file(path)
+            .map_err(|e| VerifyConstraintsError::UnableToDeleteOldLogFile(path.to_string(), e))?;
+        if let Some(handler) = &config.archive_handler {
+            archive_handler::dispatch(handler, archive, config.archive_handler_options);
+        }
+        Ok(())
+    }
+
+    /// Rotates the file at once, whatever its rotations: a new file is started, the old one is
+    /// archived (see [`FileManager::archive_old_file`]), the rotations start again from now and
+    /// the old archives are removed
+    pub(crate) fn rotate_now(&mut self, config: &Config) -> Result<(), VerifyConstraintsError> {
+        self.rotate_on_first_write = false;
+        let old_file_name = self.file_name.get_full_file_name();
+        self.create_new_file(config)
+            .map_err(VerifyConstraintsError::UnableToCreateNewFile)?;
+        self.reset_rotations(config);
+        self.archive_old_file(&old_file_name, config)?;
+        if let Err(e) = self.purge_archives(config) {
+            return Err(VerifyConstraintsError::UnableToPurgeArchives(e));
+        }
+        Ok(())
+    }
 
+    /// Archives the file that existed on startup (see [`FileManager::archive_old_file`]) and
+    /// starts a new one
+    fn rotate_existing_file(&mut self, config: &Config) -> Result<(), VerifyConstraintsError> {
+        self.rotate_on_first_write = false;
+        let old_file_name = self.file_name.get_full_file_name();
+        self.create_new_file(config)
+            .map_err(VerifyConstraintsError::UnableToCreateNewFile)?;
+        self.archive_old_file(&old_file_name, config)?;
+        if let Err(e) = self.purge_archives(config) {
+            return Err(VerifyConstraintsError::UnableToPurgeArchives(e));
+        }
+        Ok(())
+    }
+
+    /// Writes the rendered record to the file, rotating it first if its rotations are due. The
+    /// config is the snapshot of the logging call, borrowed rather than cloned: a rotation needs
+    /// most of it (the time settings, the level names and the allowed extensions for the name of
+    /// the new file, the archive settings for the old one), so a struct of the fields used would
+    /// copy nearly all of them at each record.
     pub(crate) fn write_log(
         &mut self,
         mess: &str,
-        config: Config,
+        config: &Config,
     ) -> Result<VerifyConstraintsRes, WriteLogError> {
-        let verify_res = match self.verify_constraints(&config) {
+        if self.rotate_on_first_write {
+            if let Err(e) = self.rotate_existing_file(config) {
+                eprintln!(
+                    "An error occured while starting a new file on startup: {}",
+                    e
+                );
+            }
+        }
+        // the record and its new line
+        let verify_res = match self.verify_constraints(config, mess.len() as u64 + 1) {
             Ok(r) => Ok(r),
             Err(e) => {
                 eprintln!("An error occured while verifying constraints: {}", e);
@@ -398,113 +1165,323 @@ impl FileManager {
             }
         };
 
-        let arc_file = self.curr_file.clone();
-        let mut file = (*arc_file).try_clone().map_err(|e| WriteLogError::UnableToWriteToFile(WriteToFileError::UnexpectedError(e)))?;
-        helper::write_to_file(&mut file, mess)
-            .map_err(WriteLogError::UnableToWriteToFile)?;
-
-        self.set_curr_file(file);
+        // the format may have been set after the file was opened
+        if let Err(e) = self.write_csv_header(config) {
+            return Err(WriteLogError::UnableToWriteToFile(
+                WriteToFileError::UnexpectedError(e),
+            ));
+        }
+        match self.buffer_policy {
+            BufferPolicy::PerRecord => {
+                let res = helper::write_to_file(&self.curr_file, mess);
+                self.count_written(&res, mess.len() + 1);
+                res.map_err(WriteLogError::UnableToWriteToFile)?;
+                self.after_write()
+                    .map_err(WriteLogError::UnableToSyncFile)?;
+            }
+            BufferPolicy::Batched {
+                max_bytes,
+                max_delay,
+            } => {
+                self.buffer.extend_from_slice(mess.as_bytes());
+                self.buffer.push(b'\n');
+                let since = *self.buffered_since.get_or_insert_with(Instant::now);
+                if self.buffer.len() >= max_bytes || since.elapsed() >= max_delay {
+                    self.flush_buffer().map_err(|e| {
+                        WriteLogError::UnableToWriteToFile(WriteToFileError::UnexpectedError(e))
+                    })?;
+                }
+            }
+        }
 
         verify_res.map_err(WriteLogError::VerifyConstraintsError)
     }
 }
 
+impl Drop for FileManager {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush_buffer() {
+            eprintln!(
+                "Couldn't write the buffered logs to the file due to the next error: {}",
+                e
+            );
+        }
+        if let Err(e) = self.sync_pending() {
+            eprintln!("Couldn't sync the log file due to the next error: {}", e);
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub(crate) enum RotationType {
     Period(u64),  // every 1 week for example
     Time(u8, u8), //every day at 12:00 for example
     Size(u64),    //500 MB for example
+    Hourly,       //at the start of every hour
+    Weekly {
+        weekday: chrono::Weekday,
+        hour: u8,
+        minute: u8,
+    }, //every sunday at 03:00 for example
+    Monthly {
+        day: u8,
+        hour: u8,
+        minute: u8,
+    }, //every 1st of the month at 00:00 for example, the last day of the shorter months
+    Yearly {
+        month: u8,
+        day: u8,
+    }, //every 1st of january at 00:00 for example, the 28th of february out of the leap years
 }
 
 impl RotationType {
-    pub(crate) fn try_from_string(text: &str) -> Option<RotationType> {
+    /// Parses a rotation, a size of 0 bytes (e.g. `0.0001 KB`) and a period of 0 seconds are
+    /// rejected
+    pub(crate) fn try_from_string(text: &str) -> Result<RotationType, RotationParseError> {
+        match RotationType::parse_allowing_zero(text)? {
+            RotationType::Size(0) => Err(RotationParseError::ZeroSize),
+            RotationType::Period(0) => Err(RotationParseError::PeriodTooShort(
+                std::time::Duration::ZERO,
+            )),
+            rot_type => Ok(rot_type),
+        }
+    }
+    /// Parses a rotation like [RotationType::try_from_string] but accepts a size or a period of
+    /// 0, which are limits and not rotations for [FileManager::parse_size] and
+    /// [FileManager::parse_age]
+    fn parse_allowing_zero(text: &str) -> Result<RotationType, RotationParseError> {
+        let text = text.trim().to_ascii_lowercase();
+        match text.as_str() {
+            "daily" | "midnight" => return Ok(RotationType::Time(0, 0)),
+            "hourly" => return Ok(RotationType::Hourly),
+            _ => {}
+        }
+        if let Some(weekly) = RotationType::weekly_from_string(&text) {
+            return weekly;
+        }
+        if let Some(monthly) = RotationType::monthly_from_string(&text) {
+            return monthly;
+        }
+        if let Some(yearly) = RotationType::yearly_from_string(&text) {
+            return yearly;
+        }
         if text.contains(":") {
             // time
             let sp: Vec<&str> = text.split(":").collect();
             if sp.len() != 2 {
-                return None;
+                return Err(RotationParseError::MalformedTime(text));
             }
-            let h: u8 = match sp[0].parse() {
+            let h: u8 = match sp[0].trim().parse() {
                 Ok(n) => n,
-                Err(_) => return None,
+                Err(_) => return Err(RotationParseError::InvalidNumber(sp[0].trim().to_string())),
             };
-            let m: u8 = match sp[1].parse() {
+            let m: u8 = match sp[1].trim().parse() {
                 Ok(n) => n,
-                Err(_) => return None,
+                Err(_) => return Err(RotationParseError::InvalidNumber(sp[1].trim().to_string())),
             };
-            if !(0..=23).contains(&h) {
-                return None;
-            }
-            if !(0..=59).contains(&m) {
-                return None;
+            if !(0..=23).contains(&h) || !(0..=59).contains(&m) {
+                return Err(RotationParseError::InvalidTime { hour: h, minute: m });
             }
-            Some(RotationType::Time(h, m))
-        } else if text.ends_with(" KB")
-            || text.ends_with(" MB")
-            || text.ends_with(" GB")
-            || text.ends_with(" TB")
-        {
-            //size
-            let multiply_factor;
-            if text.ends_with(" KB") {
-                multiply_factor = 1024;
-            } else if text.ends_with(" MB") {
-                multiply_factor = 1024 * 1024;
-            } else if text.ends_with(" GB") {
-                multiply_factor = 1024 * 1024 * 1024;
-            } else if text.ends_with(" TB") {
-                multiply_factor = 1024 * 1024 * 1024 * 1024;
-            } else {
-                multiply_factor = 1;
-            }
-
-            let t_len = text.len();
-            let text = &text[0..(t_len - 3)];
-            let num: u64 = match text.parse() {
-                Ok(n) => n,
-                Err(_) => {
-                    return None;
-                }
-            };
-            Some(RotationType::Size(num * multiply_factor))
-        } else if text.ends_with(" hour")
-            || text.ends_with(" day")
-            || text.ends_with(" week")
-            || text.ends_with(" month")
-            || text.ends_with(" year")
-        {
+            return Ok(RotationType::Time(h, m));
+        }
+        let (number, unit) = RotationType::split_number(&text);
+        if number.is_empty() {
+            return Err(RotationParseError::InvalidNumber(text));
+        }
+        if unit.is_empty() {
+            return Err(RotationParseError::MissingUnit);
+        }
+        let invalid_number = || RotationParseError::InvalidNumber(number.to_string());
+        if let Some(multiply_factor) = RotationType::size_unit(unit) {
+            //size, a decimal number is floored to bytes
+            let num: f64 = number.parse().map_err(|_| invalid_number())?;
+            Ok(RotationType::Size(
+                (num * multiply_factor as f64).floor() as u64
+            ))
+        } else if let Some(multiply_factor) = RotationType::period_unit(unit) {
             // period
-            let multiply_factor: u64;
-            let finish_txt: &str = {
-                if text.ends_with(" hour") {
-                    multiply_factor = 60 * 60;
-                    " hour"
-                } else if text.ends_with(" day") {
-                    multiply_factor = 60 * 60 * 24;
-                    " day"
-                } else if text.ends_with(" week") {
-                    multiply_factor = 60 * 60 * 24 * 7;
-                    " week"
-                } else if text.ends_with(" month") {
-                    multiply_factor = 60 * 60 * 24 * 30;
-                    " month"
-                } else {
-                    multiply_factor = 60 * 60 * 24 * 365;
-                    " year"
-                }
-            };
-            let fin_len = finish_txt.len();
-            let str_len = text.len();
-            let text_to_parse = &text[0..(str_len - fin_len)];
-            let num: u64 = match text_to_parse.parse() {
-                Ok(n) => n,
-                Err(_) => {
-                    return None;
-                }
-            };
-            Some(RotationType::Period(num * multiply_factor))
+            let num: u64 = number.parse().map_err(|_| invalid_number())?;
+            num.checked_mul(multiply_factor)
+                .map(RotationType::Period)
+                .ok_or_else(invalid_number)
         } else {
-            None
+            Err(RotationParseError::UnknownUnit(unit.to_string()))
+        }
+    }
+    /// Parses a weekly rotation like `sunday 03:00` or `weekly sunday 03:00` (in lowercase),
+    /// returns `None` if the text isn't a weekly rotation
+    fn weekly_from_string(text: &str) -> Option<Result<RotationType, RotationParseError>> {
+        let (day, time) = match text.strip_prefix("weekly ") {
+            Some(rest) => rest
+                .trim_start()
+                .split_once(' ')
+                .unwrap_or((rest.trim_start(), "")),
+            None => text.split_once(' ')?,
+        };
+        let weekday: chrono::Weekday = match day.parse() {
+            Ok(weekday) => weekday,
+            // only a text starting with `weekly` has to be a weekly rotation
+            Err(_) if text.starts_with("weekly ") => {
+                return Some(Err(RotationParseError::UnknownWeekday(day.to_string())))
+            }
+            Err(_) => return None,
+        };
+        let time = time.trim();
+        Some(match RotationType::try_from_string(time) {
+            Ok(RotationType::Time(hour, minute)) => Ok(RotationType::Weekly {
+                weekday,
+                hour,
+                minute,
+            }),
+            Err(e @ RotationParseError::InvalidTime { .. }) => Err(e),
+            _ => Err(RotationParseError::MalformedTime(time.to_string())),
+        })
+    }
+    /// Parses a monthly rotation like `monthly`, `monthly 15` or `monthly 15 03:00` (in lowercase),
+    /// at 00:00 on the 1st by default, returns `None` if the text isn't a monthly rotation
+    fn monthly_from_string(text: &str) -> Option<Result<RotationType, RotationParseError>> {
+        let rest = match text.strip_prefix("monthly") {
+            Some(rest) if rest.is_empty() || rest.starts_with(' ') => rest.trim(),
+            _ => return None,
+        };
+        let (day, time) = rest.split_once(' ').unwrap_or((rest, ""));
+        let day: u8 = match day {
+            "" => 1,
+            day => match day.parse() {
+                Ok(day) if (1..=31).contains(&day) => day,
+                _ => return Some(Err(RotationParseError::InvalidDayOfMonth(day.to_string()))),
+            },
+        };
+        let time = time.trim();
+        if time.is_empty() {
+            return Some(Ok(RotationType::Monthly {
+                day,
+                hour: 0,
+                minute: 0,
+            }));
+        }
+        Some(match RotationType::try_from_string(time) {
+            Ok(RotationType::Time(hour, minute)) => Ok(RotationType::Monthly { day, hour, minute }),
+            Err(e @ RotationParseError::InvalidTime { .. }) => Err(e),
+            _ => Err(RotationParseError::MalformedTime(time.to_string())),
+        })
+    }
+    /// Parses a yearly rotation like `yearly` or `yearly 07-01` (month and day, in lowercase), on
+    /// the 1st of january by default, returns `None` if the text isn't a yearly rotation
+    fn yearly_from_string(text: &str) -> Option<Result<RotationType, RotationParseError>> {
+        let rest = match text.strip_prefix("yearly") {
+            Some(rest) if rest.is_empty() || rest.starts_with(' ') => rest.trim(),
+            _ => return None,
+        };
+        if rest.is_empty() {
+            return Some(Ok(RotationType::Yearly { month: 1, day: 1 }));
+        }
+        let invalid_date = || RotationParseError::InvalidDayOfYear(rest.to_string());
+        let (month, day) = match rest.split_once('-') {
+            Some(date) => date,
+            None => return Some(Err(invalid_date())),
+        };
+        let (month, day): (u8, u8) = match (month.trim().parse(), day.trim().parse()) {
+            (Ok(month), Ok(day)) => (month, day),
+            _ => return Some(Err(invalid_date())),
+        };
+        // the 29th of february is a day of the leap years
+        let exists = chrono::NaiveDate::from_ymd_opt(2024, month.into(), day.into()).is_some();
+        Some(match exists {
+            true => Ok(RotationType::Yearly { month, day }),
+            false => Err(invalid_date()),
+        })
+    }
+    /// Splits a lowercase text like `5 mb`, `5mb` or `1.5  gb` into its number and its unit
+    fn split_number(text: &str) -> (&str, &str) {
+        let split_at = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split_at);
+        (number, unit.trim())
+    }
+    const SIZE_UNITS: [(&str, u64); 4] = [
+        ("kb", 1024),
+        ("mb", 1024 * 1024),
+        ("gb", 1024 * 1024 * 1024),
+        ("tb", 1024 * 1024 * 1024 * 1024),
+    ];
+    const PERIOD_UNITS: [(&str, u64); 7] = [
+        ("second", 1),
+        ("minute", 60),
+        ("hour", 60 * 60),
+        ("day", 60 * 60 * 24),
+        ("week", 60 * 60 * 24 * 7),
+        ("month", 60 * 60 * 24 * 30),
+        ("year", 60 * 60 * 24 * 365),
+    ];
+    /// The bytes of a lowercase size unit like `kb` or `mb`
+    fn size_unit(unit: &str) -> Option<u64> {
+        RotationType::SIZE_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, bytes)| *bytes)
+    }
+    /// The seconds of a lowercase period unit like `minute` or `days`
+    fn period_unit(unit: &str) -> Option<u64> {
+        // the plural form has the same meaning
+        let singular = unit.strip_suffix('s').unwrap_or(unit);
+        RotationType::PERIOD_UNITS
+            .iter()
+            .find(|(name, _)| *name == singular)
+            .map(|(_, secs)| *secs)
+    }
+}
+
+/// Renders the rotation back into a string that parses to the same rotation, in the largest unit
+/// that divides it (e.g. `2 week`, `500 MB`, `12:30`, `sunday 03:00`).
+impl std::fmt::Display for RotationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            RotationType::Period(secs) => {
+                let (unit, unit_secs) = RotationType::PERIOD_UNITS
+                    .iter()
+                    .rev()
+                    .find(|(_, unit_secs)| secs % unit_secs == 0)
+                    .unwrap_or(&RotationType::PERIOD_UNITS[0]);
+                write!(f, "{} {}", secs / unit_secs, unit)
+            }
+            RotationType::Time(hour, minute) => write!(f, "{:02}:{:02}", hour, minute),
+            RotationType::Size(bytes) => {
+                match RotationType::SIZE_UNITS
+                    .iter()
+                    .rev()
+                    .find(|(_, unit_bytes)| bytes % unit_bytes == 0)
+                {
+                    Some((unit, unit_bytes)) => {
+                        write!(f, "{} {}", bytes / unit_bytes, unit.to_ascii_uppercase())
+                    }
+                    // less than a whole kilobyte, written as a decimal
+                    None => write!(f, "{} KB", bytes as f64 / 1024.0),
+                }
+            }
+            RotationType::Hourly => f.write_str("hourly"),
+            RotationType::Weekly {
+                weekday,
+                hour,
+                minute,
+            } => {
+                const WEEKDAYS: [&str; 7] = [
+                    "monday",
+                    "tuesday",
+                    "wednesday",
+                    "thursday",
+                    "friday",
+                    "saturday",
+                    "sunday",
+                ];
+                let day = WEEKDAYS[weekday.num_days_from_monday() as usize];
+                write!(f, "{} {:02}:{:02}", day, hour, minute)
+            }
+            RotationType::Monthly { day, hour, minute } => {
+                write!(f, "monthly {} {:02}:{:02}", day, hour, minute)
+            }
+            RotationType::Yearly { month, day } => write!(f, "yearly {:02}-{:02}", month, day),
         }
     }
 }
@@ -512,13 +1489,64 @@ impl RotationType {
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct Rotation {
     rotation_type: RotationType,
-    next_rotation: u64,
+    /// Unix time of the next time or period rotation, the size for the size rotations
+    pub(crate) next_rotation: u64,
 }
 impl Rotation {
-    pub(crate) fn init_from_rotation_type(rot_type: RotationType) -> Rotation {
+    /// Whether the rotation is due at the unix time `now` for a file of `size` bytes about to
+    /// get a record of `pending` bytes
+    fn is_due(&self, now: u64, size: u64, pending: u64) -> bool {
+        match self.rotation_type {
+            // an empty file takes the record, even if it is longer than the size
+            RotationType::Size(_) => size > 0 && size + pending > self.next_rotation,
+            RotationType::Period(_)
+            | RotationType::Time(_, _)
+            | RotationType::Hourly
+            | RotationType::Weekly { .. }
+            | RotationType::Monthly { .. }
+            | RotationType::Yearly { .. } => now > self.next_rotation,
+        }
+    }
+    /// The number of days of the month (from 1) of the year
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        let (next_year, next_month) = match month {
+            12 => (year + 1, 1),
+            _ => (year, month + 1),
+        };
+        chrono::NaiveDate::from_ymd_opt(next_year, next_month, 1)
+            .and_then(|first| first.pred_opt())
+            .map_or(31, |last| last.day())
+    }
+    /// Unix time of the moment of the day of the month on the clock of `now`, the day is the last
+    /// one of the month if the month is shorter (e.g. the 31st of april is the 30th)
+    fn unix_at(
+        now: chrono::DateTime<chrono::FixedOffset>,
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+    ) -> i64 {
+        let day = day.min(Rotation::days_in_month(year, month));
+        chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .map(|moment| (moment - *now.offset()).and_utc().timestamp())
+            .unwrap_or_default()
+    }
+    pub(crate) fn init_from_rotation_type(
+        rot_type: RotationType,
+        timezone: TimeZoneChoice,
+    ) -> Rotation {
+        Rotation::init_at(rot_type, helper::get_now(timezone))
+    }
+    /// Computes the next rotation from the given moment, on the clock of its offset
+    pub(crate) fn init_at(
+        rot_type: RotationType,
+        now: chrono::DateTime<chrono::FixedOffset>,
+    ) -> Rotation {
         match rot_type {
             RotationType::Period(p) => {
-                let unix_time: u64 = chrono::Utc::now().timestamp().try_into().unwrap_or(0);
+                let unix_time: u64 = now.timestamp().try_into().unwrap_or(0);
                 let next_to_rotate = unix_time + p;
                 Rotation {
                     rotation_type: rot_type,
@@ -528,31 +1556,88 @@ impl Rotation {
             RotationType::Time(h, m) => {
                 let h = h as u64;
                 let m = m as u64;
-                let now = chrono::Local::now();
-                let curr_h: u64 = now.hour().into();
-                let curr_m: u64 = now.minute().into();
-                if curr_h < h || (curr_h == h && curr_m < m) {
+                let unix: u64 = now.timestamp().max(0) as u64;
+                // the seconds count, so the rotation happens at hh:mm:00 on the clock
+                let secs_curr: u64 = now.num_seconds_from_midnight().into();
+                let secs_desirable = (h * 60 * 60) + (m * 60);
+                if secs_curr < secs_desirable {
                     // if next rotation is today
-                    let unix: u64 = now.timestamp().max(0) as u64;
-                    let secs_curr = ((curr_h) * 60 * 60) + (curr_m * 60);
-                    let secs_desirable = (h * 60 * 60) + (m * 60);
-                    let diff = secs_desirable - secs_curr;
                     Rotation {
                         rotation_type: rot_type,
-                        next_rotation: unix + diff,
+                        next_rotation: unix + secs_desirable - secs_curr,
                     }
                 } else {
                     //tomorrow
-                    let unix: u64 = now.timestamp().max(0) as u64;
-                    let secs_till_tomorrow =
-                        (24 * 60 * 60) - ((curr_h * 60 * 60) + (curr_m * 60));
-                    let secs_desirable = ((h * 60 * 60) + (m * 60));
+                    let secs_till_tomorrow = (24 * 60 * 60) - secs_curr;
                     Rotation {
                         rotation_type: rot_type,
                         next_rotation: unix + secs_till_tomorrow + secs_desirable,
                     }
                 }
             }
+            RotationType::Hourly => {
+                let unix: u64 = now.timestamp().max(0) as u64;
+                let secs_in_hour: u64 = (now.minute() * 60 + now.second()).into();
+                Rotation {
+                    rotation_type: rot_type,
+                    next_rotation: unix + (60 * 60) - secs_in_hour,
+                }
+            }
+            RotationType::Weekly {
+                weekday,
+                hour,
+                minute,
+            } => {
+                const WEEK: u64 = 7 * 24 * 60 * 60;
+                let unix: u64 = now.timestamp().max(0) as u64;
+                // seconds since the start of the week (monday 00:00) on the clock
+                let secs_curr = u64::from(now.weekday().num_days_from_monday()) * 24 * 60 * 60
+                    + u64::from(now.num_seconds_from_midnight());
+                let secs_desirable = u64::from(weekday.num_days_from_monday()) * 24 * 60 * 60
+                    + u64::from(hour) * 60 * 60
+                    + u64::from(minute) * 60;
+                // if the moment is now it has just been rotated, the next one is in a week
+                let diff = match secs_desirable > secs_curr {
+                    true => secs_desirable - secs_curr,
+                    false => WEEK - secs_curr + secs_desirable,
+                };
+                Rotation {
+                    rotation_type: rot_type,
+                    next_rotation: unix + diff,
+                }
+            }
+            RotationType::Monthly { day, hour, minute } => {
+                let moment = |year: i32, month: u32| {
+                    Rotation::unix_at(now, year, month, day.into(), hour.into(), minute.into())
+                };
+                let (year, month) = (now.year(), now.month());
+                let mut next = moment(year, month);
+                // if the moment is now it has just been rotated, the next one is next month
+                if next <= now.timestamp() {
+                    next = match month {
+                        12 => moment(year + 1, 1),
+                        _ => moment(year, month + 1),
+                    };
+                }
+                Rotation {
+                    rotation_type: rot_type,
+                    next_rotation: next.max(0) as u64,
+                }
+            }
+            RotationType::Yearly { month, day } => {
+                // the 29th of february is the 28th out of the leap years
+                let moment =
+                    |year: i32| Rotation::unix_at(now, year, month.into(), day.into(), 0, 0);
+                let mut next = moment(now.year());
+                // if the moment is now it has just been rotated, the next one is next year
+                if next <= now.timestamp() {
+                    next = moment(now.year() + 1);
+                }
+                Rotation {
+                    rotation_type: rot_type,
+                    next_rotation: next.max(0) as u64,
+                }
+            }
             RotationType::Size(s) => Rotation {
                 rotation_type: rot_type,
                 next_rotation: s,
@@ -564,14 +1649,23 @@ impl Rotation {
 #[derive(Clone, Debug)]
 pub(crate) enum CompressionType {
     Zip,
+    Gzip,
+    #[cfg(feature = "tar-gz")]
+    TarGz,
+    #[cfg(feature = "zstd")]
+    Zstd,
 }
 
 impl CompressionType {
     pub(crate) fn try_from_string(text: &str) -> Option<CompressionType> {
-        if text == "zip" {
-            Some(CompressionType::Zip)
-        } else {
-            None
+        match text {
+            "zip" => Some(CompressionType::Zip),
+            "gzip" | "gz" => Some(CompressionType::Gzip),
+            #[cfg(feature = "tar-gz")]
+            "tar.gz" | "tgz" => Some(CompressionType::TarGz),
+            #[cfg(feature = "zstd")]
+            "zstd" => Some(CompressionType::Zstd),
+            _ => None,
         }
     }
 }
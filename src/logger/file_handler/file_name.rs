@@ -2,9 +2,10 @@
 //!
 //! [`FileName`] constructs a concrete file name from a pattern provided by
 //! [`FileFormatter`]. It also supports incrementing the name when multiple files
-//! with the same base name are created.
+//! with the same base name are created, in the `{num}` placeholders of the pattern or before
+//! the extension.
 
-use crate::{helper, Level};
+use crate::{helper, Config};
 
 use crate::logger::formatter::LogPart;
 
@@ -16,6 +17,9 @@ pub(crate) struct FileName {
     file_name: String,
     file_num: Option<u32>,
     file_extension: String,
+    /// Byte offsets in `file_name` of the `{num}` placeholders and their zero-padding, the number
+    /// is written there instead of before the extension
+    num_slots: Vec<(usize, usize)>,
 }
 
 #[derive(Debug, Error)]
@@ -38,39 +42,65 @@ impl FileName {
             .map(|x| x.to_string())
             .collect()
     }
-    fn is_acceptable_file_extension(ext: &str) -> bool {
+    /// Whether the extension is `txt`, `log` or one of the extensions allowed in the config with
+    /// [`crate::logger::set_allowed_file_extensions`]
+    pub(crate) fn is_acceptable_file_extension(ext: &str, config: &Config) -> bool {
         FileName::acceptable_file_extensions().contains(&ext.to_string())
+            || config
+                .allowed_file_extensions
+                .iter()
+                .any(|allowed| allowed == ext)
     }
+    /// Gives the name the next number, written in its `{num}` placeholders or before the
+    /// extension without them
     pub(crate) fn increase_num(&mut self) {
         match self.file_num {
             None => self.file_num = Some(1),
             Some(num) => self.file_num = Some(num + 1),
         };
     }
-    /// Expands a list of [`LogPart`] values into a concrete file name string.
-    pub fn get_string_from_log_parts(parts: Vec<LogPart>, level: Level) -> String {
-        let time_str = helper::get_current_time_in_string();
-        let date_str = helper::get_current_date_in_string();
-        let mut res = String::new();
-        for part in &parts {
+    /// Expands a list of [`LogPart`] values into a concrete file name string, using the level,
+    /// level names and time settings of the config.
+    pub(crate) fn get_string_from_log_parts(parts: Vec<LogPart>, config: &Config) -> String {
+        FileName::render_log_parts(&parts, config).concat()
+    }
+    /// Expands each of the [`LogPart`] values, at the same time. `{num}` is rendered empty, the
+    /// number is written by the [`FileName`].
+    fn render_log_parts(parts: &[LogPart], config: &Config) -> Vec<String> {
+        let time_settings = config.time_settings;
+        let time_str = helper::get_current_time_in_string(time_settings);
+        let date_str = helper::get_current_date_in_string(time_settings);
+        let now = helper::get_now(time_settings.timezone);
+        let mut res = Vec::with_capacity(parts.len());
+        for part in parts {
             let temp = match part {
-                LogPart::Time => &time_str,
-                LogPart::Date => &date_str,
-                LogPart::Level => &level.to_string(),
+                LogPart::Time(None) => &time_str,
+                LogPart::Date(None) => &date_str,
+                LogPart::Time(Some(spec)) | LogPart::Date(Some(spec)) => {
+                    &now.format(spec).to_string()
+                }
+                LogPart::Level(style) => &style.apply(&config.level_name(config.level)),
+                // colons aren't allowed in the file names on windows
+                LogPart::Iso8601 => &helper::format_iso8601(&now).replace(':', "-"),
+                LogPart::Hostname => helper::get_hostname(),
+                LogPart::Pid => helper::get_pid(),
+                LogPart::RunId => helper::get_run_id(),
+                LogPart::Env(_, value) => value,
                 LogPart::Text(tt) => tt,
+                LogPart::Num(_) => "",
                 _ => {
                     eprintln!("Incrorrect part given!");
                     ""
                 }
             };
-            res.push_str(temp);
+            res.push(temp.to_string());
         }
         res
     }
-    /// Build a [`FileName`] from a [`FileFormatter`] using the provided log level.
-    pub fn from_file_formatter(
+    /// Build a [`FileName`] from a [`FileFormatter`] using the provided config.
+    pub(crate) fn from_file_formatter(
         format: FileFormatter,
-        level: Level,
+        config: &Config,
     ) -> Result<FileName, FileNameFromFileFormatterError> {
         let mut parts = format.format;
         if parts.is_empty() {
@@ -91,7 +121,7 @@ impl FileName {
         let file_name_without_ext = iter.next().unwrap_or("");
 
         // Check if the extension is acceptable
-        if !FileName::is_acceptable_file_extension(extension) {
+        if !FileName::is_acceptable_file_extension(extension, config) {
             return Err(FileNameFromFileFormatterError::IncorrectFileExtension);
         }
 
@@ -99,12 +129,20 @@ impl FileName {
         let parts_len = parts.len();
         parts[parts_len - 1] = LogPart::Text(file_name_without_ext.to_string());
 
-        // Build the final file name
-        let file_name = FileName::get_string_from_log_parts(parts, level);
+        // Build the final file name, keeping the place of each `{num}`
+        let mut file_name = String::new();
+        let mut num_slots = Vec::new();
+        for (part, rendered) in parts.iter().zip(FileName::render_log_parts(&parts, config)) {
+            if let LogPart::Num(padding) = part {
+                num_slots.push((file_name.len(), *padding));
+            }
+            file_name.push_str(&rendered);
+        }
         Ok(FileName {
             file_name,
             file_num: None,
             file_extension: extension.to_string(),
+            num_slots,
         })
     }
     /// Returns the full file name including any appended index and extension.
@@ -115,11 +153,19 @@ impl FileName {
 impl From<FileName> for String {
     fn from(value: FileName) -> Self {
         let mut txt = value.file_name;
-        if let Some(num) = value.file_num {
-            txt.push('(');
-            txt.push_str(&num.to_string());
-            txt.push(')');
-        };
+        if value.num_slots.is_empty() {
+            if let Some(num) = value.file_num {
+                txt.push('(');
+                txt.push_str(&num.to_string());
+                txt.push(')');
+            };
+        }
+        // the first file is the number 1, the placeholders are filled from the last one so the
+        // offsets of the others stay right
+        let num = value.file_num.unwrap_or(0) + 1;
+        for (idx, padding) in value.num_slots.iter().rev() {
+            txt.insert_str(*idx, &format!("{:0width$}", num, width = padding));
+        }
         txt.push('.');
         txt.push_str(&value.file_extension);
         txt
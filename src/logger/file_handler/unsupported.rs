@@ -0,0 +1,120 @@
+//! Replaces the file manager on the targets without a file system, see [`super`].
+
+use thiserror::Error;
+
+use crate::{ArchiveInfo, BufferPolicy, Config, SyncPolicy, TimeZoneChoice};
+
+/// No file can be opened on this target, so there is no file manager.
+#[derive(Debug)]
+pub(crate) enum FileManager {}
+
+#[derive(Error, Debug)]
+/// Errors produced when creating a [`FileManager`] from a format string.
+pub enum FileManagerFromStringError {
+    #[error("the log files aren't supported on this target")]
+    UnsupportedTarget,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+/// Errors produced when parsing a rotation, see [`FileManager::add_rotation`].
+pub enum RotationParseError {
+    #[error("the log files aren't supported on this target")]
+    UnsupportedTarget,
+}
+
+impl FileManager {
+    /// There are no archives to remove on this target, no age is accepted.
+    pub(crate) fn parse_age(_text: &str) -> Option<u64> {
+        None
+    }
+    /// There are no archives to remove on this target, no size is accepted.
+    pub(crate) fn parse_size(_text: &str) -> Option<u64> {
+        None
+    }
+    pub(crate) fn init_from_string(
+        _format: &str,
+        _config: Config,
+    ) -> Result<FileManager, FileManagerFromStringError> {
+        Err(FileManagerFromStringError::UnsupportedTarget)
+    }
+    pub(crate) fn add_rotation(
+        &mut self,
+        _string: &str,
+        _timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        match *self {}
+    }
+    pub(crate) fn add_rotation_period(
+        &mut self,
+        _period: std::time::Duration,
+        _timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        match *self {}
+    }
+    pub(crate) fn add_rotation_size(
+        &mut self,
+        _bytes: u64,
+        _timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        match *self {}
+    }
+    pub(crate) fn add_rotation_at(
+        &mut self,
+        _hour: u8,
+        _minute: u8,
+        _timezone: TimeZoneChoice,
+    ) -> Result<(), RotationParseError> {
+        match *self {}
+    }
+    pub(crate) fn remove_rotations(&mut self) {
+        match *self {}
+    }
+    pub(crate) fn remove_rotation(&mut self, _string: &str) -> Result<bool, RotationParseError> {
+        match *self {}
+    }
+    pub(crate) fn list_rotations(&self) -> Vec<String> {
+        match *self {}
+    }
+    pub(crate) fn set_compression(&mut self, _string: &str) -> bool {
+        match *self {}
+    }
+    pub(crate) fn write_log(&mut self, _mess: &str, _config: &Config) -> std::io::Result<()> {
+        match *self {}
+    }
+    pub(crate) fn set_buffer_policy(&mut self, _policy: BufferPolicy) -> std::io::Result<()> {
+        match *self {}
+    }
+    pub(crate) fn flush_buffer(&mut self) -> std::io::Result<()> {
+        match *self {}
+    }
+    pub(crate) fn set_sync_policy(&mut self, _policy: SyncPolicy) -> std::io::Result<()> {
+        match *self {}
+    }
+    pub(crate) fn sync_pending(&mut self) -> std::io::Result<()> {
+        match *self {}
+    }
+    pub(crate) fn purge_archives(&self, _config: &Config) -> std::io::Result<usize> {
+        match *self {}
+    }
+    pub(crate) fn rotate_now(&mut self, _config: &Config) -> std::io::Result<()> {
+        match *self {}
+    }
+    pub(crate) fn get_file_name(&self) -> String {
+        match *self {}
+    }
+    pub(crate) fn has_fixed_text(&self) -> bool {
+        match *self {}
+    }
+    pub(crate) fn purge_old_logs(
+        &self,
+        _max_age: u64,
+        _active: &[String],
+        _config: &Config,
+    ) -> std::io::Result<usize> {
+        match *self {}
+    }
+    /// No archive is written on this target.
+    pub(crate) fn list_archives(_config: &Config) -> std::io::Result<Vec<ArchiveInfo>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,223 @@
+//! Programmatic construction of log formats.
+//!
+//! [`LogFormat::builder`] builds the same formats as the templates of
+//! [`set_level_formatting`](crate::logger::set_level_formatting), without placeholder names to
+//! misspell or characters to escape.
+//!
+//! ```rust
+//! use loggit::logger::format_builder::LogFormat;
+//! use loggit::logger::formatter::LogColor;
+//! use loggit::logger::set_level_format_built;
+//! use loggit::Level;
+//!
+//! let format = LogFormat::builder()
+//!     .colored(LogColor::Green, |b| b.text("[").level().text("]"))
+//!     .text(" (")
+//!     .file()
+//!     .text(":")
+//!     .line()
+//!     .text(") - ")
+//!     .message()
+//!     .build();
+//! set_level_format_built(Level::INFO, format).unwrap();
+//! ```
+
+use crate::logger::formatter::{
+    resolve_env_var, LevelStyle, LogColor, LogFormatWrapper, LogFormatter, LogPart, LogStyle,
+};
+
+/// A log format built with [`LogFormat::builder`], to be installed with
+/// [`set_level_format_built`](crate::logger::set_level_format_built) or
+/// [`set_global_format_built`](crate::logger::set_global_format_built).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFormat {
+    pub(crate) formatter: LogFormatter,
+}
+
+impl LogFormat {
+    /// Starts an empty format.
+    pub fn builder() -> LogFormatBuilder {
+        LogFormatBuilder::default()
+    }
+}
+
+/// Writes the format as a template accepted by
+/// [`set_level_formatting`](crate::logger::set_level_formatting).
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.formatter.fmt(f)
+    }
+}
+
+/// Builder of a [`LogFormat`]: each method appends a text or a placeholder, with the colors and
+/// styles of the enclosing [`colored`](LogFormatBuilder::colored),
+/// [`background`](LogFormatBuilder::background) and style blocks.
+#[derive(Debug, Default)]
+pub struct LogFormatBuilder {
+    parts: Vec<LogFormatWrapper>,
+    color: Option<LogColor>,
+    bg_color: Option<LogColor>,
+    style: LogStyle,
+}
+
+impl LogFormatBuilder {
+    fn push(mut self, part: LogPart) -> Self {
+        self.parts.push(LogFormatWrapper {
+            color: self.color,
+            bg_color: self.bg_color,
+            style: self.style,
+            width: None,
+            part,
+        });
+        self
+    }
+    fn with_style(mut self, style: LogStyle, f: impl FnOnce(Self) -> Self) -> Self {
+        let outer = self.style;
+        if !self.style.contains(style) {
+            self.style.toggle(style);
+        }
+        let mut res = f(self);
+        res.style = outer;
+        res
+    }
+
+    /// Appends a literal text, rendered as it is (no placeholders or tags in it).
+    pub fn text(self, text: &str) -> Self {
+        self.push(LogPart::Text(text.to_string()))
+    }
+    /// Appends the message, like `{message}`.
+    pub fn message(self) -> Self {
+        self.push(LogPart::Message)
+    }
+    /// Appends the level, like `{level}`.
+    pub fn level(self) -> Self {
+        self.push(LogPart::Level(LevelStyle::Upper))
+    }
+    /// Appends the level in lowercase, like `{level:lower}`.
+    pub fn level_lower(self) -> Self {
+        self.push(LogPart::Level(LevelStyle::Lower))
+    }
+    /// Appends the first letter of the level, like `{level:short}`.
+    pub fn level_short(self) -> Self {
+        self.push(LogPart::Level(LevelStyle::Short))
+    }
+    /// Appends the symbol of the level, like `{symbol}`.
+    pub fn symbol(self) -> Self {
+        self.push(LogPart::Symbol)
+    }
+    /// Appends the file, like `{file}`.
+    pub fn file(self) -> Self {
+        self.push(LogPart::File)
+    }
+    /// Appends the last `components` components of the file path, like `{file_short:N}`.
+    pub fn file_short(self, components: usize) -> Self {
+        self.push(LogPart::FileShort(components.max(1)))
+    }
+    /// Appends the line, like `{line}`.
+    pub fn line(self) -> Self {
+        self.push(LogPart::Line)
+    }
+    /// Appends the column, like `{column}`.
+    pub fn column(self) -> Self {
+        self.push(LogPart::Column)
+    }
+    /// Appends the module path, like `{module}`.
+    pub fn module(self) -> Self {
+        self.push(LogPart::ModulePath)
+    }
+    /// Appends the date, like `{date}`.
+    pub fn date(self) -> Self {
+        self.push(LogPart::Date(None))
+    }
+    /// Appends the time, like `{time}`.
+    pub fn time(self) -> Self {
+        self.push(LogPart::Time(None))
+    }
+    /// Appends the date and time as RFC 3339, like `{iso8601}`.
+    pub fn iso8601(self) -> Self {
+        self.push(LogPart::Iso8601)
+    }
+    /// Appends the seconds since the unix epoch, like `{timestamp}`.
+    pub fn timestamp(self) -> Self {
+        self.push(LogPart::Timestamp)
+    }
+    /// Appends the milliseconds since the unix epoch, like `{timestamp_ms}`.
+    pub fn timestamp_ms(self) -> Self {
+        self.push(LogPart::TimestampMs)
+    }
+    /// Appends the seconds since the logger initialization, like `{elapsed}`.
+    pub fn elapsed(self) -> Self {
+        self.push(LogPart::Elapsed)
+    }
+    /// Appends the milliseconds since the logger initialization, like `{elapsed_ms}`.
+    pub fn elapsed_ms(self) -> Self {
+        self.push(LogPart::ElapsedMs)
+    }
+    /// Appends the name of the thread, like `{thread}`.
+    pub fn thread(self) -> Self {
+        self.push(LogPart::Thread)
+    }
+    /// Appends the id of the thread, like `{thread_id}`.
+    pub fn thread_id(self) -> Self {
+        self.push(LogPart::ThreadId)
+    }
+    /// Appends the id of the process, like `{pid}`.
+    pub fn pid(self) -> Self {
+        self.push(LogPart::Pid)
+    }
+    /// Appends the name of the machine, like `{hostname}`.
+    pub fn hostname(self) -> Self {
+        self.push(LogPart::Hostname)
+    }
+    /// Appends the id of the run, like `{run_id}`.
+    pub fn run_id(self) -> Self {
+        self.push(LogPart::RunId)
+    }
+    /// Appends the context fields, like `{context}`.
+    pub fn context(self) -> Self {
+        self.push(LogPart::Context)
+    }
+    /// Appends the value of an environment variable, like `{env:NAME}`. The variable is read
+    /// now.
+    pub fn env(self, name: &str) -> Self {
+        self.push(LogPart::Env(name.to_string(), resolve_env_var(name)))
+    }
+
+    /// Colors the parts appended by `f`, like `<color>...<color>`. The blocks can be nested.
+    pub fn colored(mut self, color: LogColor, f: impl FnOnce(Self) -> Self) -> Self {
+        let outer = self.color.replace(color);
+        let mut res = f(self);
+        res.color = outer;
+        res
+    }
+    /// Sets the background color of the parts appended by `f`, like `<bg_color>...<bg_color>`.
+    pub fn background(mut self, color: LogColor, f: impl FnOnce(Self) -> Self) -> Self {
+        let outer = self.bg_color.replace(color);
+        let mut res = f(self);
+        res.bg_color = outer;
+        res
+    }
+    /// Makes the parts appended by `f` bold, like `<bold>...<bold>`.
+    pub fn bold(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.with_style(LogStyle::BOLD, f)
+    }
+    /// Makes the parts appended by `f` dim, like `<dim>...<dim>`.
+    pub fn dim(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.with_style(LogStyle::DIM, f)
+    }
+    /// Makes the parts appended by `f` italic, like `<italic>...<italic>`.
+    pub fn italic(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.with_style(LogStyle::ITALIC, f)
+    }
+    /// Underlines the parts appended by `f`, like `<underline>...<underline>`.
+    pub fn underline(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.with_style(LogStyle::UNDERLINE, f)
+    }
+
+    /// Finishes the format.
+    pub fn build(self) -> LogFormat {
+        LogFormat {
+            formatter: LogFormatter { parts: self.parts },
+        }
+    }
+}
@@ -4,11 +4,14 @@
 //! [`LogFormatter`] structures used internally by the logger.  It exposes
 //! helper enums and functions to interpret color tags and placeholder blocks.
 
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
 use thiserror::Error;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 /// Colors that can be applied to portions of a formatted log message.
-pub(crate) enum LogColor {
+pub enum LogColor {
     Red,
     Green,
     Blue,
@@ -16,19 +19,27 @@ pub(crate) enum LogColor {
     Black,
     White,
     Purple,
+    BrightRed,
+    BrightGreen,
+    BrightBlue,
+    BrightYellow,
+    BrightWhite,
+    /// Gray
+    BrightBlack,
+    BrightPurple,
+    /// A 24-bit color given as `<#rrggbb>` or `<rgb(r,g,b)>`
+    Rgb(u8, u8, u8),
+    /// A color of the xterm 256 colors palette given as `<cN>`
+    Indexed(u8),
+    /// The color of the level of the message (`<level>`), resolved when the log is rendered
+    Level,
 }
 
 impl From<&str> for LogColor {
     fn from(value: &str) -> Self {
-        match value {
-            "red" => LogColor::Red,
-            "green" => LogColor::Green,
-            "blue" => LogColor::Blue,
-            "yellow" => LogColor::Yellow,
-            "black" => LogColor::Black,
-            "white" => LogColor::White,
-            "purple" => LogColor::Purple,
-            _ => {
+        match LogColor::try_from_str(value) {
+            Some(color) => color,
+            None => {
                 eprintln!("Incorrect color given!");
                 LogColor::White
             }
@@ -42,23 +53,226 @@ impl From<String> for LogColor {
 }
 
 impl LogColor {
-    fn get_colors_str() -> [&'static str; 7] {
-        ["red", "green", "blue", "yellow", "black", "white", "purple"]
+    fn get_colors_str() -> [&'static str; 15] {
+        [
+            "red",
+            "green",
+            "blue",
+            "yellow",
+            "black",
+            "white",
+            "purple",
+            "bright_red",
+            "bright_green",
+            "bright_blue",
+            "bright_yellow",
+            "bright_white",
+            "bright_black",
+            "bright_purple",
+            "level",
+        ]
+    }
+    /// Parses a color from its name, a hex value (`#ff8800`) or an rgb value (`rgb(255,136,0)`)
+    pub(crate) fn try_from_str(value: &str) -> Option<LogColor> {
+        match value {
+            "red" => Some(LogColor::Red),
+            "green" => Some(LogColor::Green),
+            "blue" => Some(LogColor::Blue),
+            "yellow" => Some(LogColor::Yellow),
+            "black" => Some(LogColor::Black),
+            "white" => Some(LogColor::White),
+            "purple" => Some(LogColor::Purple),
+            "bright_red" => Some(LogColor::BrightRed),
+            "bright_green" => Some(LogColor::BrightGreen),
+            "bright_blue" => Some(LogColor::BrightBlue),
+            "bright_yellow" => Some(LogColor::BrightYellow),
+            "bright_white" => Some(LogColor::BrightWhite),
+            "bright_black" => Some(LogColor::BrightBlack),
+            "bright_purple" => Some(LogColor::BrightPurple),
+            "level" => Some(LogColor::Level),
+            _ => LogColor::parse_rgb(value)
+                .map(|(r, g, b)| LogColor::Rgb(r, g, b))
+                .or_else(|| LogColor::parse_indexed(value).map(LogColor::Indexed)),
+        }
+    }
+    /// Returns true if the value has the `c<N>` shape of a palette color, even if the index is out
+    /// of range
+    fn is_indexed_like(value: &str) -> bool {
+        match value.strip_prefix('c') {
+            Some(num) => !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()),
+            None => false,
+        }
+    }
+    /// Parses `c<N>` strings where N is a 256 colors palette index (0-255)
+    fn parse_indexed(value: &str) -> Option<u8> {
+        if !LogColor::is_indexed_like(value) {
+            return None;
+        }
+        value[1..].parse::<u8>().ok()
     }
-    pub(crate) fn get_ascii(&self) -> &'static str {
+    /// Parses `#rrggbb` and `rgb(r,g,b)` strings into RGB components
+    fn parse_rgb(value: &str) -> Option<(u8, u8, u8)> {
+        if let Some(hex) = value.strip_prefix('#') {
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+            return Some((r, g, b));
+        }
+        let inner = value.strip_prefix("rgb(")?.strip_suffix(')')?;
+        let components = inner
+            .split(',')
+            .map(|c| c.trim().parse::<u8>())
+            .collect::<Result<Vec<u8>, _>>()
+            .ok()?;
+        match components.as_slice() {
+            [r, g, b] => Some((*r, *g, *b)),
+            _ => None,
+        }
+    }
+    /// Returns the parameters of the escape sequence setting this color as the foreground (or
+    /// background) color: `38;2;r;g;b` for 24-bit colors, `38;5;n` for the palette ones and the
+    /// standard `90`-`97` codes for the bright ones
+    fn get_sgr_color(&self, background: bool) -> String {
+        let (extended, bright) = if background { (48, 100) } else { (38, 90) };
+        let (r, g, b) = match self {
+            LogColor::Red => (255, 0, 0),       // #FF0000
+            LogColor::Green => (0, 255, 0),     // #00FF00
+            LogColor::Blue => (0, 0, 255),      // #0000FF
+            LogColor::Yellow => (255, 255, 0),  // #FFFF00
+            LogColor::Black => (0, 0, 0),       // #000000
+            LogColor::White => (255, 255, 255), // #FFFFFF
+            LogColor::Purple => (128, 0, 128),  // #800080
+            LogColor::Rgb(r, g, b) => (*r, *g, *b),
+            LogColor::Indexed(n) => return format!("{};5;{}", extended, n),
+            LogColor::BrightBlack => return (bright).to_string(),
+            LogColor::BrightRed => return (bright + 1).to_string(),
+            LogColor::BrightGreen => return (bright + 2).to_string(),
+            LogColor::BrightYellow => return (bright + 3).to_string(),
+            LogColor::BrightBlue => return (bright + 4).to_string(),
+            LogColor::BrightPurple => return (bright + 5).to_string(),
+            LogColor::BrightWhite => return (bright + 7).to_string(),
+            // resolved with `resolve` before the rendering, white is only a safety net
+            LogColor::Level => (255, 255, 255),
+        };
+        format!("{};2;{};{};{}", extended, r, g, b)
+    }
+    /// Returns the name of the color as written in a tag, e.g. `bright_red`, `#ff8800` or `c208`
+    pub(crate) fn name(&self) -> String {
+        match self {
+            LogColor::Red => "red".to_string(),
+            LogColor::Green => "green".to_string(),
+            LogColor::Blue => "blue".to_string(),
+            LogColor::Yellow => "yellow".to_string(),
+            LogColor::Black => "black".to_string(),
+            LogColor::White => "white".to_string(),
+            LogColor::Purple => "purple".to_string(),
+            LogColor::BrightRed => "bright_red".to_string(),
+            LogColor::BrightGreen => "bright_green".to_string(),
+            LogColor::BrightBlue => "bright_blue".to_string(),
+            LogColor::BrightYellow => "bright_yellow".to_string(),
+            LogColor::BrightWhite => "bright_white".to_string(),
+            LogColor::BrightBlack => "bright_black".to_string(),
+            LogColor::BrightPurple => "bright_purple".to_string(),
+            LogColor::Level => "level".to_string(),
+            LogColor::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            LogColor::Indexed(n) => format!("c{}", n),
+        }
+    }
+    /// Replaces [`LogColor::Level`] by the color of the level of the message
+    pub(crate) fn resolve(self, level_color: LogColor) -> LogColor {
         match self {
-            LogColor::Red => "\x1b[38;2;255;0;0m",       // #FF0000
-            LogColor::Green => "\x1b[38;2;0;255;0m",     // #00FF00
-            LogColor::Blue => "\x1b[38;2;0;0;255m",      // #0000FF
-            LogColor::Yellow => "\x1b[38;2;255;255;0m",  // #FFFF00
-            LogColor::Black => "\x1b[38;2;0;0;0m",       // #000000
-            LogColor::White => "\x1b[38;2;255;255;255m", // #FFFFFF
-            LogColor::Purple => "\x1b[38;2;128;0;128m",  // #800080
+            LogColor::Level => level_color,
+            color => color,
+        }
+    }
+    pub(crate) fn get_ascii(&self) -> String {
+        format!("\x1b[{}m", self.get_sgr_color(false))
+    }
+
+    pub(crate) fn get_bg_ascii(&self) -> String {
+        format!("\x1b[{}m", self.get_sgr_color(true))
+    }
+
+    /// Wraps the text with the escape sequences of the given foreground and background colors
+    /// and text style
+    pub(crate) fn colorize_str(
+        text: &str,
+        color: Option<LogColor>,
+        bg_color: Option<LogColor>,
+        style: LogStyle,
+    ) -> String {
+        let mut res = String::new();
+        if let Some(c) = color {
+            res.push_str(&c.get_ascii());
+        }
+        if let Some(c) = bg_color {
+            res.push_str(&c.get_bg_ascii());
+        }
+        res.push_str(&style.get_ascii());
+        res.push_str(text);
+        res.push_str("\x1b[0m");
+        res
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+/// Set of text styles (bold, dim, italic, underline) applied to a part of a log message.
+pub(crate) struct LogStyle(u8);
+
+impl LogStyle {
+    pub(crate) const BOLD: LogStyle = LogStyle(1);
+    pub(crate) const DIM: LogStyle = LogStyle(1 << 1);
+    pub(crate) const ITALIC: LogStyle = LogStyle(1 << 2);
+    pub(crate) const UNDERLINE: LogStyle = LogStyle(1 << 3);
+
+    /// Returns the style corresponding to a tag name, e.g. `bold`
+    pub(crate) fn from_name(name: &str) -> Option<LogStyle> {
+        match name {
+            "bold" => Some(LogStyle::BOLD),
+            "dim" => Some(LogStyle::DIM),
+            "italic" => Some(LogStyle::ITALIC),
+            "underline" => Some(LogStyle::UNDERLINE),
+            _ => None,
         }
     }
+    /// The styles with their tag names, in the order of their tags in a template
+    const NAMED: [(LogStyle, &'static str); 4] = [
+        (LogStyle::BOLD, "bold"),
+        (LogStyle::DIM, "dim"),
+        (LogStyle::ITALIC, "italic"),
+        (LogStyle::UNDERLINE, "underline"),
+    ];
 
-    pub(crate) fn colorize_str(text: &str, color: LogColor) -> String {
-        format!("{}{}{}", color.get_ascii(), text, "\x1b[0m")
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+    /// Returns the styles set in any of the two
+    pub(crate) fn union(self, other: LogStyle) -> LogStyle {
+        LogStyle(self.0 | other.0)
+    }
+    pub(crate) fn contains(&self, other: LogStyle) -> bool {
+        self.0 & other.0 == other.0
+    }
+    /// Adds the style if it isn't set yet, otherwise removes it
+    pub(crate) fn toggle(&mut self, other: LogStyle) {
+        self.0 ^= other.0;
+    }
+    pub(crate) fn get_ascii(&self) -> String {
+        let mut res = String::new();
+        for (style, code) in [
+            (LogStyle::BOLD, "\x1b[1m"),
+            (LogStyle::DIM, "\x1b[2m"),
+            (LogStyle::ITALIC, "\x1b[3m"),
+            (LogStyle::UNDERLINE, "\x1b[4m"),
+        ] {
+            if self.contains(style) {
+                res.push_str(code);
+            }
+        }
+        res
     }
 }
 
@@ -66,33 +280,395 @@ impl LogColor {
 /// A single placeholder or text fragment parsed from a format string.
 pub(crate) enum LogPart {
     Message,
-    Time,
+    /// Current time, optionally formatted with a strftime specifier (`{time:%H:%M:%S}`)
+    Time(Option<String>),
     File,
     Line,
-    Date,
-    Level,
+    /// Column of the call site
+    Column,
+    /// Date and time as RFC 3339, e.g. `2025-03-05T09:05:03.123Z`
+    Iso8601,
+    /// Random id of the current run, see [`crate::run_id`]
+    RunId,
+    /// A placeholder registered with [`crate::logger::register_placeholder`], by name
+    Custom(String),
+    /// Current date, optionally formatted with a strftime specifier (`{date:%Y-%m-%d}`)
+    Date(Option<String>),
+    /// Level of the message, rendered as `INFO`, `info` or `I` (`{level}`, `{level:lower}`,
+    /// `{level:short}`)
+    Level(LevelStyle),
+    /// Symbol of the level, see [`crate::logger::set_level_symbol`]
+    Symbol,
     Text(String),
     ModulePath,
+    /// Name of the thread (or its id if it is unnamed)
+    Thread,
+    ThreadId,
+    /// Id of the current process
+    Pid,
+    /// Seconds since the unix epoch
+    Timestamp,
+    /// Milliseconds since the unix epoch
+    TimestampMs,
+    /// Seconds since the logger initialization, with a millisecond precision
+    Elapsed,
+    /// Milliseconds since the logger initialization
+    ElapsedMs,
+    /// Name of the machine
+    Hostname,
+    /// The last components of the file path (`{file_short}` keeps only the file name,
+    /// `{file_short:2}` the last two components)
+    FileShort(usize),
+    /// Environment variable given as `{env:NAME}`: its name and its value, resolved once when
+    /// the format is parsed
+    Env(String, String),
+    /// The context fields (see [`crate::logger::add_context`]) as `key=value` pairs
+    Context,
+    /// Counter of the file names taken by other files, zero-padded to the given width (`{num}`,
+    /// `{num:3}`). Only used by the file names, it renders nothing in the logs
+    Num(usize),
+    /// Optional section given as `{module?[{module}] }`: the placeholder deciding if the section
+    /// is rendered and the parts of the section, rendered only if the placeholder isn't empty
+    Optional(Box<LogPart>, Vec<LogFormatWrapper>),
 }
 
 impl LogPart {
-    fn get_parts_str() -> [&'static str; 9] {
+    fn get_parts_str() -> [&'static str; 25] {
         [
-            "message", "time", "date", "file", "line", "date", "level", "text", "module",
+            "message",
+            "time",
+            "date",
+            "file",
+            "line",
+            "date",
+            "level",
+            "text",
+            "module",
+            "thread",
+            "thread_id",
+            "pid",
+            "timestamp",
+            "timestamp_ms",
+            "elapsed",
+            "elapsed_ms",
+            "hostname",
+            "env",
+            "file_short",
+            "context",
+            "column",
+            "iso8601",
+            "run_id",
+            "symbol",
+            "num",
         ]
     }
+    /// Returns true if the name is one of the placeholders of the crate
+    pub(crate) fn is_builtin(name: &str) -> bool {
+        LogPart::get_parts_str().contains(&name)
+    }
+    /// Splits a placeholder into its name and its optional specifier: `date:%Y` gives
+    /// `("date", Some("%Y"))`
+    fn split_placeholder(text: &str) -> (&str, Option<&str>) {
+        match text.split_once(PLACEHOLDER_SPEC_SEPARATOR) {
+            Some((name, spec)) => (name, Some(spec)),
+            None => (text, None),
+        }
+    }
+    /// Returns true if the placeholder exists and accepts the given specifier
+    fn is_placeholder_valid(text: &str) -> bool {
+        let (name, spec) = LogPart::split_placeholder(text);
+        if !LogPart::is_builtin(name) {
+            // the registered placeholders only accept a width specifier
+            return crate::logger::is_placeholder_registered(name)
+                && spec.is_none_or(|spec| WidthSpec::parse(spec).is_some());
+        }
+        match (name, spec) {
+            ("env", None) => false,
+            (_, None) => true,
+            ("env", Some(var)) => !var.is_empty(),
+            ("level", Some(spec)) if LevelStyle::from_name(spec).is_some() => true,
+            ("file_short", Some(spec)) if spec.chars().all(|c| c.is_ascii_digit()) => {
+                parse_components_count(spec).is_some()
+            }
+            ("num", Some(spec)) => parse_num_padding(spec).is_some(),
+            (_, Some(spec)) if WidthSpec::parse(spec).is_some() => true,
+            ("date" | "time", Some(spec)) => is_strftime_spec_valid(spec),
+            (_, Some(_)) => false,
+        }
+    }
+    /// Returns the placeholder of the part (without braces) with its specifier, e.g. `date:%Y`,
+    /// or None for a text
+    fn to_placeholder(&self) -> Option<String> {
+        let with_spec = |name: &str, spec: &Option<String>| match spec {
+            Some(spec) => format!("{}{}{}", name, PLACEHOLDER_SPEC_SEPARATOR, spec),
+            None => name.to_string(),
+        };
+        let res = match self {
+            LogPart::Text(_) => return None,
+            LogPart::Message => "message".to_string(),
+            LogPart::Time(spec) => with_spec("time", spec),
+            LogPart::Date(spec) => with_spec("date", spec),
+            LogPart::File => "file".to_string(),
+            LogPart::Line => "line".to_string(),
+            LogPart::Column => "column".to_string(),
+            LogPart::Iso8601 => "iso8601".to_string(),
+            LogPart::RunId => "run_id".to_string(),
+            LogPart::Custom(name) => name.clone(),
+            LogPart::Level(LevelStyle::Upper) => "level".to_string(),
+            LogPart::Level(style) => with_spec("level", &Some(style.name().to_string())),
+            LogPart::Symbol => "symbol".to_string(),
+            LogPart::ModulePath => "module".to_string(),
+            LogPart::Thread => "thread".to_string(),
+            LogPart::ThreadId => "thread_id".to_string(),
+            LogPart::Pid => "pid".to_string(),
+            LogPart::Timestamp => "timestamp".to_string(),
+            LogPart::TimestampMs => "timestamp_ms".to_string(),
+            LogPart::Elapsed => "elapsed".to_string(),
+            LogPart::ElapsedMs => "elapsed_ms".to_string(),
+            LogPart::Hostname => "hostname".to_string(),
+            LogPart::Context => "context".to_string(),
+            LogPart::FileShort(1) => "file_short".to_string(),
+            LogPart::FileShort(n) => with_spec("file_short", &Some(n.to_string())),
+            LogPart::Num(0) => "num".to_string(),
+            LogPart::Num(n) => with_spec("num", &Some(n.to_string())),
+            LogPart::Env(name, _) => with_spec("env", &Some(name.clone())),
+            LogPart::Optional(condition, section) => format!(
+                "{}?{}",
+                condition.to_placeholder().unwrap_or_default(),
+                LogFormatter {
+                    parts: section.clone()
+                }
+            ),
+        };
+        Some(res)
+    }
+    /// Converts a placeholder into a part and its optional width specifier, e.g. `level:<5`
+    fn from_placeholder(text: &str) -> (LogPart, Option<WidthSpec>) {
+        let (name, spec) = LogPart::split_placeholder(text);
+        if name == "file_short" && spec.and_then(parse_components_count).is_some() {
+            return (LogPart::from(text), None);
+        }
+        if name == "num" {
+            return (LogPart::from(text), None);
+        }
+        match spec.and_then(WidthSpec::parse) {
+            Some(width) if name != "env" => (LogPart::from(name), Some(width)),
+            _ => (LogPart::from(text), None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// How the level name is rendered by the `{level}` placeholder.
+pub(crate) enum LevelStyle {
+    /// `INFO`
+    #[default]
+    Upper,
+    /// `info`
+    Lower,
+    /// `I`
+    Short,
+}
+
+impl LevelStyle {
+    fn from_name(name: &str) -> Option<LevelStyle> {
+        match name {
+            "upper" => Some(LevelStyle::Upper),
+            "lower" => Some(LevelStyle::Lower),
+            "short" => Some(LevelStyle::Short),
+            _ => None,
+        }
+    }
+    fn name(&self) -> &'static str {
+        match self {
+            LevelStyle::Upper => "upper",
+            LevelStyle::Lower => "lower",
+            LevelStyle::Short => "short",
+        }
+    }
+    /// Renders the level name in this style
+    pub(crate) fn apply(&self, level_name: &str) -> String {
+        match self {
+            LevelStyle::Upper => level_name.to_string(),
+            LevelStyle::Lower => level_name.to_lowercase(),
+            LevelStyle::Short => level_name.chars().take(1).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Alignment of a placeholder value inside its width.
+pub(crate) enum Alignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Width specifier of a placeholder: `[<^>][width][.max_width]`, e.g. `{level:<5}`,
+/// `{line:>4}` or `{file:.30}`.
+pub(crate) struct WidthSpec {
+    pub(crate) align: Alignment,
+    /// Minimal width, the value is padded with spaces
+    pub(crate) width: Option<usize>,
+    /// Maximal width, longer values are truncated
+    pub(crate) max_width: Option<usize>,
+}
+
+impl WidthSpec {
+    /// Parses a width specifier, returns None if the text isn't one
+    pub(crate) fn parse(spec: &str) -> Option<WidthSpec> {
+        let (align, rest) = match spec.chars().next()? {
+            '<' => (Alignment::Left, &spec[1..]),
+            '>' => (Alignment::Right, &spec[1..]),
+            '^' => (Alignment::Center, &spec[1..]),
+            _ => (Alignment::Left, spec),
+        };
+        let (width, max_width) = match rest.split_once('.') {
+            Some((width, max_width)) => (width, Some(max_width)),
+            None => (rest, None),
+        };
+        let parse_num = |num: &str| -> Option<usize> {
+            match num.chars().all(|c| c.is_ascii_digit()) {
+                true => num.parse().ok(),
+                false => None,
+            }
+        };
+        let width = match width.is_empty() {
+            true => None,
+            false => Some(parse_num(width)?),
+        };
+        let max_width = match max_width {
+            Some(max_width) => Some(parse_num(max_width)?),
+            None => None,
+        };
+        if width.is_none() && max_width.is_none() {
+            return None;
+        }
+        Some(WidthSpec {
+            align,
+            width,
+            max_width,
+        })
+    }
+    /// Writes the specifier back as it is given in a placeholder, e.g. `<5` or `.30`
+    fn to_spec_string(self) -> String {
+        let mut res = String::new();
+        if let Some(width) = self.width {
+            res.push(match self.align {
+                Alignment::Left => '<',
+                Alignment::Right => '>',
+                Alignment::Center => '^',
+            });
+            res.push_str(&width.to_string());
+        }
+        if let Some(max_width) = self.max_width {
+            res.push_str(&format!(".{}", max_width));
+        }
+        res
+    }
+    /// Truncates and pads the text according to the specifier
+    pub(crate) fn apply(&self, text: &str) -> String {
+        let text: String = match self.max_width {
+            Some(max) => text.chars().take(max).collect(),
+            None => text.to_string(),
+        };
+        let len = text.chars().count();
+        let padding = match self.width {
+            Some(width) if width > len => width - len,
+            _ => return text,
+        };
+        let (left, right) = match self.align {
+            Alignment::Left => (0, padding),
+            Alignment::Right => (padding, 0),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+        format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+    }
+}
+
+/// Separator between a placeholder and its specifier, e.g. `{date:%Y-%m-%d}`
+const PLACEHOLDER_SPEC_SEPARATOR: char = ':';
+
+/// Returns the value of the environment variable, or an empty string if it isn't set. A missing
+/// variable is reported only once, even if it is used in several formats.
+pub(crate) fn resolve_env_var(name: &str) -> String {
+    static REPORTED_MISSING: Lazy<Mutex<HashSet<String>>> = Lazy::new(Default::default);
+    match std::env::var(name) {
+        Ok(value) => value,
+        Err(_) => {
+            let mut reported = REPORTED_MISSING.lock().unwrap_or_else(|e| e.into_inner());
+            if reported.insert(name.to_string()) {
+                eprintln!(
+                    "The environment variable {} used in a format isn't set, it will be empty",
+                    name
+                );
+            }
+            String::new()
+        }
+    }
+}
+
+/// Parses the number of path components of `{file_short:N}`, N must be positive
+fn parse_components_count(spec: &str) -> Option<usize> {
+    match spec.chars().all(|c| c.is_ascii_digit()) {
+        true => spec.parse().ok().filter(|n| *n > 0),
+        false => None,
+    }
+}
+
+/// The widest zero-padding of `{num:N}`, the width of the largest number of a file
+const MAX_NUM_PADDING: usize = 10;
+
+/// Parses the zero-padding of `{num:N}`, N must be at most [MAX_NUM_PADDING]
+fn parse_num_padding(spec: &str) -> Option<usize> {
+    match spec.chars().all(|c| c.is_ascii_digit()) {
+        true => spec.parse().ok().filter(|n| *n <= MAX_NUM_PADDING),
+        false => None,
+    }
+}
+
+/// Checks that chrono is able to format a date with the given strftime specifier
+fn is_strftime_spec_valid(spec: &str) -> bool {
+    !spec.is_empty()
+        && !chrono::format::StrftimeItems::new(spec)
+            .any(|item| matches!(item, chrono::format::Item::Error))
 }
 
 impl From<&str> for LogPart {
     fn from(value: &str) -> Self {
-        match value {
+        let (name, spec) = LogPart::split_placeholder(value);
+        match name {
             "message" => LogPart::Message,
-            "time" => LogPart::Time,
-            "date" => LogPart::Date,
+            "time" => LogPart::Time(spec.map(String::from)),
+            "date" => LogPart::Date(spec.map(String::from)),
             "file" => LogPart::File,
             "line" => LogPart::Line,
-            "level" => LogPart::Level,
+            "column" => LogPart::Column,
+            "iso8601" => LogPart::Iso8601,
+            "run_id" => LogPart::RunId,
+            "level" => LogPart::Level(spec.and_then(LevelStyle::from_name).unwrap_or_default()),
+            "symbol" => LogPart::Symbol,
             "module" => LogPart::ModulePath,
+            "thread" => LogPart::Thread,
+            "thread_id" => LogPart::ThreadId,
+            "pid" => LogPart::Pid,
+            "timestamp" => LogPart::Timestamp,
+            "timestamp_ms" => LogPart::TimestampMs,
+            "elapsed" => LogPart::Elapsed,
+            "elapsed_ms" => LogPart::ElapsedMs,
+            "hostname" => LogPart::Hostname,
+            "context" => LogPart::Context,
+            "file_short" => LogPart::FileShort(spec.and_then(parse_components_count).unwrap_or(1)),
+            "num" => LogPart::Num(spec.and_then(parse_num_padding).unwrap_or(0)),
+            "env" => {
+                let var = spec.unwrap_or_default();
+                LogPart::Env(var.to_string(), resolve_env_var(var))
+            }
+            _ if crate::logger::is_placeholder_registered(name) => {
+                LogPart::Custom(name.to_string())
+            }
             _ => {
                 eprintln!("Incorrect part given!");
                 LogPart::Text(String::new())
@@ -107,12 +683,23 @@ impl From<String> for LogPart {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-/// Internal helper tying a [`LogPart`] with an optional [`LogColor`].
+/// Internal helper tying a [`LogPart`] with optional foreground and background [`LogColor`]s,
+/// a [`LogStyle`] and a [`WidthSpec`].
 pub(crate) struct LogFormatWrapper {
     pub(crate) color: Option<LogColor>,
+    pub(crate) bg_color: Option<LogColor>,
+    pub(crate) style: LogStyle,
+    pub(crate) width: Option<WidthSpec>,
     pub(crate) part: LogPart,
 }
 
+impl LogFormatWrapper {
+    /// Returns true if the part has any color or style to apply
+    pub(crate) fn is_styled(&self) -> bool {
+        self.color.is_some() || self.bg_color.is_some() || !self.style.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Representation of a parsed format string.
 ///
@@ -133,6 +720,94 @@ impl LogFormatter {
         Ok(LogFormatter { parts: wrappers })
     }
 }
+impl LogFormatter {
+    /// Returns a template that is parsed back into the same format, see the [`Display`]
+    /// implementation.
+    ///
+    /// [`Display`]: std::fmt::Display
+    pub(crate) fn to_template_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Escapes the characters of a text that would be read as tags or placeholders in a template
+fn escape_template_text(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '{' => res.push_str("{{"),
+            '}' => res.push_str("}}"),
+            '<' => res.push_str("\\<"),
+            '>' => res.push_str("\\>"),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Writes the tag of the color if there is one, the same tag opens and closes a color block
+fn color_tag(
+    f: &mut std::fmt::Formatter<'_>,
+    prefix: &str,
+    color: Option<LogColor>,
+) -> std::fmt::Result {
+    match color {
+        Some(color) => write!(f, "<{}{}>", prefix, color.name()),
+        None => Ok(()),
+    }
+}
+
+/// Writes the format as a template: the colors and styles are opened before the parts that use
+/// them and closed right after, so the template may differ from the parsed one (e.g. nested
+/// colors become consecutive blocks) while describing the same format.
+impl std::fmt::Display for LogFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut color: Option<LogColor> = None;
+        let mut bg_color: Option<LogColor> = None;
+        let mut style = LogStyle::default();
+        for wrapper in &self.parts {
+            let text = match &wrapper.part {
+                LogPart::Text(text) if text.is_empty() => continue,
+                LogPart::Text(text) => escape_template_text(text),
+                part => {
+                    let mut placeholder = part.to_placeholder().unwrap_or_default();
+                    if let Some(width) = wrapper.width {
+                        placeholder.push(PLACEHOLDER_SPEC_SEPARATOR);
+                        placeholder.push_str(&width.to_spec_string());
+                    }
+                    format!("{{{}}}", placeholder)
+                }
+            };
+            if wrapper.color != color {
+                color_tag(f, "", color)?;
+                color_tag(f, "", wrapper.color)?;
+                color = wrapper.color;
+            }
+            if wrapper.bg_color != bg_color {
+                color_tag(f, BG_COLOR_PREFIX, bg_color)?;
+                color_tag(f, BG_COLOR_PREFIX, wrapper.bg_color)?;
+                bg_color = wrapper.bg_color;
+            }
+            for (named, name) in LogStyle::NAMED {
+                if style.contains(named) != wrapper.style.contains(named) {
+                    // a style tag opens the style if it isn't active, and closes it otherwise
+                    write!(f, "<{}>", name)?;
+                    style.toggle(named);
+                }
+            }
+            write!(f, "{}", text)?;
+        }
+        color_tag(f, "", color)?;
+        color_tag(f, BG_COLOR_PREFIX, bg_color)?;
+        for (named, name) in LogStyle::NAMED {
+            if style.contains(named) {
+                write!(f, "<{}>", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Default for LogFormatter {
     fn default() -> Self {
         LogFormatter::parse_from_string("{file}-{line} <green>[{level}]<green> - {message}")
@@ -148,6 +823,54 @@ pub enum ParseStringToWrappersError {
     UnableToParsePartsToFormatter(ParsePartsToFormatterError),
 }
 
+impl ParseStringToWrappersError {
+    /// Returns where in the format string the error was found
+    pub fn position(&self) -> Option<&ParseErrorPosition> {
+        match self {
+            ParseStringToWrappersError::UnableToParseSymbolsToParts(e) => e.position(),
+            ParseStringToWrappersError::UnableToParsePartsToFormatter(e) => e.position(),
+        }
+    }
+}
+
+/// Location of an error in a format string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrorPosition {
+    /// Byte offset of the offending token in the format string
+    pub offset: usize,
+    /// The offending token, e.g. the name of an unknown placeholder or of an unclosed color
+    pub token: String,
+    /// The text surrounding the offending token
+    pub excerpt: String,
+}
+
+/// Number of bytes kept on each side of the offending token in [`ParseErrorPosition::excerpt`]
+const EXCERPT_RADIUS: usize = 12;
+
+impl ParseErrorPosition {
+    fn new(format: &str, offset: usize, token: &str) -> Self {
+        let mut start = offset.saturating_sub(EXCERPT_RADIUS);
+        while !format.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (offset + EXCERPT_RADIUS).min(format.len());
+        while !format.is_char_boundary(end) {
+            end += 1;
+        }
+        ParseErrorPosition {
+            offset,
+            token: token.to_string(),
+            excerpt: format[start..end].to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseErrorPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at byte {} near \"{}\"", self.offset, self.excerpt)
+    }
+}
+
 /// Parses a template into a vector of [`LogFormatWrapper`]s.
 ///
 /// The returned wrappers combine [`LogPart`] placeholders with optional
@@ -155,15 +878,15 @@ pub enum ParseStringToWrappersError {
 pub(crate) fn parse_string_to_wrappers(
     text: &str,
 ) -> Result<Vec<LogFormatWrapper>, ParseStringToWrappersError> {
-    let symbols_struct = string_parse(text, "".to_string(), ParseSymbs::Start);
+    let symbols_struct = string_parse(text, 0, "".to_string(), ParseSymbs::Start);
     let symbols = parse_symbs_to_vec(symbols_struct);
-    let parts = match parse_vec_of_parse_symb_to_parts(symbols) {
+    let parts = match parse_vec_of_parse_symb_to_parts(symbols, text) {
         Ok(r) => r,
         Err(e) => {
             return Err(ParseStringToWrappersError::UnableToParseSymbolsToParts(e));
         }
     };
-    parse_parts_to_formatter(parts)
+    parse_parts_to_formatter(parts, text)
         .map_err(ParseStringToWrappersError::UnableToParsePartsToFormatter)
 }
 
@@ -178,15 +901,19 @@ pub(crate) fn parse_string_to_logparts(
 // ******
 // The fundamental parser logic
 
+/// A symbol of a format string, the delimiters keep their byte offset in the string
 #[derive(Debug)]
 enum ParseSymbs {
     Start,
     AndNext(Box<ParseSymbs>, Box<ParseSymbs>),
-    AngleOpen,
-    AngleClose,
+    AngleOpen(usize),
+    AngleClose(usize),
     Text(String),
-    BracketOpen,
-    BracketClose,
+    BracketOpen(usize),
+    BracketClose(usize),
+    /// An optional section: the placeholder deciding if it is rendered, the section and the
+    /// offset of its opening brace
+    Section(String, String, usize),
 }
 
 impl From<ParseSymbs> for Vec<ParseSymbs> {
@@ -195,19 +922,33 @@ impl From<ParseSymbs> for Vec<ParseSymbs> {
     }
 }
 
+/// Prefix of the tags that set a background color, e.g. `<bg_red>`
+const BG_COLOR_PREFIX: &str = "bg_";
+
+/// A part of a format string, the tags and placeholders keep the byte offset of their opening
+/// delimiter
 #[derive(Debug)]
 enum ParseParts {
     End,
     Text(String),
-    Color(String),
-    BracketBlock(String),
+    Color(String, usize),
+    BracketBlock(String, usize),
+    Section(String, Vec<ParseParts>, usize),
 }
 impl ParseParts {
     //verify if the text in block and color is correct
     fn verify_color_block_integriy(&self) -> bool {
         match self {
-            ParseParts::Color(text) => LogColor::get_colors_str().contains(&text.as_str()),
-            ParseParts::BracketBlock(text) => LogPart::get_parts_str().contains(&text.as_str()),
+            ParseParts::Color(text, _) => {
+                let color = text.strip_prefix(BG_COLOR_PREFIX).unwrap_or(text);
+                LogColor::get_colors_str().contains(&color)
+                    || LogColor::parse_rgb(color).is_some()
+                    || LogColor::parse_indexed(color).is_some()
+                    || LogStyle::from_name(text).is_some()
+            }
+            ParseParts::BracketBlock(text, _) | ParseParts::Section(text, _, _) => {
+                LogPart::is_placeholder_valid(text)
+            }
             _ => true,
         }
     }
@@ -217,15 +958,41 @@ impl ParseParts {
 pub enum ParsePartsToFormatterError {
     #[error("unexpected error")]
     UnexpectedError,
-    #[error("incorrect data given")]
-    IncorrectDataGiven,
+    #[error("color tag <{found}> closed while <{expected}> is still open {position}")]
+    MismatchedColorTag {
+        expected: String,
+        found: String,
+        position: ParseErrorPosition,
+    },
+    #[error("color tag <{token}> is never closed {0}", token = .0.token)]
+    UnclosedColorTag(ParseErrorPosition),
+    #[error("style tag <{token}> is never closed {0}", token = .0.token)]
+    UnclosedStyleTag(ParseErrorPosition),
+}
+
+impl ParsePartsToFormatterError {
+    /// Returns where in the format string the error was found
+    pub fn position(&self) -> Option<&ParseErrorPosition> {
+        match self {
+            ParsePartsToFormatterError::UnexpectedError => None,
+            ParsePartsToFormatterError::MismatchedColorTag { position, .. }
+            | ParsePartsToFormatterError::UnclosedColorTag(position)
+            | ParsePartsToFormatterError::UnclosedStyleTag(position) => Some(position),
+        }
+    }
 }
 
 fn parse_parts_to_formatter(
     parts: Vec<ParseParts>,
+    format: &str,
 ) -> Result<Vec<LogFormatWrapper>, ParsePartsToFormatterError> {
     let mut res: Vec<LogFormatWrapper> = Vec::new();
-    let mut curr_color: Option<LogColor> = None;
+    // the opened color tags, the innermost one is the last; a part takes the innermost color
+    let mut color_stack: Vec<(String, LogColor, usize)> = Vec::new();
+    let mut bg_color_stack: Vec<(String, LogColor, usize)> = Vec::new();
+    let mut curr_style = LogStyle::default();
+    // the opened style tags, to report the ones that are never closed
+    let mut open_styles: Vec<(String, usize)> = Vec::new();
 
     let iterator = parts.into_iter();
 
@@ -233,37 +1000,89 @@ fn parse_parts_to_formatter(
         match el {
             ParseParts::End => {}
             ParseParts::Text(text) => res.push(LogFormatWrapper {
-                color: curr_color,
+                color: color_stack.last().map(|(_, c, _)| *c),
+                bg_color: bg_color_stack.last().map(|(_, c, _)| *c),
+                style: curr_style,
+                width: None,
                 part: LogPart::Text(text),
             }),
-            ParseParts::Color(text) => match curr_color {
-                None => curr_color = Some(text.into()), // if current color is none, then we open
-                // color block and change it
-                Some(color) => match color {
-                    c if c == curr_color.unwrap() => {
-                        // if current color equals to the
-                        // block we read, then we close
-                        // the color part and change color
-                        // back to None
-                        curr_color = None;
+            ParseParts::Color(text, offset) if LogStyle::from_name(&text).is_some() => {
+                // a style tag opens the style if it isn't active, and closes it otherwise
+                curr_style.toggle(LogStyle::from_name(&text).unwrap());
+                match open_styles.iter().position(|(tag, _)| *tag == text) {
+                    Some(idx) => {
+                        open_styles.remove(idx);
                     }
-                    _ => return Err(ParsePartsToFormatterError::IncorrectDataGiven), // in other
-                                                                                     // case,
-                                                                                     // colors
-                                                                                     // don't match
-                                                                                     // so the
-                                                                                     // blocks are
-                                                                                     // incorrect
-                },
-            },
-            ParseParts::BracketBlock(text) => res.push(LogFormatWrapper {
-                color: curr_color,
-                part: text.into(),
-            }),
+                    None => open_styles.push((text, offset)),
+                }
+            }
+            ParseParts::Color(text, offset) => {
+                // background tags follow the same open/close rules as the foreground ones, but
+                // they are tracked separately
+                let (color, stack) = match text.strip_prefix(BG_COLOR_PREFIX) {
+                    Some(bg) => (LogColor::from(bg), &mut bg_color_stack),
+                    None => (LogColor::from(text.as_str()), &mut color_stack),
+                };
+                match stack.last() {
+                    // the tag of the innermost color closes it, the outer color is restored
+                    Some((_, c, _)) if *c == color => {
+                        stack.pop();
+                    }
+                    // an outer color can't be closed before the inner ones
+                    Some((open_tag, _, _)) if stack.iter().any(|(_, c, _)| *c == color) => {
+                        return Err(ParsePartsToFormatterError::MismatchedColorTag {
+                            expected: open_tag.clone(),
+                            position: ParseErrorPosition::new(format, offset, &text),
+                            found: text,
+                        });
+                    }
+                    // in other case a new (nested) color block is opened
+                    _ => stack.push((text, color, offset)),
+                }
+            }
+            ParseParts::Section(name, parts, _) => {
+                // the tags of a section must be closed inside it, and its parts take the colors
+                // and styles of the section in addition to their own
+                let color = color_stack.last().map(|(_, c, _)| *c);
+                let bg_color = bg_color_stack.last().map(|(_, c, _)| *c);
+                let section = parse_parts_to_formatter(parts, format)?
+                    .into_iter()
+                    .map(|wrapper| LogFormatWrapper {
+                        color: wrapper.color.or(color),
+                        bg_color: wrapper.bg_color.or(bg_color),
+                        style: wrapper.style.union(curr_style),
+                        ..wrapper
+                    })
+                    .collect();
+                res.push(LogFormatWrapper {
+                    color,
+                    bg_color,
+                    style: curr_style,
+                    width: None,
+                    part: LogPart::Optional(Box::new(LogPart::from(name.as_str())), section),
+                })
+            }
+            ParseParts::BracketBlock(text, _) => {
+                let (part, width) = LogPart::from_placeholder(&text);
+                res.push(LogFormatWrapper {
+                    color: color_stack.last().map(|(_, c, _)| *c),
+                    bg_color: bg_color_stack.last().map(|(_, c, _)| *c),
+                    style: curr_style,
+                    width,
+                    part,
+                })
+            }
         }
     }
-    if curr_color.is_some() {
-        return Err(ParsePartsToFormatterError::IncorrectDataGiven);
+    if let Some((tag, _, offset)) = color_stack.first().or(bg_color_stack.first()) {
+        return Err(ParsePartsToFormatterError::UnclosedColorTag(
+            ParseErrorPosition::new(format, *offset, tag),
+        ));
+    }
+    if let Some((tag, offset)) = open_styles.first() {
+        return Err(ParsePartsToFormatterError::UnclosedStyleTag(
+            ParseErrorPosition::new(format, *offset, tag),
+        ));
     }
 
     Ok(res)
@@ -271,14 +1090,46 @@ fn parse_parts_to_formatter(
 
 #[derive(Debug, Error)]
 pub enum ParseSymbToPartsError {
-    #[error("incorrect data given")]
-    IncorrectDataGiven,
+    #[error("placeholder {{{token}}} is never closed {0}", token = .0.token)]
+    UnclosedPlaceholder(ParseErrorPosition),
+    #[error("unexpected }} {0}, write }}}} to get a literal brace")]
+    StrayClosingBrace(ParseErrorPosition),
+    #[error("unknown placeholder or specifier {{{token}}} {0}", token = .0.token)]
+    IncorrectPlaceholder(ParseErrorPosition),
+    #[error(
+        "incorrect color index in <{token}>: palette colors must be between c0 and c255 {0}",
+        token = .0.token
+    )]
+    IncorrectColorIndex(ParseErrorPosition),
+    #[error(
+        "unknown color tag <{token}> {0}, write \\< and \\> to get literal angle brackets",
+        token = .0.token
+    )]
+    IncorrectColorTag(ParseErrorPosition),
+    #[error("unmatched angle bracket {0}, write \\< and \\> to get literal angle brackets")]
+    UnmatchedAngleBracket(ParseErrorPosition),
     #[error("unexpected error")]
     UnexpectedError,
 }
 
+impl ParseSymbToPartsError {
+    /// Returns where in the format string the error was found
+    pub fn position(&self) -> Option<&ParseErrorPosition> {
+        match self {
+            ParseSymbToPartsError::UnexpectedError => None,
+            ParseSymbToPartsError::UnclosedPlaceholder(position)
+            | ParseSymbToPartsError::StrayClosingBrace(position)
+            | ParseSymbToPartsError::IncorrectPlaceholder(position)
+            | ParseSymbToPartsError::IncorrectColorIndex(position)
+            | ParseSymbToPartsError::IncorrectColorTag(position)
+            | ParseSymbToPartsError::UnmatchedAngleBracket(position) => Some(position),
+        }
+    }
+}
+
 fn parse_vec_of_parse_symb_to_parts(
     symbs: Vec<ParseSymbs>,
+    format: &str,
 ) -> Result<Vec<ParseParts>, ParseSymbToPartsError> {
     let mut res: Vec<ParseParts> = Vec::new();
     let mut iterator = symbs.into_iter();
@@ -288,39 +1139,81 @@ fn parse_vec_of_parse_symb_to_parts(
             ParseSymbs::Start => {}
             ParseSymbs::AndNext(_, _) => return Err(ParseSymbToPartsError::UnexpectedError),
             ParseSymbs::Text(text) => res.push(ParseParts::Text(text)),
-            ParseSymbs::AngleOpen => {
+            ParseSymbs::AngleOpen(offset) => {
+                let unmatched = || {
+                    ParseSymbToPartsError::UnmatchedAngleBracket(ParseErrorPosition::new(
+                        format, offset, "<",
+                    ))
+                };
                 let text_in_between = match iterator.next() {
                     Some(ParseSymbs::Text(text)) => text,
-                    _ => return Err(ParseSymbToPartsError::IncorrectDataGiven),
+                    _ => return Err(unmatched()),
                 };
                 match iterator.next() {
-                    Some(ParseSymbs::AngleClose) => res.push(ParseParts::Color(text_in_between)),
-                    _ => return Err(ParseSymbToPartsError::IncorrectDataGiven),
+                    Some(ParseSymbs::AngleClose(_)) => {
+                        res.push(ParseParts::Color(text_in_between, offset))
+                    }
+                    _ => return Err(unmatched()),
                 }
             }
-            ParseSymbs::BracketOpen => {
+            ParseSymbs::AngleClose(offset) => {
+                return Err(ParseSymbToPartsError::UnmatchedAngleBracket(
+                    ParseErrorPosition::new(format, offset, ">"),
+                ))
+            }
+            ParseSymbs::BracketOpen(offset) => {
                 let text_in_between = match iterator.next() {
                     Some(ParseSymbs::Text(text)) => text,
-                    _ => return Err(ParseSymbToPartsError::IncorrectDataGiven),
+                    _ => String::new(),
                 };
                 match iterator.next() {
-                    Some(ParseSymbs::BracketClose) => {
-                        res.push(ParseParts::BracketBlock(text_in_between))
+                    Some(ParseSymbs::BracketClose(_)) => {
+                        res.push(ParseParts::BracketBlock(text_in_between, offset))
+                    }
+                    _ => {
+                        return Err(ParseSymbToPartsError::UnclosedPlaceholder(
+                            ParseErrorPosition::new(format, offset, &text_in_between),
+                        ))
                     }
-                    _ => return Err(ParseSymbToPartsError::IncorrectDataGiven),
                 }
             }
-            _ => return Err(ParseSymbToPartsError::IncorrectDataGiven),
+            ParseSymbs::BracketClose(offset) => {
+                return Err(ParseSymbToPartsError::StrayClosingBrace(
+                    ParseErrorPosition::new(format, offset, "}"),
+                ))
+            }
+            ParseSymbs::Section(name, section, offset) => {
+                // the section is parsed as a format of its own, at its place in the whole format
+                let section_offset = offset + name.len() + 2;
+                let symbols =
+                    string_parse(&section, section_offset, String::new(), ParseSymbs::Start);
+                let parts = parse_vec_of_parse_symb_to_parts(parse_symbs_to_vec(symbols), format)?;
+                res.push(ParseParts::Section(name, parts, offset));
+            }
         }
     }
-    let temp: Vec<bool> = res
-        .iter()
-        .map(|x| x.verify_color_block_integriy())
-        .collect();
-    if temp.contains(&false) {
-        return Err(ParseSymbToPartsError::IncorrectDataGiven);
+    for part in &res {
+        if let ParseParts::Color(text, offset) = part {
+            let color = text.strip_prefix(BG_COLOR_PREFIX).unwrap_or(text);
+            if LogColor::is_indexed_like(color) && LogColor::parse_indexed(color).is_none() {
+                return Err(ParseSymbToPartsError::IncorrectColorIndex(
+                    ParseErrorPosition::new(format, *offset, color),
+                ));
+            }
+        }
+    }
+    match res.iter().find(|x| !x.verify_color_block_integriy()) {
+        Some(ParseParts::Color(text, offset)) => Err(ParseSymbToPartsError::IncorrectColorTag(
+            ParseErrorPosition::new(format, *offset, text),
+        )),
+        Some(ParseParts::BracketBlock(text, offset) | ParseParts::Section(text, _, offset)) => {
+            Err(ParseSymbToPartsError::IncorrectPlaceholder(
+                ParseErrorPosition::new(format, *offset, text),
+            ))
+        }
+        Some(_) => Err(ParseSymbToPartsError::UnexpectedError),
+        None => Ok(res),
     }
-    Ok(res)
 }
 
 fn parse_symbs_to_vec(symbs: ParseSymbs) -> Vec<ParseSymbs> {
@@ -342,7 +1235,38 @@ fn parse_symbs_to_vec(symbs: ParseSymbs) -> Vec<ParseSymbs> {
     res
 }
 
-fn string_parse(string: &str, acc_text: String, acc1: ParseSymbs) -> ParseSymbs {
+/// Splits an optional section starting the string, e.g. `{module?[{module}] }`, into its
+/// placeholder and its content. Returns None if the string doesn't start with a complete section.
+fn split_optional_section(string: &str) -> Option<(&str, &str)> {
+    let name_end = 1 + string[1..].find(['?', '{', '}'])?;
+    if name_end == 1 || !string[name_end..].starts_with('?') {
+        return None;
+    }
+    let bytes = string.as_bytes();
+    let mut depth = 0;
+    let mut idx = name_end + 1;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' if matches!(bytes.get(idx + 1), Some(b'<' | b'>')) => idx += 1,
+            // escaped braces of the section itself
+            b'{' | b'}' if depth == 0 && bytes.get(idx + 1) == Some(&bytes[idx]) => idx += 1,
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some((&string[1..name_end], &string[name_end + 1..idx])),
+            b'}' => depth -= 1,
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// Returns true if the last parsed symbol opens a placeholder that isn't closed yet
+fn is_in_bracket_block(acc: &ParseSymbs) -> bool {
+    matches!(acc, ParseSymbs::AndNext(_, last) if matches!(**last, ParseSymbs::BracketOpen(_)))
+}
+
+/// Splits the format into symbols, `pos` is the byte offset of `string` in the whole format
+fn string_parse(string: &str, pos: usize, acc_text: String, acc1: ParseSymbs) -> ParseSymbs {
     if string.is_empty() {
         if !acc_text.is_empty() {
             ParseSymbs::AndNext(Box::new(acc1), Box::new(ParseSymbs::Text(acc_text)))
@@ -351,6 +1275,40 @@ fn string_parse(string: &str, acc_text: String, acc1: ParseSymbs) -> ParseSymbs
         }
     } else {
         let curr_char = string.chars().next().unwrap();
+        let char_len = curr_char.len_utf8();
+        if is_in_bracket_block(&acc1) && ['<', '>'].contains(&curr_char) {
+            // angle brackets inside a placeholder belong to its specifier, e.g. `{level:<5}`
+            let mut str_to_ret = acc_text;
+            str_to_ret.push(curr_char);
+            return string_parse(&string[1..], pos + 1, str_to_ret, acc1);
+        }
+        if !is_in_bracket_block(&acc1) && (string.starts_with("\\<") || string.starts_with("\\>")) {
+            // an escaped angle bracket is a literal one, e.g. `\<-` gives `<-`
+            let mut str_to_ret = acc_text;
+            str_to_ret.push_str(&string[1..2]);
+            return string_parse(&string[2..], pos + 2, str_to_ret, acc1);
+        }
+        if !is_in_bracket_block(&acc1) && (string.starts_with("{{") || string.starts_with("}}")) {
+            // a doubled brace is an escaped literal brace, e.g. `{{json}}` gives `{json}`
+            let mut str_to_ret = acc_text;
+            str_to_ret.push(curr_char);
+            return string_parse(&string[2..], pos + 2, str_to_ret, acc1);
+        }
+        if !is_in_bracket_block(&acc1) && curr_char == '{' {
+            if let Some((name, section)) = split_optional_section(string) {
+                let len = name.len() + section.len() + 3;
+                let acc = ParseSymbs::AndNext(Box::new(acc1), Box::new(ParseSymbs::Text(acc_text)));
+                let acc = ParseSymbs::AndNext(
+                    Box::new(acc),
+                    Box::new(ParseSymbs::Section(
+                        name.to_string(),
+                        section.to_string(),
+                        pos,
+                    )),
+                );
+                return string_parse(&string[len..], pos + len, String::new(), acc);
+            }
+        }
         let mut acc_to_ret: ParseSymbs = acc1;
         let mut str_to_ret: String = acc_text;
         if ['{', '}', '<', '>'].contains(&curr_char) {
@@ -361,27 +1319,34 @@ fn string_parse(string: &str, acc_text: String, acc1: ParseSymbs) -> ParseSymbs
         match curr_char {
             '{' => string_parse(
                 &string[1..],
+                pos + 1,
                 str_to_ret,
-                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::BracketOpen)),
+                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::BracketOpen(pos))),
             ),
             '}' => string_parse(
                 &string[1..],
+                pos + 1,
                 str_to_ret,
-                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::BracketClose)),
+                ParseSymbs::AndNext(
+                    Box::new(acc_to_ret),
+                    Box::new(ParseSymbs::BracketClose(pos)),
+                ),
             ),
             '<' => string_parse(
                 &string[1..],
+                pos + 1,
                 str_to_ret,
-                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::AngleOpen)),
+                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::AngleOpen(pos))),
             ),
             '>' => string_parse(
                 &string[1..],
+                pos + 1,
                 str_to_ret,
-                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::AngleClose)),
+                ParseSymbs::AndNext(Box::new(acc_to_ret), Box::new(ParseSymbs::AngleClose(pos))),
             ),
             el => {
                 str_to_ret.push(el);
-                string_parse(&string[1..], str_to_ret, acc_to_ret)
+                string_parse(&string[char_len..], pos + char_len, str_to_ret, acc_to_ret)
             }
         }
     }
@@ -15,6 +15,18 @@ fn parse_config_from_env() -> Result<ConfigForSerde, ReadFromConfigFileError> {
         res_conf.level = Some(v.to_owned())
     }
 
+    if let Ok(v) = env::var("stderr_threshold") {
+        res_conf.stderr_threshold = Some(v.to_owned())
+    }
+
+    if let Ok(v) = env::var("terminal_level") {
+        res_conf.terminal_level = Some(v.to_owned())
+    }
+
+    if let Ok(v) = env::var("file_level") {
+        res_conf.file_level = Some(v.to_owned())
+    }
+
     if let Ok(v) = env::var("print_to_terminal") {
         res_conf.print_to_terminal = Some(v.to_owned());
     };
@@ -23,6 +35,17 @@ fn parse_config_from_env() -> Result<ConfigForSerde, ReadFromConfigFileError> {
         res_conf.colorized = Some(v.to_owned());
     };
 
+    if let Ok(v) = env::var("color_when") {
+        res_conf.color_when = Some(v.to_owned());
+    };
+
+    if let Ok(v) = env::var("terminal_output_format") {
+        res_conf.terminal_output_format = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_output_format") {
+        res_conf.file_output_format = Some(v.to_owned());
+    }
+
     if let Ok(v) = env::var("global_formatting") {
         res_conf.global_formatting = Some(v.to_owned());
     }
@@ -41,6 +64,54 @@ fn parse_config_from_env() -> Result<ConfigForSerde, ReadFromConfigFileError> {
     if let Ok(v) = env::var("error_formatting") {
         res_conf.error_formatting = Some(v.to_owned());
     }
+    if let Ok(v) = env::var("file_global_formatting") {
+        res_conf.file_global_formatting = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_trace_formatting") {
+        res_conf.file_trace_formatting = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_debug_formatting") {
+        res_conf.file_debug_formatting = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_info_formatting") {
+        res_conf.file_info_formatting = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_warn_formatting") {
+        res_conf.file_warn_formatting = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_error_formatting") {
+        res_conf.file_error_formatting = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("level_name_trace") {
+        res_conf.level_name_trace = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("level_name_debug") {
+        res_conf.level_name_debug = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("level_name_info") {
+        res_conf.level_name_info = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("level_name_warn") {
+        res_conf.level_name_warn = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("level_name_error") {
+        res_conf.level_name_error = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("trace_symbol") {
+        res_conf.trace_symbol = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("debug_symbol") {
+        res_conf.debug_symbol = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("info_symbol") {
+        res_conf.info_symbol = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("warn_symbol") {
+        res_conf.warn_symbol = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("error_symbol") {
+        res_conf.error_symbol = Some(v.to_owned());
+    }
 
     if let Ok(v) = env::var("file_name") {
         res_conf.file_name = Some(v.to_owned());
@@ -51,6 +122,43 @@ fn parse_config_from_env() -> Result<ConfigForSerde, ReadFromConfigFileError> {
     if let Ok(v) = env::var("archive_dir") {
         res_conf.archive_dir = Some(v.to_owned());
     }
+    if let Ok(v) = env::var("archive_max_total_size") {
+        res_conf.archive_max_total_size = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("archive_name_format") {
+        res_conf.archive_name_format = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("archive_subdir_format") {
+        res_conf.archive_subdir_format = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("archive_mode") {
+        res_conf.archive_mode = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("rotation_mode") {
+        res_conf.rotation_mode = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("rotate_on_startup") {
+        res_conf.rotate_on_startup = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("purge_on_startup") {
+        res_conf.purge_on_startup = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("file_permissions") {
+        res_conf.file_permissions = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("allowed_file_extensions") {
+        let extensions = v.split(',').map(|ext| ext.trim().to_string()).collect();
+        res_conf.allowed_file_extensions = Some(extensions);
+    }
+    if let Ok(v) = env::var("syslog_addr") {
+        res_conf.syslog_addr = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("syslog_facility") {
+        res_conf.syslog_facility = Some(v.to_owned());
+    }
+    if let Ok(v) = env::var("syslog_app_name") {
+        res_conf.syslog_app_name = Some(v.to_owned());
+    }
     if let Ok(v) = env::var("rotations") {
         let mut rots = Vec::<String>::new();
         if !v.contains(',') {
@@ -6,7 +6,7 @@
 use std::io::Read;
 
 use crate::logger::set_errors::{ParseConfigError, ReadFromConfigFileError};
-use crate::Level;
+use crate::{ArchiveMode, ColorWhen, Level, OutputFormat, RotationMode};
 
 use crate::logger;
 use env_file_reader;
@@ -18,38 +18,107 @@ use serde::Serialize;
 pub(crate) struct ConfigForSerde {
     pub(crate) enabled: Option<String>,
     pub(crate) level: Option<String>,
+    pub(crate) stderr_threshold: Option<String>,
+    pub(crate) terminal_level: Option<String>,
+    pub(crate) file_level: Option<String>,
     pub(crate) print_to_terminal: Option<String>,
     pub(crate) colorized: Option<String>,
+    pub(crate) color_when: Option<String>,
+    pub(crate) terminal_output_format: Option<String>,
+    pub(crate) file_output_format: Option<String>,
     pub(crate) global_formatting: Option<String>,
     pub(crate) trace_formatting: Option<String>,
     pub(crate) debug_formatting: Option<String>,
     pub(crate) info_formatting: Option<String>,
     pub(crate) warn_formatting: Option<String>,
     pub(crate) error_formatting: Option<String>,
+    pub(crate) file_global_formatting: Option<String>,
+    pub(crate) file_trace_formatting: Option<String>,
+    pub(crate) file_debug_formatting: Option<String>,
+    pub(crate) file_info_formatting: Option<String>,
+    pub(crate) file_warn_formatting: Option<String>,
+    pub(crate) file_error_formatting: Option<String>,
+    pub(crate) level_name_trace: Option<String>,
+    pub(crate) level_name_debug: Option<String>,
+    pub(crate) level_name_info: Option<String>,
+    pub(crate) level_name_warn: Option<String>,
+    pub(crate) level_name_error: Option<String>,
+    pub(crate) trace_symbol: Option<String>,
+    pub(crate) debug_symbol: Option<String>,
+    pub(crate) info_symbol: Option<String>,
+    pub(crate) warn_symbol: Option<String>,
+    pub(crate) error_symbol: Option<String>,
 
     pub(crate) file_name: Option<String>,
     pub(crate) compression: Option<String>,
     pub(crate) rotations: Option<Vec<String>>,
     pub(crate) archive_dir: Option<String>,
+    pub(crate) archive_max_total_size: Option<String>,
+    pub(crate) archive_name_format: Option<String>,
+    pub(crate) archive_subdir_format: Option<String>,
+    pub(crate) archive_mode: Option<String>,
+    pub(crate) rotation_mode: Option<String>,
+    pub(crate) rotate_on_startup: Option<String>,
+    pub(crate) purge_on_startup: Option<String>,
+    pub(crate) file_permissions: Option<String>,
+    pub(crate) allowed_file_extensions: Option<Vec<String>>,
+
+    pub(crate) syslog_addr: Option<String>,
+    pub(crate) syslog_facility: Option<String>,
+    pub(crate) syslog_app_name: Option<String>,
 }
 
 #[derive(Default)]
 pub(crate) struct InterConfig {
     enabled: Option<bool>,
     level: Option<Level>,
+    stderr_threshold: Option<Level>,
+    terminal_level: Option<Level>,
+    file_level: Option<Level>,
     print_to_terminal: Option<bool>,
     colorized: Option<bool>,
+    color_when: Option<ColorWhen>,
+    terminal_output_format: Option<OutputFormat>,
+    file_output_format: Option<OutputFormat>,
     global_formatting: Option<String>,
     trace_formatting: Option<String>,
     debug_formatting: Option<String>,
     info_formatting: Option<String>,
     warn_formatting: Option<String>,
     error_formatting: Option<String>,
+    file_global_formatting: Option<String>,
+    file_trace_formatting: Option<String>,
+    file_debug_formatting: Option<String>,
+    file_info_formatting: Option<String>,
+    file_warn_formatting: Option<String>,
+    file_error_formatting: Option<String>,
+    level_name_trace: Option<String>,
+    level_name_debug: Option<String>,
+    level_name_info: Option<String>,
+    level_name_warn: Option<String>,
+    level_name_error: Option<String>,
+    trace_symbol: Option<String>,
+    debug_symbol: Option<String>,
+    info_symbol: Option<String>,
+    warn_symbol: Option<String>,
+    error_symbol: Option<String>,
 
     file_name: Option<String>,
     compression: Option<String>,
     rotations: Option<Vec<String>>,
     archive_dir: Option<String>,
+    archive_max_total_size: Option<String>,
+    archive_name_format: Option<String>,
+    archive_subdir_format: Option<String>,
+    archive_mode: Option<ArchiveMode>,
+    rotation_mode: Option<RotationMode>,
+    rotate_on_startup: Option<bool>,
+    purge_on_startup: Option<String>,
+    file_permissions: Option<u32>,
+    allowed_file_extensions: Option<Vec<String>>,
+
+    #[cfg(feature = "syslog")]
+    syslog: Option<logger::syslog::SyslogConfig>,
 }
 
 impl InterConfig {
@@ -67,6 +136,15 @@ impl InterConfig {
         if let Some(level) = self.level {
             logger::set_log_level(level)?;
         }
+        if let Some(level) = self.stderr_threshold {
+            logger::set_stderr_threshold(level)?;
+        }
+        if let Some(level) = self.terminal_level {
+            logger::set_terminal_level(level)?;
+        }
+        if let Some(level) = self.file_level {
+            logger::set_file_level(level)?;
+        }
 
         // Terminal output
         if let Some(to_term) = self.print_to_terminal {
@@ -77,6 +155,17 @@ impl InterConfig {
         if let Some(col) = self.colorized {
             logger::set_colorized(col)?;
         }
+        if let Some(when) = self.color_when {
+            logger::set_color_when(when)?;
+        }
+
+        // Output formats
+        if let Some(format) = self.terminal_output_format {
+            logger::set_terminal_output_format(format)?;
+        }
+        if let Some(format) = self.file_output_format {
+            logger::set_file_output_format(format)?;
+        }
 
         // Formatting
         if let Some(fmt) = self.global_formatting {
@@ -97,8 +186,77 @@ impl InterConfig {
         if let Some(fmt) = self.error_formatting {
             logger::set_level_formatting(Level::ERROR, &fmt)?;
         }
+        if let Some(fmt) = self.file_global_formatting {
+            logger::set_file_global_formatting(&fmt)?;
+        }
+        if let Some(fmt) = self.file_trace_formatting {
+            logger::set_file_formatting(Level::TRACE, &fmt)?;
+        }
+        if let Some(fmt) = self.file_debug_formatting {
+            logger::set_file_formatting(Level::DEBUG, &fmt)?;
+        }
+        if let Some(fmt) = self.file_info_formatting {
+            logger::set_file_formatting(Level::INFO, &fmt)?;
+        }
+        if let Some(fmt) = self.file_warn_formatting {
+            logger::set_file_formatting(Level::WARN, &fmt)?;
+        }
+        if let Some(fmt) = self.file_error_formatting {
+            logger::set_file_formatting(Level::ERROR, &fmt)?;
+        }
+
+        // Level names
+        if let Some(name) = self.level_name_trace {
+            logger::set_level_name(Level::TRACE, &name)?;
+        }
+        if let Some(name) = self.level_name_debug {
+            logger::set_level_name(Level::DEBUG, &name)?;
+        }
+        if let Some(name) = self.level_name_info {
+            logger::set_level_name(Level::INFO, &name)?;
+        }
+        if let Some(name) = self.level_name_warn {
+            logger::set_level_name(Level::WARN, &name)?;
+        }
+        if let Some(name) = self.level_name_error {
+            logger::set_level_name(Level::ERROR, &name)?;
+        }
+
+        // Level symbols
+        if let Some(symbol) = self.trace_symbol {
+            logger::set_level_symbol(Level::TRACE, &symbol)?;
+        }
+        if let Some(symbol) = self.debug_symbol {
+            logger::set_level_symbol(Level::DEBUG, &symbol)?;
+        }
+        if let Some(symbol) = self.info_symbol {
+            logger::set_level_symbol(Level::INFO, &symbol)?;
+        }
+        if let Some(symbol) = self.warn_symbol {
+            logger::set_level_symbol(Level::WARN, &symbol)?;
+        }
+        if let Some(symbol) = self.error_symbol {
+            logger::set_level_symbol(Level::ERROR, &symbol)?;
+        }
 
         // File output
+        if let Some(rotate) = self.rotate_on_startup {
+            // before the file is set, it decides whether the existing file is continued
+            logger::set_rotate_on_startup(rotate)?;
+        }
+        if let Some(age) = self.purge_on_startup {
+            // before the file is set, which removes the old files
+            logger::set_purge_on_startup(&age)?;
+        }
+        if let Some(mode) = self.file_permissions {
+            // before the file is set, which creates it
+            logger::set_file_permissions(mode)?;
+        }
+        if let Some(extensions) = self.allowed_file_extensions {
+            // before the file is set, whose extension is checked
+            let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+            logger::set_allowed_file_extensions(&extensions)?;
+        }
         if let Some(pattern) = self.file_name {
             logger::set_file(&pattern)?;
         }
@@ -109,16 +267,57 @@ impl InterConfig {
             // we ignore the returned PathBuf here
             let _ = logger::set_archive_dir(&dir)?;
         }
+        if let Some(size) = self.archive_max_total_size {
+            logger::set_archive_max_total_size(&size)?;
+        }
+        if let Some(format) = self.archive_name_format {
+            logger::set_archive_name_format(&format)?;
+        }
+        if let Some(format) = self.archive_subdir_format {
+            logger::set_archive_subdir_format(&format)?;
+        }
+        if let Some(mode) = self.archive_mode {
+            logger::set_archive_mode(mode)?;
+        }
         if let Some(rotations) = self.rotations {
             for rot in rotations {
                 logger::add_rotation(&rot)?;
             }
         }
+        if let Some(mode) = self.rotation_mode {
+            logger::set_rotation_mode(mode)?;
+        }
+
+        // Syslog output
+        #[cfg(feature = "syslog")]
+        if let Some(syslog) = self.syslog {
+            logger::set_syslog(syslog)?;
+        }
 
         Ok(())
     }
 }
 
+fn parse_level(value: &str) -> Result<Level, ParseConfigError> {
+    match value.to_lowercase().as_str() {
+        "trace" => Ok(Level::TRACE),
+        "debug" => Ok(Level::DEBUG),
+        "info" => Ok(Level::INFO),
+        "warn" => Ok(Level::WARN),
+        "error" => Ok(Level::ERROR),
+        _ => Err(ParseConfigError::IncorrectValue),
+    }
+}
+
+fn parse_output_format(value: &str) -> Result<OutputFormat, ParseConfigError> {
+    match value.to_lowercase().as_str() {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        "csv" => Ok(OutputFormat::Csv),
+        _ => Err(ParseConfigError::IncorrectValue),
+    }
+}
+
 impl TryFrom<ConfigForSerde> for InterConfig {
     type Error = ParseConfigError;
 
@@ -135,14 +334,16 @@ impl TryFrom<ConfigForSerde> for InterConfig {
         };
 
         if let Some(v) = value.level {
-            match v.to_lowercase().as_str() {
-                "trace" => res_conf.level = Some(Level::TRACE),
-                "debug" => res_conf.level = Some(Level::DEBUG),
-                "info" => res_conf.level = Some(Level::INFO),
-                "warn" => res_conf.level = Some(Level::WARN),
-                "error" => res_conf.level = Some(Level::ERROR),
-                _ => return Err(ParseConfigError::IncorrectValue),
-            };
+            res_conf.level = Some(parse_level(&v)?);
+        }
+        if let Some(v) = value.stderr_threshold {
+            res_conf.stderr_threshold = Some(parse_level(&v)?);
+        }
+        if let Some(v) = value.terminal_level {
+            res_conf.terminal_level = Some(parse_level(&v)?);
+        }
+        if let Some(v) = value.file_level {
+            res_conf.file_level = Some(parse_level(&v)?);
         }
 
         if let Some(v) = value.print_to_terminal {
@@ -161,6 +362,22 @@ impl TryFrom<ConfigForSerde> for InterConfig {
             };
         };
 
+        if let Some(v) = value.color_when {
+            match v.to_lowercase().as_str() {
+                "auto" => res_conf.color_when = Some(ColorWhen::Auto),
+                "always" => res_conf.color_when = Some(ColorWhen::Always),
+                "never" => res_conf.color_when = Some(ColorWhen::Never),
+                _ => return Err(ParseConfigError::IncorrectValue),
+            };
+        };
+
+        if let Some(v) = value.terminal_output_format {
+            res_conf.terminal_output_format = Some(parse_output_format(&v)?);
+        }
+        if let Some(v) = value.file_output_format {
+            res_conf.file_output_format = Some(parse_output_format(&v)?);
+        }
+
         if let Some(v) = value.global_formatting {
             res_conf.global_formatting = Some(v);
         }
@@ -179,6 +396,54 @@ impl TryFrom<ConfigForSerde> for InterConfig {
         if let Some(v) = value.error_formatting {
             res_conf.error_formatting = Some(v);
         }
+        if let Some(v) = value.file_global_formatting {
+            res_conf.file_global_formatting = Some(v);
+        }
+        if let Some(v) = value.file_trace_formatting {
+            res_conf.file_trace_formatting = Some(v);
+        }
+        if let Some(v) = value.file_debug_formatting {
+            res_conf.file_debug_formatting = Some(v);
+        }
+        if let Some(v) = value.file_info_formatting {
+            res_conf.file_info_formatting = Some(v);
+        }
+        if let Some(v) = value.file_warn_formatting {
+            res_conf.file_warn_formatting = Some(v);
+        }
+        if let Some(v) = value.file_error_formatting {
+            res_conf.file_error_formatting = Some(v);
+        }
+        if let Some(v) = value.level_name_trace {
+            res_conf.level_name_trace = Some(v);
+        }
+        if let Some(v) = value.level_name_debug {
+            res_conf.level_name_debug = Some(v);
+        }
+        if let Some(v) = value.level_name_info {
+            res_conf.level_name_info = Some(v);
+        }
+        if let Some(v) = value.level_name_warn {
+            res_conf.level_name_warn = Some(v);
+        }
+        if let Some(v) = value.level_name_error {
+            res_conf.level_name_error = Some(v);
+        }
+        if let Some(v) = value.trace_symbol {
+            res_conf.trace_symbol = Some(v);
+        }
+        if let Some(v) = value.debug_symbol {
+            res_conf.debug_symbol = Some(v);
+        }
+        if let Some(v) = value.info_symbol {
+            res_conf.info_symbol = Some(v);
+        }
+        if let Some(v) = value.warn_symbol {
+            res_conf.warn_symbol = Some(v);
+        }
+        if let Some(v) = value.error_symbol {
+            res_conf.error_symbol = Some(v);
+        }
 
         if let Some(v) = value.file_name {
             res_conf.file_name = Some(v);
@@ -189,9 +454,77 @@ impl TryFrom<ConfigForSerde> for InterConfig {
         if let Some(v) = value.archive_dir {
             res_conf.archive_dir = Some(v);
         }
+        if let Some(v) = value.archive_max_total_size {
+            res_conf.archive_max_total_size = Some(v);
+        }
+        if let Some(v) = value.archive_name_format {
+            res_conf.archive_name_format = Some(v);
+        }
+        if let Some(v) = value.archive_subdir_format {
+            res_conf.archive_subdir_format = Some(v);
+        }
+        if let Some(v) = value.archive_mode {
+            match v.to_lowercase().as_str() {
+                "compress" => res_conf.archive_mode = Some(ArchiveMode::Compress),
+                "move" => res_conf.archive_mode = Some(ArchiveMode::Move),
+                "leave_in_place" => res_conf.archive_mode = Some(ArchiveMode::LeaveInPlace),
+                _ => return Err(ParseConfigError::IncorrectValue),
+            };
+        }
+        if let Some(v) = value.rotation_mode {
+            match v.to_lowercase().as_str() {
+                "any" => res_conf.rotation_mode = Some(RotationMode::Any),
+                "all" => res_conf.rotation_mode = Some(RotationMode::All),
+                _ => return Err(ParseConfigError::IncorrectValue),
+            };
+        }
+        if let Some(v) = value.rotate_on_startup {
+            match v.as_str() {
+                "true" => res_conf.rotate_on_startup = Some(true),
+                "false" => res_conf.rotate_on_startup = Some(false),
+                _ => return Err(ParseConfigError::IncorrectValue),
+            };
+        }
+        if let Some(v) = value.purge_on_startup {
+            res_conf.purge_on_startup = Some(v);
+        }
+        if let Some(v) = value.file_permissions {
+            // octal, like chmod
+            let digits = v.trim().trim_start_matches("0o");
+            match u32::from_str_radix(digits, 8) {
+                Ok(mode) => res_conf.file_permissions = Some(mode),
+                Err(_) => return Err(ParseConfigError::IncorrectValue),
+            };
+        }
         if let Some(v) = value.rotations {
             res_conf.rotations = Some(v)
         }
+        if let Some(v) = value.allowed_file_extensions {
+            res_conf.allowed_file_extensions = Some(v)
+        }
+
+        let syslog_set = value.syslog_addr.is_some()
+            || value.syslog_facility.is_some()
+            || value.syslog_app_name.is_some();
+        if syslog_set {
+            #[cfg(not(feature = "syslog"))]
+            return Err(ParseConfigError::FeatureRequired("syslog"));
+            #[cfg(feature = "syslog")]
+            {
+                let mut syslog = logger::syslog::SyslogConfig::default();
+                if let Some(v) = value.syslog_addr {
+                    syslog.addr = v;
+                }
+                if let Some(v) = value.syslog_facility {
+                    syslog.facility = logger::syslog::Facility::from_name(&v)
+                        .ok_or(ParseConfigError::IncorrectValue)?;
+                }
+                if let Some(v) = value.syslog_app_name {
+                    syslog.app_name = v;
+                }
+                res_conf.syslog = Some(syslog);
+            }
+        }
         Ok(res_conf)
     }
 }
@@ -218,6 +551,18 @@ fn parse_config_from_env_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
         res_conf.level = Some(v.to_owned())
     }
 
+    if let Some(v) = vars_r.get("stderr_threshold") {
+        res_conf.stderr_threshold = Some(v.to_owned())
+    }
+
+    if let Some(v) = vars_r.get("terminal_level") {
+        res_conf.terminal_level = Some(v.to_owned())
+    }
+
+    if let Some(v) = vars_r.get("file_level") {
+        res_conf.file_level = Some(v.to_owned())
+    }
+
     if let Some(v) = vars_r.get("print_to_terminal") {
         res_conf.print_to_terminal = Some(v.to_owned());
     };
@@ -226,6 +571,17 @@ fn parse_config_from_env_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
         res_conf.colorized = Some(v.to_owned());
     };
 
+    if let Some(v) = vars_r.get("color_when") {
+        res_conf.color_when = Some(v.to_owned());
+    };
+
+    if let Some(v) = vars_r.get("terminal_output_format") {
+        res_conf.terminal_output_format = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_output_format") {
+        res_conf.file_output_format = Some(v.to_owned());
+    }
+
     if let Some(v) = vars_r.get("global_formatting") {
         res_conf.global_formatting = Some(v.to_owned());
     }
@@ -244,6 +600,54 @@ fn parse_config_from_env_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
     if let Some(v) = vars_r.get("error_formatting") {
         res_conf.error_formatting = Some(v.to_owned());
     }
+    if let Some(v) = vars_r.get("file_global_formatting") {
+        res_conf.file_global_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_trace_formatting") {
+        res_conf.file_trace_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_debug_formatting") {
+        res_conf.file_debug_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_info_formatting") {
+        res_conf.file_info_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_warn_formatting") {
+        res_conf.file_warn_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_error_formatting") {
+        res_conf.file_error_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("level_name_trace") {
+        res_conf.level_name_trace = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("level_name_debug") {
+        res_conf.level_name_debug = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("level_name_info") {
+        res_conf.level_name_info = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("level_name_warn") {
+        res_conf.level_name_warn = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("level_name_error") {
+        res_conf.level_name_error = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("trace_symbol") {
+        res_conf.trace_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("debug_symbol") {
+        res_conf.debug_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("info_symbol") {
+        res_conf.info_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("warn_symbol") {
+        res_conf.warn_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("error_symbol") {
+        res_conf.error_symbol = Some(v.to_owned());
+    }
 
     if let Some(v) = vars_r.get("file_name") {
         res_conf.file_name = Some(v.to_owned());
@@ -254,6 +658,43 @@ fn parse_config_from_env_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
     if let Some(v) = vars_r.get("archive_dir") {
         res_conf.archive_dir = Some(v.to_owned());
     }
+    if let Some(v) = vars_r.get("archive_max_total_size") {
+        res_conf.archive_max_total_size = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("archive_name_format") {
+        res_conf.archive_name_format = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("archive_subdir_format") {
+        res_conf.archive_subdir_format = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("archive_mode") {
+        res_conf.archive_mode = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("rotation_mode") {
+        res_conf.rotation_mode = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("rotate_on_startup") {
+        res_conf.rotate_on_startup = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("purge_on_startup") {
+        res_conf.purge_on_startup = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("file_permissions") {
+        res_conf.file_permissions = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("allowed_file_extensions") {
+        let extensions = v.split(',').map(|ext| ext.trim().to_string()).collect();
+        res_conf.allowed_file_extensions = Some(extensions);
+    }
+    if let Some(v) = vars_r.get("syslog_addr") {
+        res_conf.syslog_addr = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("syslog_facility") {
+        res_conf.syslog_facility = Some(v.to_owned());
+    }
+    if let Some(v) = vars_r.get("syslog_app_name") {
+        res_conf.syslog_app_name = Some(v.to_owned());
+    }
     if let Some(v) = vars_r.get("rotations") {
         let mut rots = Vec::<String>::new();
         if !v.contains(',') {
@@ -318,6 +759,18 @@ fn parse_config_from_ini_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
         res_conf.level = Some(v.to_owned())
     }
 
+    if let Some(v) = section.get("stderr_threshold") {
+        res_conf.stderr_threshold = Some(v.to_owned())
+    }
+
+    if let Some(v) = section.get("terminal_level") {
+        res_conf.terminal_level = Some(v.to_owned())
+    }
+
+    if let Some(v) = section.get("file_level") {
+        res_conf.file_level = Some(v.to_owned())
+    }
+
     if let Some(v) = section.get("print_to_terminal") {
         res_conf.print_to_terminal = Some(v.to_owned());
     };
@@ -326,6 +779,17 @@ fn parse_config_from_ini_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
         res_conf.colorized = Some(v.to_owned());
     };
 
+    if let Some(v) = section.get("color_when") {
+        res_conf.color_when = Some(v.to_owned());
+    };
+
+    if let Some(v) = section.get("terminal_output_format") {
+        res_conf.terminal_output_format = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_output_format") {
+        res_conf.file_output_format = Some(v.to_owned());
+    }
+
     if let Some(v) = section.get("global_formatting") {
         res_conf.global_formatting = Some(v.to_owned());
     }
@@ -344,6 +808,54 @@ fn parse_config_from_ini_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
     if let Some(v) = section.get("error_formatting") {
         res_conf.error_formatting = Some(v.to_owned());
     }
+    if let Some(v) = section.get("file_global_formatting") {
+        res_conf.file_global_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_trace_formatting") {
+        res_conf.file_trace_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_debug_formatting") {
+        res_conf.file_debug_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_info_formatting") {
+        res_conf.file_info_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_warn_formatting") {
+        res_conf.file_warn_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_error_formatting") {
+        res_conf.file_error_formatting = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("level_name_trace") {
+        res_conf.level_name_trace = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("level_name_debug") {
+        res_conf.level_name_debug = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("level_name_info") {
+        res_conf.level_name_info = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("level_name_warn") {
+        res_conf.level_name_warn = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("level_name_error") {
+        res_conf.level_name_error = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("trace_symbol") {
+        res_conf.trace_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("debug_symbol") {
+        res_conf.debug_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("info_symbol") {
+        res_conf.info_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("warn_symbol") {
+        res_conf.warn_symbol = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("error_symbol") {
+        res_conf.error_symbol = Some(v.to_owned());
+    }
 
     if let Some(v) = section.get("file") {
         res_conf.file_name = Some(v.to_owned());
@@ -354,6 +866,43 @@ fn parse_config_from_ini_file(path: &str) -> Result<ConfigForSerde, ReadFromConf
     if let Some(v) = section.get("archive_dir") {
         res_conf.archive_dir = Some(v.to_owned());
     }
+    if let Some(v) = section.get("archive_max_total_size") {
+        res_conf.archive_max_total_size = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("archive_name_format") {
+        res_conf.archive_name_format = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("archive_subdir_format") {
+        res_conf.archive_subdir_format = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("archive_mode") {
+        res_conf.archive_mode = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("rotation_mode") {
+        res_conf.rotation_mode = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("rotate_on_startup") {
+        res_conf.rotate_on_startup = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("purge_on_startup") {
+        res_conf.purge_on_startup = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("file_permissions") {
+        res_conf.file_permissions = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("allowed_file_extensions") {
+        let extensions = v.split(',').map(|ext| ext.trim().to_string()).collect();
+        res_conf.allowed_file_extensions = Some(extensions);
+    }
+    if let Some(v) = section.get("syslog_addr") {
+        res_conf.syslog_addr = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("syslog_facility") {
+        res_conf.syslog_facility = Some(v.to_owned());
+    }
+    if let Some(v) = section.get("syslog_app_name") {
+        res_conf.syslog_app_name = Some(v.to_owned());
+    }
     if let Some(v) = section.get("rotations") {
         let mut rots = Vec::<String>::new();
         if !v.contains(',') {
@@ -4,7 +4,11 @@ use crate::logger;
 use thiserror::Error;
 
 use super::{
-    file_handler::file_manager::FileManagerFromStringError, formatter::ParseStringToWrappersError,
+    file_handler::{
+        archive_name::ArchiveNameFormatError,
+        file_manager::{FileManagerFromStringError, RotationParseError},
+    },
+    formatter::ParseStringToWrappersError,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -14,6 +18,8 @@ pub enum AccessError {
     LoadConfig,
     #[error("file isn’t set")]
     FileNotSet,
+    #[error("no file with the given id")]
+    UnknownFile,
 }
 
 #[derive(Error, Debug)]
@@ -24,6 +30,18 @@ pub enum SetFileError {
     UnableToLoadFromString(FileManagerFromStringError),
     #[error("the file isn't set")]
     FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
+    #[error("the log files aren't supported on this target")]
+    UnsupportedTarget,
+}
+
+#[derive(Error, Debug)]
+pub enum RemoveFileError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("no file with the given id")]
+    UnknownFile,
 }
 
 #[derive(Error, Debug)]
@@ -32,6 +50,8 @@ pub enum SetCompressionError {
     UnableToLoadConfig,
     #[error("a file isn't set")]
     FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
     #[error("incorrect compression value")]
     IncorrectCompressionValue,
 }
@@ -42,8 +62,50 @@ pub enum AddRotationError {
     UnableToLoadConfig,
     #[error("a file isn't set")]
     FileIsntSet,
-    #[error("incorrect format given")]
-    IncorrectFormatGiven,
+    #[error("no file with the given id")]
+    UnknownFile,
+    #[error(
+        "incorrect format given: {0} (expected e.g. \"1 day\", \"5 minutes\", \"12:30\", \"daily\", \"hourly\", \"sunday 03:00\", \"monthly 1 00:00\", \"yearly\" or \"500 MB\")"
+    )]
+    IncorrectFormatGiven(RotationParseError),
+    #[error("incorrect value given: {0}")]
+    IncorrectValueGiven(RotationParseError),
+}
+
+#[derive(Error, Debug)]
+pub enum RemoveRotationError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("a file isn't set")]
+    FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
+    #[error("incorrect format given: {0}")]
+    IncorrectFormatGiven(RotationParseError),
+    #[error("no such rotation is configured")]
+    RotationNotFound,
+}
+
+#[derive(Error, Debug)]
+pub enum ListRotationsError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("a file isn't set")]
+    FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
+}
+
+#[derive(Error, Debug)]
+pub enum RotateError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("a file isn't set")]
+    FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
+    #[error("unable to rotate the file: {0}")]
+    UnableToRotate(String),
 }
 
 #[derive(Error, Debug)]
@@ -52,6 +114,40 @@ pub enum SetLogLevelError {
     UnableToLoadConfig,
 }
 #[derive(Error, Debug)]
+pub enum SetFileBufferingError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("a file isn't set")]
+    FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
+    #[error("unable to write the buffered records: {0}")]
+    UnableToWriteBuffer(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetFileSyncError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("a file isn't set")]
+    FileIsntSet,
+    #[error("no file with the given id")]
+    UnknownFile,
+    #[error("unable to sync the file: {0}")]
+    UnableToSync(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetOutputLevelError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+#[derive(Error, Debug)]
+pub enum SetStderrThresholdError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+#[derive(Error, Debug)]
 pub enum SetPrintToTerminalError {
     #[error("unable to load config")]
     UnableToLoadConfig,
@@ -63,14 +159,316 @@ pub enum SetColorizedError {
     UnableToLoadConfig,
 }
 
+#[derive(Error, Debug)]
+pub enum SetColorWhenError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetMaxMessageLenError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetMultilineError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetAutoLevelColorsError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetTimePrecisionError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetZeroPaddingError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetTimezoneError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect offset: {0} seconds, it must be strictly between -86400 and 86400")]
+    IncorrectOffset(i32),
+}
+
+#[derive(Error, Debug)]
+pub enum SetLevelNameError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetLevelSymbolError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetLevelColorError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect color given: {0}")]
+    IncorrectColorGiven(String),
+}
+
+#[derive(Error, Debug)]
+pub enum RegisterPlaceholderError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect placeholder name given: {0}")]
+    IncorrectNameGiven(String),
+}
+
+#[derive(Error, Debug)]
+pub enum SetContextError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("the key of a context field can't be empty")]
+    EmptyKey,
+}
+
+#[derive(Error, Debug)]
+pub enum SetFormatterError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum AddSinkError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum RemoveSinkError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("no sink with the given id")]
+    UnknownSink,
+}
+
+#[derive(Error, Debug)]
+pub enum SetRingBufferError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("the capacity of the ring buffer must be greater than zero")]
+    ZeroCapacity,
+}
+
+#[derive(Error, Debug)]
+pub enum SetRingBufferLevelError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetTcpSinkError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect address given, expected host:port: {0}")]
+    IncorrectAddressGiven(String),
+    #[error("unable to start the sending thread: {0}")]
+    UnableToStartThread(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetSyslogError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("unable to open a socket to the syslog server: {0}")]
+    UnableToOpenSocket(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetAsyncError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("the capacity of the queue must be greater than zero")]
+    ZeroCapacity,
+    #[error("unable to start the writer thread: {0}")]
+    UnableToStartThread(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetOverflowPolicyError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetWebhookError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect url given, expected an http:// or https:// url: {0}")]
+    IncorrectUrlGiven(String),
+    #[error("unable to start the posting thread: {0}")]
+    UnableToStartThread(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetSinkError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("unable to register the event source: {0}")]
+    UnableToRegisterSource(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetOutputFormatError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetWriterError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
 #[derive(Error, Debug)]
 pub enum SetLevelFormattingError {
     #[error("unable to load config")]
     UnableToLoadConfig,
-    #[error("incorrect formatting")] // TODO!
+    #[error("incorrect formatting: {0}")]
     IncorrectFormatGiven(ParseStringToWrappersError),
 }
 
+#[derive(Error, Debug)]
+pub enum SetArchiveRetentionError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect age given, expected a period like \"30 day\"")]
+    IncorrectAgeGiven,
+}
+
+#[derive(Error, Debug)]
+pub enum SetRotateOnStartupError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetAllowedFileExtensionsError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect extension given: {0}")]
+    IncorrectExtensionGiven(String),
+}
+
+#[derive(Error, Debug)]
+pub enum SetFilePermissionsError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect mode given, expected permission bits like 0o640")]
+    IncorrectModeGiven,
+    #[error("the file permissions are only supported on unix")]
+    UnsupportedPlatform,
+}
+
+#[derive(Error, Debug)]
+pub enum SetPurgeOnStartupError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect age given, expected a period like \"7 day\"")]
+    IncorrectAgeGiven,
+}
+
+#[derive(Error, Debug)]
+pub enum PurgeError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("a file isn't set")]
+    FileIsntSet,
+    #[error("incorrect age given, expected a period like \"7 day\"")]
+    IncorrectAgeGiven,
+    #[error("the name of a file pattern has no fixed text to find its old files by")]
+    NoFixedTextInPattern,
+    #[error("unable to remove the old log files: {0}")]
+    UnableToRemoveFiles(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiveMaxTotalSizeError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect size given, expected a size like \"200 MB\"")]
+    IncorrectSizeGiven,
+}
+
+#[derive(Error, Debug)]
+pub enum PurgeArchivesError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("unable to remove the old archives: {0}")]
+    UnableToRemoveArchives(#[from] std::io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiverError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiveNameFormatError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect archive name format: {0}")]
+    IncorrectFormatGiven(#[from] ArchiveNameFormatError),
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiveSubdirFormatError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("incorrect archive subdirectory format: {0}")]
+    IncorrectFormatGiven(#[from] ArchiveNameFormatError),
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiveModeError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetRotationModeError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiveChecksumError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum SetArchiveHandlerError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum ListArchivesError {
+    #[error("unable to load config")]
+    UnableToLoadConfig,
+    #[error("unable to read the archive dir: {0}")]
+    UnableToReadArchiveDir(#[from] std::io::Error),
+}
+
 #[derive(Error, Debug)]
 pub enum SetArchiveDirError {
     #[error("unable to load config")]
@@ -85,11 +483,43 @@ impl From<ParseStringToWrappersError> for SetLevelFormattingError {
     }
 }
 
+impl From<AccessError> for SetFileBufferingError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::LoadConfig => SetFileBufferingError::UnableToLoadConfig,
+            AccessError::FileNotSet => SetFileBufferingError::FileIsntSet,
+            AccessError::UnknownFile => SetFileBufferingError::UnknownFile,
+        }
+    }
+}
+
+impl From<AccessError> for SetFileSyncError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::LoadConfig => SetFileSyncError::UnableToLoadConfig,
+            AccessError::FileNotSet => SetFileSyncError::FileIsntSet,
+            AccessError::UnknownFile => SetFileSyncError::UnknownFile,
+        }
+    }
+}
+
 impl From<AccessError> for SetCompressionError {
     fn from(e: AccessError) -> Self {
         match e {
             AccessError::LoadConfig => SetCompressionError::UnableToLoadConfig,
             AccessError::FileNotSet => SetCompressionError::FileIsntSet,
+            AccessError::UnknownFile => SetCompressionError::UnknownFile,
+        }
+    }
+}
+
+impl From<FileManagerFromStringError> for SetFileError {
+    fn from(e: FileManagerFromStringError) -> Self {
+        match e {
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            FileManagerFromStringError::UnsupportedTarget => SetFileError::UnsupportedTarget,
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            e => SetFileError::UnableToLoadFromString(e),
         }
     }
 }
@@ -99,6 +529,7 @@ impl From<AccessError> for SetFileError {
         match e {
             AccessError::LoadConfig => SetFileError::UnableToLoadConfig,
             AccessError::FileNotSet => SetFileError::FileIsntSet,
+            AccessError::UnknownFile => SetFileError::UnknownFile,
         }
     }
 }
@@ -108,6 +539,37 @@ impl From<AccessError> for AddRotationError {
         match e {
             AccessError::LoadConfig => AddRotationError::UnableToLoadConfig,
             AccessError::FileNotSet => AddRotationError::FileIsntSet,
+            AccessError::UnknownFile => AddRotationError::UnknownFile,
+        }
+    }
+}
+
+impl From<AccessError> for RemoveRotationError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::LoadConfig => RemoveRotationError::UnableToLoadConfig,
+            AccessError::FileNotSet => RemoveRotationError::FileIsntSet,
+            AccessError::UnknownFile => RemoveRotationError::UnknownFile,
+        }
+    }
+}
+
+impl From<AccessError> for ListRotationsError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::LoadConfig => ListRotationsError::UnableToLoadConfig,
+            AccessError::FileNotSet => ListRotationsError::FileIsntSet,
+            AccessError::UnknownFile => ListRotationsError::UnknownFile,
+        }
+    }
+}
+
+impl From<AccessError> for RotateError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::LoadConfig => RotateError::UnableToLoadConfig,
+            AccessError::FileNotSet => RotateError::FileIsntSet,
+            AccessError::UnknownFile => RotateError::UnknownFile,
         }
     }
 }
@@ -130,12 +592,30 @@ pub enum ReadFromConfigFileError {
     #[error("failed to set log level: {0}")]
     SetLogLevel(#[from] logger::set_errors::SetLogLevelError),
 
+    #[error("failed to set an output level: {0}")]
+    SetOutputLevel(#[from] logger::set_errors::SetOutputLevelError),
+
+    #[error("failed to set the stderr threshold: {0}")]
+    SetStderrThreshold(#[from] logger::set_errors::SetStderrThresholdError),
+
     #[error("failed to set print_to_terminal: {0}")]
     SetPrintToTerminal(#[from] logger::set_errors::SetPrintToTerminalError),
 
     #[error("failed to set colorized: {0}")]
     SetColorized(#[from] logger::set_errors::SetColorizedError),
 
+    #[error("failed to set color_when: {0}")]
+    SetColorWhen(#[from] logger::set_errors::SetColorWhenError),
+
+    #[error("failed to set an output format: {0}")]
+    SetOutputFormat(#[from] logger::set_errors::SetOutputFormatError),
+
+    #[error("failed to set a level name: {0}")]
+    SetLevelName(#[from] logger::set_errors::SetLevelNameError),
+
+    #[error("failed to set a level symbol: {0}")]
+    SetLevelSymbol(#[from] logger::set_errors::SetLevelSymbolError),
+
     #[error("failed to set global formatting: {0}")]
     SetLevelFormatting(#[from] logger::set_errors::SetLevelFormattingError),
 
@@ -150,10 +630,42 @@ pub enum ReadFromConfigFileError {
 
     #[error("failed to set archive dir: {0}")]
     SetArchiveDirError(#[from] logger::set_errors::SetArchiveDirError),
+
+    #[error("failed to set rotate_on_startup: {0}")]
+    SetRotateOnStartup(#[from] logger::set_errors::SetRotateOnStartupError),
+
+    #[error("failed to set purge_on_startup: {0}")]
+    SetPurgeOnStartup(#[from] logger::set_errors::SetPurgeOnStartupError),
+
+    #[error("failed to set file_permissions: {0}")]
+    SetFilePermissions(#[from] logger::set_errors::SetFilePermissionsError),
+
+    #[error("failed to set the allowed file extensions: {0}")]
+    SetAllowedFileExtensions(#[from] logger::set_errors::SetAllowedFileExtensionsError),
+
+    #[error("failed to set the maximal total size of the archives: {0}")]
+    SetArchiveMaxTotalSize(#[from] logger::set_errors::SetArchiveMaxTotalSizeError),
+
+    #[error("failed to set the archive name format: {0}")]
+    SetArchiveNameFormat(#[from] logger::set_errors::SetArchiveNameFormatError),
+
+    #[error("failed to set the archive subdirectory format: {0}")]
+    SetArchiveSubdirFormat(#[from] logger::set_errors::SetArchiveSubdirFormatError),
+
+    #[error("failed to set the archive mode: {0}")]
+    SetArchiveMode(#[from] logger::set_errors::SetArchiveModeError),
+
+    #[error("failed to set the rotation mode: {0}")]
+    SetRotationMode(#[from] logger::set_errors::SetRotationModeError),
+
+    #[error("failed to set syslog: {0}")]
+    SetSyslog(#[from] logger::set_errors::SetSyslogError),
 }
 
 #[derive(Debug, Error)]
 pub enum ParseConfigError {
     #[error("incorrect value given")]
     IncorrectValue,
+    #[error("the `{0}` feature of loggit is required")]
+    FeatureRequired(&'static str),
 }
@@ -0,0 +1,217 @@
+//! Sending of the logs to a syslog server over UDP (behind the `syslog` feature).
+//!
+//! The records are formatted per [RFC 5424](https://www.rfc-editor.org/rfc/rfc5424):
+//! `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA MSG`, one datagram each.
+//! The structured data and the message id aren't used (`-`).
+
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::{helper, Level, LogRecord};
+
+/// Syslog facility of the records, see [SyslogConfig].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Facility {
+    Kern,
+    #[default]
+    User,
+    Mail,
+    Daemon,
+    Auth,
+    Syslog,
+    Lpr,
+    News,
+    Uucp,
+    Cron,
+    AuthPriv,
+    Ftp,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+impl Facility {
+    /// Returns the numerical code of the facility.
+    pub fn code(&self) -> u8 {
+        match self {
+            Facility::Kern => 0,
+            Facility::User => 1,
+            Facility::Mail => 2,
+            Facility::Daemon => 3,
+            Facility::Auth => 4,
+            Facility::Syslog => 5,
+            Facility::Lpr => 6,
+            Facility::News => 7,
+            Facility::Uucp => 8,
+            Facility::Cron => 9,
+            Facility::AuthPriv => 10,
+            Facility::Ftp => 11,
+            Facility::Local0 => 16,
+            Facility::Local1 => 17,
+            Facility::Local2 => 18,
+            Facility::Local3 => 19,
+            Facility::Local4 => 20,
+            Facility::Local5 => 21,
+            Facility::Local6 => 22,
+            Facility::Local7 => 23,
+        }
+    }
+    /// Parses the name of a facility as used by syslog: `user`, `daemon`, `local0`, ...
+    pub fn from_name(name: &str) -> Option<Facility> {
+        let facility = match name.to_lowercase().as_str() {
+            "kern" => Facility::Kern,
+            "user" => Facility::User,
+            "mail" => Facility::Mail,
+            "daemon" => Facility::Daemon,
+            "auth" => Facility::Auth,
+            "syslog" => Facility::Syslog,
+            "lpr" => Facility::Lpr,
+            "news" => Facility::News,
+            "uucp" => Facility::Uucp,
+            "cron" => Facility::Cron,
+            "authpriv" => Facility::AuthPriv,
+            "ftp" => Facility::Ftp,
+            "local0" => Facility::Local0,
+            "local1" => Facility::Local1,
+            "local2" => Facility::Local2,
+            "local3" => Facility::Local3,
+            "local4" => Facility::Local4,
+            "local5" => Facility::Local5,
+            "local6" => Facility::Local6,
+            "local7" => Facility::Local7,
+            _ => return None,
+        };
+        Some(facility)
+    }
+}
+
+/// Settings of the syslog output, see [set_syslog](crate::logger::set_syslog).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogConfig {
+    /// Address of the syslog server, `host:port`
+    pub addr: String,
+    pub facility: Facility,
+    /// Name of the application in the records, `-` if empty
+    pub app_name: String,
+    /// Maximal size of a datagram in bytes, the longer messages are truncated
+    pub max_len: usize,
+}
+
+impl Default for SyslogConfig {
+    /// The local syslog server (`127.0.0.1:514`), the `user` facility, the name of the executable
+    /// and datagrams of 2048 bytes at most.
+    fn default() -> Self {
+        let app_name = std::env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        Self {
+            addr: "127.0.0.1:514".to_string(),
+            facility: Facility::User,
+            app_name,
+            max_len: 2048,
+        }
+    }
+}
+
+/// Socket connected to the syslog server.
+#[derive(Clone)]
+pub(crate) struct SyslogSink {
+    socket: Arc<UdpSocket>,
+    config: Arc<SyslogConfig>,
+    /// Set after a failed send, so the failures are reported once until a send succeeds
+    failed: Arc<AtomicBool>,
+}
+
+impl SyslogSink {
+    /// Resolves the address of the server and opens a socket to it.
+    pub(crate) fn open(config: SyslogConfig) -> std::io::Result<SyslogSink> {
+        let addr = config.addr.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "the address can't be resolved",
+            )
+        })?;
+        let local: SocketAddr = match addr {
+            SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+            SocketAddr::V6(_) => ([0u16; 8], 0).into(),
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(addr)?;
+        Ok(SyslogSink {
+            socket: Arc::new(socket),
+            config: Arc::new(config),
+            failed: Arc::new(AtomicBool::new(false)),
+        })
+    }
+    /// Sends the record as one datagram, the failures are reported once.
+    pub(crate) fn send(&self, record: &LogRecord) {
+        let datagram = format_record(record, &self.config);
+        match self.socket.send(datagram.as_bytes()) {
+            Ok(_) => self.failed.store(false, Ordering::Relaxed),
+            Err(e) => {
+                if !self.failed.swap(true, Ordering::Relaxed) {
+                    eprintln!("Couldn't send a log to the syslog server: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the syslog severity of the level: ERROR is `err` (3), WARN `warning` (4), INFO `info`
+/// (6), DEBUG and TRACE `debug` (7).
+pub(crate) fn severity(level: Level) -> u8 {
+    match level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+/// Returns the value of a header field: printable ASCII without spaces, `-` if empty.
+fn header_field(value: &str, max_len: usize) -> String {
+    let value: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    match value.is_empty() {
+        true => "-".to_string(),
+        false => value,
+    }
+}
+
+/// Formats the record per RFC 5424, truncating the message to fit in `config.max_len` bytes.
+pub(crate) fn format_record(record: &LogRecord, config: &SyslogConfig) -> String {
+    let pri = config.facility.code() * 8 + severity(record.level);
+    let timestamp = chrono::DateTime::<chrono::Utc>::from(record.timestamp)
+        .to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+    let header = format!(
+        "<{}>1 {} {} {} {} - - ",
+        pri,
+        timestamp,
+        header_field(helper::get_hostname(), 255),
+        header_field(&config.app_name, 48),
+        std::process::id()
+    );
+    let message = &record.message;
+    if header.len() + message.len() <= config.max_len {
+        return header + message;
+    }
+    // the marker's length is at most the one with the length of the whole message
+    let marker_len = format!("… [truncated {} bytes]", message.len()).len();
+    let available = config
+        .max_len
+        .saturating_sub(header.len() + marker_len)
+        .max(1);
+    header + &helper::truncate_message(message, available)
+}
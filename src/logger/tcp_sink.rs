@@ -0,0 +1,121 @@
+//! Shipping of the logs to a remote collector over TCP (behind the `tcp` feature).
+//!
+//! The logging call only queues the rendered record, a background thread owns the connection:
+//! it connects on the first record, writes the queued records one line each and reconnects with
+//! an exponential backoff when the connection fails. The records queued while the collector is
+//! unreachable are kept up to [`QUEUE_CAPACITY`], the oldest ones are dropped after that.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::net::TcpStream;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+/// Maximal number of records waiting to be sent
+pub(crate) const QUEUE_CAPACITY: usize = 1024;
+const MIN_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Handle of the background thread sending the records to the collector, the thread stops once
+/// every handle is dropped.
+#[derive(Clone)]
+pub(crate) struct TcpSink {
+    sender: SyncSender<String>,
+    /// Set while records are dropped because the queue is full, so it's reported once
+    dropping: Arc<AtomicBool>,
+}
+
+impl TcpSink {
+    /// Starts the background thread sending the records to `addr` (`host:port`), the connection
+    /// is opened with the first record.
+    pub(crate) fn start(addr: &str) -> std::io::Result<TcpSink> {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let addr = addr.to_string();
+        std::thread::Builder::new()
+            .name("loggit-tcp".to_string())
+            .spawn(move || run(addr, receiver))?;
+        Ok(TcpSink {
+            sender,
+            dropping: Arc::new(AtomicBool::new(false)),
+        })
+    }
+    /// Queues a line without waiting, it is dropped if the queue is full.
+    pub(crate) fn send(&self, line: String) {
+        match self.sender.try_send(line) {
+            Ok(_) => self.dropping.store(false, Ordering::Relaxed),
+            Err(TrySendError::Full(_)) => {
+                if !self.dropping.swap(true, Ordering::Relaxed) {
+                    eprintln!(
+                        "The TCP log queue is full, the next logs are dropped until it empties!"
+                    );
+                }
+            }
+            Err(TrySendError::Disconnected(_)) => (),
+        }
+    }
+}
+
+/// Body of the background thread.
+fn run(addr: String, receiver: Receiver<String>) {
+    let mut stream: Option<TcpStream> = None;
+    let mut pending: VecDeque<String> = VecDeque::new();
+    let mut backoff = MIN_BACKOFF;
+    let mut next_attempt = Instant::now();
+    // the failures are reported once until the connection succeeds again
+    let mut reported = false;
+    loop {
+        // waits for a record, or for the next attempt to connect if some records are pending
+        let received = match pending.is_empty() {
+            true => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            false => receiver.recv_timeout(next_attempt.saturating_duration_since(Instant::now())),
+        };
+        match received {
+            Ok(line) => {
+                if pending.len() == QUEUE_CAPACITY {
+                    pending.pop_front();
+                }
+                pending.push_back(line);
+            }
+            Err(RecvTimeoutError::Timeout) => (),
+            // the sink was removed
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        if stream.is_none() && Instant::now() >= next_attempt {
+            match TcpStream::connect(&addr) {
+                Ok(s) => {
+                    stream = Some(s);
+                    backoff = MIN_BACKOFF;
+                    reported = false;
+                }
+                Err(e) => {
+                    if !reported {
+                        eprintln!("Couldn't connect to the log collector {}: {}", addr, e);
+                        reported = true;
+                    }
+                    next_attempt = Instant::now() + backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+        if let Some(s) = stream.as_mut() {
+            while let Some(line) = pending.front() {
+                if let Err(e) = s.write_all(line.as_bytes()) {
+                    if !reported {
+                        eprintln!("Couldn't send a log to the collector {}: {}", addr, e);
+                        reported = true;
+                    }
+                    // reconnects right away, the backoff applies if it fails
+                    stream = None;
+                    next_attempt = Instant::now();
+                    break;
+                }
+                pending.pop_front();
+            }
+        }
+    }
+}
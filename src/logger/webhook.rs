@@ -0,0 +1,169 @@
+//! Posting of the error logs to a webhook (behind the `http` feature).
+//!
+//! The logging call only queues a small alert, a background thread posts them as JSON: at most
+//! one request per [`MIN_INTERVAL`], the alerts emitted in the meantime are coalesced into the
+//! `suppressed` count of the next request. So a burst of errors produces a few messages in the
+//! chat, not hundreds.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use crate::{helper, Level, LogRecord};
+
+/// Minimal time between two requests
+pub(crate) const MIN_INTERVAL: Duration = Duration::from_secs(10);
+/// Maximal number of alerts waiting to be coalesced, the next ones are only counted
+const QUEUE_CAPACITY: usize = 64;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The fields of a record sent to the webhook
+struct Alert {
+    timestamp: String,
+    level: String,
+    message: String,
+    location: String,
+}
+
+impl Alert {
+    fn from_record(record: &LogRecord) -> Alert {
+        let timestamp = chrono::DateTime::<chrono::Utc>::from(record.timestamp).fixed_offset();
+        Alert {
+            timestamp: helper::format_iso8601(&timestamp),
+            level: record.level.to_string(),
+            message: record.message.clone(),
+            location: format!("{}:{}", record.file, record.line),
+        }
+    }
+    /// Returns the body of the request. `text` (Slack) and `content` (Discord) hold a readable
+    /// summary, the other fields are for the other receivers.
+    fn to_json(&self, suppressed: u64) -> String {
+        let mut summary = format!("[{}] {} ({})", self.level, self.message, self.location);
+        if suppressed > 0 {
+            summary.push_str(&format!(" (+{} more)", suppressed));
+        }
+        serde_json::json!({
+            "text": summary,
+            "content": summary,
+            "timestamp": self.timestamp,
+            "level": self.level,
+            "message": self.message,
+            "location": self.location,
+            "suppressed": suppressed,
+        })
+        .to_string()
+    }
+}
+
+/// Handle of the background thread posting the alerts, the thread stops once every handle is
+/// dropped.
+#[derive(Clone)]
+pub(crate) struct WebhookSink {
+    sender: SyncSender<Alert>,
+    min_level: Level,
+    /// Alerts that didn't fit in the queue, added to the next `suppressed` count
+    overflow: Arc<AtomicU64>,
+}
+
+impl WebhookSink {
+    /// Starts the background thread posting the records of `min_level` and above to `url`, at
+    /// most one request per `interval`.
+    pub(crate) fn start(
+        url: &str,
+        min_level: Level,
+        interval: Duration,
+    ) -> std::io::Result<WebhookSink> {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let overflow = Arc::new(AtomicU64::new(0));
+        let url = url.to_string();
+        let thread_overflow = overflow.clone();
+        std::thread::Builder::new()
+            .name("loggit-webhook".to_string())
+            .spawn(move || run(url, interval, receiver, thread_overflow))?;
+        Ok(WebhookSink {
+            sender,
+            min_level,
+            overflow,
+        })
+    }
+    /// Queues the record without waiting if its level is high enough.
+    pub(crate) fn send(&self, record: &LogRecord) {
+        if record.level < self.min_level {
+            return;
+        }
+        match self.sender.try_send(Alert::from_record(record)) {
+            Ok(_) | Err(TrySendError::Disconnected(_)) => (),
+            Err(TrySendError::Full(_)) => {
+                self.overflow.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Body of the background thread.
+fn run(url: String, interval: Duration, receiver: Receiver<Alert>, overflow: Arc<AtomicU64>) {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+    let mut pending: Option<Alert> = None;
+    let mut suppressed: u64 = 0;
+    let mut next_post = Instant::now();
+    // the failures are reported once until a request succeeds again
+    let mut reported = false;
+    loop {
+        // waits for an alert, or for the end of the interval if one is pending
+        let received = match pending {
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(_) => receiver.recv_timeout(next_post.saturating_duration_since(Instant::now())),
+        };
+        match received {
+            Ok(alert) => match pending {
+                None => pending = Some(alert),
+                Some(_) => suppressed += 1,
+            },
+            Err(RecvTimeoutError::Timeout) => (),
+            // the webhook was removed, the alert held back by the interval is posted at once
+            Err(RecvTimeoutError::Disconnected) => {
+                if let Some(alert) = pending.take() {
+                    suppressed += overflow.swap(0, Ordering::Relaxed);
+                    post(&agent, &url, &alert, suppressed, &mut reported);
+                }
+                return;
+            }
+        }
+
+        if Instant::now() < next_post {
+            continue;
+        }
+        if let Some(alert) = pending.take() {
+            suppressed += overflow.swap(0, Ordering::Relaxed);
+            post(&agent, &url, &alert, suppressed, &mut reported);
+            suppressed = 0;
+            next_post = Instant::now() + interval;
+        }
+    }
+}
+
+/// Posts the alert with the number of the ones coalesced into it, a failure is reported unless
+/// one already is.
+fn post(agent: &ureq::Agent, url: &str, alert: &Alert, suppressed: u64, reported: &mut bool) {
+    let res = agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&alert.to_json(suppressed));
+    match res {
+        Ok(_) => *reported = false,
+        Err(e) => {
+            // the url isn't printed, it usually contains a secret token
+            let reason = match e {
+                ureq::Error::Status(code, _) => format!("status code {}", code),
+                ureq::Error::Transport(t) => t.kind().to_string(),
+            };
+            if !*reported {
+                eprintln!("Couldn't post a log to the webhook: {}", reason);
+                *reported = true;
+            }
+        }
+    }
+}
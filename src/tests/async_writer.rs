@@ -0,0 +1,59 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::logger::async_writer::AsyncWriter;
+use crate::logger::file_handler::file_manager::FileManager;
+use crate::{stats, Config, FileId, FileOutput, Overflow};
+
+/// Writes `r0` while the file is locked, so the writer thread is stuck on it, then `r1` to `r5`
+/// in a queue of two records, and returns the written file.
+fn write_with_stalled_writer(format: &str, policy: Overflow) -> String {
+    let manager = FileManager::init_from_string(format, Config::default()).unwrap();
+    let file_name = manager.get_file_name();
+    let file = FileOutput {
+        id: FileId(0),
+        manager: Arc::new(Mutex::new(manager)),
+        levels: None,
+    };
+    let config = Arc::new(Config {
+        files: vec![file.clone()],
+        overflow_policy: policy,
+        ..Default::default()
+    });
+    let writer = AsyncWriter::start(2).unwrap();
+
+    let stalled = file.manager.lock().unwrap();
+    writer.write("r0".to_string(), vec![file.clone()], config.clone());
+    while writer.queued_records() > 0 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let dropped_before = stats().dropped_records;
+    for i in 1..=5 {
+        writer.write(format!("r{}", i), vec![file.clone()], config.clone());
+    }
+    assert_eq!(stats().dropped_records - dropped_before, 3);
+    drop(stalled);
+
+    writer.flush();
+    writer.shutdown();
+    let contents = fs::read_to_string(&file_name).unwrap();
+    let _ = fs::remove_file(&file_name);
+    contents
+}
+
+// one test, the counter is global
+#[test]
+fn drop_policies_count_the_dropped_records() {
+    let contents = write_with_stalled_writer("drop_newest_{date}_{time}.log", Overflow::DropNewest);
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[..3], ["r0", "r1", "r2"]);
+    assert!(lines[3].contains("dropped 3 records due to backpressure"));
+
+    let contents = write_with_stalled_writer("drop_oldest_{date}_{time}.log", Overflow::DropOldest);
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[..3], ["r0", "r4", "r5"]);
+    assert!(lines[3].contains("dropped 3 records due to backpressure"));
+}
@@ -1,15 +1,20 @@
-use crate::logger::file_handler::file_manager::{CompressFileError, FileManager};
+use crate::logger::file_handler::file_manager::{
+    CompressFileError, FileManager, SyncData, VerifyConstraintsRes,
+};
 use crate::Level;
-use crate::{logger, Config};
+use crate::{logger, BufferPolicy, Config, RotationMode, SyncPolicy};
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Helper to build a dummy configuration.
 /// Adjust fields as necessary if your Config struct requires more fields.
 fn dummy_config() -> Config {
     Config {
         level: Level::INFO,
-        file_manager: None,
+        files: Vec::new(),
         ..Default::default()
     }
 }
@@ -50,9 +55,9 @@ fn test_init_from_string_invalid() {
 fn test_remove_rotations() {
     let mut fm = get_dummy_file_manager();
     // Add a valid rotation
-    let added = fm.add_rotation("1 day");
+    let added = fm.add_rotation("1 day", Default::default());
     assert!(
-        added,
+        added.is_ok(),
         "Expected add_rotation to succeed for a valid rotation definition"
     );
     fm.remove_rotations();
@@ -68,13 +73,16 @@ fn test_remove_rotations() {
 fn test_add_rotation() {
     let mut fm = get_dummy_file_manager();
     // Valid rotation
-    let valid = fm.add_rotation("1 day");
-    assert!(valid, "Expected add_rotation to succeed with '1 day'");
+    let valid = fm.add_rotation("1 day", Default::default());
+    assert!(
+        valid.is_ok(),
+        "Expected add_rotation to succeed with '1 day'"
+    );
 
-    // Invalid rotation should return false.
-    let invalid = fm.add_rotation("invalid");
+    // Invalid rotation should return an error.
+    let invalid = fm.add_rotation("invalid", Default::default());
     assert!(
-        !invalid,
+        invalid.is_err(),
         "Expected add_rotation to fail with an invalid rotation string"
     );
 }
@@ -88,7 +96,7 @@ fn test_set_and_remove_compression() {
     // Remove compression
     fm.remove_compression();
     // Now, trying to compress should fail due to missing settings.
-    let result = fm.compress_file("nonexistent.txt");
+    let result = fm.compress_file("nonexistent.txt", &dummy_config());
     match result {
         Err(CompressFileError::UnableToGetCompressionSettings) => {}
         _ => panic!("Expected an error for missing compression settings after removal"),
@@ -138,7 +146,7 @@ fn test_write_log_success() {
 
     // Write a log message.
     let log_message = "Test log message";
-    let write_res = fm.write_log(log_message, config);
+    let write_res = fm.write_log(log_message, &config);
     assert!(write_res.is_ok(), "Expected write_log to succeed");
 
     // Verify the log file contains the message.
@@ -154,7 +162,11 @@ fn test_write_log_success() {
 #[test]
 fn test_compress_file() {
     let mut fm = get_dummy_file_manager();
-    let config = dummy_config();
+    // the archives are written to the archive dir of the given config
+    let config = Config {
+        archive_dir: Some("loggit_archives".into()),
+        ..dummy_config()
+    };
     // Create a new file.
     fm.create_new_file(&config)
         .expect("Expected file creation to succeed");
@@ -169,9 +181,7 @@ fn test_compress_file() {
         "Expected setting compression to succeed"
     );
 
-    assert!(logger::set_archive_dir("loggit_archives").is_ok());
-
-    let comp_res = fm.compress_file(&file_name);
+    let comp_res = fm.compress_file(&file_name, &config);
     assert!(comp_res.is_ok(), "Expected compress_file to succeed");
 
     // Check that the zip archive was created in the compression folder.
@@ -190,18 +200,21 @@ fn test_compress_file() {
 #[test]
 fn rotation_by_size_triggers_compression() {
     // 1. Set up
-    let mut fm = FileManager::init_from_string("big_{date}_{time}.log", dummy_config()).unwrap();
-    fm.add_rotation("1 KB"); // rotate when > 1024 bytes
+    let config = Config {
+        archive_dir: Some("loggit_archives".into()),
+        ..dummy_config()
+    };
+    let mut fm = FileManager::init_from_string("big_{date}_{time}.log", config.clone()).unwrap();
+    fm.add_rotation("1 KB", Default::default()).unwrap(); // rotate when > 1024 bytes
     fm.set_compression("zip"); // enable compression
-    logger::set_archive_dir("loggit_archives").unwrap();
 
     // 2. Create a starting file and write 1500 bytes (> 1 KB)
-    fm.create_new_file(&dummy_config()).unwrap();
+    fm.create_new_file(&config).unwrap();
     let file_name = fm.get_file_name();
     std::fs::write(&file_name, vec![0u8; 1500]).unwrap();
 
     // 3. Write a log line – verify_constraints() will run inside
-    let outcome = fm.write_log("hello", dummy_config()).unwrap();
+    let outcome = fm.write_log("hello", &config).unwrap();
     assert!(matches!(
         outcome,
         logger::file_handler::file_manager::VerifyConstraintsRes::NewFileCreated
@@ -218,3 +231,684 @@ fn rotation_by_size_triggers_compression() {
     let _ = std::fs::remove_file(&archived);
     let _ = std::fs::remove_dir_all("loggit_archives");
 }
+
+#[test]
+fn time_rotation_follows_the_timezone() {
+    use crate::helper;
+    use crate::logger::file_handler::file_manager::{Rotation, RotationType};
+    use crate::TimeZoneChoice;
+    use chrono::Timelike;
+
+    let timezone = TimeZoneChoice::FixedOffset(5 * 3600);
+    // a rotation two minutes from now on the UTC+5 clock
+    let target = helper::get_now(timezone) + chrono::Duration::minutes(2);
+    let rot = Rotation::init_from_rotation_type(
+        RotationType::Time(target.hour() as u8, target.minute() as u8),
+        timezone,
+    );
+    let dbg = format!("{:?}", rot);
+    let next_rotation: i64 = dbg
+        .split("next_rotation: ")
+        .nth(1)
+        .unwrap()
+        .trim_end_matches(" }")
+        .parse()
+        .unwrap();
+    let diff = next_rotation - chrono::Utc::now().timestamp();
+    assert!(
+        (60..=121).contains(&diff),
+        "expected the rotation in ~2 minutes, got {} seconds",
+        diff
+    );
+}
+
+#[test]
+fn csv_header_in_each_new_file() {
+    let config = Config {
+        file_output_format: crate::OutputFormat::Csv,
+        ..dummy_config()
+    };
+    let mut fm = FileManager::init_from_string("csv_{date}_{time}.log", config.clone()).unwrap();
+    let first_file = fm.get_file_name();
+    fm.write_log("2024-01-01T00:00:00Z,INFO,m,f.rs,1,first", &config)
+        .unwrap();
+    fm.write_log("2024-01-01T00:00:00Z,INFO,m,f.rs,2,second", &config)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&first_file).unwrap(),
+        "timestamp,level,module,file,line,message\n\
+         2024-01-01T00:00:00Z,INFO,m,f.rs,1,first\n\
+         2024-01-01T00:00:00Z,INFO,m,f.rs,2,second\n"
+    );
+
+    // a rotated file starts with the header as well, the old one is archived and deleted
+    let _ = fs::remove_file(&first_file);
+    fm.create_new_file(&config).unwrap();
+    let second_file = fm.get_file_name();
+    assert_eq!(
+        fs::read_to_string(&second_file).unwrap(),
+        "timestamp,level,module,file,line,message\n"
+    );
+
+    let _ = fs::remove_file(&first_file);
+    let _ = fs::remove_file(&second_file);
+}
+
+#[test]
+fn batched_records_survive_a_rotation() {
+    let config = dummy_config();
+    let mut fm =
+        FileManager::init_from_string("batched_{date}_{time}.log", config.clone()).unwrap();
+    fm.add_rotation("1 KB", Default::default()).unwrap();
+    // the rotation creates the archive dir, the one of the other tests
+    logger::set_archive_dir("loggit_archives").unwrap();
+    fm.set_buffer_policy(BufferPolicy::Batched {
+        max_bytes: 300,
+        max_delay: Duration::from_secs(3600),
+    })
+    .unwrap();
+    let first_file = fm.get_file_name();
+    // 11 bytes per record, the file rotates before it would be over 1024 bytes
+    let mut rotated_at = None;
+    for i in 0..100 {
+        // without compression the old file is kept
+        let res = fm.write_log(&format!("record {:03}", i), &config);
+        if matches!(res, Ok(VerifyConstraintsRes::NewFileCreated)) && rotated_at.is_none() {
+            rotated_at = Some(i);
+        }
+    }
+    fm.flush_buffer().unwrap();
+    let second_file = fm.get_file_name();
+
+    // the buffered records count in the size of the file
+    assert_eq!(rotated_at, Some(93));
+    assert_eq!(fs::metadata(&first_file).unwrap().len(), 93 * 11);
+    let mut content = fs::read_to_string(&first_file).unwrap();
+    // within the same second the new file is numbered
+    assert_ne!(second_file, first_file);
+    content += &fs::read_to_string(&second_file).unwrap();
+    let expected: String = (0..100).map(|i| format!("record {:03}\n", i)).collect();
+    assert_eq!(content, expected);
+
+    let _ = fs::remove_file(&first_file);
+    let _ = fs::remove_file(&second_file);
+}
+
+/// Counts the syncs instead of syncing the file.
+#[derive(Debug)]
+struct CountingSync(Arc<AtomicUsize>);
+
+impl SyncData for CountingSync {
+    fn sync_data(&self, _file: &fs::File) -> std::io::Result<()> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn sync_policy_decides_when_the_file_is_synced() {
+    let config = dummy_config();
+    let mut fm = FileManager::init_from_string("synced_{date}_{time}.log", config.clone()).unwrap();
+    let file_name = fm.get_file_name();
+    let syncs = Arc::new(AtomicUsize::new(0));
+    fm.set_syncer(Box::new(CountingSync(syncs.clone())));
+    let synced = || syncs.load(Ordering::SeqCst);
+
+    // never synced by default
+    fm.write_log("never", &config).unwrap();
+    assert_eq!(synced(), 0);
+
+    fm.set_sync_policy(SyncPolicy::EveryRecord).unwrap();
+    fm.write_log("every 1", &config).unwrap();
+    fm.write_log("every 2", &config).unwrap();
+    assert_eq!(synced(), 2);
+
+    // synced at the first write once the interval has passed
+    fm.set_sync_policy(SyncPolicy::Interval(Duration::from_millis(50)))
+        .unwrap();
+    fm.write_log("interval 1", &config).unwrap();
+    assert_eq!(synced(), 2);
+    std::thread::sleep(Duration::from_millis(60));
+    fm.write_log("interval 2", &config).unwrap();
+    assert_eq!(synced(), 3);
+    fm.write_log("interval 3", &config).unwrap();
+    assert_eq!(synced(), 3);
+    // the pending write is synced when the policy changes
+    fm.set_sync_policy(SyncPolicy::EveryRecord).unwrap();
+    assert_eq!(synced(), 4);
+
+    // a batch is synced once it is written
+    fm.set_buffer_policy(BufferPolicy::Batched {
+        max_bytes: 1024,
+        max_delay: Duration::from_secs(3600),
+    })
+    .unwrap();
+    fm.write_log("batched 1", &config).unwrap();
+    fm.write_log("batched 2", &config).unwrap();
+    assert_eq!(synced(), 4);
+    fm.flush_buffer().unwrap();
+    assert_eq!(synced(), 5);
+
+    drop(fm);
+    let _ = fs::remove_file(&file_name);
+}
+
+#[test]
+fn size_rotation_counts_the_written_bytes() {
+    let config = dummy_config();
+    let mut fm =
+        FileManager::init_from_string("counted_{date}_{time}.log", config.clone()).unwrap();
+    fm.add_rotation("1 KB", Default::default()).unwrap();
+    logger::set_archive_dir("loggit_archives").unwrap();
+    let file_name = fm.get_file_name();
+    // the content the file had when it was opened is read once from the metadata
+    fs::write(&file_name, vec![b'.'; 1000]).unwrap();
+
+    // 11 bytes per record: 1000, 1011, 1022 and then 1033 would be > 1024 bytes
+    let mut rotated_at = None;
+    for i in 0..5 {
+        // without compression the old file is kept
+        let res = fm.write_log(&format!("record {:03}", i), &config);
+        if matches!(res, Ok(VerifyConstraintsRes::NewFileCreated)) && rotated_at.is_none() {
+            rotated_at = Some(i);
+        }
+    }
+    assert_eq!(rotated_at, Some(2));
+
+    let second_file = fm.get_file_name();
+    drop(fm);
+    let _ = fs::remove_file(&file_name);
+    let _ = fs::remove_file(&second_file);
+}
+
+#[test]
+fn pid_stays_the_same_at_each_rotation() {
+    let config = dummy_config();
+    let mut fm = FileManager::init_from_string("pid_{pid}_{time}.log", config.clone()).unwrap();
+    fm.add_rotation("1 KB", Default::default()).unwrap();
+    let prefix = format!("pid_{}_", std::process::id());
+    let mut files = vec![fm.get_file_name()];
+
+    let record = "x".repeat(899);
+    for _ in 0..3 {
+        fm.write_log(&record, &config).unwrap();
+        let file = fm.get_file_name();
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    drop(fm);
+
+    assert_eq!(files.len(), 3);
+    for file in &files {
+        assert!(file.starts_with(&prefix), "{file} hasn't the pid");
+        let _ = fs::remove_file(file);
+    }
+}
+
+#[test]
+fn rotation_fills_the_num_placeholder() {
+    let config = dummy_config();
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let mut fm =
+        FileManager::init_from_string(&format!("numbered_{}_{{num:3}}.log", ts), config.clone())
+            .unwrap();
+    fm.add_rotation("1 KB", Default::default()).unwrap();
+    let mut files = vec![fm.get_file_name()];
+
+    let record = "x".repeat(899);
+    for _ in 0..3 {
+        // without compression the old file is kept and its number is taken
+        fm.write_log(&record, &config).unwrap();
+        let file = fm.get_file_name();
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    drop(fm);
+
+    let expected: Vec<String> = (1..=3)
+        .map(|num| format!("numbered_{}_{:03}.log", ts, num))
+        .collect();
+    assert_eq!(files, expected);
+    for file in &files {
+        let _ = fs::remove_file(file);
+    }
+}
+
+#[test]
+fn size_rotation_never_exceeds_the_size() {
+    let config = dummy_config();
+    let mut fm =
+        FileManager::init_from_string("bounded_{date}_{time}.log", config.clone()).unwrap();
+    fm.add_rotation("1 KB", Default::default()).unwrap();
+    let mut files = vec![fm.get_file_name()];
+
+    // 900 bytes with the new line, a second one would make the file 1800 bytes
+    let record = "x".repeat(899);
+    let huge = "y".repeat(1499);
+    for i in 0..6 {
+        // a record longer than the size goes alone to a new file
+        let mess = if i == 3 { &huge } else { &record };
+        fm.write_log(mess, &config).unwrap();
+        let file = fm.get_file_name();
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    drop(fm);
+
+    let sizes: Vec<u64> = files
+        .iter()
+        .map(|file| fs::metadata(file).unwrap().len())
+        .collect();
+    assert_eq!(sizes, vec![900, 900, 900, 1500, 900, 900]);
+    for file in &files {
+        let _ = fs::remove_file(file);
+    }
+}
+
+#[test]
+fn daily_and_hourly_rotations_follow_the_clock() {
+    use crate::logger::file_handler::file_manager::{Rotation, RotationType};
+    use crate::TimeZoneChoice;
+
+    for keyword in ["daily", "Midnight", " DAILY "] {
+        assert_eq!(
+            RotationType::try_from_string(keyword),
+            Ok(RotationType::Time(0, 0))
+        );
+    }
+    assert_eq!(
+        RotationType::try_from_string("Hourly"),
+        Ok(RotationType::Hourly)
+    );
+
+    // the next UTC midnight and the next top of the hour, not 24 hours or 1 hour from now
+    let now = chrono::Utc::now().timestamp() as u64;
+    let daily = Rotation::init_from_rotation_type(RotationType::Time(0, 0), TimeZoneChoice::Utc);
+    assert_eq!(daily.next_rotation % (24 * 60 * 60), 0);
+    assert!(daily.next_rotation > now && daily.next_rotation <= now + 24 * 60 * 60);
+    let hourly = Rotation::init_from_rotation_type(RotationType::Hourly, TimeZoneChoice::Utc);
+    assert_eq!(hourly.next_rotation % (60 * 60), 0);
+    assert!(hourly.next_rotation > now && hourly.next_rotation <= now + 60 * 60);
+
+    // once midnight has passed the file is rotated and the next one is the following midnight
+    let config = dummy_config();
+    let mut fm = FileManager::init_from_string("daily_{date}_{time}.log", config.clone()).unwrap();
+    let first_file = fm.get_file_name();
+    assert!(fm.add_rotation("daily", TimeZoneChoice::Utc).is_ok());
+    logger::set_archive_dir("loggit_archives").unwrap();
+    fm.rotations_mut()[0].next_rotation = now - 1;
+    // without compression the old file is kept
+    assert!(matches!(
+        fm.write_log("after midnight", &config),
+        Ok(VerifyConstraintsRes::NewFileCreated)
+    ));
+    let next = fm.rotations_mut()[0].next_rotation;
+    assert_eq!(next % (24 * 60 * 60), 0);
+    assert!(next > now);
+    assert!(matches!(
+        fm.write_log("same day", &config),
+        Ok(VerifyConstraintsRes::ConstraintsPassed)
+    ));
+
+    let second_file = fm.get_file_name();
+    drop(fm);
+    let _ = fs::remove_file(&first_file);
+    let _ = fs::remove_file(&second_file);
+}
+
+#[test]
+fn weekly_rotation_finds_the_next_matching_moment() {
+    use crate::logger::file_handler::file_manager::{Rotation, RotationType};
+    use chrono::{DateTime, Weekday};
+
+    let sunday_3 = RotationType::Weekly {
+        weekday: Weekday::Sun,
+        hour: 3,
+        minute: 0,
+    };
+    for text in ["sunday 03:00", "Weekly Sunday 03:00", "sun 3:00"] {
+        assert_eq!(RotationType::try_from_string(text), Ok(sunday_3));
+    }
+    for text in [
+        "funday 03:00",
+        "sunday 25:00",
+        "weekly sunday",
+        "sunday 5 MB",
+    ] {
+        assert!(RotationType::try_from_string(text).is_err(), "{}", text);
+    }
+
+    let next = |rot_type: RotationType, now: &str| -> String {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        let next = Rotation::init_at(rot_type, now).next_rotation;
+        DateTime::from_timestamp(next as i64, 0)
+            .unwrap()
+            .with_timezone(now.offset())
+            .to_rfc3339()
+    };
+    let at = |weekday, hour, minute| RotationType::Weekly {
+        weekday,
+        hour,
+        minute,
+    };
+    // 2024-01-03 is a wednesday
+    let now = "2024-01-03T10:00:00+00:00";
+    // later in the current week
+    assert_eq!(next(sunday_3, now), "2024-01-07T03:00:00+00:00");
+    assert_eq!(
+        next(at(Weekday::Wed, 10, 1), now),
+        "2024-01-03T10:01:00+00:00"
+    );
+    // earlier in the current week
+    assert_eq!(
+        next(at(Weekday::Mon, 9, 0), now),
+        "2024-01-08T09:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(Weekday::Wed, 9, 59), now),
+        "2024-01-10T09:59:00+00:00"
+    );
+    // exactly now: just rotated, the next one is in a week
+    assert_eq!(
+        next(at(Weekday::Wed, 10, 0), now),
+        "2024-01-10T10:00:00+00:00"
+    );
+    // the seconds of the current minute count
+    assert_eq!(
+        next(at(Weekday::Wed, 10, 0), "2024-01-03T09:59:30+00:00"),
+        "2024-01-03T10:00:00+00:00"
+    );
+    // on the clock of the time zone: it is already sunday at UTC+5
+    assert_eq!(
+        next(sunday_3, "2024-01-07T02:00:00+05:00"),
+        "2024-01-07T03:00:00+05:00"
+    );
+}
+
+#[test]
+fn monthly_rotation_follows_the_calendar() {
+    use crate::logger::file_handler::file_manager::{Rotation, RotationType};
+    use chrono::DateTime;
+
+    let at = |day, hour, minute| RotationType::Monthly { day, hour, minute };
+    for (text, rot_type) in [
+        ("monthly", at(1, 0, 0)),
+        ("Monthly 15", at(15, 0, 0)),
+        ("monthly 31 23:30", at(31, 23, 30)),
+    ] {
+        assert_eq!(RotationType::try_from_string(text), Ok(rot_type));
+        // rendered back into the same rotation
+        let rendered = rot_type.to_string();
+        assert_eq!(RotationType::try_from_string(&rendered), Ok(rot_type));
+    }
+    for text in [
+        "monthly 0",
+        "monthly 32",
+        "monthly first",
+        "monthly 1 25:00",
+        "monthly 1 5 MB",
+    ] {
+        assert!(RotationType::try_from_string(text).is_err(), "{}", text);
+    }
+    // `1 month` is still a period of 30 days
+    assert_eq!(
+        RotationType::try_from_string("1 month"),
+        Ok(RotationType::Period(30 * 24 * 60 * 60))
+    );
+
+    let next = |rot_type: RotationType, now: &str| -> String {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        let next = Rotation::init_at(rot_type, now).next_rotation;
+        DateTime::from_timestamp(next as i64, 0)
+            .unwrap()
+            .with_timezone(now.offset())
+            .to_rfc3339()
+    };
+    // later in the current month, then in the next one
+    assert_eq!(
+        next(at(15, 3, 0), "2024-01-10T10:00:00+00:00"),
+        "2024-01-15T03:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(1, 0, 0), "2024-01-10T10:00:00+00:00"),
+        "2024-02-01T00:00:00+00:00"
+    );
+    // exactly now: just rotated, the next one is next month
+    assert_eq!(
+        next(at(1, 0, 0), "2024-02-01T00:00:00+00:00"),
+        "2024-03-01T00:00:00+00:00"
+    );
+    // the 31st is the last day of the shorter months, february of leap years included
+    assert_eq!(
+        next(at(31, 0, 0), "2024-02-10T00:00:00+00:00"),
+        "2024-02-29T00:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(31, 0, 0), "2023-02-10T00:00:00+00:00"),
+        "2023-02-28T00:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(30, 12, 0), "2023-02-28T13:00:00+00:00"),
+        "2023-03-30T12:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(31, 0, 0), "2024-04-01T00:00:00+00:00"),
+        "2024-04-30T00:00:00+00:00"
+    );
+    // and the day doesn't drift once the months are longer again
+    assert_eq!(
+        next(at(31, 0, 0), "2024-04-30T00:00:00+00:00"),
+        "2024-05-31T00:00:00+00:00"
+    );
+    // across the year
+    assert_eq!(
+        next(at(1, 0, 0), "2024-12-15T00:00:00+00:00"),
+        "2025-01-01T00:00:00+00:00"
+    );
+    // on the clock of the time zone: it is already the 1st at UTC+5
+    assert_eq!(
+        next(at(1, 3, 0), "2024-03-01T02:00:00+05:00"),
+        "2024-03-01T03:00:00+05:00"
+    );
+}
+
+#[test]
+fn yearly_rotation_follows_the_calendar() {
+    use crate::logger::file_handler::file_manager::{Rotation, RotationType};
+    use chrono::DateTime;
+
+    let at = |month, day| RotationType::Yearly { month, day };
+    for (text, rot_type) in [
+        ("yearly", at(1, 1)),
+        ("Yearly 07-01", at(7, 1)),
+        ("yearly 2-29", at(2, 29)),
+    ] {
+        assert_eq!(RotationType::try_from_string(text), Ok(rot_type));
+        // rendered back into the same rotation
+        let rendered = rot_type.to_string();
+        assert_eq!(RotationType::try_from_string(&rendered), Ok(rot_type));
+    }
+    for text in [
+        "yearly 13-01",
+        "yearly 02-30",
+        "yearly 04-31",
+        "yearly july",
+        "yearly 07",
+    ] {
+        assert!(RotationType::try_from_string(text).is_err(), "{}", text);
+    }
+    // `1 year` is still a period of 365 days
+    assert_eq!(
+        RotationType::try_from_string("1 year"),
+        Ok(RotationType::Period(365 * 24 * 60 * 60))
+    );
+
+    let next = |rot_type: RotationType, now: &str| -> String {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        let next = Rotation::init_at(rot_type, now).next_rotation;
+        DateTime::from_timestamp(next as i64, 0)
+            .unwrap()
+            .with_timezone(now.offset())
+            .to_rfc3339()
+    };
+    // from the 31st of december to the 1st of january
+    assert_eq!(
+        next(at(1, 1), "2024-12-31T23:59:00+00:00"),
+        "2025-01-01T00:00:00+00:00"
+    );
+    // exactly now: just rotated, the next one is next year
+    assert_eq!(
+        next(at(1, 1), "2025-01-01T00:00:00+00:00"),
+        "2026-01-01T00:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(7, 1), "2025-01-01T00:00:00+00:00"),
+        "2025-07-01T00:00:00+00:00"
+    );
+    // the 29th of february is the 28th out of the leap years
+    assert_eq!(
+        next(at(2, 29), "2023-06-01T00:00:00+00:00"),
+        "2024-02-29T00:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(2, 29), "2024-02-29T10:00:00+00:00"),
+        "2025-02-28T00:00:00+00:00"
+    );
+    assert_eq!(
+        next(at(2, 29), "2027-03-01T00:00:00+00:00"),
+        "2028-02-29T00:00:00+00:00"
+    );
+    // a leap day start doesn't shift the other days
+    assert_eq!(
+        next(at(3, 1), "2024-02-29T00:00:00+00:00"),
+        "2024-03-01T00:00:00+00:00"
+    );
+    // on the clock of the time zone: it is already 2025 at UTC+5
+    assert_eq!(
+        next(at(1, 1), "2025-01-01T02:00:00+05:00"),
+        "2026-01-01T00:00:00+05:00"
+    );
+}
+
+#[test]
+fn rotate_now_starts_a_new_file_and_the_rotations_again() {
+    let config = dummy_config();
+    let mut fm =
+        FileManager::init_from_string("rotate_now_{date}_{time}.log", config.clone()).unwrap();
+    fm.add_rotation("1 hour", Default::default()).unwrap();
+    fm.write_log("before", &config).unwrap();
+    let first_file = fm.get_file_name();
+    let now = chrono::Utc::now().timestamp() as u64;
+    fm.rotations_mut()[0].next_rotation = now + 60;
+
+    // without compression nor archive dir the old file is kept
+    fm.rotate_now(&config).unwrap();
+    let second_file = fm.get_file_name();
+    assert_ne!(second_file, first_file);
+    assert!(fm.rotations_mut()[0].next_rotation >= now + 60 * 60);
+    fm.write_log("after", &config).unwrap();
+    assert_eq!(fs::read_to_string(&first_file).unwrap(), "before\n");
+    assert_eq!(fs::read_to_string(&second_file).unwrap(), "after\n");
+
+    drop(fm);
+    let _ = fs::remove_file(&first_file);
+    let _ = fs::remove_file(&second_file);
+}
+
+#[test]
+fn typed_rotations_check_their_boundaries() {
+    use crate::logger::file_handler::file_manager::RotationParseError;
+
+    let mut fm = get_dummy_file_manager();
+    let tz = Default::default();
+    assert!(fm.add_rotation_period(Duration::from_secs(1), tz).is_ok());
+    // whole seconds only
+    assert!(fm
+        .add_rotation_period(Duration::from_millis(2500), tz)
+        .is_ok());
+    assert_eq!(
+        fm.add_rotation_period(Duration::from_millis(999), tz),
+        Err(RotationParseError::PeriodTooShort(Duration::from_millis(
+            999
+        )))
+    );
+    assert_eq!(
+        fm.add_rotation_size(0, tz),
+        Err(RotationParseError::ZeroSize)
+    );
+    assert!(fm.add_rotation_size(1, tz).is_ok());
+    assert!(fm.add_rotation_size(1536, tz).is_ok());
+    assert!(fm.add_rotation_at(0, 0, tz).is_ok());
+    assert!(fm.add_rotation_at(23, 59, tz).is_ok());
+    assert_eq!(
+        fm.add_rotation_at(24, 0, tz),
+        Err(RotationParseError::InvalidTime {
+            hour: 24,
+            minute: 0
+        })
+    );
+    assert!(fm.add_rotation_at(12, 60, tz).is_err());
+    assert_eq!(
+        fm.list_rotations(),
+        vec![
+            "1 second",
+            "2 second",
+            "0.0009765625 KB",
+            "1.5 KB",
+            "00:00",
+            "23:59"
+        ]
+    );
+}
+
+#[test]
+fn rotation_mode_decides_when_several_rotations_rotate() {
+    let record = "x".repeat(299);
+    for mode in [RotationMode::Any, RotationMode::All] {
+        let config = Config {
+            rotation_mode: mode,
+            ..dummy_config()
+        };
+        let mut fm =
+            FileManager::init_from_string("rotation_mode_{date}_{time}.log", config.clone())
+                .unwrap();
+        fm.add_rotation("1 hour", Default::default()).unwrap();
+        fm.add_rotation("1 KB", Default::default()).unwrap();
+        let mut files = vec![fm.get_file_name()];
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut write = |fm: &mut FileManager| {
+            let res = fm.write_log(&record, &config).unwrap();
+            if !files.contains(&fm.get_file_name()) {
+                files.push(fm.get_file_name());
+            }
+            matches!(res, VerifyConstraintsRes::NewFileCreated)
+        };
+
+        // the time is due, the file is small
+        assert!(!write(&mut fm));
+        fm.rotations_mut()[0].next_rotation = now - 1;
+        assert_eq!(write(&mut fm), mode == RotationMode::Any);
+        if mode == RotationMode::All {
+            // the time rotation stays due until the size is reached: 300, 600, 900 and 1200
+            assert!(!write(&mut fm));
+            assert_eq!(fm.rotations_mut()[0].next_rotation, now - 1);
+            assert!(write(&mut fm));
+        }
+        // both start again, the time is no longer due
+        assert!(fm.rotations_mut()[0].next_rotation >= now + 60 * 60);
+
+        // the size is reached, the time isn't due: the new file has the last record, then 600,
+        // 900 and 1200
+        assert!(!write(&mut fm));
+        assert!(!write(&mut fm));
+        assert_eq!(write(&mut fm), mode == RotationMode::Any);
+
+        drop(fm);
+        for file in &files {
+            let _ = fs::remove_file(file);
+        }
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
             SetCompressionError, SetFileError, SetLevelFormattingError,
         },
     },
-    Level, CONFIG,
+    ColorWhen, Level, CONFIG,
 };
 
 // RAII guard for managing a single environment variable
@@ -81,7 +81,11 @@ impl Drop for EnvVarsGuard {
 }
 
 fn config_snapshot() -> LoggerConfig {
-    CONFIG.read().expect("CONFIG should be readable").clone()
+    CONFIG
+        .read()
+        .expect("CONFIG should be readable")
+        .as_ref()
+        .clone()
 }
 
 fn cleanup_archive_dir(path_str: &str) {
@@ -114,16 +118,31 @@ const ALL_CONFIG_KEYS: &[&str] = &[
     "level",
     "print_to_terminal",
     "colorized",
+    "color_when",
     "global_formatting",
     "trace_formatting",
     "debug_formatting",
     "info_formatting",
     "warn_formatting",
     "error_formatting",
+    "level_name_trace",
+    "level_name_debug",
+    "level_name_info",
+    "level_name_warn",
+    "level_name_error",
     "file_name",
     "compression",
     "rotations",
     "archive_dir",
+    "archive_max_total_size",
+    "archive_name_format",
+    "archive_subdir_format",
+    "archive_mode",
+    "rotation_mode",
+    "rotate_on_startup",
+    "purge_on_startup",
+    "file_permissions",
+    "allowed_file_extensions",
 ];
 
 #[test]
@@ -237,19 +256,45 @@ fn env_print_to_terminal_variants() {
     ));
 }
 
+#[test]
+fn env_color_when_variants() {
+    logger::init();
+    let _guard_always = EnvVarGuard::new("color_when", "always");
+    assert!(load_config_from_env().is_ok());
+    assert_eq!(config_snapshot().color_when, ColorWhen::Always);
+    drop(_guard_always);
+
+    logger::init();
+    let _guard_invalid = EnvVarGuard::new("color_when", "rainbow");
+    let result_invalid = load_config_from_env();
+    assert!(matches!(
+        result_invalid,
+        Err(ReadFromConfigFileError::ParseError(s)) if s == "incorrect value given"
+    ));
+}
+
+#[test]
+fn env_level_names() {
+    logger::init();
+    let _guard = EnvVarGuard::new("level_name_warn", "CAUTION");
+    assert!(load_config_from_env().is_ok());
+    assert_eq!(config_snapshot().level_name(Level::WARN), "CAUTION");
+    assert_eq!(config_snapshot().level_name(Level::INFO), "INFO");
+}
+
 #[test]
 fn env_colorized_variants() {
     // true
     logger::init();
     let _guard_true = EnvVarGuard::new("colorized", "true");
     assert!(load_config_from_env().is_ok());
-    assert!(config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Auto);
 
     // false
     logger::init();
     let _guard_false = EnvVarGuard::new("colorized", "false");
     assert!(load_config_from_env().is_ok());
-    assert!(!config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Never);
 
     // invalid
     logger::init();
@@ -378,7 +423,7 @@ fn env_file_config() {
     let _guard_valid = EnvVarGuard::new("file_name", &file_pattern);
     assert!(load_config_from_env().is_ok());
     let cfg = config_snapshot();
-    assert!(cfg.file_manager.is_some());
+    assert!(cfg.file_manager().is_some());
 
     // Log something to trigger file creation
     crate::info!("Test message for env_file_config");
@@ -442,10 +487,10 @@ fn env_compression_config() {
     let _guard_comp = EnvVarGuard::new("compression", "zip");
     assert!(load_config_from_env().is_ok());
     let cfg = config_snapshot();
-    assert!(cfg.file_manager.is_some());
+    assert!(cfg.file_manager().is_some());
     // Check internal state if possible, or rely on behavior (e.g. rotation creates zip)
     // For now, just check it was accepted:
-    let fm_dbg = format!("{:?}", cfg.file_manager.as_ref().unwrap().lock().unwrap());
+    let fm_dbg = format!("{:?}", cfg.file_manager().unwrap().lock().unwrap());
     assert!(fm_dbg.contains("Zip"));
     cleanup_log_files(&file_pattern[..file_pattern.rfind('.').unwrap_or(file_pattern.len())]);
 
@@ -503,7 +548,7 @@ fn env_rotations_config() {
     assert!(load_config_from_env().is_ok());
     let fm_dbg1 = format!(
         "{:?}",
-        config_snapshot().file_manager.unwrap().lock().unwrap()
+        config_snapshot().file_manager().unwrap().lock().unwrap()
     );
     assert!(fm_dbg1.matches("Rotation { rotation_type: Period").count() == 1);
     cleanup_log_files(&file_pattern[..file_pattern.rfind('.').unwrap_or(file_pattern.len())]);
@@ -515,7 +560,7 @@ fn env_rotations_config() {
     assert!(load_config_from_env().is_ok());
     let fm_dbg2 = format!(
         "{:?}",
-        config_snapshot().file_manager.unwrap().lock().unwrap()
+        config_snapshot().file_manager().unwrap().lock().unwrap()
     );
     assert!(fm_dbg2.matches("Rotation { rotation_type:").count() == 2);
     assert!(fm_dbg2.contains("Size"));
@@ -530,7 +575,7 @@ fn env_rotations_config() {
     assert!(matches!(
         res_invalid,
         Err(ReadFromConfigFileError::AddRotation(
-            AddRotationError::IncorrectFormatGiven
+            AddRotationError::IncorrectFormatGiven(_)
         ))
     ));
     cleanup_log_files(&file_pattern[..file_pattern.rfind('.').unwrap_or(file_pattern.len())]);
@@ -554,7 +599,7 @@ fn env_rotations_config() {
     assert!(matches!(
         res_empty,
         Err(ReadFromConfigFileError::AddRotation(
-            AddRotationError::IncorrectFormatGiven
+            AddRotationError::IncorrectFormatGiven(_)
         ))
     ));
     cleanup_log_files(&file_pattern[..file_pattern.rfind('.').unwrap_or(file_pattern.len())]);
@@ -567,7 +612,7 @@ fn env_rotations_config() {
     assert!(matches!(
         res_comma,
         Err(ReadFromConfigFileError::AddRotation(
-            AddRotationError::IncorrectFormatGiven // Fails on the first empty part
+            AddRotationError::IncorrectFormatGiven(_) // Fails on the first empty part
         ))
     ));
     cleanup_log_files(&file_pattern[..file_pattern.rfind('.').unwrap_or(file_pattern.len())]);
@@ -631,7 +676,7 @@ fn env_partial_config() {
 
     let cfg = config_snapshot();
     assert_eq!(cfg.level, Level::ERROR);
-    assert!(cfg.colorized);
+    assert_eq!(cfg.color_when, ColorWhen::Auto);
     // Check a field that wasn't set, it should be default
     assert_eq!(cfg.print_to_terminal, default_config.print_to_terminal);
     assert_eq!(
@@ -15,7 +15,7 @@ use std::{
 
 use crate::{
     logger::{init, load_config_from_file, set_errors::ReadFromConfigFileError},
-    Level, CONFIG,
+    ColorWhen, Level, CONFIG,
 };
 
 use crate::logger::formatter::LogFormatter;
@@ -36,7 +36,11 @@ fn temp_env_file(contents: &str) -> PathBuf {
 
 /// Helper that returns a cloned snapshot of CONFIG after acquiring a read lock.
 fn config_snapshot() -> crate::Config {
-    CONFIG.read().expect("CONFIG should be initialised").clone()
+    CONFIG
+        .read()
+        .expect("CONFIG should be initialised")
+        .as_ref()
+        .clone()
 }
 
 #[test]
@@ -122,14 +126,14 @@ fn env_colorized_variants() {
     init();
     let p = temp_env_file("colorized=true\n");
     assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
-    assert!(config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Auto);
     fs::remove_file(p).ok();
 
     // false
     init();
     let p = temp_env_file("colorized=false\n");
     assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
-    assert!(!config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Never);
     fs::remove_file(p).ok();
 
     // invalid
@@ -191,10 +195,10 @@ fn env_file_and_compression_and_rotations() {
 
     let cfg = config_snapshot();
     assert!(
-        cfg.file_manager.is_some(),
+        cfg.file_manager().is_some(),
         "file_manager should be configured"
     );
-    let fm_dbg = format!("{:?}", cfg.file_manager.as_ref().unwrap());
+    let fm_dbg = format!("{:?}", cfg.file_manager().unwrap());
     assert!(
         fm_dbg.contains("rotation: ["),
         "rotations should be present"
@@ -263,3 +267,18 @@ fn env_missing_file() {
         Err(ReadFromConfigFileError::ReadFileError(_))
     ));
 }
+
+#[test]
+fn env_rotate_on_startup() {
+    init();
+    let p = temp_env_file("rotate_on_startup=true\n");
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(config_snapshot().rotate_on_startup);
+    fs::remove_file(p).ok();
+
+    init();
+    let p = temp_env_file("rotate_on_startup=maybe\n");
+    let res = load_config_from_file(p.to_str().unwrap());
+    assert!(matches!(res, Err(ReadFromConfigFileError::ParseError(_))));
+    fs::remove_file(p).ok();
+}
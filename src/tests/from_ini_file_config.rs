@@ -7,7 +7,7 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::logger::formatter::LogFormatter;
+use crate::logger::formatter::{LogColor, LogFormatter};
 use crate::Config as LoggerConfig;
 use crate::{
     logger::{
@@ -18,7 +18,7 @@ use crate::{
             SetPrintToTerminalError,
         },
     },
-    Level, CONFIG,
+    ColorWhen, Level, CONFIG,
 };
 
 // Helper to create a temporary INI file
@@ -40,7 +40,11 @@ fn temp_ini_file(contents: &str) -> PathBuf {
 
 // Helper to get a snapshot of the config
 fn config_snapshot() -> LoggerConfig {
-    CONFIG.read().expect("CONFIG should be readable").clone()
+    CONFIG
+        .read()
+        .expect("CONFIG should be readable")
+        .as_ref()
+        .clone()
 }
 
 // Teardown for archive directories
@@ -148,13 +152,13 @@ fn ini_colorized_variants() {
     init(); // true
     let p_true = temp_ini_file("[Config]\ncolorized=true\n");
     assert!(load_config_from_file(p_true.to_str().unwrap()).is_ok());
-    assert!(config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Auto);
     fs::remove_file(p_true).ok();
 
     init(); // false
     let p_false = temp_ini_file("[Config]\ncolorized=false\n");
     assert!(load_config_from_file(p_false.to_str().unwrap()).is_ok());
-    assert!(!config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Never);
     fs::remove_file(p_false).ok();
 
     init(); // invalid
@@ -231,11 +235,11 @@ fn ini_full_file_config_valid() {
     );
 
     let cfg = config_snapshot();
-    assert!(cfg.file_manager.is_some());
+    assert!(cfg.file_manager().is_some());
     assert_eq!(cfg.archive_dir, Some(PathBuf::from(&archive_dir_name)));
     assert!(Path::new(&archive_dir_name).is_dir());
 
-    let fm_lock = cfg.file_manager.as_ref().unwrap().lock().unwrap();
+    let fm_lock = cfg.file_manager().unwrap().lock().unwrap();
     let fm_dbg = format!("{:?}", fm_lock);
     assert!(fm_dbg.contains("Zip"));
     assert!(fm_dbg.contains("Period"));
@@ -269,6 +273,25 @@ fn ini_compression_without_file_name() {
     fs::remove_file(p).ok();
 }
 
+#[test]
+fn ini_gzip_compression() {
+    init();
+    let log = format!(
+        "ini_gzip_{}.log",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    let p = temp_ini_file(&format!("[Config]\nfile={}\ncompression=gz\n", log));
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let fm_dbg = format!(
+        "{:?}",
+        config_snapshot().file_manager().unwrap().lock().unwrap()
+    );
+    assert!(fm_dbg.contains("Gzip"));
+    init();
+    fs::remove_file(p).ok();
+    fs::remove_file(log).ok();
+}
+
 #[test]
 fn ini_invalid_compression_value() {
     init();
@@ -305,7 +328,7 @@ fn ini_invalid_rotation_value() {
     assert!(matches!(
         res,
         Err(ReadFromConfigFileError::AddRotation(
-            AddRotationError::IncorrectFormatGiven
+            AddRotationError::IncorrectFormatGiven(_)
         ))
     ));
     fs::remove_file(p).ok();
@@ -332,7 +355,7 @@ fn ini_rotations_string_variants() {
         );
 
         let cfg = config_snapshot();
-        let fm_lock = cfg.file_manager.as_ref().unwrap().lock().unwrap();
+        let fm_lock = cfg.file_manager().unwrap().lock().unwrap();
         let fm_dbg = format!("{:?}", fm_lock);
         // A bit fragile, but count occurrences of "Rotation { rotation_type:"
         assert_eq!(
@@ -351,7 +374,7 @@ fn ini_rotations_string_variants() {
     assert!(matches!(
         res_empty_rot,
         Err(ReadFromConfigFileError::AddRotation(
-            AddRotationError::IncorrectFormatGiven
+            AddRotationError::IncorrectFormatGiven(_)
         ))
     ));
     fs::remove_file(p_empty_rot).ok();
@@ -441,7 +464,7 @@ fn ini_fields_outside_config_section_ignored() {
     assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
     let cfg = config_snapshot();
     assert_eq!(cfg.level, Level::INFO); // Default, not trace
-    assert!(cfg.colorized);
+    assert_eq!(cfg.color_when, ColorWhen::Auto);
     fs::remove_file(p).ok();
 }
 
@@ -503,3 +526,73 @@ fn ini_file_name_without_extension_in_path() {
         Err(ReadFromConfigFileError::IncorrectFileName)
     ));
 }
+
+#[test]
+fn ini_global_formatting_indexed_colors() {
+    init();
+    let fmt_txt = "<c208>[{level}]<c208> {message}";
+    let p = temp_ini_file(&format!("[Config]\nglobal_formatting={}\n", fmt_txt));
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let cfg = config_snapshot();
+    let expected = LogFormatter::parse_from_string(fmt_txt).unwrap();
+    assert_eq!(cfg.info_log_format.parts, expected.parts);
+    assert!(cfg
+        .info_log_format
+        .parts
+        .iter()
+        .any(|w| w.color == Some(LogColor::Indexed(208))));
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn ini_level_names() {
+    init();
+    let p = temp_ini_file("[Config]\nlevel_name_debug=VERBOSE\n");
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let cfg = config_snapshot();
+    assert_eq!(cfg.level_name(Level::DEBUG), "VERBOSE");
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn ini_archive_max_total_size() {
+    init();
+    let p = temp_ini_file("[Config]\narchive_max_total_size=1 GB\n");
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(
+        config_snapshot().archive_max_total_size,
+        Some(1024 * 1024 * 1024)
+    );
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn ini_archive_name_format() {
+    init();
+    let p = temp_ini_file("[Config]\narchive_name_format={name}_{num}.{ext}.gz\n");
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(config_snapshot().archive_name_format.is_some());
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn ini_weekly_rotation() {
+    init();
+    let p = temp_ini_file("[Config]\nfile=weekly_ini_app.log\nrotations=sunday 03:00, 10 MB\n");
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let cfg = config_snapshot();
+    let fm_dbg = format!("{:?}", cfg.file_manager().unwrap().lock().unwrap());
+    assert!(fm_dbg.contains("Weekly { weekday: Sun, hour: 3, minute: 0 }"));
+    assert!(fm_dbg.contains("Size"));
+    fs::remove_file(p).ok();
+    fs::remove_file("weekly_ini_app.log").ok();
+}
+
+#[test]
+fn ini_rotate_on_startup() {
+    init();
+    let p = temp_ini_file("[Config]\nrotate_on_startup=true\n");
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(config_snapshot().rotate_on_startup);
+    fs::remove_file(p).ok();
+}
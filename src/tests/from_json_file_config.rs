@@ -15,7 +15,7 @@ use crate::{
             SetPrintToTerminalError,
         },
     },
-    Level, CONFIG,
+    ArchiveMode, ColorWhen, Level, OutputFormat, RotationMode, CONFIG,
 };
 // Assuming Config is accessible as crate::Config because this file is in src/tests/
 use crate::logger::formatter::LogFormatter;
@@ -40,7 +40,11 @@ fn temp_json_file(contents: &str) -> PathBuf {
 
 // Helper to get a snapshot of the config
 fn config_snapshot() -> LoggerConfig {
-    CONFIG.read().expect("CONFIG should be readable").clone()
+    CONFIG
+        .read()
+        .expect("CONFIG should be readable")
+        .as_ref()
+        .clone()
 }
 
 // Teardown for archive directories
@@ -145,18 +149,90 @@ fn json_print_to_terminal_variants() {
     fs::remove_file(p_invalid).ok();
 }
 
+#[test]
+fn json_color_when_variants() {
+    init();
+    let p_always = temp_json_file(r#"{"color_when": "always"}"#);
+    assert!(load_config_from_file(p_always.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().color_when, ColorWhen::Always);
+    fs::remove_file(p_always).ok();
+
+    init();
+    let p_never = temp_json_file(r#"{"color_when": "Never"}"#);
+    assert!(load_config_from_file(p_never.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().color_when, ColorWhen::Never);
+    fs::remove_file(p_never).ok();
+
+    init(); // color_when wins over colorized
+    let p_both = temp_json_file(r#"{"colorized": "false", "color_when": "auto"}"#);
+    assert!(load_config_from_file(p_both.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().color_when, ColorWhen::Auto);
+    fs::remove_file(p_both).ok();
+
+    init(); // invalid
+    let p_invalid = temp_json_file(r#"{"color_when": "sometimes"}"#);
+    let res = load_config_from_file(p_invalid.to_str().unwrap());
+    assert!(
+        matches!(&res, Err(ReadFromConfigFileError::ParseError(s)) if s == "incorrect value given"),
+        "Unexpected error: {:?}",
+        res
+    );
+    fs::remove_file(p_invalid).ok();
+}
+
+#[test]
+fn json_output_format_variants() {
+    init();
+    let p = temp_json_file(r#"{"terminal_output_format": "text", "file_output_format": "JSON"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().terminal_output_format, OutputFormat::Text);
+    assert_eq!(config_snapshot().file_output_format, OutputFormat::Json);
+    fs::remove_file(p).ok();
+
+    init(); // invalid
+    let p_invalid = temp_json_file(r#"{"file_output_format": "xml"}"#);
+    let res = load_config_from_file(p_invalid.to_str().unwrap());
+    assert!(
+        matches!(&res, Err(ReadFromConfigFileError::ParseError(s)) if s == "incorrect value given"),
+        "Unexpected error: {:?}",
+        res
+    );
+    fs::remove_file(p_invalid).ok();
+}
+
+#[test]
+fn json_output_level_variants() {
+    init();
+    let p = temp_json_file(r#"{"level": "warn", "terminal_level": "INFO", "file_level": "trace"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().level, Level::WARN);
+    assert_eq!(config_snapshot().terminal_level, Some(Level::INFO));
+    assert_eq!(config_snapshot().file_level, Some(Level::TRACE));
+    fs::remove_file(p).ok();
+
+    init(); // invalid
+    let p_invalid = temp_json_file(r#"{"file_level": "verbose"}"#);
+    let res = load_config_from_file(p_invalid.to_str().unwrap());
+    assert!(
+        matches!(&res, Err(ReadFromConfigFileError::ParseError(s)) if s == "incorrect value given"),
+        "Unexpected error: {:?}",
+        res
+    );
+    fs::remove_file(p_invalid).ok();
+}
+
 #[test]
 fn json_colorized_variants() {
     init(); // true
     let p_true = temp_json_file(r#"{"colorized": "true"}"#);
     assert!(load_config_from_file(p_true.to_str().unwrap()).is_ok());
-    assert!(config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Auto);
     fs::remove_file(p_true).ok();
 
     init(); // false
     let p_false = temp_json_file(r#"{"colorized": "false"}"#);
     assert!(load_config_from_file(p_false.to_str().unwrap()).is_ok());
-    assert!(!config_snapshot().colorized);
+    assert_eq!(config_snapshot().color_when, ColorWhen::Never);
     fs::remove_file(p_false).ok();
 
     init(); // invalid
@@ -199,6 +275,66 @@ fn json_global_formatting_ok_and_bad() {
     fs::remove_file(p_bad).ok();
 }
 
+#[test]
+fn json_global_formatting_rgb_colors() {
+    init();
+    let fmt_txt = "<#ff8800>[{level}]<#ff8800> <rgb(0,128,255)>{message}<rgb(0,128,255)>";
+    let p = temp_json_file(&format!(r#"{{"global_formatting": "{}"}}"#, fmt_txt));
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+
+    let cfg = config_snapshot();
+    let expected = LogFormatter::parse_from_string(fmt_txt).unwrap();
+    assert_eq!(cfg.info_log_format.parts, expected.parts);
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_formatting_with_pid() {
+    init();
+    let fmt_txt = "[{pid}] {level}: {message}";
+    let p = temp_json_file(&format!(r#"{{"warn_formatting": "{}"}}"#, fmt_txt));
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+
+    let cfg = config_snapshot();
+    let expected = LogFormatter::parse_from_string(fmt_txt).unwrap();
+    assert_eq!(cfg.warn_log_format.parts, expected.parts);
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_file_formatting() {
+    init();
+    let global_fmt_txt = "{date} {time} [{level}] {message}";
+    let error_fmt_txt = "[{level}] {file}:{line} {message}";
+    let content = format!(
+        r#"{{
+        "global_formatting": "{{level}}: {{message}}",
+        "file_global_formatting": "{}",
+        "file_error_formatting": "{}"
+    }}"#,
+        global_fmt_txt, error_fmt_txt
+    );
+    let p = temp_json_file(&content);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+
+    let cfg = config_snapshot();
+    let terminal_fmt = LogFormatter::parse_from_string("{level}: {message}").unwrap();
+    assert_eq!(cfg.info_log_format.parts, terminal_fmt.parts);
+    assert_eq!(
+        cfg.file_log_formats[&Level::INFO].parts,
+        LogFormatter::parse_from_string(global_fmt_txt)
+            .unwrap()
+            .parts
+    );
+    assert_eq!(
+        cfg.file_log_formats[&Level::ERROR].parts,
+        LogFormatter::parse_from_string(error_fmt_txt)
+            .unwrap()
+            .parts
+    );
+    fs::remove_file(p).ok();
+}
+
 #[test]
 fn json_individual_level_formatting() {
     init();
@@ -272,7 +408,7 @@ fn json_full_file_config_valid() {
 
     let cfg = config_snapshot();
     assert!(
-        cfg.file_manager.is_some(),
+        cfg.file_manager().is_some(),
         "File manager should be configured"
     );
     assert_eq!(cfg.archive_dir, Some(PathBuf::from(&archive_dir_name)));
@@ -282,7 +418,7 @@ fn json_full_file_config_valid() {
     );
 
     // Check rotations and compression via debug output of file_manager
-    let fm_lock = cfg.file_manager.as_ref().unwrap().lock().unwrap();
+    let fm_lock = cfg.file_manager().unwrap().lock().unwrap();
     let fm_dbg = format!("{:?}", fm_lock);
     println!("{:?}", fm_dbg);
     assert!(fm_dbg.contains("Zip"));
@@ -323,6 +459,28 @@ fn json_compression_without_file_name() {
     fs::remove_file(p).ok();
 }
 
+#[test]
+fn json_gzip_compression() {
+    init();
+    let log = format!(
+        "json_gzip_{}.log",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    let p = temp_json_file(&format!(
+        r#"{{"file_name": "{}", "compression": "gzip"}}"#,
+        log
+    ));
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let fm_dbg = format!(
+        "{:?}",
+        config_snapshot().file_manager().unwrap().lock().unwrap()
+    );
+    assert!(fm_dbg.contains("Gzip"));
+    init();
+    fs::remove_file(p).ok();
+    fs::remove_file(log).ok();
+}
+
 #[test]
 fn json_invalid_compression_value() {
     init();
@@ -359,7 +517,7 @@ fn json_invalid_rotation_value() {
     assert!(matches!(
         res,
         Err(ReadFromConfigFileError::AddRotation(
-            AddRotationError::IncorrectFormatGiven
+            AddRotationError::IncorrectFormatGiven(_)
         ))
     ));
     fs::remove_file(p).ok();
@@ -377,7 +535,7 @@ fn json_empty_rotations_array() {
     );
 
     let cfg = config_snapshot();
-    let fm_lock = cfg.file_manager.as_ref().unwrap().lock().unwrap();
+    let fm_lock = cfg.file_manager().unwrap().lock().unwrap();
     let fm_dbg = format!("{:?}", fm_lock);
     // Check that rotation list is empty in debug string
     assert!(fm_dbg.contains("rotation: []"));
@@ -551,3 +709,233 @@ fn json_file_name_without_extension_in_path() {
     ));
     // No file to remove as it wasn't necessarily created by helper if path itself is bad
 }
+
+#[test]
+fn json_level_names() {
+    init();
+    let p = temp_json_file(
+        r#"{"level_name_info": "AUDIT", "level_name_error": "FATAL", "global_formatting": "{level}"}"#,
+    );
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let cfg = config_snapshot();
+    assert_eq!(cfg.level_name(Level::INFO), "AUDIT");
+    assert_eq!(cfg.level_name(Level::ERROR), "FATAL");
+    assert_eq!(cfg.level_name(Level::WARN), "WARN");
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_level_symbols() {
+    init();
+    let p = temp_json_file(r#"{"info_symbol": "ℹ", "error_symbol": "✖"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let cfg = config_snapshot();
+    assert_eq!(cfg.level_symbols[&Level::INFO], "ℹ");
+    assert_eq!(cfg.level_symbols[&Level::ERROR], "✖");
+    assert!(!cfg.level_symbols.contains_key(&Level::WARN));
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_stderr_threshold() {
+    init();
+    let p = temp_json_file(r#"{"stderr_threshold": "warn"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().stderr_threshold, Level::WARN);
+    fs::remove_file(p).ok();
+
+    init();
+    let p = temp_json_file(r#"{"stderr_threshold": "loud"}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::ParseError(_))
+    ));
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_archive_max_total_size() {
+    init();
+    let p = temp_json_file(r#"{"archive_max_total_size": "200 MB"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(
+        config_snapshot().archive_max_total_size,
+        Some(200 * 1024 * 1024)
+    );
+    fs::remove_file(p).ok();
+
+    init(); // not a size
+    let p = temp_json_file(r#"{"archive_max_total_size": "30 day"}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::SetArchiveMaxTotalSize(_))
+    ));
+    assert_eq!(config_snapshot().archive_max_total_size, None);
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_archive_name_format() {
+    init();
+    let p = temp_json_file(r#"{"archive_name_format": "{name}_{date}_{time}.zip"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(config_snapshot().archive_name_format.is_some());
+    fs::remove_file(p).ok();
+
+    init(); // a path separator
+    let p = temp_json_file(r#"{"archive_name_format": "old/{name}.zip"}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::SetArchiveNameFormat(_))
+    ));
+    assert!(config_snapshot().archive_name_format.is_none());
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_archive_subdir_format() {
+    init();
+    let p = temp_json_file(r#"{"archive_subdir_format": "{date:%Y}/{date:%m}"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(config_snapshot().archive_subdir_format.is_some());
+    fs::remove_file(p).ok();
+
+    init(); // an absolute path
+    let p = temp_json_file(r#"{"archive_subdir_format": "/tmp/{date}"}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::SetArchiveSubdirFormat(_))
+    ));
+    assert!(config_snapshot().archive_subdir_format.is_none());
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_archive_mode() {
+    init();
+    let p = temp_json_file(r#"{"archive_mode": "move"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().archive_mode, Some(ArchiveMode::Move));
+    fs::remove_file(p).ok();
+
+    init();
+    let p = temp_json_file(r#"{"archive_mode": "Leave_In_Place"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(
+        config_snapshot().archive_mode,
+        Some(ArchiveMode::LeaveInPlace)
+    );
+    fs::remove_file(p).ok();
+
+    init(); // invalid
+    let p = temp_json_file(r#"{"archive_mode": "zip"}"#);
+    let res = load_config_from_file(p.to_str().unwrap());
+    assert!(
+        matches!(&res, Err(ReadFromConfigFileError::ParseError(s)) if s == "incorrect value given"),
+        "Unexpected error: {:?}",
+        res
+    );
+    assert!(config_snapshot().archive_mode.is_none());
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_rotation_mode() {
+    init();
+    let p = temp_json_file(r#"{"rotation_mode": "ALL"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().rotation_mode, RotationMode::All);
+    fs::remove_file(p).ok();
+
+    init(); // invalid
+    let p = temp_json_file(r#"{"rotation_mode": "both"}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::ParseError(_))
+    ));
+    assert_eq!(config_snapshot().rotation_mode, RotationMode::Any);
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_weekly_rotation() {
+    init();
+    let p = temp_json_file(
+        r#"{"file_name": "weekly_app.log", "rotations": ["weekly sunday 03:00", "monday 12:30"]}"#,
+    );
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    let cfg = config_snapshot();
+    let fm_dbg = format!("{:?}", cfg.file_manager().unwrap().lock().unwrap());
+    assert!(fm_dbg.contains("Weekly { weekday: Sun, hour: 3, minute: 0 }"));
+    assert!(fm_dbg.contains("Weekly { weekday: Mon, hour: 12, minute: 30 }"));
+    fs::remove_file(p).ok();
+    fs::remove_file("weekly_app.log").ok();
+}
+
+#[test]
+fn json_rotate_on_startup() {
+    init();
+    let p = temp_json_file(r#"{"rotate_on_startup": "true"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(config_snapshot().rotate_on_startup);
+    fs::remove_file(p).ok();
+
+    init();
+    let p = temp_json_file(r#"{"rotate_on_startup": "false"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert!(!config_snapshot().rotate_on_startup);
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_allowed_file_extensions() {
+    init();
+    let log = format!(
+        "json_ext_{}.jsonl",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    let p = temp_json_file(&format!(r#"{{"file_name": "{}"}}"#, log));
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::SetFile(_))
+    ));
+    fs::remove_file(p).ok();
+
+    init();
+    let p = temp_json_file(&format!(
+        r#"{{"allowed_file_extensions": ["jsonl", "csv"], "file_name": "{}"}}"#,
+        log
+    ));
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().allowed_file_extensions, ["jsonl", "csv"]);
+    assert!(config_snapshot().file_manager().is_some());
+    init();
+    fs::remove_file(p).ok();
+    fs::remove_file(log).ok();
+
+    init(); // not an extension
+    let p = temp_json_file(r#"{"allowed_file_extensions": ["json/l"]}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::SetAllowedFileExtensions(_))
+    ));
+    fs::remove_file(p).ok();
+}
+
+#[test]
+fn json_purge_on_startup() {
+    init();
+    let p = temp_json_file(r#"{"purge_on_startup": "7 day"}"#);
+    assert!(load_config_from_file(p.to_str().unwrap()).is_ok());
+    assert_eq!(config_snapshot().purge_on_startup, Some(7 * 24 * 3600));
+    fs::remove_file(p).ok();
+
+    init(); // not an age
+    let p = temp_json_file(r#"{"purge_on_startup": "12 MB"}"#);
+    assert!(matches!(
+        load_config_from_file(p.to_str().unwrap()),
+        Err(ReadFromConfigFileError::SetPurgeOnStartup(_))
+    ));
+    assert_eq!(config_snapshot().purge_on_startup, None);
+    fs::remove_file(p).ok();
+}
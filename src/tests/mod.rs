@@ -1,30 +1,39 @@
+mod async_writer;
 mod file_manager;
 mod from_env_config;
 mod from_env_file_config;
 mod from_ini_file_config;
 mod from_json_file_config;
+#[cfg(feature = "http")]
+mod webhook;
 use crate::Level;
 
 use crate::helper;
 use crate::logger::file_handler::file_formatter::FileFormatter;
 use crate::logger::file_handler::file_manager::RotationType;
 use crate::logger::file_handler::file_name::FileName;
-use crate::logger::formatter::{parse_string_to_logparts, LogPart};
+use crate::logger::formatter::{
+    parse_string_to_logparts, parse_string_to_wrappers, Alignment, LevelStyle, LogColor,
+    LogFormatWrapper, LogFormatter, LogPart, LogStyle, ParsePartsToFormatterError,
+    ParseStringToWrappersError, ParseSymbToPartsError, WidthSpec,
+};
 use crate::logger::init;
+use crate::logger::set_errors::{
+    RegisterPlaceholderError, SetContextError, SetLevelColorError, SetLevelFormattingError,
+    SetTimezoneError,
+};
 use crate::*;
 use std::fs;
 
 #[test]
 fn parse_rotation_type() {
     let res = crate::logger::file_handler::file_manager::RotationType::try_from_string("dfsa week");
-    assert_eq!(res, None);
+    assert!(res.is_err());
 
     let res = crate::logger::file_handler::file_manager::RotationType::try_from_string("23 week");
     assert_eq!(
         res,
-        Some(
-            crate::logger::file_handler::file_manager::RotationType::Period(60 * 60 * 24 * 7 * 23)
-        )
+        Ok(crate::logger::file_handler::file_manager::RotationType::Period(60 * 60 * 24 * 7 * 23))
     )
 }
 
@@ -57,11 +66,62 @@ fn test_file_formatter_no_extension() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_file_formatter_directories() {
+    // only the name of the file is checked
+    assert!(FileFormatter::try_from_string("logs/app_{date}.log").is_ok());
+    assert!(FileFormatter::try_from_string("/var/log/50%/app.log").is_ok());
+    assert!(FileFormatter::try_from_string("logs/app<.log").is_err());
+    assert!(FileFormatter::try_from_string("logs/").is_err());
+    assert!(FileFormatter::try_from_string("logs.d/app").is_err());
+}
+
+#[test]
+fn test_file_formatter_matches() {
+    let config = Config::default();
+    let file_formatter = FileFormatter::try_from_string("logs/app_{date}_{time}.log").unwrap();
+    assert!(file_formatter.matches("logs/app_16-10-2026_09:05:03.log", &config));
+    assert!(file_formatter.matches("logs/app_16-10-2026_09:05:03(12).log", &config));
+    assert!(file_formatter.matches("logs/app_6-1-2026_9:5:3.log", &config));
+    assert!(!file_formatter.matches("logs/app_16-10-2026_09:05:03.log.zip", &config));
+    assert!(!file_formatter.matches("logs/app_16-10-2026_09:05:03.txt", &config));
+    assert!(!file_formatter.matches("logs/other_16-10-2026.log", &config));
+    // the placeholders match by their shape
+    assert!(!file_formatter.matches("logs/app_backup_09:05:03.log", &config));
+    assert!(!file_formatter.matches("logs/app_16-10-2026_latest.log", &config));
+    // a placeholder doesn't match a subdirectory
+    assert!(!file_formatter.matches("logs/app_old/16-10-2026_09:05:03.log", &config));
+
+    // the file names can't have a `%`, their directories can
+    let file_formatter = FileFormatter::try_from_string("{date:%Y%m%d}/app_{pid}.log").unwrap();
+    assert!(file_formatter.matches("20261016/app_4242.log", &config));
+    assert!(!file_formatter.matches("2026-10-16/app_4242.log", &config));
+    assert!(!file_formatter.matches("20261016/app_main.log", &config));
+
+    let file_formatter = FileFormatter::try_from_string("app_{level}_{num:3}.log").unwrap();
+    assert!(file_formatter.matches("app_INFO_007.log", &config));
+    assert!(!file_formatter.matches("app_NOTICE_007.log", &config));
+    assert!(!file_formatter.matches("app_INFO_x.log", &config));
+}
+
+#[test]
+fn test_file_formatter_has_fixed_text() {
+    let has_fixed_text = |format: &str| {
+        FileFormatter::try_from_string(format)
+            .unwrap()
+            .has_fixed_text()
+    };
+    assert!(has_fixed_text("logs/app_{date}.log"));
+    assert!(has_fixed_text("{date}_v2.log"));
+    assert!(!has_fixed_text("app/{date}.log"));
+    assert!(!has_fixed_text("{date}_{time}.log"));
+}
+
 #[test]
 fn test_file_name_from_formatter_success() {
     let format_str = "log_{date}_{time}.txt";
     let file_formatter = FileFormatter::try_from_string(format_str).unwrap();
-    let file_name = FileName::from_file_formatter(file_formatter, Level::INFO);
+    let file_name = FileName::from_file_formatter(file_formatter, &Config::default());
     assert!(file_name.is_ok());
     let file_name = file_name.unwrap();
     let full_file_name: String = file_name.into();
@@ -70,19 +130,69 @@ fn test_file_name_from_formatter_success() {
 }
 
 #[test]
-fn test_file_name_from_formatter_incorrect_extension() {
-    // Use an extension that is not in the allowed list
+fn test_file_name_num_placeholder() {
+    let config = Config::default();
+    let date = helper::get_current_date_in_string(config.time_settings);
+
+    // the number has its own place in the name, from 1
+    let file_formatter = FileFormatter::try_from_string("app_{date}_{num:3}.log").unwrap();
+    let mut file_name = FileName::from_file_formatter(file_formatter, &config).unwrap();
+    assert_eq!(
+        file_name.get_full_file_name(),
+        format!("app_{}_001.log", date)
+    );
+    file_name.increase_num();
+    assert_eq!(
+        file_name.get_full_file_name(),
+        format!("app_{}_002.log", date)
+    );
+
+    let file_formatter = FileFormatter::try_from_string("{num}-app-{num}.log").unwrap();
+    let mut file_name = FileName::from_file_formatter(file_formatter, &config).unwrap();
+    assert_eq!(file_name.get_full_file_name(), "1-app-1.log");
+    for _ in 0..11 {
+        file_name.increase_num();
+    }
+    assert_eq!(file_name.get_full_file_name(), "12-app-12.log");
+
+    // without it, before the extension
+    let file_formatter = FileFormatter::try_from_string("app.log").unwrap();
+    let mut file_name = FileName::from_file_formatter(file_formatter, &config).unwrap();
+    assert_eq!(file_name.get_full_file_name(), "app.log");
+    file_name.increase_num();
+    assert_eq!(file_name.get_full_file_name(), "app(1).log");
+
+    assert!(FileFormatter::try_from_string("app_{num:x}.log").is_err());
+    assert!(FileFormatter::try_from_string("app_{num:<5}.log").is_err());
+    assert!(FileFormatter::try_from_string("app_{num:10}.log").is_ok());
+    assert!(FileFormatter::try_from_string("app_{num:11}.log").is_err());
+    assert!(FileFormatter::try_from_string("app_{num:99999999999999}.log").is_err());
+}
+
+#[test]
+fn test_file_name_from_formatter_allowed_extensions() {
+    // Use an extension that is not in the default list
     let format_str = "log_{date}.csv";
     let file_formatter = FileFormatter::try_from_string(format_str);
     assert!(file_formatter.is_ok());
-    let file_name = FileName::from_file_formatter(file_formatter.unwrap(), Level::DEBUG);
+    let file_formatter = file_formatter.unwrap();
+    let file_name = FileName::from_file_formatter(file_formatter.clone(), &Config::default());
     assert!(file_name.is_err());
+
+    // once it is allowed in the config
+    let config = Config {
+        allowed_file_extensions: vec!["jsonl".to_string(), "csv".to_string()],
+        ..Default::default()
+    };
+    let file_name = FileName::from_file_formatter(file_formatter, &config);
+    let full_file_name: String = file_name.unwrap().into();
+    assert!(full_file_name.ends_with(".csv"));
 }
 
 #[test]
 fn test_helper_date_time() {
-    let date_str = helper::get_current_date_in_string();
-    let time_str = helper::get_current_time_in_string();
+    let date_str = helper::get_current_date_in_string(Default::default());
+    let time_str = helper::get_current_time_in_string(Default::default());
     // Check that the helper functions return non-empty strings
     assert!(!date_str.is_empty());
     assert!(!time_str.is_empty());
@@ -130,19 +240,258 @@ fn test_log_macros_execution() {
 fn test_rotation_type_parsing() {
     // Invalid rotation string
     let invalid = RotationType::try_from_string("invalid");
-    assert!(invalid.is_none());
+    assert!(invalid.is_err());
 
     // Test size rotation
     let size = RotationType::try_from_string("500 MB");
-    assert!(size.is_some());
+    assert!(size.is_ok());
 
     // Test period rotation
     let period = RotationType::try_from_string("2 week");
-    assert!(period.is_some());
+    assert!(period.is_ok());
 
     // Test time rotation
     let time = RotationType::try_from_string("12:30");
-    assert!(time.is_some());
+    assert!(time.is_ok());
+}
+
+#[test]
+fn test_rotation_seconds_minutes_and_plurals() {
+    assert_eq!(
+        RotationType::try_from_string("30 second"),
+        Ok(RotationType::Period(30))
+    );
+    assert_eq!(
+        RotationType::try_from_string("5 minute"),
+        Ok(RotationType::Period(5 * 60))
+    );
+    assert_eq!(
+        RotationType::try_from_string("5 minutes"),
+        Ok(RotationType::Period(5 * 60))
+    );
+    assert_eq!(
+        RotationType::try_from_string("2 days"),
+        Ok(RotationType::Period(2 * 24 * 60 * 60))
+    );
+    assert_eq!(
+        RotationType::try_from_string("1 hours"),
+        Ok(RotationType::Period(60 * 60))
+    );
+    assert!(RotationType::try_from_string("5 minutess").is_err());
+    assert!(RotationType::try_from_string("minute").is_err());
+}
+
+#[test]
+fn test_rotation_forgiving_sizes_and_periods() {
+    let accepted = [
+        // the exact strings keep working
+        ("5 KB", RotationType::Size(5 * 1024)),
+        ("500 MB", RotationType::Size(500 * 1024 * 1024)),
+        ("2 GB", RotationType::Size(2 * 1024 * 1024 * 1024)),
+        ("1 TB", RotationType::Size(1024 * 1024 * 1024 * 1024)),
+        ("1 day", RotationType::Period(24 * 60 * 60)),
+        ("12:30", RotationType::Time(12, 30)),
+        // case, whitespace and plurals
+        ("5MB", RotationType::Size(5 * 1024 * 1024)),
+        ("5 mb", RotationType::Size(5 * 1024 * 1024)),
+        ("  5   Mb  ", RotationType::Size(5 * 1024 * 1024)),
+        ("2Hours", RotationType::Period(2 * 60 * 60)),
+        ("3   WEEKS", RotationType::Period(3 * 7 * 24 * 60 * 60)),
+        (" 12:30 ", RotationType::Time(12, 30)),
+        // decimal sizes are floored to bytes
+        ("1.5 GB", RotationType::Size(1024 * 1024 * 1024 * 3 / 2)),
+        ("0.5kb", RotationType::Size(512)),
+        ("1.0001 KB", RotationType::Size(1024)),
+    ];
+    for (text, expected) in accepted {
+        assert_eq!(
+            RotationType::try_from_string(text),
+            Ok(expected),
+            "{text:?}"
+        );
+    }
+
+    let rejected = [
+        "",
+        "MB",
+        "5",
+        "5 MBs",
+        "5 bytes",
+        "-5 MB",
+        "1.2.3 MB",
+        ". MB",
+        "1.5 hour",
+        "5 mega bytes",
+        "25:00",
+        "12:60",
+    ];
+    for text in rejected {
+        assert!(RotationType::try_from_string(text).is_err(), "{text:?}");
+    }
+
+    // a size is at least a byte once floored, a period at least a second
+    use crate::logger::file_handler::file_manager::RotationParseError;
+    for text in ["0 MB", "0.0001 kb"] {
+        assert_eq!(
+            RotationType::try_from_string(text),
+            Err(RotationParseError::ZeroSize),
+            "{text:?}"
+        );
+    }
+    assert_eq!(
+        RotationType::try_from_string("0 second"),
+        Err(RotationParseError::PeriodTooShort(
+            std::time::Duration::ZERO
+        ))
+    );
+}
+
+#[test]
+fn test_rotation_renders_back_to_its_string() {
+    let cases = [
+        ("1 day", "1 day"),
+        ("24 hours", "1 day"),
+        ("90 minute", "90 minute"),
+        ("14 days", "2 week"),
+        ("5 seconds", "5 second"),
+        ("12:30", "12:30"),
+        ("daily", "00:00"),
+        ("hourly", "hourly"),
+        ("weekly Sun 3:00", "sunday 03:00"),
+        ("500 mb", "500 MB"),
+        ("1.5 GB", "1536 MB"),
+        ("0.5 KB", "0.5 KB"),
+        ("1024 KB", "1 MB"),
+    ];
+    for (text, rendered) in cases {
+        let rotation = RotationType::try_from_string(text).unwrap();
+        assert_eq!(rotation.to_string(), rendered, "{text:?}");
+        assert_eq!(
+            RotationType::try_from_string(rendered),
+            Ok(rotation),
+            "{rendered:?}"
+        );
+    }
+}
+
+#[test]
+fn test_rotation_parse_errors() {
+    use crate::logger::file_handler::file_manager::RotationParseError;
+    use crate::logger::set_errors::AddRotationError;
+
+    let cases = [
+        (
+            "2 fortnights",
+            RotationParseError::UnknownUnit("fortnights".to_string()),
+        ),
+        ("5 MBs", RotationParseError::UnknownUnit("mbs".to_string())),
+        ("5", RotationParseError::MissingUnit),
+        (
+            "bad value",
+            RotationParseError::InvalidNumber("bad value".to_string()),
+        ),
+        (
+            "1.5 hour",
+            RotationParseError::InvalidNumber("1.5".to_string()),
+        ),
+        (
+            "1.2.3 MB",
+            RotationParseError::InvalidNumber("1.2.3".to_string()),
+        ),
+        (
+            "25:00",
+            RotationParseError::InvalidTime {
+                hour: 25,
+                minute: 0,
+            },
+        ),
+        (
+            "sunday 12:60",
+            RotationParseError::InvalidTime {
+                hour: 12,
+                minute: 60,
+            },
+        ),
+        (
+            "12:30:00",
+            RotationParseError::MalformedTime("12:30:00".to_string()),
+        ),
+        (
+            "sunday 5 MB",
+            RotationParseError::MalformedTime("5 mb".to_string()),
+        ),
+        (
+            "weekly funday 03:00",
+            RotationParseError::UnknownWeekday("funday".to_string()),
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(
+            RotationType::try_from_string(text),
+            Err(expected),
+            "{text:?}"
+        );
+    }
+
+    // the message of the setter tells what was wrong and what is accepted
+    let message = AddRotationError::IncorrectFormatGiven(RotationParseError::UnknownUnit(
+        "fortnights".to_string(),
+    ))
+    .to_string();
+    assert!(message.contains("unknown unit `fortnights`"), "{message}");
+    assert!(message.contains("\"1 day\""), "{message}");
+}
+
+#[test]
+fn test_archive_name_format() {
+    use crate::logger::file_handler::archive_name::{ArchiveNameFormat, ArchiveNameFormatError};
+
+    let config = Config::default();
+    let date = helper::get_current_date_in_string(config.time_settings);
+    let format = ArchiveNameFormat::try_from_string("{name}_{date}-{ext}.zip").unwrap();
+    assert_eq!(
+        format.render("app.log", 1, &config),
+        format!("app_{date}-log.zip")
+    );
+    // the next names are numbered before the extension, or by `{num}`
+    assert_eq!(
+        format.render("app.log", 3, &config),
+        format!("app_{date}-log(2).zip")
+    );
+    let numbered = ArchiveNameFormat::try_from_string("{name}.{num}.gz").unwrap();
+    assert_eq!(numbered.render("app(1).log", 2, &config), "app(1).2.gz");
+
+    // the fixed parts are used to find the archives of a file
+    assert_eq!(
+        format.fixed_prefix_and_suffix("app_", ".log"),
+        ("app_".to_string(), "-log.zip".to_string())
+    );
+    assert_eq!(
+        numbered.fixed_prefix_and_suffix("", ".log"),
+        (String::new(), ".gz".to_string())
+    );
+    let fixed = ArchiveNameFormat::try_from_string("old.log.zip").unwrap();
+    assert_eq!(
+        fixed.fixed_prefix_and_suffix("app_", ".log"),
+        ("old.log".to_string(), ".zip".to_string())
+    );
+
+    assert!(matches!(
+        ArchiveNameFormat::try_from_string(" "),
+        Err(ArchiveNameFormatError::EmptyStringGiven)
+    ));
+    assert!(matches!(
+        ArchiveNameFormat::try_from_string("../{name}.zip"),
+        Err(ArchiveNameFormatError::IncorrectCharacterGiven('/'))
+    ));
+    assert!(matches!(
+        ArchiveNameFormat::try_from_string("{name}_{message}.zip"),
+        Err(ArchiveNameFormatError::UnsupportedPart)
+    ));
+    assert!(matches!(
+        ArchiveNameFormat::try_from_string("{nam}.zip"),
+        Err(ArchiveNameFormatError::UnsupportedPart)
+    ));
 }
 
 #[test]
@@ -156,18 +505,1492 @@ fn test_set_file_and_compression_and_rotation() {
     // Check that the internal config now includes a file_manager.
     let config_state = CONFIG.read().unwrap();
     let cfg = config_state;
-    assert!(cfg.file_manager.is_some());
+    assert!(cfg.file_manager().is_some());
 
     // Optionally, clean up any generated file if needed.
-    let file_name = cfg
-        .file_manager
-        .as_ref()
-        .unwrap()
-        .lock()
-        .unwrap()
-        .get_file_name();
+    let file_name = cfg.file_manager().unwrap().lock().unwrap().get_file_name();
 
     if fs::metadata(&file_name).is_ok() {
         let _ = fs::remove_file(file_name);
     }
 }
+
+#[test]
+fn test_color_when() {
+    use crate::logger::should_colorize;
+
+    assert!(should_colorize(ColorWhen::Always, false));
+    assert!(should_colorize(ColorWhen::Always, true));
+    assert!(!should_colorize(ColorWhen::Never, false));
+    assert!(!should_colorize(ColorWhen::Never, true));
+
+    init();
+    logger::set_colorized(false).unwrap();
+    assert_eq!(CONFIG.read().unwrap().color_when, ColorWhen::Never);
+    logger::set_colorized(true).unwrap();
+    assert_eq!(CONFIG.read().unwrap().color_when, ColorWhen::Auto);
+    logger::set_color_when(ColorWhen::Always).unwrap();
+    assert_eq!(CONFIG.read().unwrap().color_when, ColorWhen::Always);
+}
+
+#[test]
+fn test_enable_ansi_support() {
+    // nothing has to be enabled on non-windows platforms, and calling it twice is fine
+    #[cfg(not(windows))]
+    assert!(crate::logger::console::enable_ansi_support());
+    let first = crate::logger::console::enable_ansi_support();
+    assert_eq!(first, crate::logger::console::enable_ansi_support());
+}
+
+/// Parses a format dropping the empty text parts produced between adjacent tags
+/// Builds the info of a `msg` message logged from `file.rs:1` on the `main` thread
+fn test_log_info(level: Level) -> LogRecord {
+    LogRecord {
+        module_path: "module".to_string(),
+        file: "file.rs".to_string(),
+        line: 1,
+        column: 5,
+        message: "msg".to_string(),
+        level,
+        thread: "main".to_string(),
+        thread_id: "1".to_string(),
+        timestamp: std::time::SystemTime::now(),
+        context: logger::current_context(&current_config()),
+    }
+}
+
+/// Returns a snapshot of the current config, the rendering functions take it explicitly.
+fn current_config() -> std::sync::Arc<Config> {
+    CONFIG.read().unwrap().clone()
+}
+
+fn non_empty_wrappers(format: &str) -> Vec<LogFormatWrapper> {
+    parse_string_to_wrappers(format)
+        .unwrap()
+        .into_iter()
+        .filter(|w| w.part != LogPart::Text(String::new()))
+        .collect()
+}
+
+#[test]
+fn test_background_color_tags() {
+    let wrappers = non_empty_wrappers("<bg_red><white>[{level}]<white><bg_red> {message}");
+    assert_eq!(wrappers[0].part, LogPart::Text("[".to_string()));
+    assert_eq!(wrappers[0].color, Some(LogColor::White));
+    assert_eq!(wrappers[0].bg_color, Some(LogColor::Red));
+    assert_eq!(wrappers[1].part, LogPart::Level(LevelStyle::Upper));
+    assert_eq!(wrappers[1].bg_color, Some(LogColor::Red));
+    let message = wrappers.last().unwrap();
+    assert_eq!(message.part, LogPart::Message);
+    assert_eq!(message.color, None);
+    assert_eq!(message.bg_color, None);
+
+    // unclosed and mismatched tags are rejected, for backgrounds and foregrounds alike
+    assert!(LogFormatter::parse_from_string("<bg_red>{message}").is_err());
+    assert!(LogFormatter::parse_from_string("<bg_red>{message}<bg_blue>").is_err());
+    assert!(LogFormatter::parse_from_string("<red>{message}<blue>").is_err());
+    assert!(LogFormatter::parse_from_string("<bg_pink>{message}<bg_pink>").is_err());
+
+    let colored = LogColor::colorize_str(
+        "ERROR",
+        Some(LogColor::White),
+        Some(LogColor::Red),
+        LogStyle::default(),
+    );
+    assert_eq!(
+        colored,
+        "\x1b[38;2;255;255;255m\x1b[48;2;255;0;0mERROR\x1b[0m"
+    );
+}
+
+#[test]
+fn test_style_tags() {
+    let wrappers = non_empty_wrappers("<bold><red>{level}<red><bold> <dim>{file}<dim>");
+    assert_eq!(wrappers[0].part, LogPart::Level(LevelStyle::Upper));
+    assert_eq!(wrappers[0].color, Some(LogColor::Red));
+    assert_eq!(wrappers[0].style, LogStyle::BOLD);
+    assert_eq!(wrappers[1].style, LogStyle::default());
+    assert_eq!(wrappers[2].part, LogPart::File);
+    assert_eq!(wrappers[2].style, LogStyle::DIM);
+
+    let mut both = LogStyle::BOLD;
+    both.toggle(LogStyle::UNDERLINE);
+    let colored = LogColor::colorize_str("x", None, None, both);
+    assert_eq!(colored, "\x1b[1m\x1b[4mx\x1b[0m");
+
+    // unknown styles and unclosed tags are reported as incorrect formats
+    init();
+    assert!(matches!(
+        logger::set_global_formatting("<blink>{message}<blink>"),
+        Err(SetLevelFormattingError::IncorrectFormatGiven(_))
+    ));
+    assert!(matches!(
+        logger::set_global_formatting("<italic>{message}"),
+        Err(SetLevelFormattingError::IncorrectFormatGiven(_))
+    ));
+}
+
+#[test]
+fn test_rgb_color_tags() {
+    assert_eq!(
+        LogColor::try_from_str("#ff8800"),
+        Some(LogColor::Rgb(255, 136, 0))
+    );
+    assert_eq!(
+        LogColor::try_from_str("#FF8800"),
+        Some(LogColor::Rgb(255, 136, 0))
+    );
+    assert_eq!(
+        LogColor::try_from_str("rgb(255, 136,0)"),
+        Some(LogColor::Rgb(255, 136, 0))
+    );
+    assert_eq!(LogColor::try_from_str("#ff88"), None);
+    assert_eq!(LogColor::try_from_str("#gg8800"), None);
+    assert_eq!(LogColor::try_from_str("rgb(256,0,0)"), None);
+    assert_eq!(LogColor::try_from_str("rgb(1,2)"), None);
+
+    let wrappers =
+        non_empty_wrappers("<#ff0000>{level}<#ff0000> <rgb(0,0,255)>{message}<rgb(0,0,255)>");
+    assert_eq!(wrappers[0].part, LogPart::Level(LevelStyle::Upper));
+    assert_eq!(wrappers[0].color, Some(LogColor::Rgb(255, 0, 0)));
+    assert_eq!(wrappers[2].part, LogPart::Message);
+    assert_eq!(wrappers[2].color, Some(LogColor::Rgb(0, 0, 255)));
+    // hex and rgb forms of the same color close each other, different values don't
+    assert!(parse_string_to_wrappers("<#0000ff>{message}<rgb(0,0,255)>").is_ok());
+    assert!(parse_string_to_wrappers("<#ff0000>{message}<#00ff00>").is_err());
+    assert!(parse_string_to_wrappers("<bg_#ff0000>{message}<bg_#ff0000>").is_ok());
+
+    assert_eq!(
+        LogColor::Rgb(255, 136, 0).get_ascii(),
+        "\x1b[38;2;255;136;0m"
+    );
+    // named colors keep their escape codes
+    assert_eq!(LogColor::Purple.get_ascii(), "\x1b[38;2;128;0;128m");
+}
+
+#[test]
+fn test_indexed_color_tags() {
+    init();
+    logger::set_global_formatting("<c208>[{level}]<c208> <bg_c17>{message}<bg_c17>").unwrap();
+    let cfg = CONFIG.read().unwrap();
+    let parts: Vec<_> = cfg
+        .warn_log_format
+        .parts
+        .iter()
+        .filter(|w| w.part != LogPart::Text(String::new()))
+        .collect();
+    assert_eq!(parts[1].part, LogPart::Level(LevelStyle::Upper));
+    assert_eq!(parts[1].color, Some(LogColor::Indexed(208)));
+    assert_eq!(parts[4].part, LogPart::Message);
+    assert_eq!(parts[4].bg_color, Some(LogColor::Indexed(17)));
+    drop(cfg);
+
+    assert_eq!(LogColor::Indexed(208).get_ascii(), "\x1b[38;5;208m");
+    assert_eq!(LogColor::Indexed(17).get_bg_ascii(), "\x1b[48;5;17m");
+
+    assert!(matches!(
+        parse_string_to_wrappers("<c256>{message}<c256>"),
+        Err(ParseStringToWrappersError::UnableToParseSymbolsToParts(
+            ParseSymbToPartsError::IncorrectColorIndex(_)
+        ))
+    ));
+    assert!(parse_string_to_wrappers("<c>{message}<c>").is_err());
+}
+
+#[test]
+fn test_bright_color_tags() {
+    init();
+    // the basic colors keep their escape codes
+    assert_eq!(LogColor::Red.get_ascii(), "\x1b[38;2;255;0;0m");
+    assert_eq!(LogColor::Purple.get_ascii(), "\x1b[38;2;128;0;128m");
+    assert_eq!(LogColor::Black.get_bg_ascii(), "\x1b[48;2;0;0;0m");
+
+    assert_eq!(LogColor::BrightBlack.get_ascii(), "\x1b[90m");
+    assert_eq!(LogColor::BrightRed.get_ascii(), "\x1b[91m");
+    assert_eq!(LogColor::BrightWhite.get_ascii(), "\x1b[97m");
+    assert_eq!(LogColor::BrightBlue.get_bg_ascii(), "\x1b[104m");
+
+    let format = "<bright_black>{time}<bright_black> <bright_purple>{message}<bright_purple>";
+    assert!(logger::set_global_formatting(format).is_ok());
+    let parts = non_empty_wrappers(format);
+    assert_eq!(parts[0].color, Some(LogColor::BrightBlack));
+    assert_eq!(parts[2].color, Some(LogColor::BrightPurple));
+
+    assert!(parse_string_to_wrappers("<bright_orange>{message}<bright_orange>").is_err());
+}
+
+#[test]
+fn test_auto_level_colors() {
+    init();
+    logger::set_global_formatting("[{level}] <blue>{message}<blue>").unwrap();
+
+    // disabled by default
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::ERROR), true),
+        format!("[ERROR] {}msg\x1b[0m", LogColor::Blue.get_ascii())
+    );
+
+    logger::set_auto_level_colors(true).unwrap();
+    let red = LogColor::Red.get_ascii();
+    let blue = LogColor::Blue.get_ascii();
+    // the explicit tag wins over the automatic color
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::ERROR), true),
+        format!("{red}[\x1b[0m{red}ERROR\x1b[0m{red}] \x1b[0m{blue}msg\x1b[0m")
+    );
+    // INFO keeps the default terminal color
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), true),
+        format!("[INFO] {blue}msg\x1b[0m")
+    );
+    assert_eq!(
+        logger::auto_level_color(Level::WARN),
+        Some(LogColor::Yellow)
+    );
+    assert_eq!(
+        logger::auto_level_color(Level::TRACE),
+        Some(LogColor::BrightBlack)
+    );
+    // uncolored output (e.g. files) ignores the automatic colors
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::ERROR), false),
+        "[ERROR] msg"
+    );
+    logger::set_auto_level_colors(false).unwrap();
+}
+
+#[test]
+fn test_thread_placeholders() {
+    init();
+    let parts = parse_string_to_logparts("[{thread}:{thread_id}] {message}").unwrap();
+    assert!(parts.contains(&LogPart::Thread));
+    assert!(parts.contains(&LogPart::ThreadId));
+    assert!(logger::set_level_formatting(Level::INFO, "[{thread}] {message}").is_ok());
+
+    // file names can't depend on the thread
+    assert!(FileFormatter::try_from_string("log_{thread}.txt").is_err());
+    assert!(FileFormatter::try_from_string("log_{thread_id}.txt").is_err());
+
+    let handle = std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(|| {
+            (
+                helper::get_current_thread_name(),
+                helper::get_current_thread_id(),
+            )
+        })
+        .unwrap();
+    let (name, id) = handle.join().unwrap();
+    assert_eq!(name, "worker");
+    assert!(id.parse::<u64>().is_ok());
+
+    let unnamed = std::thread::spawn(|| {
+        (
+            helper::get_current_thread_name(),
+            helper::get_current_thread_id(),
+        )
+    });
+    let (name, id) = unnamed.join().unwrap();
+    assert_eq!(name, id);
+}
+
+#[test]
+fn test_pid_placeholder() {
+    init();
+    let parts = parse_string_to_logparts("[{pid}] {message}").unwrap();
+    assert_eq!(parts[1], LogPart::Pid);
+    assert!(logger::set_global_formatting("{pid}").is_ok());
+    let log_info = test_log_info(Level::INFO);
+    assert_eq!(
+        logger::string_log(&current_config(), &log_info, false),
+        std::process::id().to_string()
+    );
+
+    // it is allowed in file names, to keep the files of several processes apart
+    let formatter = FileFormatter::try_from_string("log_{hostname}_{pid}.txt").unwrap();
+    let name = FileName::get_string_from_log_parts(formatter.format, &Config::default());
+    assert_eq!(
+        name,
+        format!("log_{}_{}.txt", helper::get_hostname(), std::process::id())
+    );
+}
+
+#[test]
+fn test_timestamp_placeholders() {
+    init();
+    assert!(logger::set_global_formatting("{timestamp} {timestamp_ms}").is_ok());
+    let log_info = test_log_info(Level::INFO);
+    let res = logger::string_log(&current_config(), &log_info, false);
+    let (secs, millis) = res.split_once(' ').unwrap();
+    let secs: i64 = secs.parse().unwrap();
+    let millis: i64 = millis.parse().unwrap();
+    assert!((chrono::Utc::now().timestamp() - secs).abs() <= 2);
+    // both values come from the same instant
+    assert_eq!(millis / 1000, secs);
+
+    assert!(FileFormatter::try_from_string("log_{timestamp}.txt").is_err());
+    assert!(FileFormatter::try_from_string("log_{timestamp_ms}.txt").is_err());
+}
+
+#[test]
+fn test_date_time_specifiers() {
+    init();
+    let parts: Vec<LogPart> = non_empty_wrappers("{date:%Y-%m-%d} {time:%H:%M:%S%.3f} {date}")
+        .into_iter()
+        .map(|w| w.part)
+        .collect();
+    assert_eq!(parts[0], LogPart::Date(Some("%Y-%m-%d".to_string())));
+    assert_eq!(parts[2], LogPart::Time(Some("%H:%M:%S%.3f".to_string())));
+    assert_eq!(parts[4], LogPart::Date(None));
+
+    // invalid specifiers and specifiers on other placeholders are rejected
+    assert!(parse_string_to_logparts("{date:%Q}").is_err());
+    assert!(parse_string_to_logparts("{date:}").is_err());
+    assert!(parse_string_to_logparts("{level:%Y}").is_err());
+
+    logger::set_global_formatting("{date:%Y-%m-%d}|{date}|{time}").unwrap();
+    let res = logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    let fields: Vec<&str> = res.split('|').collect();
+    assert_eq!(fields[0], chrono::Utc::now().format("%Y-%m-%d").to_string());
+    // the formats without a specifier are unchanged
+    assert_eq!(
+        fields[1],
+        helper::get_current_date_in_string(Default::default())
+    );
+    assert_eq!(fields[2].split(':').count(), 3);
+}
+
+#[test]
+fn test_time_precision() {
+    init();
+    let with_millis = helper::TimeSettings {
+        precision: TimePrecision::Millis,
+        ..Default::default()
+    };
+    let time_str = helper::get_current_time_in_string(with_millis);
+    let (hms, millis) = time_str.split_once('.').unwrap();
+    assert_eq!(hms.split(':').count(), 3);
+    assert_eq!(millis.len(), 3);
+    assert!(millis.parse::<u32>().is_ok());
+    assert!(!helper::get_current_time_in_string(Default::default()).contains('.'));
+
+    logger::set_global_formatting("{time}").unwrap();
+    logger::set_time_precision(TimePrecision::Millis).unwrap();
+    assert!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false).contains('.')
+    );
+
+    // the precision also applies to the file names
+    let parts = parse_string_to_logparts("app_{time}.txt").unwrap();
+    let name = FileName::get_string_from_log_parts(
+        parts,
+        &Config {
+            level: Level::INFO,
+            time_settings: with_millis,
+            ..Default::default()
+        },
+    );
+    assert!(name.trim_end_matches(".txt").contains('.'));
+
+    logger::set_time_precision(TimePrecision::Seconds).unwrap();
+    assert!(
+        !logger::string_log(&current_config(), &test_log_info(Level::INFO), false).contains('.')
+    );
+}
+
+#[test]
+fn test_zero_padded_date_time() {
+    init();
+    let padded = helper::TimeSettings::default();
+    let unpadded = helper::TimeSettings {
+        zero_padded: false,
+        ..Default::default()
+    };
+    let date = helper::get_current_date_in_string(padded);
+    let (day, rest) = date.split_once('-').unwrap();
+    let (month, year) = rest.split_once('-').unwrap();
+    assert_eq!((day.len(), month.len(), year.len()), (2, 2, 4));
+    let time = helper::get_current_time_in_string(padded);
+    assert!(time.split(':').all(|c| c.len() == 2));
+
+    let date = helper::get_current_date_in_string(unpadded);
+    assert!(!date.starts_with('0'));
+    let time = helper::get_current_time_in_string(unpadded);
+    assert!(time
+        .split(':')
+        .skip(1)
+        .all(|c| !c.starts_with('0') || c == "0"));
+
+    // file names follow the same setting
+    let parts = parse_string_to_logparts("app_{date}.txt").unwrap();
+    let name = FileName::get_string_from_log_parts(
+        parts.clone(),
+        &Config {
+            level: Level::INFO,
+            time_settings: padded,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        name,
+        format!("app_{}.txt", helper::get_current_date_in_string(padded))
+    );
+    let name = FileName::get_string_from_log_parts(
+        parts,
+        &Config {
+            level: Level::INFO,
+            time_settings: unpadded,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        name,
+        format!("app_{}.txt", helper::get_current_date_in_string(unpadded))
+    );
+
+    logger::set_global_formatting("{date}").unwrap();
+    logger::set_zero_padding(false).unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        helper::get_current_date_in_string(unpadded)
+    );
+    logger::set_zero_padding(true).unwrap();
+}
+
+#[test]
+fn test_date_time_of_the_record() {
+    init();
+    // a record rendered later (async writer, ring buffer) keeps the time it was logged at
+    let logged_at = chrono::DateTime::parse_from_rfc3339("2020-02-03T04:05:06.789Z").unwrap();
+    let log_info = LogRecord {
+        timestamp: logged_at.into(),
+        ..test_log_info(Level::INFO)
+    };
+    logger::set_global_formatting("{date}|{time}|{date:%Y}|{timestamp}").unwrap();
+    logger::set_time_precision(TimePrecision::Millis).unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &log_info, false),
+        "03-02-2020|04:05:06.789|2020|1580702706"
+    );
+    logger::set_time_precision(TimePrecision::Seconds).unwrap();
+}
+
+#[test]
+fn test_timezone() {
+    init();
+    let plus_two = helper::get_now(TimeZoneChoice::FixedOffset(2 * 3600));
+    assert_eq!(plus_two.offset().local_minus_utc(), 2 * 3600);
+    assert_eq!(
+        helper::get_now(TimeZoneChoice::Utc)
+            .offset()
+            .local_minus_utc(),
+        0
+    );
+
+    assert!(matches!(
+        logger::set_timezone(TimeZoneChoice::FixedOffset(86_400)),
+        Err(SetTimezoneError::IncorrectOffset(86_400))
+    ));
+
+    logger::set_global_formatting("{date:%z}").unwrap();
+    logger::set_timezone(TimeZoneChoice::FixedOffset(-3 * 3600)).unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "-0300"
+    );
+    let settings = helper::TimeSettings {
+        timezone: TimeZoneChoice::FixedOffset(-3 * 3600),
+        ..Default::default()
+    };
+    let expected_hour = (chrono::Utc::now() - chrono::Duration::hours(3)).format("%H");
+    assert!(helper::get_current_time_in_string(settings).starts_with(&expected_hour.to_string()));
+    logger::set_timezone(TimeZoneChoice::Utc).unwrap();
+}
+
+#[test]
+fn test_elapsed_placeholders() {
+    init();
+    logger::set_global_formatting("{elapsed}|{elapsed_ms}").unwrap();
+    let first = logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    let second = logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+
+    let parse = |line: &str| {
+        let (secs, ms) = line.split_once('|').unwrap();
+        let (int, frac) = secs.split_once('.').unwrap();
+        assert_eq!(frac.len(), 3);
+        let secs_as_ms = int.parse::<u128>().unwrap() * 1000 + frac.parse::<u128>().unwrap();
+        let ms = ms.parse::<u128>().unwrap();
+        assert_eq!(secs_as_ms, ms);
+        ms
+    };
+    assert!(parse(&second) >= parse(&first) + 20);
+
+    assert!(FileFormatter::try_from_string("log_{elapsed}.txt").is_err());
+    assert!(FileFormatter::try_from_string("log_{elapsed_ms}.txt").is_err());
+}
+
+#[test]
+fn test_hostname_placeholder() {
+    init();
+    let hostname = helper::get_hostname();
+    assert!(!hostname.is_empty());
+
+    logger::set_global_formatting("[{hostname}] {message}").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        format!("[{}] msg", hostname)
+    );
+
+    // unlike the other dynamic placeholders, it is allowed in file names
+    let formatter = FileFormatter::try_from_string("{hostname}_{date}.log").unwrap();
+    let name = FileName::get_string_from_log_parts(formatter.format, &Config::default());
+    assert!(name.starts_with(&format!("{}_", hostname)));
+}
+
+#[test]
+fn test_env_placeholder() {
+    init();
+    std::env::set_var("LOGGIT_TEST_REGION", "eu-west");
+    std::env::remove_var("LOGGIT_TEST_MISSING");
+
+    logger::set_global_formatting("[{env:LOGGIT_TEST_REGION}|{env:LOGGIT_TEST_MISSING}] {message}")
+        .unwrap();
+    // the value is resolved when the format is set
+    std::env::set_var("LOGGIT_TEST_REGION", "us-east");
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "[eu-west|] msg"
+    );
+
+    assert!(parse_string_to_logparts("{env}").is_err());
+    assert!(parse_string_to_logparts("{env:}").is_err());
+
+    let formatter = FileFormatter::try_from_string("{env:LOGGIT_TEST_REGION}_app.log").unwrap();
+    let name = FileName::get_string_from_log_parts(formatter.format, &Config::default());
+    assert_eq!(name, "us-east_app.log");
+    std::env::remove_var("LOGGIT_TEST_REGION");
+}
+
+#[test]
+fn test_width_specifiers() {
+    init();
+    assert_eq!(
+        WidthSpec::parse(">20.30"),
+        Some(WidthSpec {
+            align: Alignment::Right,
+            width: Some(20),
+            max_width: Some(30)
+        })
+    );
+    assert_eq!(WidthSpec::parse("5").unwrap().align, Alignment::Left);
+    assert_eq!(WidthSpec::parse("<"), None);
+    assert_eq!(WidthSpec::parse("5a"), None);
+    assert_eq!(WidthSpec::parse(".x"), None);
+
+    assert_eq!(WidthSpec::parse("<5").unwrap().apply("INFO"), "INFO ");
+    assert_eq!(WidthSpec::parse(">4").unwrap().apply("7"), "   7");
+    assert_eq!(WidthSpec::parse("^6").unwrap().apply("ab"), "  ab  ");
+    assert_eq!(WidthSpec::parse(".3").unwrap().apply("abcdef"), "abc");
+    assert_eq!(WidthSpec::parse("2").unwrap().apply("abcdef"), "abcdef");
+
+    let wrappers = non_empty_wrappers("<red>{level:<5}<red> {file:>10}");
+    assert_eq!(wrappers[0].part, LogPart::Level(LevelStyle::Upper));
+    assert_eq!(wrappers[0].color, Some(LogColor::Red));
+    assert_eq!(wrappers[0].width, WidthSpec::parse("<5"));
+    assert_eq!(wrappers[2].part, LogPart::File);
+    // a width on a date is not a strftime specifier
+    assert_eq!(
+        non_empty_wrappers("{date:>12}")[0].part,
+        LogPart::Date(None)
+    );
+
+    // invalid specifiers fail when the format is set
+    assert!(logger::set_level_formatting(Level::INFO, "{level:<x}").is_err());
+    assert!(logger::set_level_formatting(Level::INFO, "{message:-3}").is_err());
+
+    // the width doesn't count the escape codes
+    logger::set_global_formatting("[<red>{level:<5}<red>] {line:>4}|").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "[INFO ]    1|"
+    );
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), true),
+        format!("[{}INFO \x1b[0m]    1|", LogColor::Red.get_ascii())
+    );
+}
+
+#[test]
+fn test_level_styles() {
+    init();
+    assert_eq!(
+        non_empty_wrappers("{level:lower}")[0].part,
+        LogPart::Level(LevelStyle::Lower)
+    );
+    assert!(parse_string_to_logparts("{level:small}").is_err());
+
+    logger::set_global_formatting("{level}|{level:upper}|{level:lower}|{level:short}").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::WARN), false),
+        "WARN|WARN|warn|W"
+    );
+    // Display isn't affected
+    assert_eq!(Level::WARN.to_string(), "WARN");
+
+    let formatter = FileFormatter::try_from_string("{level:lower}_{level}.log").unwrap();
+    let name = FileName::get_string_from_log_parts(
+        formatter.format,
+        &Config {
+            level: Level::ERROR,
+            ..Default::default()
+        },
+    );
+    assert_eq!(name, "error_ERROR.log");
+}
+
+#[test]
+fn test_level_names() {
+    init();
+    logger::set_global_formatting("{level}|{level:lower}").unwrap();
+    logger::set_level_name(Level::INFO, "AUDIT").unwrap();
+    logger::set_level_name(Level::ERROR, "FATAL").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "AUDIT|audit"
+    );
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::WARN), false),
+        "WARN|warn"
+    );
+
+    let config = CONFIG.read().unwrap().clone();
+    let parts = parse_string_to_logparts("{level}_app.log").unwrap();
+    assert_eq!(
+        FileName::get_string_from_log_parts(parts, &config),
+        "AUDIT_app.log"
+    );
+
+    // an empty name restores the default one
+    logger::set_level_name(Level::INFO, "").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "INFO|info"
+    );
+    logger::reset_level_names().unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::ERROR), false),
+        "ERROR|error"
+    );
+}
+
+#[test]
+fn test_file_short_placeholder() {
+    init();
+    assert_eq!(
+        helper::get_last_path_components("src/services/billing/invoice.rs", 1),
+        "invoice.rs"
+    );
+    assert_eq!(
+        helper::get_last_path_components("src\\billing\\invoice.rs", 2),
+        "billing\\invoice.rs"
+    );
+    assert_eq!(helper::get_last_path_components("main.rs", 3), "main.rs");
+
+    assert_eq!(
+        non_empty_wrappers("{file_short:2}")[0].part,
+        LogPart::FileShort(2)
+    );
+    let wrapper = &non_empty_wrappers("{file_short:>12}")[0];
+    assert_eq!(wrapper.part, LogPart::FileShort(1));
+    assert_eq!(wrapper.width, WidthSpec::parse(">12"));
+    assert!(parse_string_to_logparts("{file_short:0}").is_err());
+
+    logger::set_global_formatting("{file_short}|{file_short:2}").unwrap();
+    let mut log_info = test_log_info(Level::INFO);
+    log_info.file = "src/services/billing/invoice.rs".to_string();
+    assert_eq!(
+        logger::string_log(&current_config(), &log_info, false),
+        "invoice.rs|billing/invoice.rs"
+    );
+
+    assert!(FileFormatter::try_from_string("{file_short}.log").is_err());
+}
+
+#[test]
+fn test_escaped_braces() {
+    assert_eq!(
+        parse_string_to_logparts("{{json}} {message}").unwrap(),
+        vec![LogPart::Text("{json} ".to_string()), LogPart::Message,]
+    );
+    // at the end and next to placeholders
+    let parts: Vec<LogPart> = non_empty_wrappers("{{{level}}}: {message}}}")
+        .into_iter()
+        .map(|w| w.part)
+        .collect();
+    assert_eq!(
+        parts,
+        vec![
+            LogPart::Text("{".to_string()),
+            LogPart::Level(LevelStyle::Upper),
+            LogPart::Text("}: ".to_string()),
+            LogPart::Message,
+            LogPart::Text("}".to_string()),
+        ]
+    );
+    assert_eq!(
+        parse_string_to_logparts("{{").unwrap(),
+        vec![LogPart::Text("{".to_string())]
+    );
+    // a single brace is still a placeholder delimiter
+    assert!(parse_string_to_logparts("{weird}").is_err());
+    assert!(parse_string_to_logparts("{message").is_err());
+    assert!(parse_string_to_logparts("message}").is_err());
+
+    init();
+    logger::set_global_formatting("{{json}} {message}").unwrap();
+    let log_info = test_log_info(Level::INFO);
+    assert_eq!(
+        logger::string_log(&current_config(), &log_info, false),
+        "{json} msg"
+    );
+
+    let formatter = FileFormatter::try_from_string("app_{{x}}_{level}.log").unwrap();
+    assert_eq!(
+        formatter.format,
+        vec![
+            LogPart::Text("app_{x}_".to_string()),
+            LogPart::Level(LevelStyle::Upper),
+            LogPart::Text(".log".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_escaped_angle_brackets() {
+    assert_eq!(
+        parse_string_to_logparts("-\\> {message} \\<-").unwrap(),
+        vec![
+            LogPart::Text("-> ".to_string()),
+            LogPart::Message,
+            LogPart::Text(" <-".to_string()),
+        ]
+    );
+    let parts = non_empty_wrappers("<red>\\<{level}\\><red>");
+    assert_eq!(parts[0].part, LogPart::Text("<".to_string()));
+    assert_eq!(parts[0].color, Some(LogColor::Red));
+    assert_eq!(parts[2].part, LogPart::Text(">".to_string()));
+    // a backslash before anything else stays as it is
+    assert_eq!(
+        parse_string_to_logparts("C:\\logs {message}").unwrap()[0],
+        LogPart::Text("C:\\logs ".to_string())
+    );
+
+    // raw angle brackets are still color tags
+    let err = parse_string_to_wrappers("-> {message} <-").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParseSymbolsToParts(
+            ParseSymbToPartsError::UnmatchedAngleBracket(_)
+        )
+    ));
+    assert!(err.to_string().contains("\\<"));
+    let err = parse_string_to_wrappers("<nope>{message}<nope>").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParseSymbolsToParts(
+            ParseSymbToPartsError::IncorrectColorTag(ref pos)
+        ) if pos.token == "nope"
+    ));
+    assert!(err.to_string().contains("\\>"));
+
+    init();
+    logger::set_global_formatting("-\\> {message} \\<-").unwrap();
+    let log_info = test_log_info(Level::INFO);
+    assert_eq!(
+        logger::string_log(&current_config(), &log_info, false),
+        "-> msg <-"
+    );
+
+    // angle brackets are forbidden in file names, escaped or not
+    assert!(FileFormatter::try_from_string("app_\\<x\\>.log").is_err());
+}
+
+#[test]
+fn test_nested_color_tags() {
+    let parts = non_empty_wrappers("<blue>({file}:{line} <yellow>{level}<yellow>)<blue>");
+    let colors: Vec<(LogPart, Option<LogColor>)> =
+        parts.into_iter().map(|w| (w.part, w.color)).collect();
+    assert_eq!(
+        colors,
+        vec![
+            (LogPart::Text("(".to_string()), Some(LogColor::Blue)),
+            (LogPart::File, Some(LogColor::Blue)),
+            (LogPart::Text(":".to_string()), Some(LogColor::Blue)),
+            (LogPart::Line, Some(LogColor::Blue)),
+            (LogPart::Text(" ".to_string()), Some(LogColor::Blue)),
+            (LogPart::Level(LevelStyle::Upper), Some(LogColor::Yellow)),
+            (LogPart::Text(")".to_string()), Some(LogColor::Blue)),
+        ]
+    );
+
+    // the background colors are nested independently
+    let parts =
+        non_empty_wrappers("<bg_red><green>{level}<bg_blue>{message}<bg_blue><green><bg_red>");
+    assert_eq!(parts[0].color, Some(LogColor::Green));
+    assert_eq!(parts[0].bg_color, Some(LogColor::Red));
+    assert_eq!(parts[1].bg_color, Some(LogColor::Blue));
+
+    let err = parse_string_to_wrappers("<blue>{file}<red>{line}<blue>{message}<red>").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParsePartsToFormatter(
+            ParsePartsToFormatterError::MismatchedColorTag { ref expected, ref found, .. }
+        ) if expected == "red" && found == "blue"
+    ));
+    assert!(err
+        .to_string()
+        .contains("<blue> closed while <red> is still open"));
+    assert!(matches!(
+        parse_string_to_wrappers("<blue>{file}<red>{line}<red>"),
+        Err(ParseStringToWrappersError::UnableToParsePartsToFormatter(
+            ParsePartsToFormatterError::UnclosedColorTag(pos)
+        )) if pos.token == "blue"
+    ));
+
+    init();
+    logger::set_global_formatting("<blue>[<yellow>{level}<yellow>]<blue>").unwrap();
+    let log_info = test_log_info(Level::INFO);
+    assert_eq!(
+        logger::string_log(&current_config(), &log_info, true),
+        format!(
+            "{}[\x1b[0m{}INFO\x1b[0m{}]\x1b[0m",
+            LogColor::Blue.get_ascii(),
+            LogColor::Yellow.get_ascii(),
+            LogColor::Blue.get_ascii()
+        )
+    );
+}
+
+#[test]
+fn test_parse_error_positions() {
+    // unclosed color
+    let err = parse_string_to_wrappers("[{level}] <red>{message}").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParsePartsToFormatter(
+            ParsePartsToFormatterError::UnclosedColorTag(_)
+        )
+    ));
+    let pos = err.position().unwrap();
+    assert_eq!(pos.offset, 10);
+    assert_eq!(pos.token, "red");
+    assert_eq!(pos.excerpt, "[{level}] <red>{messag");
+
+    // unknown placeholder
+    let err = parse_string_to_wrappers("{level} {file}:{lien} - {message}").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParseSymbolsToParts(
+            ParseSymbToPartsError::IncorrectPlaceholder(_)
+        )
+    ));
+    let pos = err.position().unwrap();
+    assert_eq!(pos.offset, 15);
+    assert_eq!(pos.token, "lien");
+    assert!(err.to_string().contains("{lien} at byte 15"));
+
+    // stray closing brace
+    let err = parse_string_to_wrappers("{level}: message} {line}").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParseSymbolsToParts(
+            ParseSymbToPartsError::StrayClosingBrace(_)
+        )
+    ));
+    let pos = err.position().unwrap();
+    assert_eq!(pos.offset, 16);
+    assert_eq!(pos.token, "}");
+
+    // unclosed placeholder and unclosed style
+    let err = parse_string_to_wrappers("{level} {message").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseStringToWrappersError::UnableToParseSymbolsToParts(
+            ParseSymbToPartsError::UnclosedPlaceholder(_)
+        )
+    ));
+    assert_eq!(err.position().unwrap().offset, 8);
+    let err = parse_string_to_wrappers("<bold>{level}<bold> <italic>{message}").unwrap_err();
+    assert_eq!(err.position().unwrap().offset, 20);
+    assert_eq!(err.position().unwrap().token, "italic");
+
+    // the offsets are in bytes, the excerpt doesn't split characters
+    let err = parse_string_to_wrappers("ééééééé {nope}").unwrap_err();
+    let pos = err.position().unwrap();
+    assert_eq!(pos.offset, 15);
+    assert!(pos.excerpt.ends_with("{nope}"));
+
+    // the setters forward the position
+    let err = logger::set_global_formatting("{level} {lien}").unwrap_err();
+    assert!(matches!(
+        err,
+        SetLevelFormattingError::IncorrectFormatGiven(ref e) if e.position().unwrap().offset == 8
+    ));
+    assert!(err.to_string().contains("{lien} at byte 8"));
+}
+
+#[test]
+fn test_custom_formatter() {
+    init();
+    logger::set_global_formatting("[{level}] {message}").unwrap();
+    logger::set_formatter(|record| {
+        format!(
+            "{}|{}|{}:{}|{}",
+            record.level, record.module_path, record.file, record.line, record.message
+        )
+    })
+    .unwrap();
+    let log_info = test_log_info(Level::WARN);
+    // the callback replaces the format and no colors are added
+    assert_eq!(
+        logger::render_log(&current_config(), &log_info, true),
+        "WARN|module|file.rs:1|msg"
+    );
+
+    logger::remove_formatter().unwrap();
+    assert_eq!(
+        logger::render_log(&current_config(), &log_info, false),
+        "[WARN] msg"
+    );
+}
+
+#[test]
+fn test_context_fields() {
+    init();
+    logger::set_global_formatting("[{level}] {message} {context}").unwrap();
+    // the records capture the context when they are created
+    let render = || logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    assert_eq!(render(), "[INFO] msg ");
+
+    logger::add_context("service", "billing").unwrap();
+    logger::add_context("env", "prod").unwrap();
+    logger::add_context("note", "two words").unwrap();
+    assert_eq!(
+        render(),
+        "[INFO] msg env=prod note=\"two words\" service=billing"
+    );
+    assert_eq!(
+        test_log_info(Level::INFO).context.get("service"),
+        Some(&"billing".to_string())
+    );
+
+    logger::add_context("env", "staging").unwrap();
+    logger::remove_context("note").unwrap();
+    assert_eq!(render(), "[INFO] msg env=staging service=billing");
+    assert!(matches!(
+        logger::add_context("", "x"),
+        Err(SetContextError::EmptyKey)
+    ));
+
+    logger::clear_context().unwrap();
+    assert_eq!(render(), "[INFO] msg ");
+    assert!(FileFormatter::try_from_string("{context}.log").is_err());
+}
+
+#[test]
+fn test_scoped_context() {
+    init();
+    logger::set_global_formatting("{message} {context}").unwrap();
+    logger::add_context("service", "billing").unwrap();
+    // the records capture the context when they are created
+    let render = || logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    {
+        let _request = crate::context::scope("request_id", "abc");
+        assert_eq!(render(), "msg request_id=abc service=billing");
+        {
+            // the inner scopes shadow the outer and the global values
+            let _inner = crate::context::scope("request_id", "def");
+            let _service = crate::context::scope("service", "invoices");
+            assert_eq!(render(), "msg request_id=def service=invoices");
+        }
+        assert_eq!(render(), "msg request_id=abc service=billing");
+        // the scoped fields aren't visible from other threads
+        let other = std::thread::spawn(crate::context::scoped_fields)
+            .join()
+            .unwrap();
+        assert!(other.is_empty());
+    }
+    assert_eq!(render(), "msg service=billing");
+    logger::clear_context().unwrap();
+}
+
+#[test]
+fn test_log_record_serde() {
+    init();
+    logger::add_context("service", "billing").unwrap();
+    let record = test_log_info(Level::ERROR);
+    logger::clear_context().unwrap();
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(json.contains("\"level\":\"ERROR\""));
+    assert!(json.contains("\"context\":{\"service\":\"billing\"}"));
+    let back: LogRecord = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, record);
+}
+
+#[test]
+fn test_column_placeholder() {
+    init();
+    logger::set_global_formatting("{file}:{line}:{column}").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "file.rs:1:5"
+    );
+    assert!(FileFormatter::try_from_string("app_{column}.log").is_err());
+
+    // the macros capture the column of their call site
+    let columns = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let captured = columns.clone();
+    logger::set_formatter(move |record| {
+        captured.lock().unwrap().push((record.line, record.column));
+        String::new()
+    })
+    .unwrap();
+    let line = line!() + 1;
+    crate::info!("first");
+    {
+        crate::info!("nested");
+    }
+    logger::remove_formatter().unwrap();
+    assert_eq!(*columns.lock().unwrap(), vec![(line, 5), (line + 2, 9)]);
+}
+
+#[test]
+fn test_iso8601_placeholder() {
+    use chrono::TimeZone;
+    let date_time = chrono::Utc
+        .with_ymd_and_hms(2025, 3, 5, 9, 5, 3)
+        .unwrap()
+        .fixed_offset()
+        + chrono::Duration::milliseconds(123);
+    assert_eq!(
+        helper::format_iso8601(&date_time),
+        "2025-03-05T09:05:03.123Z"
+    );
+    let plus_two = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+    assert_eq!(
+        helper::format_iso8601(&date_time.with_timezone(&plus_two)),
+        "2025-03-05T11:05:03.123+02:00"
+    );
+
+    init();
+    logger::set_timezone(TimeZoneChoice::FixedOffset(2 * 3600)).unwrap();
+    logger::set_global_formatting("{iso8601} {message}").unwrap();
+    let line = logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    let (ts, message) = line.split_once(' ').unwrap();
+    assert_eq!(message, "msg");
+    let parsed = chrono::DateTime::parse_from_rfc3339(ts).unwrap();
+    assert_eq!(parsed.offset().local_minus_utc(), 2 * 3600);
+    assert_eq!(ts.len(), "2025-03-05T11:05:03.123+02:00".len());
+
+    // the colons are replaced in the file names
+    let formatter = FileFormatter::try_from_string("app_{iso8601}.log").unwrap();
+    let config = Config::default();
+    let name = FileName::get_string_from_log_parts(formatter.format, &config);
+    assert!(name.starts_with("app_") && name.ends_with("Z.log"));
+    assert!(!name.contains(':'));
+    logger::set_timezone(TimeZoneChoice::Utc).unwrap();
+}
+
+#[test]
+fn test_run_id_placeholder() {
+    let id = crate::run_id();
+    assert_eq!(id.len(), 8);
+    assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(crate::run_id(), id);
+
+    init();
+    logger::set_global_formatting("[{run_id}] {message}").unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        format!("[{}] msg", id)
+    );
+
+    let formatter = FileFormatter::try_from_string("app_{run_id}.log").unwrap();
+    assert_eq!(
+        FileName::get_string_from_log_parts(formatter.format, &Config::default()),
+        format!("app_{}.log", id)
+    );
+}
+
+#[test]
+fn test_level_color_tag() {
+    init();
+    logger::set_global_formatting("<level>[{level}]<level> {message}").unwrap();
+    let wrappers = non_empty_wrappers("<level>[{level}]<level> {message}");
+    assert_eq!(wrappers[0].color, Some(LogColor::Level));
+    assert!(parse_string_to_wrappers("<bg_level>{level}<bg_level>").is_ok());
+
+    let render = |level| logger::string_log(&current_config(), &test_log_info(level), true);
+    for (level, color) in [
+        (Level::ERROR, LogColor::Red),
+        (Level::WARN, LogColor::Yellow),
+        (Level::INFO, LogColor::Green),
+        (Level::DEBUG, LogColor::Rgb(0, 255, 255)),
+        (Level::TRACE, LogColor::BrightBlack),
+    ] {
+        assert!(
+            render(level).starts_with(&format!("{}[\x1b[0m", color.get_ascii())),
+            "unexpected color for {}",
+            level
+        );
+    }
+
+    logger::set_level_color(Level::INFO, "c208").unwrap();
+    assert!(render(Level::INFO).starts_with(&LogColor::Indexed(208).get_ascii()));
+    assert!(matches!(
+        logger::set_level_color(Level::INFO, "level"),
+        Err(SetLevelColorError::IncorrectColorGiven(_))
+    ));
+    assert!(logger::set_level_color(Level::INFO, "pink").is_err());
+
+    // the automatic level colors follow the custom colors
+    logger::set_global_formatting("{message}").unwrap();
+    logger::set_auto_level_colors(true).unwrap();
+    assert_eq!(
+        render(Level::INFO),
+        format!("{}msg\x1b[0m", LogColor::Indexed(208).get_ascii())
+    );
+    assert_eq!(
+        render(Level::WARN),
+        format!("{}msg\x1b[0m", LogColor::Yellow.get_ascii())
+    );
+    init();
+}
+
+#[test]
+fn test_registered_placeholders() {
+    init();
+    // unknown placeholders are rejected until they are registered
+    assert!(logger::set_global_formatting("{tenant} {message}").is_err());
+
+    let tenant = std::sync::Arc::new(std::sync::Mutex::new("acme".to_string()));
+    let current = tenant.clone();
+    logger::register_placeholder("tenant", move || current.lock().unwrap().clone()).unwrap();
+    logger::set_global_formatting("[{tenant:<6}] {message}").unwrap();
+    let render = || logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    assert_eq!(render(), "[acme  ] msg");
+
+    // the function is called for each record
+    *tenant.lock().unwrap() = "globex".to_string();
+    assert_eq!(render(), "[globex] msg");
+
+    // an unregistered placeholder is rendered as empty
+    logger::unregister_placeholder("tenant").unwrap();
+    assert_eq!(render(), "[      ] msg");
+    assert!(logger::set_global_formatting("{tenant}").is_err());
+
+    for name in ["", "level", "two words", "a:b"] {
+        assert!(matches!(
+            logger::register_placeholder(name, String::new),
+            Err(RegisterPlaceholderError::IncorrectNameGiven(_))
+        ));
+    }
+    logger::register_placeholder("tenant", String::new).unwrap();
+    assert!(parse_string_to_wrappers("{tenant:%Y}").is_err());
+    assert!(FileFormatter::try_from_string("{tenant}.log").is_err());
+    init();
+}
+
+#[test]
+fn test_format_builder() {
+    use crate::logger::format_builder::LogFormat;
+
+    let built = LogFormat::builder()
+        .colored(LogColor::Green, |b| b.text("[").level().text("]"))
+        .text(" (")
+        .file_short(2)
+        .text(":")
+        .line()
+        .text(") - ")
+        .bold(|b| b.background(LogColor::Red, |b| b.message()))
+        .text(" ")
+        .dim(|b| b.dim(|b| b.thread()).pid())
+        .build();
+    let template = "<green>[{level}]<green> ({file_short:2}:{line}) - \
+                    <bold><bg_red>{message}<bg_red><bold> <dim>{thread}{pid}<dim>";
+    assert_eq!(built.formatter.parts, non_empty_wrappers(template));
+
+    // text is not parsed
+    let built = LogFormat::builder().text("{level} <red>").build();
+    assert_eq!(
+        built.formatter.parts[0].part,
+        LogPart::Text("{level} <red>".to_string())
+    );
+
+    init();
+    logger::set_level_format_built(
+        Level::INFO,
+        LogFormat::builder().level().text(" - ").message().build(),
+    )
+    .unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "INFO - msg"
+    );
+    init();
+}
+
+#[test]
+fn test_format_to_template_round_trip() {
+    init();
+    logger::register_placeholder("tenant", String::new).unwrap();
+    let formats = [
+        "{file}-{line} <green>[{level}]<green> - {message}",
+        "<bg_red><white>[{level:short}]<white><bg_red> {message}",
+        "<bold><red>{level:<5}<red><bold> <dim>{file_short:2}:{line:>4}:{column}<dim>",
+        "<#ff8800>{date:%Y-%m-%d}<#ff8800> <c208>{time}<c208> <level>{iso8601}<level>",
+        "<red>a <blue>{module:.10}<blue> b<red> <italic><underline>{thread}<underline><italic>",
+        "{{json}} \\<- -\\> {env:HOME} {tenant:^8} {context} {run_id} {pid} {elapsed_ms}",
+    ];
+    for format in formats {
+        let parsed = LogFormatter::parse_from_string(format).unwrap();
+        let template = parsed.to_template_string();
+        let reparsed = LogFormatter::parse_from_string(&template).unwrap();
+        assert_eq!(non_empty_wrappers(format), non_empty_wrappers(&template));
+        assert_eq!(reparsed.to_template_string(), template);
+    }
+
+    logger::set_level_formatting(Level::INFO, "<green>[{level}]<green> {message}").unwrap();
+    assert_eq!(
+        logger::get_level_formatting(Level::INFO),
+        "<green>[{level}]<green> {message}"
+    );
+    assert_eq!(logger::get_global_formatting(), None);
+    logger::set_global_formatting("{level:lower}: {message}").unwrap();
+    assert_eq!(
+        logger::get_global_formatting(),
+        Some("{level:lower}: {message}".to_string())
+    );
+    init();
+}
+
+#[test]
+fn test_file_formatting() {
+    init();
+    logger::set_global_formatting("<green>{level}<green> {message}").unwrap();
+    let render_file = |level| logger::render_file_log(&current_config(), &test_log_info(level));
+    // the file output mirrors the terminal format until a file format is set
+    assert_eq!(render_file(Level::INFO), "INFO msg");
+
+    logger::set_file_formatting(Level::INFO, "[{level}] {file}:{line} - {message}").unwrap();
+    assert_eq!(render_file(Level::INFO), "[INFO] file.rs:1 - msg");
+    assert_eq!(render_file(Level::WARN), "WARN msg");
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), false),
+        "INFO msg"
+    );
+
+    logger::set_file_global_formatting("{level:lower}|{message}").unwrap();
+    assert_eq!(render_file(Level::INFO), "info|msg");
+    assert_eq!(render_file(Level::ERROR), "error|msg");
+
+    assert!(logger::set_file_formatting(Level::INFO, "<red>{message}").is_err());
+    assert_eq!(render_file(Level::INFO), "info|msg");
+    init();
+}
+
+#[test]
+fn test_optional_sections() {
+    init();
+    logger::set_global_formatting("{context?[{context}] }{message}").unwrap();
+    let render = || logger::string_log(&current_config(), &test_log_info(Level::INFO), false);
+    // the section and its text are omitted while the placeholder is empty
+    assert_eq!(render(), "msg");
+    logger::add_context("user", "bob").unwrap();
+    assert_eq!(render(), "[user=bob] msg");
+
+    // nested sections, escaped characters and width specifiers
+    logger::set_global_formatting("{module?\\<{module:>8}{context? {{{context}}}}\\> }{message}")
+        .unwrap();
+    assert_eq!(render(), "<  module {user=bob}> msg");
+    let mut record = test_log_info(Level::INFO);
+    record.module_path = String::new();
+    assert_eq!(logger::string_log(&current_config(), &record, false), "msg");
+    logger::clear_context().unwrap();
+    assert_eq!(render(), "<  module> msg");
+
+    // the parts of a section take its colors and styles
+    let wrappers = non_empty_wrappers("<red>{context?<bold>ctx={context}<bold> }<red>{message}");
+    let LogPart::Optional(condition, section) = &wrappers[0].part else {
+        panic!("expected an optional section, got {:?}", wrappers[0].part);
+    };
+    assert_eq!(**condition, LogPart::Context);
+    assert_eq!(wrappers[0].color, Some(LogColor::Red));
+    let section: Vec<_> = section
+        .iter()
+        .filter(|w| w.part != LogPart::Text(String::new()))
+        .collect();
+    assert_eq!(section.len(), 3);
+    assert!(section.iter().all(|w| w.color == Some(LogColor::Red)));
+    assert!(section[..2].iter().all(|w| w.style == LogStyle::BOLD));
+    assert_eq!(section[2].style, LogStyle::default());
+    assert_eq!(wrappers[1].part, LogPart::Message);
+    assert_eq!(wrappers[1].color, None);
+
+    // the tags of a section are closed inside it
+    assert!(parse_string_to_wrappers("{context?<red>{context}}<red>").is_err());
+    assert!(parse_string_to_wrappers("{unknown?[{message}]}").is_err());
+    assert!(parse_string_to_wrappers("{context?[{context}]").is_err());
+    let err = parse_string_to_wrappers("ab {context?[{lien}]}").unwrap_err();
+    assert_eq!(err.position().unwrap().offset, 13);
+    assert!(FileFormatter::try_from_string("{hostname?{hostname}}.log").is_err());
+
+    let format =
+        "<red>{context?<bold>ctx={context}<bold> }<red>{module?[{module}{context? {context}}]}";
+    let template = LogFormatter::parse_from_string(format)
+        .unwrap()
+        .to_template_string();
+    let reparsed = LogFormatter::parse_from_string(&template).unwrap();
+    assert_eq!(reparsed.to_template_string(), template);
+    logger::add_context("user", "bob").unwrap();
+    logger::set_global_formatting(format).unwrap();
+    let expected = logger::string_log(&current_config(), &test_log_info(Level::INFO), true);
+    logger::set_global_formatting(&template).unwrap();
+    assert_eq!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), true),
+        expected
+    );
+    init();
+}
+
+#[test]
+fn test_multiline_messages() {
+    init();
+    logger::set_global_formatting("[<red>{level}<red>] {message} ({line})").unwrap();
+    let mut record = test_log_info(Level::INFO);
+    record.message = "first\nsecond\nthird".to_string();
+
+    // the terminal keeps the message as it is and the file escapes it by default
+    assert_eq!(
+        logger::render_log(&current_config(), &record, false),
+        "[INFO] first\nsecond\nthird (1)"
+    );
+    assert_eq!(
+        logger::render_file_log(&current_config(), &record),
+        "[INFO] first\\nsecond\\nthird (1)"
+    );
+
+    logger::set_multiline(MultilineStyle::Indent).unwrap();
+    assert_eq!(
+        logger::render_log(&current_config(), &record, false),
+        "[INFO] first\n       second\n       third (1)"
+    );
+    // the escape codes don't count in the indentation
+    assert_eq!(
+        logger::render_log(&current_config(), &record, true),
+        format!(
+            "[{}INFO\x1b[0m] first\n       second\n       third (1)",
+            LogColor::Red.get_ascii()
+        )
+    );
+    assert_eq!(
+        logger::render_file_log(&current_config(), &record),
+        "[INFO] first\n       second\n       third (1)"
+    );
+
+    logger::set_multiline(MultilineStyle::PrefixEach).unwrap();
+    logger::set_file_multiline(MultilineStyle::Escape).unwrap();
+    assert_eq!(
+        logger::render_log(&current_config(), &record, false),
+        "[INFO] first (1)\n[INFO] second (1)\n[INFO] third (1)"
+    );
+    assert_eq!(
+        logger::render_file_log(&current_config(), &record),
+        "[INFO] first\\nsecond\\nthird (1)"
+    );
+
+    // the single line messages are not changed
+    assert_eq!(
+        logger::render_log(&current_config(), &test_log_info(Level::INFO), false),
+        "[INFO] msg (1)"
+    );
+    init();
+}
+
+#[test]
+fn test_max_message_len() {
+    assert_eq!(helper::truncate_message("abcdef", 0), "abcdef");
+    assert_eq!(helper::truncate_message("abcdef", 6), "abcdef");
+    assert_eq!(
+        helper::truncate_message("abcdef", 4),
+        "abcd… [truncated 2 bytes]"
+    );
+    // the message is cut at a character boundary: `é` takes two bytes
+    assert_eq!(helper::truncate_message("aéb", 2), "a… [truncated 3 bytes]");
+
+    init();
+    logger::set_global_formatting("[{level}] {message}").unwrap();
+    logger::set_max_message_len(2).unwrap();
+    let mut record = test_log_info(Level::INFO);
+    record.message = "a\nbcd".to_string();
+    assert_eq!(
+        logger::render_log(&current_config(), &record, false),
+        "[INFO] a\n… [truncated 3 bytes]"
+    );
+    assert_eq!(
+        logger::render_file_log(&current_config(), &record),
+        "[INFO] a\\n… [truncated 3 bytes]"
+    );
+    logger::set_max_message_len(0).unwrap();
+    assert_eq!(
+        logger::render_log(&current_config(), &record, false),
+        "[INFO] a\nbcd"
+    );
+    init();
+}
+
+#[test]
+fn test_level_symbol_placeholder() {
+    init();
+    logger::set_global_formatting("<level>{symbol}<level> [{symbol:<2}] {message}").unwrap();
+    let render = |level| logger::string_log(&current_config(), &test_log_info(level), false);
+    // the levels have no symbol by default
+    assert_eq!(render(Level::INFO), " [  ] msg");
+
+    logger::set_level_symbol(Level::INFO, "✔").unwrap();
+    logger::set_level_symbol(Level::WARN, "⚠").unwrap();
+    assert_eq!(render(Level::INFO), "✔ [✔ ] msg");
+    assert_eq!(render(Level::WARN), "⚠ [⚠ ] msg");
+    assert_eq!(render(Level::ERROR), " [  ] msg");
+    // the symbol takes the colors of its tags
+    assert!(
+        logger::string_log(&current_config(), &test_log_info(Level::INFO), true)
+            .starts_with(&format!("{}✔\x1b[0m", LogColor::Green.get_ascii()))
+    );
+
+    logger::set_level_symbol(Level::INFO, "").unwrap();
+    assert_eq!(render(Level::INFO), " [  ] msg");
+    assert!(FileFormatter::try_from_string("{symbol}.log").is_err());
+    init();
+}
+
+#[test]
+fn test_stderr_threshold() {
+    use crate::logger::is_stderr_level;
+
+    // only the errors go to stderr by default
+    init();
+    assert_eq!(CONFIG.read().unwrap().stderr_threshold, Level::ERROR);
+    assert!(is_stderr_level(Level::ERROR, Level::ERROR));
+    assert!(!is_stderr_level(Level::WARN, Level::ERROR));
+
+    logger::set_stderr_threshold(Level::WARN).unwrap();
+    assert_eq!(CONFIG.read().unwrap().stderr_threshold, Level::WARN);
+    assert!(is_stderr_level(Level::ERROR, Level::WARN));
+    assert!(is_stderr_level(Level::WARN, Level::WARN));
+    assert!(!is_stderr_level(Level::INFO, Level::WARN));
+    assert!(is_stderr_level(Level::TRACE, Level::TRACE));
+    init();
+}
@@ -0,0 +1,65 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::test_log_info;
+use crate::logger::webhook::WebhookSink;
+use crate::Level;
+
+/// Starts an HTTP server answering 200 to every request, the bodies are sent to the receiver.
+fn start_server() -> (String, mpsc::Receiver<serde_json::Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            let _ = sender.send(serde_json::from_slice(&body).unwrap());
+        }
+    });
+    (url, receiver)
+}
+
+#[test]
+fn webhook_posts_and_coalesces_alerts() {
+    let (url, requests) = start_server();
+    let sink = WebhookSink::start(&url, Level::ERROR, Duration::from_millis(500)).unwrap();
+
+    sink.send(&test_log_info(Level::ERROR));
+    let first = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(first["level"], "ERROR");
+    assert_eq!(first["message"], "msg");
+    assert_eq!(first["location"], "file.rs:1");
+    assert_eq!(first["suppressed"], 0);
+    assert_eq!(first["text"], "[ERROR] msg (file.rs:1)");
+
+    // sent during the interval: posted together once it ends, the warning is ignored
+    sink.send(&test_log_info(Level::WARN));
+    for _ in 0..3 {
+        sink.send(&test_log_info(Level::ERROR));
+    }
+    let second = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(second["suppressed"], 2);
+    assert_eq!(second["text"], "[ERROR] msg (file.rs:1) (+2 more)");
+    assert!(requests.recv_timeout(Duration::from_secs(1)).is_err());
+}
@@ -0,0 +1,65 @@
+// tests/allocations.rs
+//
+// Counts the allocations made by a log written to a file, so the hot path doesn't start cloning
+// the formats or the config per record again.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use loggit::{
+    info,
+    logger::{init, set_file, set_global_formatting, set_print_to_terminal},
+};
+
+/// The system allocator, counting the allocations
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const RECORDS: usize = 200;
+
+#[test]
+fn file_log_allocations() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_global_formatting("{date} {time} [{level}] <red>{file}:{line}<red> {message}").unwrap();
+    let file_name = format!(
+        "allocations_{}.log",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    set_file(&file_name).unwrap();
+    // the first log initializes the lazy values
+    info!("warm up");
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for i in 0..RECORDS {
+        info!("record {}", i);
+    }
+    let per_record = (ALLOCATIONS.load(Ordering::Relaxed) - before) / RECORDS;
+    // about 40 when the formats and the config are borrowed from the snapshot, it was 76 when
+    // they were cloned for each record
+    assert!(
+        per_record <= 50,
+        "{} allocations per record, expected at most 50",
+        per_record
+    );
+
+    init();
+    let _ = fs::remove_file(&file_name);
+}
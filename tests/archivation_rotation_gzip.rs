@@ -0,0 +1,39 @@
+// tests/archivation_rotation_gzip.rs
+//
+// Checks that size‑based rotation with the gzip compression produces a .gz
+// archive of the old file in the configured archive directory.
+
+mod common;
+
+use std::{fs, io::Read};
+
+use loggit::logger::{set_archive_dir, set_compression};
+
+#[test]
+fn rotation_creates_gzip_archive() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("gzrot_{ts}");
+    let archive_dir = format!("gzarch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+
+    assert!(set_compression("tar").is_err());
+    common::set_rotated_file(&format!("{prefix}_{{date}}_{{time}}.log"), Some("gzip"));
+    common::log_messages("msg");
+
+    // the archives are readable with `zcat`, the first one holds the first records
+    let archives = common::entries(&archive_dir);
+    assert!(!archives.is_empty(), "no archive produced after rotation");
+    let mut content = String::new();
+    for name in &archives {
+        assert!(name.ends_with(".gz"), "{name} isn't a gzip file");
+        let path = format!("{archive_dir}/{name}");
+        assert!(fs::metadata(&path).unwrap().len() > 0, "{name} is empty");
+        flate2::read::GzDecoder::new(fs::File::open(path).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+    }
+    assert!(content.contains("msg 0: lorem ipsum"), "{content}");
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
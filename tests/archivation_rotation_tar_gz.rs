@@ -0,0 +1,80 @@
+// tests/archivation_rotation_tar_gz.rs
+//
+// Checks that the rotated files of a day are bundled in a single tarball in the
+// configured archive directory with the `tar.gz` compression.
+#![cfg(feature = "tar-gz")]
+
+use std::{fs, io::Read};
+
+use loggit::{
+    info,
+    logger::{
+        add_rotation, init, set_archive_dir, set_compression, set_file, set_log_level,
+        set_print_to_terminal,
+    },
+    Level,
+};
+
+#[test]
+fn rotations_are_bundled_in_a_tarball() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::INFO).unwrap();
+
+    // Unique prefixes so parallel `cargo test` jobs never clash.
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let prefix = format!("tgzrot_{ts}-");
+    let archive_dir = format!("tgzarch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+
+    // the same name for every file, the tarball numbers the entries
+    set_file(&format!("{prefix}{{date}}.log")).unwrap();
+    set_compression("tar.gz").unwrap();
+    add_rotation("1 KB").unwrap();
+
+    // each record is about 300 bytes, so 10 records make at least two rotations
+    let filler = "x".repeat(256);
+    for n in 0..10 {
+        info!("msg {n}: {filler}");
+    }
+
+    // a single tarball for the day, with an entry per rotated file
+    let tarballs: Vec<_> = fs::read_dir(&archive_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(tarballs.len(), 1, "{tarballs:?}");
+    let name = tarballs[0]
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    assert!(name.starts_with(&format!("{prefix}logs-")), "{name}");
+    assert!(name.ends_with(".tar.gz"), "{name}");
+
+    let tarball = fs::File::open(&tarballs[0]).unwrap();
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(tarball));
+    let mut names = Vec::new();
+    let mut content = String::new();
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        names.push(entry.path().unwrap().to_string_lossy().to_string());
+        entry.read_to_string(&mut content).unwrap();
+    }
+    assert!(names.len() >= 2, "{names:?}");
+    let mut unique = names.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), names.len(), "{names:?}");
+    assert!(content.contains("msg 0: "));
+    assert!(content.contains("msg 3: "));
+
+    init();
+    for entry in fs::read_dir(".").unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_string_lossy().contains(&prefix) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    let _ = fs::remove_dir_all(&archive_dir);
+}
@@ -0,0 +1,56 @@
+// tests/archivation_rotation_zstd.rs
+//
+// Checks that a rotation with the zstd compression writes a `.zst` archive
+// which decompresses to the content of the old file.
+#![cfg(feature = "zstd")]
+
+use std::fs;
+
+use loggit::{
+    info,
+    logger::{
+        add_rotation, init, set_archive_dir, set_compression, set_file, set_log_level,
+        set_print_to_terminal,
+    },
+    Level,
+};
+
+#[test]
+fn rotation_creates_zstd_archive() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::INFO).unwrap();
+
+    // Unique prefixes so parallel `cargo test` jobs never clash.
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let log_file = format!("zstdrot_{ts}.log");
+    let archive_dir = format!("zstdarch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+
+    set_file(&log_file).unwrap();
+    set_compression("zstd").unwrap();
+    add_rotation("1 KB").unwrap();
+
+    // three records of about 300 bytes fit in the file, the fourth one rotates it
+    let filler = "x".repeat(256);
+    for n in 0..3 {
+        info!("msg {n}: {filler}");
+    }
+    let original = fs::read(&log_file).unwrap();
+    info!("msg 3: {filler}");
+
+    let archive = format!("{archive_dir}/{log_file}.zst");
+    let decompressed = zstd::decode_all(fs::File::open(&archive).unwrap()).unwrap();
+    assert_eq!(decompressed, original);
+    assert!(fs::metadata(&archive).unwrap().len() < original.len() as u64);
+    assert!(fs::metadata(&log_file).is_err(), "the old file is kept");
+
+    init();
+    for entry in fs::read_dir(".").unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_string_lossy().contains(&format!("zstdrot_{ts}")) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    let _ = fs::remove_dir_all(&archive_dir);
+}
@@ -0,0 +1,40 @@
+// tests/archivation_subdirectory.rs
+//
+// Checks that the log files written in a subdirectory are archived in the archive directory
+// itself, named after the file without its directories.
+
+mod common;
+
+use std::fs;
+
+use loggit::logger::set_archive_dir;
+
+#[test]
+fn rotation_archives_files_of_a_subdirectory() {
+    common::init_quiet();
+    let ts = common::unique();
+    let log_dir = format!("subdir_logs_{ts}");
+    let archive_dir = format!("subdir_arch_{ts}");
+    fs::create_dir(&log_dir).unwrap();
+    set_archive_dir(&archive_dir).unwrap();
+
+    common::set_rotated_file(&format!("{log_dir}/app_{{date}}_{{time}}.log"), Some("zip"));
+    common::log_messages("msg");
+
+    let archives = common::entries(&archive_dir);
+    assert!(!archives.is_empty(), "no archive produced after rotation");
+    for name in &archives {
+        assert!(
+            name.starts_with("app_") && name.ends_with(".log.zip"),
+            "{name} isn't the archive of a log file"
+        );
+        let file = fs::File::open(format!("{archive_dir}/{name}")).unwrap();
+        let mut zip = zip::ZipArchive::new(file).unwrap();
+        assert_eq!(
+            zip.by_index(0).unwrap().name(),
+            name.trim_end_matches(".zip")
+        );
+    }
+
+    common::clean_up(&log_dir, &[&log_dir, &archive_dir]);
+}
@@ -0,0 +1,52 @@
+// tests/archive_checksum.rs
+//
+// Checks that each archive gets a `<archive>.sha256` file with the hash of the archive in the
+// format of `sha256sum`, recomputed here, and that a purge removes it with its archive.
+#![cfg(feature = "checksum")]
+
+mod common;
+
+use std::fs;
+
+use loggit::{
+    logger::{purge_archives, set_archive_checksum, set_archive_dir, set_archive_max_total_size},
+    ChecksumAlgo,
+};
+use sha2::{Digest, Sha256};
+
+#[test]
+fn archives_get_their_checksum() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("checksum_{ts}");
+    let archive_dir = format!("checksum_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    set_archive_checksum(Some(ChecksumAlgo::Sha256)).unwrap();
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+    common::log_messages("msg");
+
+    let names = common::entries(&archive_dir);
+    let archives: Vec<&String> = names.iter().filter(|name| name.ends_with(".zip")).collect();
+    assert!(!archives.is_empty(), "no archive produced after rotation");
+    assert_eq!(
+        names.len(),
+        2 * archives.len(),
+        "not one checksum by archive: {names:?}"
+    );
+    for name in archives {
+        let content = fs::read(format!("{archive_dir}/{name}")).unwrap();
+        let hex: String = Sha256::digest(&content)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let checksum = fs::read_to_string(format!("{archive_dir}/{name}.sha256")).unwrap();
+        assert_eq!(checksum, format!("{hex}  {name}\n"));
+    }
+
+    // the checksums go with their archives
+    set_archive_max_total_size("0 KB").unwrap();
+    assert!(purge_archives().unwrap() >= 1);
+    assert!(common::entries(&archive_dir).is_empty());
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
@@ -0,0 +1,37 @@
+// tests/archive_dir_change.rs
+//
+// Checks that a new archive directory set with `set_archive_dir` applies from the next rotation,
+// even once archives were already written to the previous one.
+
+mod common;
+
+use loggit::logger::set_archive_dir;
+
+#[test]
+fn archive_dir_applies_from_the_next_rotation() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("archdirchange_{ts}");
+    let first_dir = format!("archdirchange_first_{ts}");
+    let second_dir = format!("archdirchange_second_{ts}");
+    set_archive_dir(&first_dir).unwrap();
+
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+    common::log_messages("first");
+    let first = common::files(&first_dir, &prefix);
+    assert!(!first.is_empty(), "no archive in the first directory");
+
+    set_archive_dir(&second_dir).unwrap();
+    common::log_messages("second");
+    assert!(
+        !common::files(&second_dir, &prefix).is_empty(),
+        "no archive in the new directory"
+    );
+    assert_eq!(
+        common::files(&first_dir, &prefix),
+        first,
+        "archived to the old directory"
+    );
+
+    common::clean_up(&prefix, &[&first_dir, &second_dir]);
+}
@@ -0,0 +1,131 @@
+// tests/archive_handler.rs
+//
+// Checks that the archives are given to the handler set with `set_archive_handler`: the built-in
+// `MoveToDir` moves them to a second directory from the background thread without replacing the
+// ones already there, and a failing handler keeps them or has them removed by
+// `ArchiveHandlerOptions::keep_on_failure`.
+
+mod common;
+
+use std::{
+    io::Read,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use loggit::{
+    logger::{
+        archivation::MoveToDir, flush, set_archive_dir, set_archive_handler,
+        set_archive_handler_options,
+    },
+    ArchiveHandler, ArchiveHandlerOptions,
+};
+
+/// A handler failing on every archive.
+struct Failing;
+
+impl ArchiveHandler for Failing {
+    fn handle(&self, _archive: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        Err("the upload failed".into())
+    }
+}
+
+/// Counts the archives given to the handler it wraps.
+struct Counting(MoveToDir, Arc<AtomicUsize>);
+
+impl ArchiveHandler for Counting {
+    fn handle(&self, archive: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        self.1.fetch_add(1, Ordering::SeqCst);
+        self.0.handle(archive)
+    }
+}
+
+/// Returns the content of the file archived in the zip archive.
+fn unzip(path: &str) -> String {
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+    let mut content = String::new();
+    zip.by_index(0)
+        .unwrap()
+        .read_to_string(&mut content)
+        .unwrap();
+    content
+}
+
+/// Logs to new files of the prefix until they are rotated several times.
+fn log_with_rotations(prefix: &str, archive_dir: &str) {
+    set_archive_dir(archive_dir).unwrap();
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+    common::log_messages("msg");
+    flush().unwrap();
+}
+
+#[test]
+fn archives_are_handled() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("handler_{ts}");
+
+    // moved to the backup directory in the background
+    let archive_dir = format!("handler_arch_{ts}");
+    let backup_dir = format!("handler_backup_{ts}");
+    let rotations = Arc::new(AtomicUsize::new(0));
+    set_archive_handler(Arc::new(Counting(
+        MoveToDir::new(&backup_dir),
+        rotations.clone(),
+    )))
+    .unwrap();
+    set_archive_handler_options(ArchiveHandlerOptions {
+        background: true,
+        ..Default::default()
+    })
+    .unwrap();
+    log_with_rotations(&format!("{prefix}_moved"), &archive_dir);
+    let moved = common::entries(&backup_dir);
+    assert!(
+        !moved.is_empty(),
+        "no archive moved to the backup directory"
+    );
+    // the rotations of the same second give the same names, none is replaced
+    assert_eq!(moved.len(), rotations.load(Ordering::SeqCst));
+    let mut content = common::content(".", &format!("{prefix}_moved"));
+    for name in &moved {
+        assert!(name.ends_with(".zip"), "{name} isn't an archive");
+        content += &unzip(&format!("{backup_dir}/{name}"));
+    }
+    common::assert_all_logged(&content, "msg");
+    assert!(
+        common::entries(&archive_dir).is_empty(),
+        "an archive wasn't moved"
+    );
+
+    // kept when the handler fails
+    let kept_dir = format!("handler_kept_{ts}");
+    set_archive_handler(Arc::new(Failing)).unwrap();
+    set_archive_handler_options(ArchiveHandlerOptions::default()).unwrap();
+    log_with_rotations(&format!("{prefix}_kept"), &kept_dir);
+    assert!(
+        !common::entries(&kept_dir).is_empty(),
+        "the archives weren't kept"
+    );
+
+    // removed when the handler fails
+    let removed_dir = format!("handler_removed_{ts}");
+    set_archive_handler_options(ArchiveHandlerOptions {
+        keep_on_failure: false,
+        ..Default::default()
+    })
+    .unwrap();
+    log_with_rotations(&format!("{prefix}_removed"), &removed_dir);
+    assert!(
+        common::entries(&removed_dir).is_empty(),
+        "the archives weren't removed"
+    );
+
+    common::clean_up(
+        &prefix,
+        &[&archive_dir, &backup_dir, &kept_dir, &removed_dir],
+    );
+}
@@ -0,0 +1,68 @@
+// tests/archive_mode.rs
+//
+// Checks that `set_archive_mode` chooses what a rotation does with the old file whatever the
+// compression is: moved into the archive directory untouched, or left next to the new file.
+
+mod common;
+
+use loggit::{
+    logger::{init, set_archive_dir, set_archive_mode},
+    ArchiveMode,
+};
+
+/// Logs the messages with the mode and the zip compression, returns the prefix of the log files.
+fn log_with_mode(mode: ArchiveMode, archive_dir: &str) -> String {
+    common::init_quiet();
+    let prefix = format!("archmode_{}", common::unique());
+    set_archive_dir(archive_dir).unwrap();
+    set_archive_mode(mode).unwrap();
+    // the compression is ignored by the modes without compression
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+    common::log_messages("msg");
+    init();
+    prefix
+}
+
+/// The old files are moved into the archive directory uncompressed.
+fn check_move_mode() {
+    let archive_dir = format!("archmode_move_{}", common::unique());
+    let prefix = log_with_mode(ArchiveMode::Move, &archive_dir);
+
+    let archived = common::files(&archive_dir, &prefix);
+    assert!(!archived.is_empty(), "no file moved after rotation");
+    for name in &archived {
+        assert!(name.ends_with(".log"), "{name} isn't a plain log file");
+    }
+    assert_eq!(
+        common::files(".", &prefix).len(),
+        1,
+        "the old files are left in place"
+    );
+    let all = common::content(".", &prefix) + &common::content(&archive_dir, &prefix);
+    common::assert_all_logged(&all, "msg");
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
+
+/// The old files are left next to the new one, nothing is archived.
+fn check_leave_in_place_mode() {
+    let archive_dir = format!("archmode_leave_{}", common::unique());
+    let prefix = log_with_mode(ArchiveMode::LeaveInPlace, &archive_dir);
+
+    let logs = common::files(".", &prefix);
+    assert!(logs.len() >= 2, "no new log file after rotation: {logs:?}");
+    assert!(
+        common::entries(&archive_dir).is_empty(),
+        "a file was archived"
+    );
+    common::assert_all_logged(&common::content(".", &prefix), "msg");
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
+
+// the modes are checked one after the other, the config is shared by the whole test binary
+#[test]
+fn archive_mode_chooses_what_is_done_with_the_old_files() {
+    check_move_mode();
+    check_leave_in_place_mode();
+}
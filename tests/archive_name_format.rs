@@ -0,0 +1,49 @@
+// tests/archive_name_format.rs
+//
+// Checks that the archives of the rotated files are named by the format set with
+// `set_archive_name_format`, numbered when the name is already taken, and that no record is lost.
+
+mod common;
+
+use std::{fs, io::Read};
+
+use loggit::logger::{set_archive_dir, set_archive_name_format};
+
+#[test]
+fn archives_are_named_by_the_format() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("archname_{ts}");
+    let archive_dir = format!("archname_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+
+    assert!(set_archive_name_format("../{name}.gz").is_err());
+    // every archive of the day gets the same name, so they are numbered
+    set_archive_name_format("{name}-rotated-{date}.{ext}.gz").unwrap();
+    common::set_rotated_file(&format!("{prefix}.log"), Some("gzip"));
+    common::log_messages("msg");
+
+    let names = common::entries(&archive_dir);
+    assert!(names.len() >= 2, "not enough rotations: {names:?}");
+    for name in &names {
+        assert!(
+            name.starts_with(&prefix) && name.contains("-rotated-") && name.ends_with(".gz"),
+            "{name} isn't named by the format"
+        );
+    }
+    assert!(
+        names.iter().any(|name| name.ends_with(".log(1).gz")),
+        "the archives aren't numbered: {names:?}"
+    );
+
+    let mut content = common::content(".", &prefix);
+    for name in &names {
+        let file = fs::File::open(format!("{archive_dir}/{name}")).unwrap();
+        flate2::read::GzDecoder::new(file)
+            .read_to_string(&mut content)
+            .unwrap();
+    }
+    common::assert_all_logged(&content, "msg");
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
@@ -0,0 +1,74 @@
+// tests/archive_retention.rs
+//
+// Checks that the archives older than the retention age are removed by `purge_archives` and on
+// rotation, and that the other files of the archive directory are kept.
+
+use std::{
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use loggit::{
+    info,
+    logger::{
+        add_rotation, init, purge_archives, set_archive_dir, set_archive_retention_age,
+        set_compression, set_file, set_print_to_terminal,
+    },
+};
+
+/// Creates a file in the archive directory, modified `days` ago.
+fn backdated(dir: &str, name: &str, days: u64) -> String {
+    let path = format!("{dir}/{name}");
+    let file = fs::File::create(&path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60))
+        .unwrap();
+    path
+}
+
+#[test]
+fn old_archives_are_removed() {
+    init();
+    set_print_to_terminal(false).unwrap();
+
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let prefix = format!("retained_{ts}");
+    let archive_dir = format!("retention_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    set_file(&format!("{prefix}_{{date}}_{{time}}.log")).unwrap();
+
+    assert!(set_archive_retention_age("1 KB").is_err());
+    assert!(set_archive_retention_age("12:00").is_err());
+    set_archive_retention_age("30 day").unwrap();
+
+    let old = backdated(&archive_dir, &format!("{prefix}_old.log.zip"), 40);
+    let recent = backdated(&archive_dir, &format!("{prefix}_recent.log.zip"), 10);
+    // not archives of the log file: another prefix, another extension, not a zip archive
+    let other_prefix = backdated(&archive_dir, &format!("other_{ts}_old.log.zip"), 40);
+    let other_extension = backdated(&archive_dir, &format!("{prefix}_old.txt.zip"), 40);
+    let not_zip = backdated(&archive_dir, &format!("{prefix}_old.log"), 40);
+
+    assert_eq!(purge_archives().unwrap(), 1);
+    assert!(fs::metadata(&old).is_err());
+    for kept in [&recent, &other_prefix, &other_extension, &not_zip] {
+        assert!(fs::metadata(kept).is_ok(), "{kept} was removed");
+    }
+
+    // the rotation removes them as well
+    let old = backdated(&archive_dir, &format!("{prefix}_older.log.zip"), 40);
+    set_compression("zip").unwrap();
+    add_rotation("1 KB").unwrap();
+    for n in 0..50 {
+        info!("msg {n}: lorem ipsum dolor sit amet, consectetur adipiscing elit.");
+    }
+    assert!(fs::metadata(&old).is_err());
+    assert!(fs::metadata(&recent).is_ok());
+
+    init();
+    for entry in fs::read_dir(".").unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_string_lossy().contains(&prefix) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    let _ = fs::remove_dir_all(&archive_dir);
+}
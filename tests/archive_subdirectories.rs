@@ -0,0 +1,56 @@
+// tests/archive_subdirectories.rs
+//
+// Checks that the archives are written to the subdirectory of their date set with
+// `set_archive_subdir_format`, created on demand, and that a purge looks into the
+// subdirectories and removes the ones it empties. Two dates are simulated with the time zones
+// UTC+14 and UTC-12, whose dates always differ.
+
+mod common;
+
+use loggit::{
+    logger::{
+        purge_archives, set_archive_dir, set_archive_max_total_size, set_archive_subdir_format,
+        set_timezone,
+    },
+    TimeZoneChoice,
+};
+
+#[test]
+fn archives_are_sorted_by_date() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("subdirs_{ts}");
+    let archive_dir = format!("subdirs_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    assert!(set_archive_subdir_format("../{date}").is_err());
+    assert!(set_archive_subdir_format("{date}/{message}").is_err());
+    set_archive_subdir_format("{date:%Y-%m-%d}").unwrap();
+
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+
+    for offset in [14, -12] {
+        set_timezone(TimeZoneChoice::FixedOffset(offset * 3600)).unwrap();
+        common::log_messages("msg");
+    }
+
+    let subdirs = common::entries(&archive_dir);
+    assert_eq!(subdirs.len(), 2, "not one directory by date: {subdirs:?}");
+    for subdir in &subdirs {
+        assert!(
+            chrono::NaiveDate::parse_from_str(subdir, "%Y-%m-%d").is_ok(),
+            "{subdir} isn't a date"
+        );
+        let archives = common::entries(&format!("{archive_dir}/{subdir}"));
+        assert!(!archives.is_empty(), "no archive in {subdir}");
+        for name in archives {
+            assert!(name.starts_with(&prefix) && name.ends_with(".log.zip"));
+        }
+    }
+
+    // the purge finds the archives of both dates and removes the emptied directories
+    set_archive_max_total_size("0 KB").unwrap();
+    assert!(purge_archives().unwrap() >= 2);
+    assert!(common::entries(&archive_dir).is_empty());
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
@@ -0,0 +1,69 @@
+// tests/archive_total_size.rs
+//
+// Checks that the oldest archives are removed until they fit in the maximal total size, after the
+// ones older than the retention age.
+
+use std::{
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use loggit::logger::{
+    init, purge_archives, set_archive_dir, set_archive_max_total_size, set_archive_retention_age,
+    set_file, set_print_to_terminal,
+};
+
+/// Creates a file of `size` bytes in the archive directory, modified `days` ago.
+fn archive(dir: &str, name: &str, size: usize, days: u64) -> String {
+    let path = format!("{dir}/{name}");
+    fs::write(&path, vec![0u8; size]).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60))
+        .unwrap();
+    path
+}
+
+#[test]
+fn oldest_archives_are_removed_beyond_the_total_size() {
+    init();
+    set_print_to_terminal(false).unwrap();
+
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let prefix = format!("capped_{ts}");
+    let archive_dir = format!("capped_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    set_file(&format!("{prefix}_{{date}}.log")).unwrap();
+
+    assert!(set_archive_max_total_size("1 day").is_err());
+    set_archive_max_total_size("1 KB").unwrap();
+    set_archive_retention_age("30 day").unwrap();
+
+    let expired = archive(&archive_dir, &format!("{prefix}_1.log.zip"), 100, 40);
+    let oldest = archive(&archive_dir, &format!("{prefix}_2.log.zip"), 400, 3);
+    let older = archive(&archive_dir, &format!("{prefix}_3.log.zip"), 400, 2);
+    let newest = archive(&archive_dir, &format!("{prefix}_4.log.zip"), 400, 1);
+    // not an archive of the log file, it doesn't count in the total size
+    let other = archive(&archive_dir, &format!("other_{ts}.log.zip"), 2000, 5);
+
+    // the expired one by the age, then 1200 bytes > 1 KB: the oldest one
+    assert_eq!(purge_archives().unwrap(), 2);
+    for removed in [&expired, &oldest] {
+        assert!(fs::metadata(removed).is_err(), "{removed} was kept");
+    }
+    for kept in [&older, &newest, &other] {
+        assert!(fs::metadata(kept).is_ok(), "{kept} was removed");
+    }
+    assert_eq!(purge_archives().unwrap(), 0);
+
+    init();
+    for entry in fs::read_dir(".").unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_string_lossy().contains(&prefix) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    let _ = fs::remove_dir_all(&archive_dir);
+}
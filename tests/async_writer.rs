@@ -0,0 +1,59 @@
+// tests/async_writer.rs
+//
+// Checks the async mode: the logs of several threads are written by the writer thread, none is
+// dropped when the queue is full, and `flush` waits for them.
+
+use std::fs;
+
+use loggit::{
+    info,
+    logger::{
+        flush, init, set_async, set_async_with_capacity, set_file, set_global_formatting,
+        set_print_to_terminal,
+    },
+};
+
+#[test]
+fn async_mode_writes_every_record() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_global_formatting("{message}").unwrap();
+    let file_name = format!(
+        "async_writer_{}.log",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    set_file(&file_name).unwrap();
+    assert!(set_async_with_capacity(0).is_err());
+
+    // a queue of one record is always full, the logging calls wait for the writer
+    set_async_with_capacity(1).unwrap();
+    let threads: Vec<_> = (0..4)
+        .map(|t| {
+            std::thread::spawn(move || {
+                for i in 0..250 {
+                    info!("thread {} record {}", t, i);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    flush().unwrap();
+    let contents = fs::read_to_string(&file_name).unwrap();
+    assert_eq!(contents.lines().count(), 1000);
+    for t in 0..4 {
+        assert!(contents.contains(&format!("thread {} record 249\n", t)));
+    }
+
+    // the queued records are written before going back to the synchronous mode
+    set_async(true).unwrap();
+    info!("queued");
+    set_async(false).unwrap();
+    info!("synchronous");
+    let contents = fs::read_to_string(&file_name).unwrap();
+    assert!(contents.ends_with("queued\nsynchronous\n"));
+
+    init();
+    let _ = fs::remove_file(&file_name);
+}
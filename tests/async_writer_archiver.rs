@@ -0,0 +1,62 @@
+// tests/async_writer_archiver.rs
+//
+// Checks that an archiver logging and flushing from the writer thread of the async mode doesn't
+// wait on the queue it is the only one to empty, even when the queue is full.
+
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    time::Duration,
+};
+
+use loggit::{
+    error,
+    logger::{add_file_for_levels, add_rotation_for, flush, set_archiver, set_async_with_capacity},
+    Level,
+};
+
+#[test]
+fn archiver_logs_from_the_writer_thread() {
+    common::init_quiet();
+    let prefix = format!("asyncarch_{}", common::unique());
+    let logs = add_file_for_levels(&[Level::INFO], &format!("{prefix}_{{time}}.log")).unwrap();
+    add_rotation_for(logs, "1 KB").unwrap();
+    // the archiver logs to another file, the rotated one is locked
+    add_file_for_levels(&[Level::ERROR], &format!("{prefix}_errors.log")).unwrap();
+    // the rotations of the same second reuse the names, the copies are numbered
+    let copies = AtomicUsize::new(0);
+    let copy_prefix = prefix.clone();
+    set_archiver(move |path| {
+        // the logging thread fills the queue meanwhile
+        std::thread::sleep(Duration::from_millis(50));
+        error!("archived {}", path.display());
+        flush()?;
+        let n = copies.fetch_add(1, Ordering::SeqCst);
+        std::fs::copy(path, format!("{copy_prefix}_copy_{n}.log"))?;
+        Ok(())
+    })
+    .unwrap();
+    set_async_with_capacity(2).unwrap();
+
+    let (done_sender, done_receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        common::log_messages("msg");
+        flush().unwrap();
+        let _ = done_sender.send(());
+    });
+    assert!(
+        done_receiver.recv_timeout(Duration::from_secs(30)).is_ok(),
+        "the writer thread is stuck on its own queue"
+    );
+
+    let errors = common::content(".", &format!("{prefix}_errors"));
+    assert!(errors.contains("archived "), "the archiver didn't log");
+    let logs = common::content(".", &format!("{prefix}_"));
+    common::assert_all_logged(&logs, "msg");
+
+    common::clean_up(&prefix, &[]);
+}
@@ -0,0 +1,51 @@
+// tests/channel_sink.rs
+//
+// Checks that a channel sink receives the records of every thread and detaches itself once its
+// receiver is dropped.
+
+use std::sync::mpsc;
+use std::thread;
+
+use loggit::{
+    info,
+    logger::{add_channel_sink, init, remove_sink, set_print_to_terminal},
+};
+
+const THREADS: usize = 4;
+const MSG_PER_THREAD: usize = 50;
+
+#[test]
+fn channel_sink_captures_records() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let id = add_channel_sink(sender).unwrap();
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            thread::spawn(move || {
+                for n in 0..MSG_PER_THREAD {
+                    info!("thread={id} line={n}");
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().expect("worker panicked");
+    }
+
+    let mut messages: Vec<String> = receiver.try_iter().map(|r| r.message).collect();
+    messages.sort();
+    let mut expected: Vec<String> = (0..THREADS)
+        .flat_map(|id| (0..MSG_PER_THREAD).map(move |n| format!("thread={id} line={n}")))
+        .collect();
+    expected.sort();
+    assert_eq!(messages, expected);
+
+    // the logging goes on without the receiver, and the sink is removed
+    drop(receiver);
+    info!("nobody listens");
+    info!("still logging");
+    assert!(remove_sink(id).is_err());
+    init();
+}
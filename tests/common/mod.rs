@@ -0,0 +1,96 @@
+// tests/common/mod.rs
+//
+// Fixture shared by the tests rotating log files: a quiet logger, a file rotated every KB, the
+// messages filling it and the clean-up. Each test binary only uses a part of it.
+#![allow(dead_code)]
+
+use std::fs;
+
+use loggit::{
+    info,
+    logger::{add_rotation, init, set_compression, set_file, set_log_level, set_print_to_terminal},
+    Level,
+};
+
+/// Each message is a line of over a hundred bytes, so the messages fill several files rotated
+/// after 1 KB.
+pub const MESSAGES: usize = 50;
+
+/// Resets the logger, which then writes the infos and above to the files only.
+pub fn init_quiet() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::INFO).unwrap();
+}
+
+/// Returns a number unique to the test, so parallel `cargo test` jobs never share a file.
+pub fn unique() -> i64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap()
+}
+
+/// Logs to the file of the pattern, rotated as soon as it exceeds 1 KB and archived with the
+/// compression if one is given.
+pub fn set_rotated_file(pattern: &str, compression: Option<&str>) {
+    set_file(pattern).unwrap();
+    if let Some(compression) = compression {
+        set_compression(compression).unwrap();
+    }
+    add_rotation("1 KB").unwrap();
+}
+
+/// Logs the [MESSAGES] messages `{tag} {n}: lorem ipsum…`.
+pub fn log_messages(tag: &str) {
+    for n in 0..MESSAGES {
+        info!("{tag} {n}: lorem ipsum dolor sit amet, consectetur adipiscing elit.");
+    }
+}
+
+/// Checks that none of the messages logged by [log_messages] is missing from the content.
+pub fn assert_all_logged(content: &str, tag: &str) {
+    for n in 0..MESSAGES {
+        assert!(
+            content.contains(&format!("{tag} {n}: ")),
+            "{tag} {n} is lost"
+        );
+    }
+}
+
+/// Returns the names of the entries of the directory, none if it doesn't exist.
+pub fn entries(dir: &str) -> Vec<String> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Returns the names of the entries of the directory starting with the prefix.
+pub fn files(dir: &str, prefix: &str) -> Vec<String> {
+    let mut names: Vec<String> = entries(dir)
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Returns the content of the text files of the directory starting with the prefix.
+pub fn content(dir: &str, prefix: &str) -> String {
+    files(dir, prefix)
+        .iter()
+        .map(|name| fs::read_to_string(format!("{dir}/{name}")).unwrap())
+        .collect()
+}
+
+/// Resets the logger, removes the files of the prefix from the current directory and the
+/// directories.
+pub fn clean_up(prefix: &str, dirs: &[&str]) {
+    init();
+    for name in files(".", prefix) {
+        let _ = fs::remove_file(name);
+    }
+    for dir in dirs {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,53 @@
+// tests/csv_output.rs
+//
+// Checks the CSV encoding of the file output: a header row, then one row per log with the
+// fields quoted per RFC 4180 when needed.
+
+use std::fs;
+
+use loggit::{
+    info,
+    logger::{init, set_file, set_file_output_format, set_print_to_terminal},
+    warn, OutputFormat,
+};
+
+#[test]
+fn csv_file_output() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    let prefix = format!(
+        "csv_out_{}",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    let file_name = format!("{prefix}.log");
+    set_file(&file_name).unwrap();
+    set_file_output_format(OutputFormat::Csv).unwrap();
+
+    info!("plain");
+    warn!("with, a comma and \"quotes\"\nand a second line");
+
+    let contents = fs::read_to_string(&file_name).unwrap();
+    let mut lines = contents.lines();
+    assert_eq!(
+        lines.next(),
+        Some("timestamp,level,module,file,line,message")
+    );
+    let info_row: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(info_row.len(), 6);
+    assert!(info_row[0].ends_with('Z'));
+    assert_eq!(
+        info_row[1..4],
+        ["INFO", "csv_output", "tests/csv_output.rs"]
+    );
+    assert!(info_row[4].parse::<u32>().is_ok());
+    assert_eq!(info_row[5], "plain");
+
+    // the quoted message spans two lines
+    let warn_row = format!("{}\n{}", lines.next().unwrap(), lines.next().unwrap());
+    assert!(warn_row.ends_with(",\"with, a comma and \"\"quotes\"\"\nand a second line\""));
+    assert!(warn_row.contains(",WARN,csv_output,tests/csv_output.rs,"));
+    assert_eq!(lines.next(), None);
+
+    init();
+    let _ = fs::remove_file(&file_name);
+}
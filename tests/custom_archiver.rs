@@ -0,0 +1,61 @@
+// tests/custom_archiver.rs
+//
+// Checks that the archiver set with `set_archiver` replaces the compression of the rotated files:
+// it gets the path of each old file, the new file is created before, and a failing archiver keeps
+// the old file.
+
+mod common;
+
+use std::{
+    fs,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use loggit::logger::{remove_archiver, set_archiver};
+
+#[test]
+fn archiver_replaces_the_compression() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("custom_arch_{ts}");
+    let moved_dir = format!("custom_arch_moved_{ts}");
+    fs::create_dir(&moved_dir).unwrap();
+
+    common::set_rotated_file(&format!("{prefix}_{{date}}_{{time}}.log"), Some("zip"));
+    let target = moved_dir.clone();
+    let archived = AtomicUsize::new(0);
+    set_archiver(move |path: &Path| {
+        // the files rotated within a second have the same name, so they are numbered
+        let n = archived.fetch_add(1, Ordering::SeqCst);
+        let name = path.file_name().unwrap().to_string_lossy();
+        fs::rename(path, Path::new(&target).join(format!("{n}_{name}")))?;
+        Ok(())
+    })
+    .unwrap();
+    common::log_messages("msg");
+
+    // the old files are moved by the archiver instead of being zipped, no record is lost
+    let moved: Vec<String> = common::entries(&moved_dir)
+        .into_iter()
+        .filter(|name| name.ends_with(".log"))
+        .collect();
+    assert!(!moved.is_empty(), "the archiver wasn't called");
+    let current = common::files(".", &prefix);
+    assert_eq!(current.len(), 1, "the old files are left: {current:?}");
+    let content = common::content(&moved_dir, "") + &common::content(".", &prefix);
+    common::assert_all_logged(&content, "msg");
+    assert!(common::files("archives", &prefix).is_empty());
+
+    // a failing archiver keeps the old file, the logs go to the new one
+    set_archiver(|_| Err("uploader is down".into())).unwrap();
+    common::log_messages("kept");
+    assert!(
+        common::files(".", &prefix).len() > 1,
+        "the old files are removed"
+    );
+    assert_eq!(common::entries(&moved_dir).len(), moved.len());
+
+    remove_archiver().unwrap();
+    common::clean_up(&prefix, &[&moved_dir]);
+}
@@ -0,0 +1,43 @@
+// tests/file_permissions.rs
+//
+// Checks that the log files and the archives of the rotated ones are created with the mode set
+// with `set_file_permissions`, whatever the umask.
+#![cfg(unix)]
+
+mod common;
+
+use std::{fs, os::unix::fs::PermissionsExt};
+
+use loggit::logger::{set_archive_dir, set_file_permissions};
+
+/// Returns the permission bits of the file.
+fn mode(path: &str) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o777
+}
+
+#[test]
+fn created_files_get_the_permissions() {
+    common::init_quiet();
+
+    assert!(set_file_permissions(0o10000).is_err());
+    set_file_permissions(0o640).unwrap();
+    let ts = common::unique();
+    let prefix = format!("perms_{ts}");
+    let archive_dir = format!("perms_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+    common::log_messages("msg");
+
+    let logs = common::files(".", &prefix);
+    let archives: Vec<String> = common::entries(&archive_dir)
+        .iter()
+        .map(|name| format!("{archive_dir}/{name}"))
+        .collect();
+    assert!(!logs.is_empty(), "no log file written");
+    assert!(!archives.is_empty(), "no archive produced after rotation");
+    for path in logs.iter().chain(&archives) {
+        assert_eq!(mode(path), 0o640, "{path} has other permissions");
+    }
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
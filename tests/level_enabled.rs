@@ -0,0 +1,49 @@
+// tests/level_enabled.rs
+//
+// Checks that the macros don't evaluate their arguments when no output emits the level, and that
+// the per-output levels and the ring buffer are taken into account.
+
+use std::cell::Cell;
+
+use loggit::{
+    debug, error,
+    logger::{
+        enable_ring_buffer, init, level_enabled, set_log_level, set_print_to_terminal,
+        set_ring_buffer_level, set_terminal_level,
+    },
+    trace, Level,
+};
+
+/// Returns the value and counts the calls.
+fn counted<'a>(calls: &Cell<u32>, value: &'a str) -> &'a str {
+    calls.set(calls.get() + 1);
+    value
+}
+
+#[test]
+fn filtered_logs_are_not_formatted() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::WARN).unwrap();
+    let calls = Cell::new(0);
+
+    assert!(!level_enabled(Level::DEBUG));
+    debug!("{}", counted(&calls, "skipped"));
+    assert_eq!(calls.get(), 0);
+    error!("{}", counted(&calls, "emitted"));
+    assert_eq!(calls.get(), 1);
+
+    // an output with a lower level enables it
+    set_terminal_level(Level::DEBUG).unwrap();
+    assert!(level_enabled(Level::DEBUG));
+    assert!(!level_enabled(Level::TRACE));
+
+    // so does the ring buffer
+    enable_ring_buffer(10).unwrap();
+    set_ring_buffer_level(Some(Level::TRACE)).unwrap();
+    assert!(level_enabled(Level::TRACE));
+    trace!("{}", counted(&calls, "kept"));
+    assert_eq!(calls.get(), 2);
+
+    init();
+}
@@ -0,0 +1,73 @@
+// tests/level_files.rs
+//
+// Checks that a file added for some levels receives only their logs while the main file gets
+// all of them.
+
+use std::fs;
+use std::path::PathBuf;
+
+use loggit::{
+    debug, error, info,
+    logger::{
+        add_file_for_levels, add_rotation_for, init, set_file, set_log_level, set_print_to_terminal,
+    },
+    warn, Level,
+};
+
+/// Returns the paths in `.` starting with the prefix.
+fn find_files(prefix: &str) -> Vec<PathBuf> {
+    fs::read_dir(".")
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(prefix))
+        })
+        .collect()
+}
+
+fn read_file(prefix: &str) -> String {
+    let files = find_files(prefix);
+    assert_eq!(files.len(), 1, "expected one file starting with {prefix}");
+    fs::read_to_string(&files[0]).unwrap()
+}
+
+#[test]
+fn error_file_gets_only_its_levels() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::DEBUG).unwrap();
+    let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let main_prefix = format!("levels_main_{nanos}");
+    let errors_prefix = format!("levels_errors_{nanos}");
+
+    set_file(&format!("{main_prefix}_{{date}}.txt")).unwrap();
+    let errors_file = add_file_for_levels(
+        &[Level::WARN, Level::ERROR],
+        &format!("{errors_prefix}.log"),
+    )
+    .unwrap();
+    add_rotation_for(errors_file, "1 day").unwrap();
+
+    debug!("debug line");
+    info!("info line");
+    warn!("warn line");
+    error!("error line");
+    info!("another info line");
+
+    assert_eq!(read_file(&main_prefix).lines().count(), 5);
+    let errors = read_file(&errors_prefix);
+    let lines: Vec<&str> = errors.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("warn line"));
+    assert!(lines[1].ends_with("error line"));
+
+    init();
+    for prefix in [main_prefix, errors_prefix] {
+        for path in find_files(&prefix) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
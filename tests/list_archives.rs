@@ -0,0 +1,60 @@
+// tests/list_archives.rs
+//
+// Checks that `list_archives` finds the archives written by a rotation, with their size and the
+// name of the archived file with an allowed extension, and skips the other files of the archive
+// directory.
+
+mod common;
+
+use std::fs;
+
+use loggit::logger::{list_archives, set_allowed_file_extensions, set_archive_dir};
+
+#[test]
+fn rotated_files_are_listed() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("listarch_{ts}");
+    let archive_dir = format!("listarch_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    fs::create_dir_all(&archive_dir).unwrap();
+    fs::write(format!("{archive_dir}/notes.txt"), "not an archive").unwrap();
+
+    common::set_rotated_file(&format!("{prefix}_{{time}}.log"), Some("zip"));
+    common::log_messages("msg");
+
+    let archives = list_archives().unwrap();
+    assert!(!archives.is_empty(), "no archive listed after rotation");
+    for archive in &archives {
+        let name = archive.path.file_name().unwrap().to_string_lossy();
+        assert!(
+            name.starts_with(&prefix) && name.ends_with(".log.zip"),
+            "{name} is listed"
+        );
+        assert_eq!(archive.file_name.as_deref(), name.strip_suffix(".zip"));
+        assert_eq!(archive.size, fs::metadata(&archive.path).unwrap().len());
+    }
+    assert!(archives
+        .windows(2)
+        .all(|pair| pair[0].modified <= pair[1].modified));
+
+    // the extensions allowed for the log files name the archived file too
+    set_allowed_file_extensions(&["jsonl"]).unwrap();
+    fs::write(format!("{archive_dir}/{prefix}_extra.jsonl.zip"), "").unwrap();
+    fs::write(format!("{archive_dir}/{prefix}_extra.bin.zip"), "").unwrap();
+    let file_name = |name: &str| {
+        let archives = list_archives().unwrap();
+        let archive = archives
+            .into_iter()
+            .find(|archive| archive.path.ends_with(name))
+            .unwrap();
+        archive.file_name
+    };
+    assert_eq!(
+        file_name(&format!("{prefix}_extra.jsonl.zip")),
+        Some(format!("{prefix}_extra.jsonl"))
+    );
+    assert_eq!(file_name(&format!("{prefix}_extra.bin.zip")), None);
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
@@ -0,0 +1,60 @@
+// tests/list_rotations.rs
+//
+// Checks that the configured rotations are listed back as strings and that a rotation can be
+// removed by a string parsing to the same rotation.
+
+use std::fs;
+
+use loggit::logger::{
+    add_rotation, init, list_rotations, remove_rotation, remove_rotations,
+    set_errors::{ListRotationsError, RemoveRotationError},
+    set_file,
+};
+
+#[test]
+fn rotations_are_listed_and_removed() {
+    init();
+    assert!(matches!(
+        list_rotations(),
+        Err(ListRotationsError::FileIsntSet)
+    ));
+    assert!(matches!(
+        remove_rotation("1 day"),
+        Err(RemoveRotationError::FileIsntSet)
+    ));
+    assert!(matches!(
+        remove_rotations(),
+        Err(RemoveRotationError::FileIsntSet)
+    ));
+
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let log_file = format!("listed_{ts}.log");
+    set_file(&log_file).unwrap();
+    assert!(list_rotations().unwrap().is_empty());
+
+    add_rotation("2 weeks").unwrap();
+    add_rotation("1.5 GB").unwrap();
+    add_rotation("Weekly Sun 3:00").unwrap();
+    assert_eq!(
+        list_rotations().unwrap(),
+        vec!["2 week", "1536 MB", "sunday 03:00"]
+    );
+
+    // any string parsing to the same rotation removes it
+    remove_rotation("14 days").unwrap();
+    assert_eq!(list_rotations().unwrap(), vec!["1536 MB", "sunday 03:00"]);
+    assert!(matches!(
+        remove_rotation("14 days"),
+        Err(RemoveRotationError::RotationNotFound)
+    ));
+    assert!(matches!(
+        remove_rotation("2 fortnights"),
+        Err(RemoveRotationError::IncorrectFormatGiven(_))
+    ));
+
+    remove_rotations().unwrap();
+    assert!(list_rotations().unwrap().is_empty());
+
+    init();
+    let _ = fs::remove_file(&log_file);
+}
@@ -8,8 +8,8 @@
 use std::{fs, thread, time::Duration};
 
 use loggit::{
-    info,
-    logger::{init, set_file, set_log_level, set_print_to_terminal},
+    context, info,
+    logger::{init, set_file, set_global_formatting, set_log_level, set_print_to_terminal},
     Level,
 };
 
@@ -126,3 +126,46 @@ fn concurrent_level_switching() {
 
     cleanup(&log_path);
 }
+
+/// Each thread logs with its own scoped context, which must never show up in the lines of the
+/// other threads.
+#[test]
+fn concurrent_scoped_context() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::INFO).unwrap();
+    set_global_formatting("{message} {context}").unwrap();
+
+    let prefix = format!("ctx_log_{}", chrono::Utc::now().timestamp_nanos());
+    set_file(&format!("{prefix}_{{date}}_{{time}}.txt")).unwrap();
+
+    let mut handles = Vec::with_capacity(THREADS);
+    for id in 0..THREADS {
+        handles.push(thread::spawn(move || {
+            let _request = context::scope("request_id", &format!("req{id}"));
+            for n in 0..MSG_PER_THREAD {
+                let _step = context::scope("step", &n.to_string());
+                info!("thread={id}");
+            }
+        }));
+    }
+    for h in handles {
+        h.join().expect("worker panicked");
+    }
+
+    let log_path = find_log_file(&prefix);
+    let contents = fs::read_to_string(&log_path).expect("unable to read log file");
+    assert_eq!(contents.lines().count(), THREADS * MSG_PER_THREAD);
+    for line in contents.lines() {
+        let id = line
+            .strip_prefix("thread=")
+            .and_then(|rest| rest.split(' ').next())
+            .expect("unexpected line");
+        assert!(
+            line.contains(&format!("request_id=req{id} step=")),
+            "line with a foreign context: {line}"
+        );
+    }
+
+    cleanup(&log_path);
+}
@@ -0,0 +1,77 @@
+// tests/multiple_files.rs
+//
+// Checks that the logs are written to every file added with `add_file` and that the files can
+// be configured and removed one by one.
+
+use std::fs;
+use std::path::PathBuf;
+
+use loggit::{
+    info,
+    logger::{
+        add_file, add_rotation_for, init, remove_file, set_compression_for, set_file,
+        set_print_to_terminal,
+    },
+};
+
+/// Returns the paths in `.` starting with the prefix.
+fn find_files(prefix: &str) -> Vec<PathBuf> {
+    fs::read_dir(".")
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(prefix))
+        })
+        .collect()
+}
+
+fn read_file(prefix: &str) -> String {
+    let files = find_files(prefix);
+    assert_eq!(files.len(), 1, "expected one file starting with {prefix}");
+    fs::read_to_string(&files[0]).unwrap()
+}
+
+#[test]
+fn logs_are_written_to_every_file() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let debug_prefix = format!("multi_debug_{nanos}");
+    let audit_prefix = format!("multi_audit_{nanos}");
+
+    let debug_file = add_file(&format!("{debug_prefix}_{{date}}.log")).unwrap();
+    let audit_file = add_file(&format!("{audit_prefix}_{{date}}.txt")).unwrap();
+    add_rotation_for(debug_file, "10 MB").unwrap();
+    set_compression_for(audit_file, "zip").unwrap();
+    assert!(add_rotation_for(audit_file, "not a rotation").is_err());
+
+    info!("first");
+    remove_file(audit_file).unwrap();
+    info!("second");
+    assert!(remove_file(audit_file).is_err());
+    assert!(set_compression_for(audit_file, "zip").is_err());
+
+    let debug_contents = read_file(&debug_prefix);
+    let audit_contents = read_file(&audit_prefix);
+    assert_eq!(debug_contents.lines().count(), 2);
+    assert_eq!(audit_contents.lines().count(), 1);
+    assert!(audit_contents.contains("first"));
+
+    // `set_file` replaces all the files
+    let single_prefix = format!("multi_single_{nanos}");
+    set_file(&format!("{single_prefix}_{{date}}.txt")).unwrap();
+    info!("third");
+    assert_eq!(read_file(&debug_prefix).lines().count(), 2);
+    assert_eq!(read_file(&single_prefix).lines().count(), 1);
+    assert!(add_rotation_for(debug_file, "1 day").is_err());
+
+    init();
+    for prefix in [debug_prefix, audit_prefix, single_prefix] {
+        for path in find_files(&prefix) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
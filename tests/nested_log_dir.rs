@@ -0,0 +1,51 @@
+// tests/nested_log_dir.rs
+//
+// Checks that the log files of a pattern with directories are written to them, the missing
+// directories being created, for a relative and an absolute path, and that the rotated files
+// stay in them.
+
+mod common;
+
+use std::{fs, path::Path};
+
+use loggit::{
+    info,
+    logger::{flush, set_file},
+};
+
+/// Returns the contents of the files of the directory and its subdirectories.
+fn contents(dir: &Path) -> String {
+    let mut res = String::new();
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            res.push_str(&contents(&path));
+        } else {
+            res.push_str(&fs::read_to_string(path).unwrap());
+        }
+    }
+    res
+}
+
+#[test]
+fn logs_go_to_missing_directories() {
+    common::init_quiet();
+    let ts = common::unique();
+
+    // relative, with the date in the directories
+    let relative = format!("nested_logs_{ts}");
+    common::set_rotated_file(&format!("{relative}/a/{{date}}/app_{{time}}.log"), None);
+    common::log_messages("msg");
+    flush().unwrap();
+    common::assert_all_logged(&contents(Path::new(&relative)), "msg");
+
+    // absolute
+    let absolute = std::env::temp_dir().join(format!("nested_logs_{ts}"));
+    let pattern = absolute.join("b").join("c").join("app.log");
+    set_file(pattern.to_str().unwrap()).unwrap();
+    info!("absolute");
+    flush().unwrap();
+    assert!(fs::read_to_string(&pattern).unwrap().contains("absolute"));
+
+    common::clean_up(&relative, &[&relative, absolute.to_str().unwrap()]);
+}
@@ -0,0 +1,72 @@
+// tests/output_format.rs
+//
+// Checks that the terminal and the file outputs can use different encodings: text in the
+// terminal, JSON Lines in the file.
+
+use std::{
+    fs,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use loggit::{
+    info,
+    logger::{
+        add_context, init, set_file, set_file_output_format, set_global_formatting, set_writer,
+    },
+    warn, OutputFormat,
+};
+
+/// A writer appending to a buffer shared with the test.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn text_terminal_and_json_file() {
+    init();
+    let buffer = SharedBuffer::default();
+    set_writer(Box::new(buffer.clone())).unwrap();
+    set_global_formatting("[{level}] {message}").unwrap();
+    let prefix = format!(
+        "json_out_{}",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    let file_name = format!("{prefix}.log");
+    set_file(&file_name).unwrap();
+    set_file_output_format(OutputFormat::Json).unwrap();
+    add_context("service", "billing").unwrap();
+
+    info!("hello \"json\"");
+    warn!("second");
+
+    let terminal = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(terminal, "[INFO] hello \"json\"\n[WARN] second\n");
+
+    let contents = fs::read_to_string(&file_name).unwrap();
+    let lines: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["level"], "INFO");
+    assert_eq!(lines[0]["message"], "hello \"json\"");
+    assert_eq!(lines[0]["module"], "output_format");
+    assert_eq!(lines[0]["file"], "tests/output_format.rs");
+    assert_eq!(lines[0]["context"]["service"], "billing");
+    assert!(lines[0]["line"].is_u64());
+    assert!(lines[0]["ts"].as_str().unwrap().ends_with('Z'));
+    assert_eq!(lines[1]["level"], "WARN");
+
+    init();
+    let _ = fs::remove_file(&file_name);
+}
@@ -0,0 +1,65 @@
+// tests/output_levels.rs
+//
+// Checks that the terminal and the file outputs apply their own minimal levels: DEBUG in the
+// file while the terminal only gets the warnings.
+
+use std::{
+    fs,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use loggit::{
+    debug, info,
+    logger::{
+        init, set_file, set_file_level, set_global_formatting, set_log_level, set_terminal_level,
+        set_writer,
+    },
+    trace, warn, Level,
+};
+
+/// A writer appending to a buffer shared with the test.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn terminal_and_file_levels() {
+    init();
+    let buffer = SharedBuffer::default();
+    set_writer(Box::new(buffer.clone())).unwrap();
+    set_global_formatting("[{level}] {message}").unwrap();
+    let file_name = format!(
+        "output_levels_{}.log",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    set_file(&file_name).unwrap();
+    set_log_level(Level::INFO).unwrap();
+    set_file_level(Level::DEBUG).unwrap();
+    set_terminal_level(Level::WARN).unwrap();
+
+    trace!("nowhere");
+    debug!("file only");
+    info!("file only too");
+    warn!("everywhere");
+
+    let terminal = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(terminal, "[WARN] everywhere\n");
+    let contents = fs::read_to_string(&file_name).unwrap();
+    assert_eq!(
+        contents,
+        "[DEBUG] file only\n[INFO] file only too\n[WARN] everywhere\n"
+    );
+
+    init();
+    let _ = fs::remove_file(&file_name);
+}
@@ -0,0 +1,43 @@
+// tests/period_rotation.rs
+//
+// Checks that a period rotation in seconds archives the file once the period has passed.
+
+use std::{fs, thread, time::Duration};
+
+use loggit::{
+    info,
+    logger::{
+        add_rotation, init, set_archive_dir, set_compression, set_file, set_print_to_terminal,
+    },
+};
+
+#[test]
+fn rotation_every_second_creates_zip_archive() {
+    init();
+    set_print_to_terminal(false).unwrap();
+
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let prefix = format!("secrot_{ts}");
+    let archive_dir = format!("secrot_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    set_file(&format!("{prefix}_{{date}}_{{time}}.log")).unwrap();
+    set_compression("zip").unwrap();
+    add_rotation("1 second").unwrap();
+
+    info!("before the rotation");
+    let archives = || fs::read_dir(&archive_dir).map_or(0, |entries| entries.count());
+    assert_eq!(archives(), 0);
+    // the deadline is counted in whole seconds
+    thread::sleep(Duration::from_millis(2100));
+    info!("after the rotation");
+    assert_eq!(archives(), 1);
+
+    init();
+    for entry in fs::read_dir(".").unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_string_lossy().contains(&prefix) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    let _ = fs::remove_dir_all(&archive_dir);
+}
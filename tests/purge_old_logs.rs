@@ -0,0 +1,93 @@
+// tests/purge_old_logs.rs
+//
+// Checks that `purge_old_logs` removes the old log files of the pattern, numbered or not, and
+// keeps the recent ones, the files of other patterns or shapes and the file being written to,
+// that it refuses a pattern without fixed text, and that `set_purge_on_startup` runs it when the
+// file is set.
+
+use std::{
+    fs::{self, File},
+    time::{Duration, SystemTime},
+};
+
+use loggit::logger::{
+    init, purge_old_logs, set_errors::PurgeError, set_file, set_print_to_terminal,
+    set_purge_on_startup,
+};
+
+/// Creates the file in the directory, last modified `age` ago.
+fn create(dir: &str, name: &str, age: Duration) {
+    let file = File::create(format!("{dir}/{name}")).unwrap();
+    file.set_modified(SystemTime::now() - age).unwrap();
+}
+
+/// Returns the sorted names of the files of the directory.
+fn entries(dir: &str) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn old_log_files_are_purged() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let dir = format!("purge_logs_{ts}");
+    fs::create_dir(&dir).unwrap();
+    let week = Duration::from_secs(7 * 24 * 3600);
+    let day = Duration::from_secs(24 * 3600);
+
+    assert!(matches!(
+        purge_old_logs("7 day"),
+        Err(PurgeError::FileIsntSet)
+    ));
+    set_file(&format!("{dir}/app_{{date}}.log")).unwrap();
+    assert!(matches!(
+        purge_old_logs("7 MB"),
+        Err(PurgeError::IncorrectAgeGiven)
+    ));
+
+    create(&dir, "app_01-01-2020.log", 2 * week);
+    create(&dir, "app_01-01-2020(1).log", 2 * week);
+    create(&dir, "app_14-10-2026.log", day);
+    create(&dir, "other_01-01-2020.log", 2 * week);
+    create(&dir, "app_01-01-2020.log.zip", 2 * week);
+    create(&dir, "app_01-01-2020.txt", 2 * week);
+    // not a date
+    create(&dir, "app_backup.log", 2 * week);
+    // the file being written to, even if it is old
+    let today = chrono::Utc::now().format("%d-%m-%Y").to_string();
+    create(&dir, &format!("app_{today}.log"), 2 * week);
+
+    assert_eq!(purge_old_logs("7 day").unwrap(), 2);
+    let mut kept = vec![
+        "app_01-01-2020.log.zip".to_string(),
+        "app_01-01-2020.txt".to_string(),
+        "app_backup.log".to_string(),
+        "app_14-10-2026.log".to_string(),
+        format!("app_{today}.log"),
+        "other_01-01-2020.log".to_string(),
+    ];
+    kept.sort();
+    assert_eq!(entries(&dir), kept);
+
+    // a name without fixed text could match the files of others
+    set_file(&format!("{dir}/{{date}}.log")).unwrap();
+    assert!(matches!(
+        purge_old_logs("7 day"),
+        Err(PurgeError::NoFixedTextInPattern)
+    ));
+
+    // at startup, by the pattern set
+    set_purge_on_startup("1 hour").unwrap();
+    set_file(&format!("{dir}/other_{{date}}.log")).unwrap();
+    assert!(!entries(&dir).contains(&"other_01-01-2020.log".to_string()));
+    assert!(entries(&dir).contains(&"app_14-10-2026.log".to_string()));
+
+    init();
+    let _ = fs::remove_dir_all(&dir);
+}
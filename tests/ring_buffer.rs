@@ -0,0 +1,59 @@
+// tests/ring_buffer.rs
+//
+// Checks that the ring buffer keeps the last records, including the ones below the log level
+// when a ring buffer level is set.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use loggit::{
+    debug, info,
+    logger::{
+        add_sink, disable_ring_buffer, enable_ring_buffer, init, recent_logs,
+        set_print_to_terminal, set_ring_buffer_level,
+    },
+    warn, Level,
+};
+
+fn messages() -> Vec<String> {
+    recent_logs().into_iter().map(|r| r.message).collect()
+}
+
+#[test]
+fn ring_buffer_keeps_last_records() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    assert!(recent_logs().is_empty());
+    assert!(enable_ring_buffer(0).is_err());
+
+    enable_ring_buffer(3).unwrap();
+    for i in 0..5 {
+        info!("record {}", i);
+    }
+    debug!("below the log level");
+    assert_eq!(messages(), vec!["record 2", "record 3", "record 4"]);
+
+    // the records below the log level are kept but not emitted
+    let emitted = Arc::new(AtomicUsize::new(0));
+    let counter = emitted.clone();
+    add_sink(move |_| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    })
+    .unwrap();
+    set_ring_buffer_level(Some(Level::DEBUG)).unwrap();
+    debug!("debug context");
+    warn!("warning");
+    assert_eq!(messages(), vec!["record 4", "debug context", "warning"]);
+    assert_eq!(emitted.load(Ordering::Relaxed), 1);
+
+    // shrinking keeps the most recent records
+    enable_ring_buffer(1).unwrap();
+    assert_eq!(messages(), vec!["warning"]);
+
+    disable_ring_buffer().unwrap();
+    info!("not kept");
+    assert!(recent_logs().is_empty());
+    init();
+}
@@ -0,0 +1,96 @@
+// tests/rotate_now.rs
+//
+// Checks that `rotate_now` archives the current log file at once, fails without a log file, and
+// loses no record while other threads are logging.
+
+use std::{fs, io::Read, thread};
+
+use loggit::{
+    info,
+    logger::{
+        add_rotation, init, rotate_now, set_archive_dir, set_compression, set_errors::RotateError,
+        set_file, set_log_level, set_print_to_terminal,
+    },
+    Level,
+};
+
+const THREADS: usize = 4;
+const MESSAGES: usize = 100;
+
+/// Returns the names of the files of the directory starting with the prefix.
+fn files(dir: &str, prefix: &str) -> Vec<String> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(prefix))
+        .collect()
+}
+
+#[test]
+fn rotate_now_archives_the_current_file() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::INFO).unwrap();
+    assert!(matches!(rotate_now(), Err(RotateError::FileIsntSet)));
+
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let prefix = format!("rotatenow_{ts}");
+    let archive_dir = format!("rotatenow_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    set_file(&format!("{prefix}_{{time}}.log")).unwrap();
+    set_compression("zip").unwrap();
+    // never due during the test
+    add_rotation("1 day").unwrap();
+
+    info!("before the rotation");
+    rotate_now().unwrap();
+    let archives = files(&archive_dir, &prefix);
+    assert_eq!(archives.len(), 1, "not one archive: {archives:?}");
+
+    // the other threads keep logging while the files are rotated
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            thread::spawn(move || {
+                for n in 0..MESSAGES {
+                    info!("thread {t} msg {n}");
+                }
+            })
+        })
+        .collect();
+    for _ in 0..5 {
+        rotate_now().unwrap();
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut content: String = files(".", &prefix)
+        .iter()
+        .map(|name| fs::read_to_string(name).unwrap())
+        .collect();
+    for name in files(&archive_dir, &prefix) {
+        let file = fs::File::open(format!("{archive_dir}/{name}")).unwrap();
+        let mut zip = zip::ZipArchive::new(file).unwrap();
+        zip.by_index(0)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+    }
+    assert!(content.contains("before the rotation"));
+    for t in 0..THREADS {
+        for n in 0..MESSAGES {
+            let record = format!("thread {t} msg {n}\n");
+            assert_eq!(
+                content.matches(&record).count(),
+                1,
+                "{record} is lost or doubled"
+            );
+        }
+    }
+
+    init();
+    for name in files(".", &prefix) {
+        let _ = fs::remove_file(name);
+    }
+    let _ = fs::remove_dir_all(&archive_dir);
+}
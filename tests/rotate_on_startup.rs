@@ -0,0 +1,65 @@
+// tests/rotate_on_startup.rs
+//
+// Checks that an existing log file is continued by default, and that a new file is started (and
+// the old one archived when a compression is set) with `set_rotate_on_startup(true)`.
+
+use std::{fs, path::Path};
+
+use loggit::{
+    info,
+    logger::{
+        init, set_archive_dir, set_compression, set_file, set_print_to_terminal,
+        set_rotate_on_startup,
+    },
+};
+
+#[test]
+fn existing_file_is_continued_or_rotated() {
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let prefix = format!("startup_{ts}");
+    let log_file = format!("{prefix}.log");
+    let numbered_file = format!("{prefix}(1).log");
+    let archive_dir = format!("startup_arch_{ts}");
+    fs::write(&log_file, "previous run\n").unwrap();
+
+    // appended by default
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_file(&log_file).unwrap();
+    info!("appended");
+    let content = fs::read_to_string(&log_file).unwrap();
+    assert!(content.starts_with("previous run\n"));
+    assert!(content.contains("appended"));
+    assert!(!Path::new(&numbered_file).exists());
+
+    // a new file, the old one is kept without a compression
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_rotate_on_startup(true).unwrap();
+    set_file(&log_file).unwrap();
+    info!("fresh");
+    assert_eq!(fs::read_to_string(&log_file).unwrap(), content);
+    assert!(fs::read_to_string(&numbered_file)
+        .unwrap()
+        .contains("fresh"));
+    fs::remove_file(&numbered_file).unwrap();
+
+    // archived with a compression
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_archive_dir(&archive_dir).unwrap();
+    set_rotate_on_startup(true).unwrap();
+    set_file(&log_file).unwrap();
+    set_compression("zip").unwrap();
+    info!("after the archive");
+    assert!(!Path::new(&log_file).exists());
+    assert!(Path::new(&format!("{archive_dir}/{log_file}.zip")).exists());
+    assert!(fs::read_to_string(&numbered_file)
+        .unwrap()
+        .contains("after the archive"));
+
+    init();
+    let _ = fs::remove_file(&log_file);
+    let _ = fs::remove_file(&numbered_file);
+    let _ = fs::remove_dir_all(&archive_dir);
+}
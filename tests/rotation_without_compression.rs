@@ -0,0 +1,37 @@
+// tests/rotation_without_compression.rs
+//
+// Checks that a rotation without a compression keeps the old log file as it is next to the new
+// one, and moves it into the archive directory uncompressed once one is set.
+
+mod common;
+
+use loggit::logger::set_archive_dir;
+
+#[test]
+fn rotation_keeps_plain_files() {
+    common::init_quiet();
+    let ts = common::unique();
+    let prefix = format!("plainrot_{ts}");
+    common::set_rotated_file(&format!("{prefix}_{{date}}_{{time}}.log"), None);
+    common::log_messages("msg");
+
+    // the old files are left in place, next to the new one, and no record is lost
+    let files = common::files(".", &prefix);
+    assert!(
+        files.len() >= 2,
+        "no new log file after rotation: {files:?}"
+    );
+    common::assert_all_logged(&common::content(".", &prefix), "msg");
+
+    // with an archive dir the old file is moved there uncompressed
+    let archive_dir = format!("plain_arch_{ts}");
+    set_archive_dir(&archive_dir).unwrap();
+    common::log_messages("archived");
+    let archived = common::files(&archive_dir, &prefix);
+    assert!(!archived.is_empty(), "no plain file in the archive dir");
+    for name in archived {
+        assert!(name.ends_with(".log"), "{name} is compressed");
+    }
+
+    common::clean_up(&prefix, &[&archive_dir]);
+}
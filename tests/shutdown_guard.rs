@@ -0,0 +1,45 @@
+// tests/shutdown_guard.rs
+//
+// Checks that dropping the guard of `init_with_guard` writes the records queued in the async
+// mode without resetting the config, and that the logs emitted after the shutdown only go to
+// stderr.
+
+use std::fs;
+
+use loggit::{
+    info,
+    logger::{
+        init, init_with_guard, set_async_with_capacity, set_file, set_global_formatting,
+        set_print_to_terminal,
+    },
+};
+
+#[test]
+fn guard_writes_the_queued_records() {
+    let file_name = format!(
+        "shutdown_guard_{}.log",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap()
+    );
+    // the guard keeps the config set before it, e.g. the one imported from loggit.json
+    init();
+    set_global_formatting("{message}").unwrap();
+    {
+        let _guard = init_with_guard();
+        set_print_to_terminal(false).unwrap();
+        set_file(&file_name).unwrap();
+        set_async_with_capacity(8).unwrap();
+        for i in 0..200 {
+            info!("record {}", i);
+        }
+    }
+    let contents = fs::read_to_string(&file_name).unwrap();
+    assert_eq!(contents.lines().count(), 200);
+    assert!(contents.ends_with("record 199\n"));
+
+    // printed to stderr, not written to the file
+    info!("after the shutdown");
+    assert_eq!(fs::read_to_string(&file_name).unwrap(), contents);
+
+    init();
+    let _ = fs::remove_file(&file_name);
+}
@@ -0,0 +1,49 @@
+// tests/sink_panics.rs
+//
+// Checks that the panics of a sink don't reach the panic hook, while the other panics still do.
+// The panic hook is global to the process, so this test has a file of its own.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use loggit::{
+    info,
+    logger::{add_sink, init, remove_sink, set_print_to_terminal},
+};
+
+#[test]
+fn sink_panics_dont_reach_the_panic_hook() {
+    init();
+    set_print_to_terminal(false).unwrap();
+
+    let hook_calls = Arc::new(AtomicUsize::new(0));
+    let counter = hook_calls.clone();
+    std::panic::set_hook(Box::new(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    let sink_calls = Arc::new(AtomicUsize::new(0));
+    let calls = sink_calls.clone();
+    let id = add_sink(move |_| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        panic!("sink failure");
+    })
+    .unwrap();
+
+    // the sink panics twice, neither panic is printed by the hook
+    info!("first");
+    info!("second");
+    assert_eq!(sink_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(hook_calls.load(Ordering::SeqCst), 0);
+
+    // the panics out of the sinks still reach the hook set by the user
+    let res = std::panic::catch_unwind(|| panic!("not a sink"));
+    assert!(res.is_err());
+    assert_eq!(hook_calls.load(Ordering::SeqCst), 1);
+
+    remove_sink(id).unwrap();
+    let _ = std::panic::take_hook();
+    init();
+}
@@ -0,0 +1,62 @@
+// tests/sinks.rs
+//
+// Checks that the sinks receive the emitted records and that a panicking sink doesn't stop the
+// logging.
+
+use std::sync::{Arc, Mutex};
+
+use loggit::{
+    debug, error, info,
+    logger::{add_sink, init, remove_sink, set_log_level, set_print_to_terminal},
+    Level,
+};
+
+#[test]
+fn sinks_receive_emitted_records() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    set_log_level(Level::INFO).unwrap();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let first = received.clone();
+    let first_id = add_sink(move |record| {
+        first
+            .lock()
+            .unwrap()
+            .push(format!("first {} {}", record.level, record.message))
+    })
+    .unwrap();
+    // a panicking sink doesn't prevent the next ones from being called
+    let panicking_id = add_sink(|_| panic!("sink failure")).unwrap();
+    let second = received.clone();
+    let second_id = add_sink(move |record| {
+        second
+            .lock()
+            .unwrap()
+            .push(format!("second {}", record.line))
+    })
+    .unwrap();
+
+    info!("hello");
+    debug!("filtered out");
+    error!("failure");
+    let line = line!() - 1;
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![
+            "first INFO hello".to_string(),
+            format!("second {}", line - 2),
+            "first ERROR failure".to_string(),
+            format!("second {}", line),
+        ]
+    );
+
+    remove_sink(first_id).unwrap();
+    remove_sink(panicking_id).unwrap();
+    assert!(remove_sink(first_id).is_err());
+    received.lock().unwrap().clear();
+    info!("only the second");
+    assert_eq!(received.lock().unwrap().len(), 1);
+    remove_sink(second_id).unwrap();
+    init();
+}
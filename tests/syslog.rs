@@ -0,0 +1,80 @@
+// tests/syslog.rs
+//
+// Checks the datagrams sent to a syslog server, set in code or from a config file.
+#![cfg(feature = "syslog")]
+
+use std::net::UdpSocket;
+use std::time::Duration;
+
+use loggit::{
+    error, info,
+    logger::{
+        init, load_config_from_file, remove_syslog, set_print_to_terminal, set_syslog,
+        syslog::{Facility, SyslogConfig},
+    },
+};
+
+fn receive(server: &UdpSocket) -> String {
+    let mut buf = [0u8; 65536];
+    let len = server.recv(&mut buf).unwrap();
+    String::from_utf8(buf[..len].to_vec()).unwrap()
+}
+
+#[test]
+fn records_are_sent_per_rfc5424() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    server
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+
+    set_syslog(SyslogConfig {
+        addr: addr.clone(),
+        facility: Facility::Local0,
+        app_name: "my app".to_string(),
+        max_len: 600,
+    })
+    .unwrap();
+    info!("hello syslog");
+    let datagram = receive(&server);
+    // local0 (16) * 8 + info (6)
+    assert!(datagram.starts_with("<134>1 "), "{}", datagram);
+    let fields: Vec<&str> = datagram.splitn(8, ' ').collect();
+    assert!(fields[1].ends_with('Z'), "{}", datagram);
+    assert!(!fields[2].is_empty());
+    assert_eq!(fields[3], "myapp");
+    assert_eq!(fields[4], std::process::id().to_string());
+    assert_eq!(&fields[5..], ["-", "-", "hello syslog"]);
+
+    let long = "x".repeat(2000);
+    error!("{}", long);
+    let datagram = receive(&server);
+    assert!(datagram.starts_with("<131>1 "), "{}", datagram);
+    assert!(datagram.len() <= 600);
+    assert!(datagram.contains("… [truncated "));
+
+    // from a config file
+    remove_syslog().unwrap();
+    let path = std::env::temp_dir().join(format!("loggit_syslog_{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        format!(
+            r#"{{"syslog_addr": "{}", "syslog_facility": "daemon", "syslog_app_name": "svc"}}"#,
+            addr
+        ),
+    )
+    .unwrap();
+    load_config_from_file(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    info!("from the config file");
+    let datagram = receive(&server);
+    // daemon (3) * 8 + info (6)
+    assert!(datagram.starts_with("<30>1 "), "{}", datagram);
+    assert!(datagram.contains(" svc "));
+    assert!(datagram.ends_with("from the config file"));
+
+    remove_syslog().unwrap();
+    init();
+}
@@ -0,0 +1,47 @@
+// tests/tcp_sink.rs
+//
+// Checks that the logs are shipped to a TCP collector, even if it starts listening after the
+// first logs, and that an unreachable one doesn't block the logging.
+#![cfg(feature = "tcp")]
+
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+
+use loggit::{
+    info,
+    logger::{init, remove_tcp_sink, set_print_to_terminal, set_tcp_sink},
+};
+
+#[test]
+fn logs_are_shipped_to_the_collector() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    assert!(set_tcp_sink("no-port").is_err());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    set_tcp_sink(&addr).unwrap();
+    info!("first record");
+    info!("second record");
+
+    let (stream, _) = listener.accept().unwrap();
+    let mut lines = BufReader::new(stream).lines();
+    assert!(lines.next().unwrap().unwrap().ends_with("first record"));
+    assert!(lines.next().unwrap().unwrap().ends_with("second record"));
+
+    remove_tcp_sink().unwrap();
+
+    // an unreachable collector doesn't block the logging: binds then frees a port so nothing
+    // listens on it
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .to_string();
+    set_tcp_sink(&addr).unwrap();
+    for i in 0..2000 {
+        info!("record {}", i);
+    }
+    remove_tcp_sink().unwrap();
+    init();
+}
@@ -0,0 +1,50 @@
+// tests/typed_rotations.rs
+//
+// Checks that the rotations added from typed values are the same as the ones parsed from the
+// strings, and that the values out of range are refused.
+
+use std::{fs, time::Duration};
+
+use loggit::logger::{
+    add_rotation_at, add_rotation_period, add_rotation_size_bytes, init, list_rotations,
+    remove_rotation, set_errors::AddRotationError, set_file,
+};
+
+#[test]
+fn typed_rotations_are_added() {
+    init();
+    assert!(matches!(
+        add_rotation_at(12, 30),
+        Err(AddRotationError::FileIsntSet)
+    ));
+
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let log_file = format!("typed_{ts}.log");
+    set_file(&log_file).unwrap();
+
+    add_rotation_period(Duration::from_secs(6 * 60 * 60)).unwrap();
+    add_rotation_size_bytes(50 * 1024 * 1024).unwrap();
+    add_rotation_at(12, 30).unwrap();
+    assert_eq!(list_rotations().unwrap(), vec!["6 hour", "50 MB", "12:30"]);
+    // the same rotations as the strings
+    remove_rotation("6 hours").unwrap();
+    remove_rotation("50 MB").unwrap();
+    remove_rotation("12:30").unwrap();
+    assert!(list_rotations().unwrap().is_empty());
+
+    for res in [
+        add_rotation_at(25, 0),
+        add_rotation_at(23, 60),
+        add_rotation_period(Duration::from_millis(500)),
+        add_rotation_size_bytes(0),
+    ] {
+        assert!(
+            matches!(res, Err(AddRotationError::IncorrectValueGiven(_))),
+            "{res:?}"
+        );
+    }
+    assert!(list_rotations().unwrap().is_empty());
+
+    init();
+    let _ = fs::remove_file(&log_file);
+}
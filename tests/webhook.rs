@@ -0,0 +1,74 @@
+// tests/webhook.rs
+//
+// Checks that the errors are posted to the webhook and the lower levels aren't, and that the alert
+// held back by the interval is still posted when the webhook is removed.
+#![cfg(feature = "http")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+use loggit::{
+    error, info,
+    logger::{init, remove_error_webhook, set_error_webhook, set_print_to_terminal},
+    Level,
+};
+
+/// Answers the next request to the webhook, returns its JSON body.
+fn receive(listener: &TcpListener) -> serde_json::Value {
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    assert!(request_line.starts_with("POST /hook "));
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn errors_are_posted_to_the_webhook() {
+    init();
+    set_print_to_terminal(false).unwrap();
+    assert!(set_error_webhook("hooks.example.com/abc", Level::ERROR).is_err());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    set_error_webhook(&url, Level::ERROR).unwrap();
+    info!("not posted");
+    error!("the payment service is down");
+
+    let body = receive(&listener);
+    assert_eq!(body["level"], "ERROR");
+    assert_eq!(body["message"], "the payment service is down");
+    assert!(body["location"]
+        .as_str()
+        .unwrap()
+        .starts_with("tests/webhook.rs:"));
+    assert_eq!(body["suppressed"], 0);
+
+    // held back by the interval, then posted when the webhook is removed
+    error!("the database is down");
+    error!("the cache is down");
+    remove_error_webhook().unwrap();
+    let body = receive(&listener);
+    assert_eq!(body["message"], "the database is down");
+    assert_eq!(body["suppressed"], 1);
+
+    init();
+}
@@ -0,0 +1,23 @@
+// tests/windows_event_log.rs
+//
+// Checks that the event source is registered on Windows and that registering it fails with an
+// error elsewhere.
+#![cfg(feature = "windows-eventlog")]
+
+use loggit::{
+    error, info,
+    logger::{remove_windows_event_log, set_windows_event_log},
+};
+
+#[test]
+fn event_source_registration() {
+    let res = set_windows_event_log("loggit-tests");
+    if cfg!(windows) {
+        res.unwrap();
+        info!("information event");
+        error!("error event");
+        remove_windows_event_log().unwrap();
+    } else {
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,63 @@
+// tests/writer.rs
+//
+// Checks that the terminal output can be captured with a custom writer. These are integration
+// tests so the config isn't shared with the unit tests.
+
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use loggit::{
+    error, info,
+    logger::{clear_writer, flush, init, set_color_when, set_global_formatting, set_writer},
+    ColorWhen,
+};
+
+/// A writer appending to a buffer shared with the test.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_captures_terminal_output() {
+    init();
+    set_global_formatting("[{level}] {message}").unwrap();
+    let buffer = SharedBuffer::default();
+    set_writer(Box::new(buffer.clone())).unwrap();
+
+    // the errors go to the writer too, and no colors are added by default
+    info!("first");
+    error!("second");
+    flush().unwrap();
+    assert_eq!(buffer.contents(), "[INFO] first\n[ERROR] second\n");
+
+    set_color_when(ColorWhen::Always).unwrap();
+    set_global_formatting("<red>{message}<red>").unwrap();
+    info!("colored");
+    assert!(buffer
+        .contents()
+        .ends_with("\x1b[38;2;255;0;0mcolored\x1b[0m\n"));
+
+    // the logs go back to stdout once the writer is removed
+    clear_writer().unwrap();
+    let len = buffer.contents().len();
+    info!("to stdout");
+    assert_eq!(buffer.contents().len(), len);
+    init();
+}